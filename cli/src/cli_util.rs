@@ -731,6 +731,7 @@ pub struct AdvanceableBookmark {
 struct AdvanceBookmarksSettings {
     enabled_bookmarks: Vec<StringPattern>,
     disabled_bookmarks: Vec<StringPattern>,
+    advance_from_revset: bool,
 }
 
 impl AdvanceBookmarksSettings {
@@ -752,6 +753,8 @@ impl AdvanceBookmarksSettings {
         Ok(Self {
             enabled_bookmarks: get_setting("enabled-branches")?,
             disabled_bookmarks: get_setting("disabled-branches")?,
+            advance_from_revset: settings
+                .get_bool("experimental-advance-branches.advance-from-revset")?,
         })
     }
 
@@ -1368,7 +1371,7 @@ to the current parents may contain changes from multiple commits.
             },
         )?;
         print_parse_diagnostics(ui, "In `snapshot.auto-track`", &diagnostics)?;
-        Ok(expression.to_matcher())
+        Ok(expression.to_matcher()?)
     }
 
     pub fn snapshot_options_with_start_tracking_matcher<'a>(
@@ -2338,9 +2341,16 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
     /// `WorkspaceCommandTransaction::advance_bookmarks()` is called with the
     /// `AdvanceableBookmark`s returned by this function.
     ///
+    /// If `experimental-advance-branches.advance-from-revset` is enabled and
+    /// the `target` commit the bookmarks will be moved to is the working-copy
+    /// commit, eligible bookmarks in the `revsets.bookmark-advance-from` revset
+    /// that are ancestors of the `from` commits can also advance.
+    ///
     /// Returns an empty `std::Vec` if no bookmarks are eligible to advance.
     pub fn get_advanceable_bookmarks<'a>(
         &self,
+        ui: &Ui,
+        target: &CommitId,
         from: impl IntoIterator<Item = &'a CommitId>,
     ) -> Result<Vec<AdvanceableBookmark>, CommandError> {
         let ab_settings = AdvanceBookmarksSettings::from_settings(self.settings())?;
@@ -2349,8 +2359,9 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             return Ok(Vec::new());
         }
 
+        let from = from.into_iter().collect_vec();
         let mut advanceable_bookmarks = Vec::new();
-        for from_commit in from {
+        for &from_commit in &from {
             for (name, _) in self.repo().view().local_bookmarks_for_commit(from_commit) {
                 if ab_settings.bookmark_is_eligible(name) {
                     advanceable_bookmarks.push(AdvanceableBookmark {
@@ -2361,6 +2372,40 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             }
         }
 
+        // The revset is relative to the working-copy commit, so it doesn't
+        // tell which bookmarks to advance to another commit.
+        if ab_settings.advance_from_revset && self.get_wc_commit_id() == Some(target) {
+            let revset = self
+                .settings()
+                .get_string("revsets.bookmark-advance-from")?;
+            let is_source_commit = self
+                .parse_revset(ui, &RevisionArg::from(revset))?
+                .evaluate()?
+                .containing_fn();
+            let index = self.repo().index();
+            for (name, ref_target) in self.repo().view().local_bookmarks() {
+                // Conflicted bookmarks can't be advanced.
+                let Some(old_commit_id) = ref_target.as_normal() else {
+                    continue;
+                };
+                if !ab_settings.bookmark_is_eligible(name)
+                    || advanceable_bookmarks.iter().any(|b| b.name == *name)
+                {
+                    continue;
+                }
+                if is_source_commit(old_commit_id)?
+                    && from
+                        .iter()
+                        .any(|from_commit| index.is_ancestor(old_commit_id, from_commit))
+                {
+                    advanceable_bookmarks.push(AdvanceableBookmark {
+                        name: name.to_owned(),
+                        old_commit_id: old_commit_id.clone(),
+                    });
+                }
+            }
+        }
+
         Ok(advanceable_bookmarks)
     }
 }
@@ -2404,6 +2449,10 @@ impl WorkspaceCommandTransaction<'_> {
         self.tx.repo_mut()
     }
 
+    pub fn set_tag(&mut self, key: String, value: String) {
+        self.tx.set_tag(key, value);
+    }
+
     pub fn check_out(&mut self, commit: &Commit) -> Result<Commit, CheckOutCommitError> {
         let name = self.helper.workspace_name().to_owned();
         self.id_prefix_context.take(); // invalidate
@@ -2526,6 +2575,7 @@ jj git init --colocate",
             err @ (StoreLoadError::ReadError { .. } | StoreLoadError::Backend(_)),
         ) => internal_error_with_message("The repository appears broken or inaccessible", err),
         WorkspaceLoadError::StoreLoadError(StoreLoadError::Signing(err)) => user_error(err),
+        WorkspaceLoadError::StoreLoadError(StoreLoadError::Config(err)) => err.into(),
         WorkspaceLoadError::WorkingCopyState(err) => internal_error(err),
         WorkspaceLoadError::DecodeRepoPath(_) | WorkspaceLoadError::Path(_) => user_error(err),
     }
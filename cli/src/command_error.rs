@@ -24,6 +24,7 @@ use itertools::Itertools as _;
 use jj_lib::absorb::AbsorbError;
 use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
+use jj_lib::bisect::BisectionError;
 use jj_lib::config::ConfigFileSaveError;
 use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigLoadError;
@@ -31,9 +32,13 @@ use jj_lib::config::ConfigMigrateError;
 use jj_lib::dsl_util::Diagnostics;
 use jj_lib::evolution::WalkPredecessorsError;
 use jj_lib::fileset::FilePatternParseError;
+use jj_lib::fileset::FilePredicateUnsupportedError;
 use jj_lib::fileset::FilesetParseError;
 use jj_lib::fileset::FilesetParseErrorKind;
 use jj_lib::fix::FixError;
+#[cfg(feature = "git")]
+use jj_lib::git_backend::GitLazyFetchError;
+use jj_lib::gitattributes::GitAttributesError;
 use jj_lib::gitignore::GitIgnoreError;
 use jj_lib::op_heads_store::OpHeadResolutionError;
 use jj_lib::op_heads_store::OpHeadsStoreError;
@@ -46,6 +51,7 @@ use jj_lib::repo::RepoLoaderError;
 use jj_lib::repo::RewriteRootCommit;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::UiPathParseError;
+use jj_lib::resolution_cache::ResolutionCacheError;
 use jj_lib::revset;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetParseError;
@@ -74,6 +80,7 @@ use crate::merge_tools::DiffEditError;
 use crate::merge_tools::MergeToolConfigError;
 use crate::merge_tools::MergeToolPartialResolutionError;
 use crate::revset_util::BookmarkNameParseError;
+use crate::revset_util::TagNameParseError;
 use crate::revset_util::UserRevsetEvaluationError;
 use crate::template_parser::TemplateParseError;
 use crate::template_parser::TemplateParseErrorKind;
@@ -252,6 +259,12 @@ impl From<jj_lib::file_util::PathError> for CommandError {
     }
 }
 
+impl From<ResolutionCacheError> for CommandError {
+    fn from(err: ResolutionCacheError) -> Self {
+        user_error_with_message("Failed to access recorded conflict resolutions", err)
+    }
+}
+
 impl From<ConfigFileSaveError> for CommandError {
     fn from(err: ConfigFileSaveError) -> Self {
         user_error(err)
@@ -321,6 +334,16 @@ impl From<BackendError> for CommandError {
     fn from(err: BackendError) -> Self {
         match &err {
             BackendError::Unsupported(_) => user_error(err),
+            #[cfg(feature = "git")]
+            BackendError::ReadObject { source, .. } | BackendError::Other(source)
+                if source.is::<GitLazyFetchError>() =>
+            {
+                user_error_with_hint(
+                    err,
+                    "The repo is a partial clone. Missing file contents are fetched from the \
+                     remote on demand, so the remote has to be reachable.",
+                )
+            }
             _ => internal_error_with_message("Unexpected error from backend", err),
         }
     }
@@ -439,6 +462,7 @@ impl From<DiffRenderError> for CommandError {
             DiffRenderError::Backend(err) => err.into(),
             DiffRenderError::AccessDenied { .. } => user_error(err),
             DiffRenderError::InvalidRepoPath(_) => user_error(err),
+            DiffRenderError::GitAttributes(err) => err.into(),
             DiffRenderError::Io(err) => err.into(),
         }
     }
@@ -451,9 +475,11 @@ impl From<ConflictResolveError> for CommandError {
             ConflictResolveError::Io(err) => err.into(),
             _ => {
                 let hint = match &err {
-                    ConflictResolveError::ConflictTooComplicated { .. } => {
-                        Some("Edit the conflict markers manually to resolve this.".to_owned())
-                    }
+                    ConflictResolveError::ConflictTooComplicated { .. } => Some(
+                        "Edit the conflict markers manually or use the builtin merge editor \
+                         (`--tool :builtin`) to resolve this."
+                            .to_owned(),
+                    ),
                     ConflictResolveError::ExecutableConflict { .. } => {
                         Some("Use `jj file chmod` to update the executable bit.".to_owned())
                     }
@@ -521,10 +547,14 @@ mod git {
     use jj_lib::git::GitExportError;
     use jj_lib::git::GitFetchError;
     use jj_lib::git::GitImportError;
+    use jj_lib::git::GitNoteError;
     use jj_lib::git::GitPushError;
     use jj_lib::git::GitRemoteManagementError;
     use jj_lib::git::GitResetHeadError;
+    use jj_lib::git::GitTagError;
     use jj_lib::git::UnexpectedGitBackendError;
+    use jj_lib::git_lfs::GitLfsError;
+    use jj_lib::git_submodule::GitSubmoduleError;
 
     use super::*;
 
@@ -535,8 +565,9 @@ mod git {
                 | GitImportError::MissingRefAncestor { .. } => Some(
                     "\
 Is this Git repository a partial clone (cloned with the --filter argument)?
-jj currently does not support partial clones. To use jj with this repository, try re-cloning with \
-                     the full repository contents."
+jj only supports partial clones omitting file contents (e.g. --filter=blob:none). To use jj with \
+                     this repository, try re-cloning with such a filter or the full repository \
+                     contents."
                         .to_string(),
                 ),
                 GitImportError::Backend(_) => None,
@@ -574,6 +605,10 @@ jj currently does not support partial clones. To use jj with this repository, tr
                 ),
                 GitFetchError::InvalidBranchPattern(_) => user_error(err),
                 GitFetchError::Subprocess(_) => user_error(err),
+                #[cfg(feature = "git-builtin-transport")]
+                GitFetchError::Builtin(_) => user_error(err),
+                GitFetchError::TransportUnavailable(_) => user_error(err),
+                GitFetchError::UnexpectedBackend(_) => user_error(err),
             }
         }
     }
@@ -582,11 +617,21 @@ jj currently does not support partial clones. To use jj with this repository, tr
         fn from(err: GitPushError) -> Self {
             match err {
                 GitPushError::NoSuchRemote(_) => user_error(err),
+                GitPushError::BookmarkNotAllowed { ref remote, .. } => {
+                    let hint = format!(
+                        "Update the `git.push-bookmarks.{remote}` setting to allow pushing it.",
+                        remote = remote.as_symbol()
+                    );
+                    user_error_with_hint(err, hint)
+                }
                 GitPushError::RemoteName(_) => user_error_with_hint(
                     err,
                     "Run `jj git remote rename` to give a different name.",
                 ),
                 GitPushError::Subprocess(_) => user_error(err),
+                #[cfg(feature = "git-builtin-transport")]
+                GitPushError::Builtin(_) => user_error(err),
+                GitPushError::TransportUnavailable(_) => user_error(err),
                 GitPushError::UnexpectedBackend(_) => user_error(err),
             }
         }
@@ -598,6 +643,29 @@ jj currently does not support partial clones. To use jj with this repository, tr
         }
     }
 
+    impl From<GitTagError> for CommandError {
+        fn from(err: GitTagError) -> Self {
+            let modified_in_git = matches!(err, GitTagError::ModifiedInGit(_));
+            let mut cmd_err = user_error_with_message("Failed to create annotated tag", err);
+            if modified_in_git {
+                cmd_err.add_hint("Run `jj git import` to import the change from Git first.");
+            }
+            cmd_err
+        }
+    }
+
+    impl From<GitNoteError> for CommandError {
+        fn from(err: GitNoteError) -> Self {
+            match err {
+                GitNoteError::Backend(err) => err.into(),
+                GitNoteError::Git(_) => {
+                    internal_error_with_message("Failed to access Git notes", err)
+                }
+                _ => user_error(err),
+            }
+        }
+    }
+
     impl From<GitResetHeadError> for CommandError {
         fn from(err: GitResetHeadError) -> Self {
             user_error_with_message("Failed to reset Git HEAD state", err)
@@ -609,6 +677,21 @@ jj currently does not support partial clones. To use jj with this repository, tr
             user_error(err)
         }
     }
+
+    impl From<GitSubmoduleError> for CommandError {
+        fn from(err: GitSubmoduleError) -> Self {
+            user_error_with_message("Failed to update Git submodule", err)
+        }
+    }
+
+    impl From<GitLfsError> for CommandError {
+        fn from(err: GitLfsError) -> Self {
+            match err {
+                GitLfsError::Backend(err) => err.into(),
+                _ => user_error_with_message("Failed to transfer Git LFS objects", err),
+            }
+        }
+    }
 }
 
 impl From<RevsetEvaluationError> for CommandError {
@@ -617,6 +700,12 @@ impl From<RevsetEvaluationError> for CommandError {
     }
 }
 
+impl From<FilePredicateUnsupportedError> for CommandError {
+    fn from(err: FilePredicateUnsupportedError) -> Self {
+        user_error(err)
+    }
+}
+
 impl From<FilesetParseError> for CommandError {
     fn from(err: FilesetParseError) -> Self {
         let hint = fileset_parse_error_hint(&err);
@@ -704,6 +793,17 @@ impl From<GitIgnoreError> for CommandError {
     }
 }
 
+impl From<GitAttributesError> for CommandError {
+    fn from(err: GitAttributesError) -> Self {
+        match err {
+            GitAttributesError::Backend(err) => err.into(),
+            GitAttributesError::ReadFile { .. } => {
+                user_error_with_message("Failed to process .gitattributes.", err)
+            }
+        }
+    }
+}
+
 impl From<ParseBulkEditMessageError> for CommandError {
     fn from(err: ParseBulkEditMessageError) -> Self {
         user_error(err)
@@ -719,6 +819,15 @@ impl From<AbsorbError> for CommandError {
     }
 }
 
+impl From<BisectionError> for CommandError {
+    fn from(err: BisectionError) -> Self {
+        match err {
+            BisectionError::Backend(err) => err.into(),
+            BisectionError::RevsetEvaluation(err) => err.into(),
+        }
+    }
+}
+
 impl From<FixError> for CommandError {
     fn from(err: FixError) -> Self {
         match err {
@@ -737,6 +846,8 @@ fn find_source_parse_error_hint(err: &dyn error::Error) -> Option<String> {
     let source = err.source()?;
     if let Some(source) = source.downcast_ref() {
         bookmark_name_parse_error_hint(source)
+    } else if let Some(source) = source.downcast_ref() {
+        tag_name_parse_error_hint(source)
     } else if let Some(source) = source.downcast_ref() {
         config_get_error_hint(source)
     } else if let Some(source) = source.downcast_ref() {
@@ -774,6 +885,14 @@ fn bookmark_name_parse_error_hint(err: &BookmarkNameParseError) -> Option<String
     }
 }
 
+fn tag_name_parse_error_hint(_: &TagNameParseError) -> Option<String> {
+    Some(
+        "See https://jj-vcs.github.io/jj/latest/revsets/ or use `jj help -k revsets` for how to \
+         quote symbols."
+            .into(),
+    )
+}
+
 fn config_get_error_hint(err: &ConfigGetError) -> Option<String> {
     match &err {
         ConfigGetError::NotFound { .. } => None,
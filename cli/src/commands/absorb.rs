@@ -79,7 +79,7 @@ pub(crate) fn cmd_absorb(
 
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+        .to_matcher()?;
 
     let repo = workspace_command.repo().as_ref();
     let source = AbsorbSource::from_commit(repo, source_commit)?;
@@ -0,0 +1,311 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bad;
+mod good;
+mod reset;
+mod run;
+mod skip;
+mod start;
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::bisect::BisectionResult;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo;
+use jj_lib::revset::RevsetExpression;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::instrument;
+
+use self::bad::cmd_bisect_bad;
+use self::bad::BisectBadArgs;
+use self::good::cmd_bisect_good;
+use self::good::BisectGoodArgs;
+use self::reset::cmd_bisect_reset;
+use self::reset::BisectResetArgs;
+use self::run::cmd_bisect_run;
+use self::run::BisectRunArgs;
+use self::skip::cmd_bisect_skip;
+use self::skip::BisectSkipArgs;
+use self::start::cmd_bisect_start;
+use self::start::BisectStartArgs;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Find a bad revision by bisection
+///
+/// Bisection searches a range of revisions for the first revision that
+/// introduced a change in behavior, such as a bug. Ancestors of good revisions
+/// are assumed to be good, and descendants of bad revisions are assumed to be
+/// bad, so only about log2(N) of the N revisions in the range need to be
+/// evaluated.
+///
+/// Use `jj bisect run` to evaluate revisions with a command, or `jj bisect
+/// start` followed by `jj bisect good`, `jj bisect bad`, and `jj bisect skip`
+/// to evaluate them manually.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum BisectCommand {
+    Bad(BisectBadArgs),
+    Good(BisectGoodArgs),
+    Reset(BisectResetArgs),
+    Run(BisectRunArgs),
+    Skip(BisectSkipArgs),
+    Start(BisectStartArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_bisect(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &BisectCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        BisectCommand::Bad(args) => cmd_bisect_bad(ui, command, args),
+        BisectCommand::Good(args) => cmd_bisect_good(ui, command, args),
+        BisectCommand::Reset(args) => cmd_bisect_reset(ui, command, args),
+        BisectCommand::Run(args) => cmd_bisect_run(ui, command, args),
+        BisectCommand::Skip(args) => cmd_bisect_skip(ui, command, args),
+        BisectCommand::Start(args) => cmd_bisect_start(ui, command, args),
+    }
+}
+
+/// State of an interactive bisection, stored in the repo directory.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct BisectState {
+    /// Commits to search, as hex commit IDs.
+    range: Vec<String>,
+    good: Vec<String>,
+    bad: Vec<String>,
+    skipped: Vec<String>,
+    /// The commit that was last checked out for evaluation.
+    current: Option<String>,
+}
+
+impl BisectState {
+    fn new(range: &[CommitId]) -> Self {
+        BisectState {
+            range: range.iter().map(|id| id.hex()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn mark(&mut self, id: &CommitId, evaluation: Evaluation) {
+        let hex = id.hex();
+        self.good.retain(|id| *id != hex);
+        self.bad.retain(|id| *id != hex);
+        self.skipped.retain(|id| *id != hex);
+        match evaluation {
+            Evaluation::Good => self.good.push(hex),
+            Evaluation::Bad => self.bad.push(hex),
+            Evaluation::Skip => self.skipped.push(hex),
+        }
+    }
+
+    fn bisector<'repo>(&self, repo: &'repo dyn Repo) -> Result<Bisector<'repo>, CommandError> {
+        let range = parse_commit_ids(&self.range)?;
+        let mut bisector = Bisector::new(repo, RevsetExpression::commits(range));
+        for id in parse_commit_ids(&self.good)? {
+            bisector.mark_good(id);
+        }
+        for id in parse_commit_ids(&self.bad)? {
+            bisector.mark_bad(id);
+        }
+        for id in parse_commit_ids(&self.skipped)? {
+            bisector.mark_skipped(id);
+        }
+        Ok(bisector)
+    }
+}
+
+fn parse_commit_ids(hexes: &[String]) -> Result<Vec<CommitId>, CommandError> {
+    hexes
+        .iter()
+        .map(|hex| {
+            CommitId::try_from_hex(hex).ok_or_else(|| {
+                internal_error_with_message(
+                    "Failed to read bisection state",
+                    format!("Invalid commit ID: {hex}"),
+                )
+            })
+        })
+        .try_collect()
+}
+
+fn state_path(workspace_command: &WorkspaceCommandHelper) -> PathBuf {
+    workspace_command.repo_path().join("bisect_state.json")
+}
+
+fn load_state(workspace_command: &WorkspaceCommandHelper) -> Result<BisectState, CommandError> {
+    let path = state_path(workspace_command);
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(user_error_with_hint(
+                "No bisection in progress",
+                "Use `jj bisect start` to start one.",
+            ));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    serde_json::from_slice(&data)
+        .map_err(|err| internal_error_with_message("Failed to read bisection state", err))
+}
+
+fn save_state(
+    workspace_command: &WorkspaceCommandHelper,
+    state: &BisectState,
+) -> Result<(), CommandError> {
+    let data = serde_json::to_vec_pretty(state)
+        .map_err(|err| internal_error_with_message("Failed to write bisection state", err))?;
+    fs::write(state_path(workspace_command), data)?;
+    Ok(())
+}
+
+/// Removes the bisection state. Returns whether there was a bisection in
+/// progress.
+fn remove_state(workspace_command: &WorkspaceCommandHelper) -> Result<bool, CommandError> {
+    match fs::remove_file(state_path(workspace_command)) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Resolves the revisions to bisect. The revisions are returned in reverse
+/// topological order.
+fn resolve_range(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    range: &RevisionArg,
+) -> Result<Vec<CommitId>, CommandError> {
+    let ids: Vec<CommitId> = workspace_command
+        .parse_revset(ui, range)?
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    if ids.is_empty() {
+        return Err(user_error("The range to bisect is empty"));
+    }
+    Ok(ids)
+}
+
+/// Finds the next revision to evaluate and checks it out in the working copy.
+fn check_out_next(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    state: &BisectState,
+) -> Result<NextStep, CommandError> {
+    let next_step = state
+        .bisector(workspace_command.repo().as_ref())?
+        .next_step()?;
+    if let NextStep::Evaluate(commit) = &next_step {
+        let mut tx = workspace_command.start_transaction();
+        if let Some(mut formatter) = ui.status_formatter() {
+            write!(formatter, "Now evaluating: ")?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+        tx.check_out(commit)?;
+        tx.finish(
+            ui,
+            format!("update to revision {} for bisection", commit.id().hex()),
+        )?;
+    }
+    Ok(next_step)
+}
+
+fn print_result(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    result: &BisectionResult,
+) -> Result<(), CommandError> {
+    let mut formatter = ui.stdout_formatter();
+    match result {
+        BisectionResult::Indeterminate => {
+            writeln!(
+                formatter,
+                "Search complete. No bad revisions found in the range."
+            )?;
+        }
+        BisectionResult::Found(commits) => {
+            if let [commit] = commits.as_slice() {
+                write!(formatter, "Search complete. The first bad revision is: ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            } else {
+                writeln!(
+                    formatter,
+                    "Search complete. Because of skipped revisions, the first bad revision could \
+                     be any of:"
+                )?;
+                for commit in commits {
+                    write!(formatter, "  ")?;
+                    workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                    writeln!(formatter)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Records the evaluation of a revision in the interactive bisection, and
+/// checks out the next revision to evaluate.
+fn mark_and_continue(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    revision: Option<&RevisionArg>,
+    evaluation: Evaluation,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut state = load_state(&workspace_command)?;
+    let id = if let Some(revision) = revision {
+        workspace_command
+            .resolve_single_rev(ui, revision)?
+            .id()
+            .clone()
+    } else if let Some(current) = &state.current {
+        parse_commit_ids(std::slice::from_ref(current))?.remove(0)
+    } else {
+        return Err(user_error("No revision is being evaluated"));
+    };
+    state.mark(&id, evaluation);
+    match check_out_next(ui, &mut workspace_command, &state)? {
+        NextStep::Evaluate(commit) => {
+            state.current = Some(commit.id().hex());
+            save_state(&workspace_command, &state)?;
+        }
+        NextStep::Done(result) => {
+            state.current = None;
+            save_state(&workspace_command, &state)?;
+            print_result(ui, &workspace_command, &result)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::process::Command;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use super::check_out_next;
+use super::print_result;
+use super::resolve_range;
+use super::BisectState;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Find the first bad revision by running a command
+///
+/// Each revision to evaluate is checked out in the working copy, and the
+/// command is run in the workspace root. The `JJ_COMMIT_ID` and
+/// `JJ_CHANGE_ID` environment variables are set to the IDs of the revision
+/// being evaluated. The exit status of the command decides the evaluation:
+///
+/// * 0: the revision is good
+///
+/// * 125: the revision can't be evaluated and is skipped
+///
+/// * 1-127 (except 125): the revision is bad
+///
+/// * Any other status, or termination by a signal, aborts the bisection
+///
+/// The command isn't run through a shell. Use e.g. `sh -c '...'` to run a
+/// shell command.
+///
+/// # Example
+///
+/// # Find the revision that broke the tests since the v1.0 tag
+/// $ jj bisect run --range 'v1.0..main' -- cargo test
+#[derive(clap::Args, Clone, Debug)]
+pub struct BisectRunArgs {
+    /// The range of revisions to search
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    range: RevisionArg,
+    /// The command to run
+    #[arg(required = true, value_name = "COMMAND")]
+    command: String,
+    /// Arguments to pass to the command
+    #[arg(value_name = "ARGS")]
+    args: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_bisect_run(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectRunArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let range = resolve_range(ui, &workspace_command, &args.range)?;
+    let mut state = BisectState::new(&range);
+    let result = loop {
+        let commit = match check_out_next(ui, &mut workspace_command, &state)? {
+            NextStep::Evaluate(commit) => commit,
+            NextStep::Done(result) => break result,
+        };
+        let mut cmd = Command::new(&args.command);
+        tracing::debug!(?cmd, commit_id = ?commit.id(), "spawning bisect command");
+        let status = cmd
+            .args(&args.args)
+            .current_dir(workspace_command.workspace_root())
+            .env("JJ_COMMIT_ID", commit.id().hex())
+            .env("JJ_CHANGE_ID", commit.change_id().reverse_hex())
+            .status()
+            .map_err(|err| user_error_with_message("Failed to run the command", err))?;
+        let evaluation = match status.code() {
+            Some(0) => Evaluation::Good,
+            Some(125) => Evaluation::Skip,
+            Some(1..=127) => Evaluation::Bad,
+            _ => {
+                return Err(user_error(format!(
+                    "Aborting bisection: the command failed with {status}"
+                )));
+            }
+        };
+        let message = match evaluation {
+            Evaluation::Good => "The revision is good.",
+            Evaluation::Bad => "The revision is bad.",
+            Evaluation::Skip => "The revision is skipped.",
+        };
+        writeln!(ui.status(), "{message}")?;
+        writeln!(ui.status())?;
+        state.mark(commit.id(), evaluation);
+    };
+    print_result(ui, &workspace_command, &result)?;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::bisect::Evaluation;
+use tracing::instrument;
+
+use super::mark_and_continue;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Mark a revision as impossible to evaluate
+///
+/// Use this if the revision can't be evaluated, for example because it
+/// doesn't build. Skipped revisions are only reported if the first bad
+/// revision can't be determined without them.
+///
+/// The next revision to evaluate is then checked out.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BisectSkipArgs {
+    /// The revision to mark [default: the revision being evaluated]
+    #[arg(
+        long,
+        short,
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: Option<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_bisect_skip(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectSkipArgs,
+) -> Result<(), CommandError> {
+    mark_and_continue(ui, command, args.revision.as_ref(), Evaluation::Skip)
+}
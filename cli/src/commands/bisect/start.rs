@@ -0,0 +1,79 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::bisect::NextStep;
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use super::check_out_next;
+use super::print_result;
+use super::resolve_range;
+use super::save_state;
+use super::state_path;
+use super::BisectState;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Start an interactive bisection
+///
+/// The first revision to evaluate is checked out. Evaluate it, for example by
+/// building it and running tests, and then mark it with `jj bisect good`, `jj
+/// bisect bad`, or `jj bisect skip`. Repeat until the first bad revision is
+/// found.
+///
+/// The range is typically given as `GOOD..BAD`, where `GOOD` is a revision
+/// known not to have the change in behavior, and `BAD` is a revision known to
+/// have it.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BisectStartArgs {
+    /// The range of revisions to search
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    range: RevisionArg,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_bisect_start(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectStartArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    if state_path(&workspace_command).exists() {
+        return Err(user_error_with_hint(
+            "A bisection is already in progress",
+            "Use `jj bisect reset` to abandon it.",
+        ));
+    }
+    let range = resolve_range(ui, &workspace_command, &args.range)?;
+    let mut state = BisectState::new(&range);
+    match check_out_next(ui, &mut workspace_command, &state)? {
+        NextStep::Evaluate(commit) => {
+            state.current = Some(commit.id().hex());
+            save_state(&workspace_command, &state)?;
+        }
+        NextStep::Done(result) => {
+            print_result(ui, &workspace_command, &result)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,165 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::RefTarget;
+use jj_lib::str_util::StringPattern;
+
+use super::find_local_bookmarks;
+use super::is_fast_forward;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Advance bookmarks to the target revision
+///
+/// Bookmarks pointing to revisions in the `revsets.bookmark-advance-from`
+/// revset are moved to the target revision. By default, these are the closest
+/// bookmarks that are ancestors of the working-copy parent. Bookmarks are only
+/// moved forward; bookmarks that aren't ancestors of the target revision are
+/// left unchanged.
+///
+/// If bookmark names are given, only the matching bookmarks will be advanced.
+///
+/// Set `experimental-advance-branches.advance-from-revset = true` to advance
+/// the same bookmarks automatically when running `jj commit` or `jj new`.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BookmarkAdvanceArgs {
+    /// Advance only bookmarks matching the given name patterns
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select bookmarks by [wildcard pattern].
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    names: Vec<StringPattern>,
+
+    /// Advance bookmarks to this revision
+    ///
+    /// Defaults to the `revsets.bookmark-advance-to` setting, which is `@` by
+    /// default.
+    #[arg(
+        long, short,
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    to: Option<RevisionArg>,
+}
+
+pub fn cmd_bookmark_advance(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BookmarkAdvanceArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().clone();
+    let target_commit = if let Some(to) = &args.to {
+        workspace_command.resolve_single_rev(ui, to)?
+    } else {
+        let to = workspace_command
+            .settings()
+            .get_string("revsets.bookmark-advance-to")?;
+        workspace_command.resolve_single_rev(ui, &RevisionArg::from(to))?
+    };
+    let matched_bookmarks = {
+        let from = workspace_command
+            .settings()
+            .get_string("revsets.bookmark-advance-from")?;
+        let is_source_commit = workspace_command
+            .parse_revset(ui, &RevisionArg::from(from))?
+            .evaluate()?
+            .containing_fn();
+        let is_source_ref = |target: &RefTarget| -> Result<bool, CommandError> {
+            for id in target.added_ids() {
+                if is_source_commit(id)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        };
+        let bookmarks = if !args.names.is_empty() {
+            find_local_bookmarks(repo.view(), &args.names)?
+        } else {
+            repo.view().local_bookmarks().collect()
+        };
+        let mut bookmarks: Vec<_> = bookmarks
+            .into_iter()
+            .filter_map(|(name, target)| {
+                is_source_ref(target)
+                    .map(|matched| matched.then_some((name, target)))
+                    .transpose()
+            })
+            .try_collect()?;
+        // Bookmarks already at the target don't need to be advanced.
+        bookmarks.retain(|(_, old_target)| old_target.as_normal() != Some(target_commit.id()));
+        bookmarks
+    };
+
+    let (advanced_bookmarks, skipped_bookmarks): (Vec<_>, Vec<_>) =
+        matched_bookmarks.into_iter().partition(|(_, old_target)| {
+            is_fast_forward(repo.as_ref(), old_target, target_commit.id())
+        });
+    if !skipped_bookmarks.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "Not advancing bookmarks that would move backwards or sideways: {names}",
+            names = skipped_bookmarks
+                .iter()
+                .map(|(name, _)| name.as_symbol())
+                .join(", "),
+        )?;
+    }
+    if advanced_bookmarks.is_empty() {
+        writeln!(ui.status(), "No bookmarks to advance.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    for (name, _) in &advanced_bookmarks {
+        tx.repo_mut()
+            .set_local_bookmark_target(name, RefTarget::normal(target_commit.id().clone()));
+    }
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(
+            formatter,
+            "Advanced {} bookmarks to ",
+            advanced_bookmarks.len()
+        )?;
+        tx.write_commit_summary(formatter.as_mut(), &target_commit)?;
+        writeln!(formatter)?;
+    }
+
+    tx.finish(
+        ui,
+        format!(
+            "advance bookmark {names} to commit {id}",
+            names = advanced_bookmarks
+                .iter()
+                .map(|(name, _)| name.as_symbol())
+                .join(", "),
+            id = target_commit.id().hex()
+        ),
+    )?;
+    Ok(())
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod advance;
 mod create;
 mod delete;
 mod forget;
@@ -32,6 +33,8 @@ use jj_lib::repo::Repo;
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
 
+use self::advance::cmd_bookmark_advance;
+use self::advance::BookmarkAdvanceArgs;
 use self::create::cmd_bookmark_create;
 use self::create::BookmarkCreateArgs;
 use self::delete::cmd_bookmark_delete;
@@ -67,6 +70,8 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/bookmarks
 #[derive(clap::Subcommand, Clone, Debug)]
 pub enum BookmarkCommand {
+    #[command(visible_alias("a"))]
+    Advance(BookmarkAdvanceArgs),
     #[command(visible_alias("c"))]
     Create(BookmarkCreateArgs),
     #[command(visible_alias("d"))]
@@ -92,6 +97,7 @@ pub fn cmd_bookmark(
     subcommand: &BookmarkCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        BookmarkCommand::Advance(args) => cmd_bookmark_advance(ui, command, args),
         BookmarkCommand::Create(args) => cmd_bookmark_create(ui, command, args),
         BookmarkCommand::Delete(args) => cmd_bookmark_delete(ui, command, args),
         BookmarkCommand::Forget(args) => cmd_bookmark_forget(ui, command, args),
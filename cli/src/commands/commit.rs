@@ -90,8 +90,9 @@ pub(crate) fn cmd_commit(
     let commit = workspace_command.repo().store().get_commit(commit_id)?;
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
-    let advanceable_bookmarks = workspace_command.get_advanceable_bookmarks(commit.parent_ids())?;
+        .to_matcher()?;
+    let advanceable_bookmarks =
+        workspace_command.get_advanceable_bookmarks(ui, commit.id(), commit.parent_ids())?;
     let diff_selector =
         workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let text_editor = workspace_command.text_editor()?;
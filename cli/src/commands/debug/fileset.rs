@@ -45,7 +45,7 @@ pub fn cmd_debug_fileset(
     writeln!(ui.stdout(), "{expression:#?}")?;
     writeln!(ui.stdout())?;
 
-    let matcher = expression.to_matcher();
+    let matcher = expression.to_matcher()?;
     writeln!(ui.stdout(), "-- Matcher:")?;
     writeln!(ui.stdout(), "{matcher:#?}")?;
     Ok(())
@@ -64,7 +64,7 @@ pub fn cmd_debug_tree(
     };
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+        .to_matcher()?;
     for (path, value) in tree.entries_matching(matcher.as_ref()) {
         let ui_path = workspace_command.format_file_path(&path);
         writeln!(ui.stdout(), "{ui_path}: {value:?}")?;
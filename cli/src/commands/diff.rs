@@ -131,11 +131,11 @@ pub(crate) fn cmd_diff(
     let workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo();
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
-    let matcher = fileset_expression.to_matcher();
 
     let from_tree;
     let to_tree;
-    let mut copy_records = CopyRecords::default();
+    // Pairs of commits to find copies between
+    let mut copy_sources_targets = vec![];
     if args.from.is_some() || args.to.is_some() {
         let resolve_revision = |r: &Option<RevisionArg>| {
             workspace_command.resolve_single_rev(ui, r.as_ref().unwrap_or(&RevisionArg::AT))
@@ -144,9 +144,7 @@ pub(crate) fn cmd_diff(
         let to = resolve_revision(&args.to)?;
         from_tree = from.tree()?;
         to_tree = to.tree()?;
-
-        let records = get_copy_records(repo.store(), from.id(), to.id(), &matcher)?;
-        copy_records.add_records(records)?;
+        copy_sources_targets.push((from.id().clone(), to.id().clone()));
     } else {
         let revision_args = args
             .revisions
@@ -183,12 +181,20 @@ pub(crate) fn cmd_diff(
 
         for p in &parents {
             for to in &heads {
-                let records = get_copy_records(repo.store(), p.id(), to.id(), &matcher)?;
-                copy_records.add_records(records)?;
+                copy_sources_targets.push((p.id().clone(), to.id().clone()));
             }
         }
     }
 
+    let matcher = fileset_expression
+        .to_matcher_with_tree_diff(&from_tree, &to_tree)
+        .block_on()?;
+    let mut copy_records = CopyRecords::default();
+    for (source_id, target_id) in &copy_sources_targets {
+        let records = get_copy_records(repo.store(), source_id, target_id, &matcher)?;
+        copy_records.add_records(records)?;
+    }
+
     // -T disables both short/long rendering formats, but it might be okay to
     // enable long format if explicitly specified (assuming -T is for short or
     // summary output.)
@@ -14,6 +14,7 @@
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use jj_lib::annotate::CopyTracking;
 use jj_lib::annotate::FileAnnotation;
 use jj_lib::annotate::FileAnnotator;
 use jj_lib::repo::Repo;
@@ -33,6 +34,8 @@ use crate::ui::Ui;
 ///
 /// Annotates a revision line by line. Each line includes the source change that
 /// introduced the associated line. A path to the desired file must be provided.
+///
+/// Lines are followed across copies and renames of the file.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileAnnotateArgs {
     /// the file to annotate
@@ -65,6 +68,12 @@ pub(crate) struct FileAnnotateArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    /// Detect lines moved or copied from other files
+    ///
+    /// Lines added to the file are attributed to the origin of identical lines
+    /// removed from other files in the same revision.
+    #[arg(long)]
+    detect_moved_lines: bool,
 }
 
 #[instrument(skip_all)]
@@ -103,6 +112,9 @@ pub(crate) fn cmd_file_annotate(
     // exclude the revisions, but will ignore diffs in those revisions as if
     // ancestor revisions had new content.
     let mut annotator = FileAnnotator::from_commit(&starting_commit, &file_path)?;
+    if args.detect_moved_lines {
+        annotator.set_copy_tracking(CopyTracking::Lines);
+    }
     annotator.compute(repo.as_ref(), &RevsetExpression::all())?;
     let annotation = annotator.to_annotation();
 
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::slice;
+
 use clap_complete::ArgValueCompleter;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -65,7 +68,8 @@ pub(crate) fn cmd_file_list(
     let tree = commit.tree()?;
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+        .to_matcher_with_trees(slice::from_ref(&tree))
+        .block_on()?;
     let template: TemplateRenderer<TreeEntry> = {
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
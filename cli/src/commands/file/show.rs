@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::io::Write as _;
+use std::slice;
 
 use clap_complete::ArgValueCompleter;
 use jj_lib::backend::BackendResult;
@@ -88,7 +89,9 @@ pub(crate) fn cmd_file_show(
         }
     }
 
-    let matcher = fileset_expression.to_matcher();
+    let matcher = fileset_expression
+        .to_matcher_with_trees(slice::from_ref(&tree))
+        .block_on()?;
     ui.request_pager();
     write_tree_entries(
         ui,
@@ -105,7 +108,7 @@ fn get_single_path(expression: &FilesetExpression) -> Option<&RepoPath> {
             // Not using pattern.as_path() because files-in:<path> shouldn't
             // select the literal <path> itself.
             FilePattern::FilePath(path) | FilePattern::PrefixPath(path) => Some(path),
-            FilePattern::FileGlob { .. } => None,
+            FilePattern::FileGlob { .. } | FilePattern::FileGlobI { .. } => None,
         },
         _ => None,
     }
@@ -52,7 +52,7 @@ pub(crate) fn cmd_file_track(
     let (mut workspace_command, auto_stats) = command.workspace_helper_with_stats(ui)?;
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+        .to_matcher()?;
     let options = workspace_command.snapshot_options_with_start_tracking_matcher(&matcher)?;
 
     let mut tx = workspace_command.start_transaction().into_inner();
@@ -30,6 +30,7 @@ use jj_lib::fix::FixError;
 use jj_lib::fix::ParallelFileFixer;
 use jj_lib::matchers::Matcher;
 use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::revset::RevsetExpression;
 use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 use pollster::FutureExt as _;
@@ -140,9 +141,20 @@ pub(crate) fn cmd_fix(
     .evaluate_to_commit_ids()?
     .try_collect()?;
     workspace_command.check_rewritable(root_commits.iter())?;
-    let matcher = workspace_command
-        .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    // File predicates are evaluated against the trees of all commits to fix.
+    let trees: Vec<_> = if fileset_expression.has_file_predicates() {
+        workspace_command
+            .attach_revset_evaluator(RevsetExpression::commits(root_commits.clone()).descendants())
+            .evaluate_to_commits()?
+            .map(|commit| Ok::<_, CommandError>(commit?.tree()?))
+            .try_collect()?
+    } else {
+        vec![]
+    };
+    let matcher = fileset_expression
+        .to_matcher_with_trees(&trees)
+        .block_on()?;
 
     let mut tx = workspace_command.start_transaction();
     let mut parallel_fixer = ParallelFileFixer::new(|store, file_to_fix| {
@@ -354,7 +366,7 @@ fn get_tools_config(ui: &mut Ui, settings: &UserSettings) -> Result<ToolsConfig,
             print_parse_diagnostics(ui, &format!("In `fix.tools.{name}`"), &diagnostics)?;
             Ok(ToolConfig {
                 command: tool.command,
-                matcher: expression.to_matcher(),
+                matcher: expression.to_matcher()?,
                 enabled: tool.enabled,
             })
         })
@@ -20,11 +20,11 @@ use std::path::Path;
 
 use jj_lib::git;
 use jj_lib::git::GitFetch;
+use jj_lib::git::GitFetchDepth;
 use jj_lib::ref_name::RefNameBuf;
 use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteNameBuf;
 use jj_lib::repo::Repo as _;
-use jj_lib::str_util::StringPattern;
 use jj_lib::workspace::Workspace;
 
 use super::write_repository_level_trunk_alias;
@@ -65,6 +65,14 @@ pub struct GitCloneArgs {
     /// Create a shallow clone of the given depth
     #[arg(long)]
     depth: Option<NonZeroU32>,
+    /// Create a partial clone using the given object filter
+    ///
+    /// For example, `--filter=blob:none` omits all file contents from the
+    /// clone. The contents are fetched from the remote when they're needed,
+    /// e.g. when checking out a commit or showing a diff. The filter is also
+    /// applied to subsequent fetches from the remote.
+    #[arg(long, value_name = "FILTER")]
+    filter: Option<String>,
 }
 
 fn clone_destination_for_source(source: &str) -> Option<&str> {
@@ -118,9 +126,16 @@ pub fn cmd_git_clone(
 
     let clone_result = (|| -> Result<_, CommandError> {
         let workspace_command = init_workspace(ui, command, &canonical_wc_path, args.colocate)?;
-        let mut workspace_command =
-            configure_remote(ui, command, workspace_command, remote_name, &source)?;
-        let default_branch = fetch_new_remote(ui, &mut workspace_command, remote_name, args.depth)?;
+        let mut workspace_command = configure_remote(
+            ui,
+            command,
+            workspace_command,
+            remote_name,
+            &source,
+            args.filter.as_deref(),
+        )?;
+        let depth = args.depth.map(GitFetchDepth::Depth);
+        let default_branch = fetch_new_remote(ui, &mut workspace_command, remote_name, depth)?;
         Ok((workspace_command, default_branch))
     })();
     if clone_result.is_err() {
@@ -155,6 +170,17 @@ pub fn cmd_git_clone(
             .view()
             .get_remote_bookmark(default_symbol);
         if let Some(commit_id) = default_branch_remote_ref.target.as_normal().cloned() {
+            // Fetch the file contents missing in a partial clone at once
+            // rather than one by one while checking out.
+            let git_settings = workspace_command.settings().git_settings()?;
+            with_remote_git_callbacks(ui, |cb| {
+                git::fetch_missing_objects(
+                    workspace_command.repo().store(),
+                    &git_settings,
+                    &commit_id,
+                    cb,
+                )
+            })?;
             let mut tx = workspace_command.start_transaction();
             if let Ok(commit) = tx.repo().store().get_commit(&commit_id) {
                 tx.check_out(&commit)?;
@@ -188,10 +214,28 @@ fn configure_remote(
     workspace_command: WorkspaceCommandHelper,
     remote_name: &RemoteName,
     source: &str,
+    filter: Option<&str>,
+) -> Result<WorkspaceCommandHelper, CommandError> {
+    git::add_remote(workspace_command.repo().store(), remote_name, source, &[])?;
+    let mut workspace_command = reload_workspace(ui, command, workspace_command)?;
+    if let Some(filter) = filter {
+        git::set_remote_partial_clone_filter(
+            workspace_command.repo().store(),
+            remote_name,
+            filter,
+        )?;
+        workspace_command = reload_workspace(ui, command, workspace_command)?;
+    }
+    Ok(workspace_command)
+}
+
+/// Reloads workspace to apply new remote configuration to
+/// gix::ThreadSafeRepository behind the store.
+fn reload_workspace(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace_command: WorkspaceCommandHelper,
 ) -> Result<WorkspaceCommandHelper, CommandError> {
-    git::add_remote(workspace_command.repo().store(), remote_name, source)?;
-    // Reload workspace to apply new remote configuration to
-    // gix::ThreadSafeRepository behind the store.
     let workspace = command.load_workspace_at(
         workspace_command.workspace_root(),
         workspace_command.settings(),
@@ -207,7 +251,7 @@ fn fetch_new_remote(
     ui: &Ui,
     workspace_command: &mut WorkspaceCommandHelper,
     remote_name: &RemoteName,
-    depth: Option<NonZeroU32>,
+    depth: Option<GitFetchDepth>,
 ) -> Result<Option<RefNameBuf>, CommandError> {
     writeln!(
         ui.status(),
@@ -219,8 +263,9 @@ fn fetch_new_remote(
     let track_default = settings.get_bool("git.track-default-bookmark-on-clone")?;
     let mut tx = workspace_command.start_transaction();
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
+    let branch_names = git_fetch.default_branch_patterns(remote_name)?;
     with_remote_git_callbacks(ui, |cb| {
-        git_fetch.fetch(remote_name, &[StringPattern::everything()], cb, depth)
+        git_fetch.fetch(remote_name, &branch_names, cb, depth)
     })?;
     let default_branch = git_fetch.get_default_branch(remote_name)?;
     let import_stats = git_fetch.import_refs()?;
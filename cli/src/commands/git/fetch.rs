@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::num::NonZeroU32;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::git;
 use jj_lib::git::GitFetch;
+use jj_lib::git::GitFetchDepth;
 use jj_lib::ref_name::RemoteName;
 use jj_lib::repo::Repo as _;
 use jj_lib::str_util::StringPattern;
@@ -27,6 +30,7 @@ use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::config_error;
+use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::commands::git::get_single_remote;
@@ -46,10 +50,14 @@ pub struct GitFetchArgs {
     /// By default, the specified name matches exactly. Use `glob:` prefix to
     /// expand `*` as a glob, e.g. `--branch 'glob:push-*'`. Other wildcard
     /// characters such as `?` are *not* supported.
+    ///
+    /// If not specified, the bookmarks configured by the
+    /// `git.fetch-bookmarks.<remote>` setting are fetched. If that is not
+    /// configured, the bookmarks specified by the fetch refspecs of the remote
+    /// (e.g. `jj git remote add --fetch`) are fetched.
     #[arg(
         long, short,
         alias = "bookmark",
-        default_value = "glob:*",
         value_parser = StringPattern::parse,
         add = ArgValueCandidates::new(complete::bookmarks),
     )]
@@ -76,6 +84,15 @@ pub struct GitFetchArgs {
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
+    /// Deepen the history of a shallow clone by the given number of commits
+    #[arg(long, value_name = "N")]
+    deepen: Option<NonZeroU32>,
+    /// Also fetch Git notes refs (`refs/notes/*`)
+    ///
+    /// Notes refs are fast-forwarded to the remote notes. Use `jj note show`
+    /// or the `notes()` template method to see the notes.
+    #[arg(long)]
+    notes: bool,
 }
 
 #[tracing::instrument(skip_all)]
@@ -118,8 +135,9 @@ pub fn cmd_git_fetch(
         .sorted()
         .collect_vec();
 
+    let depth = args.deepen.map(GitFetchDepth::Deepen);
     let mut tx = workspace_command.start_transaction();
-    do_git_fetch(ui, &mut tx, &remotes, &args.branch)?;
+    do_git_fetch(ui, &mut tx, &remotes, &args.branch, depth, args.notes)?;
     tx.finish(
         ui,
         format!(
@@ -127,6 +145,30 @@ pub fn cmd_git_fetch(
             remotes.iter().map(|n| n.as_symbol()).join(",")
         ),
     )?;
+    if depth.is_some() {
+        reindex_deepened_history(command, &workspace_command)?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the commit index after deepening a shallow clone.
+///
+/// The former shallow roots were indexed without parents, so the index has to
+/// be rebuilt from a freshly loaded store which sees their parents.
+fn reindex_deepened_history(
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<(), CommandError> {
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = repo_loader.load_operation(workspace_command.repo().op_id())?;
+    let index_store = repo_loader.index_store();
+    if let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() {
+        default_index_store.reinit().map_err(internal_error)?;
+        default_index_store
+            .build_index_at_operation(&op, repo_loader.store())
+            .map_err(internal_error)?;
+    }
     Ok(())
 }
 
@@ -169,14 +211,26 @@ fn do_git_fetch(
     tx: &mut WorkspaceCommandTransaction,
     remotes: &[&RemoteName],
     branch_names: &[StringPattern],
+    depth: Option<GitFetchDepth>,
+    notes: bool,
 ) -> Result<(), CommandError> {
     let git_settings = tx.settings().git_settings()?;
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
 
     for remote_name in remotes {
+        let branch_names = if branch_names.is_empty() {
+            &git_fetch.default_branch_patterns(remote_name)?
+        } else {
+            branch_names
+        };
         with_remote_git_callbacks(ui, |callbacks| {
-            git_fetch.fetch(remote_name, branch_names, callbacks, None)
+            git_fetch.fetch(remote_name, branch_names, callbacks, depth)
         })?;
+        if notes {
+            with_remote_git_callbacks(ui, |callbacks| {
+                git_fetch.fetch_notes(remote_name, callbacks)
+            })?;
+        }
     }
     let import_stats = git_fetch.import_refs()?;
     print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
@@ -0,0 +1,119 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "git-builtin-transport")]
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+#[cfg(feature = "git-builtin-transport")]
+use itertools::Itertools as _;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::git_lfs;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::ref_name::RemoteNameBuf;
+#[cfg(feature = "git-builtin-transport")]
+use pollster::FutureExt as _;
+
+#[cfg(feature = "git-builtin-transport")]
+use super::lfs_store_and_client;
+#[cfg(feature = "git-builtin-transport")]
+use super::revisions_lfs_pointers;
+#[cfg(feature = "git-builtin-transport")]
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+#[cfg(feature = "git-builtin-transport")]
+use crate::command_error::user_error;
+#[cfg(feature = "git-builtin-transport")]
+use crate::command_error::CommandError;
+use crate::complete;
+#[cfg(feature = "git-builtin-transport")]
+use crate::ui::Ui;
+
+/// Download Git LFS objects
+///
+/// Downloads the LFS objects referenced by the given revisions that aren't in
+/// the local LFS store. The pointer files in the working copy are then
+/// replaced by the downloaded content.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitLfsFetchArgs {
+    /// The remote to download from
+    ///
+    /// If there are multiple remotes, the remote named "origin" will be used
+    /// by default.
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<RemoteNameBuf>,
+    /// Download the objects referenced by these revisions
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+pub fn cmd_git_lfs_fetch(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitLfsFetchArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let (lfs_store, client) = lfs_store_and_client(&workspace_command, args.remote.as_deref())?;
+    let pointers = revisions_lfs_pointers(ui, &workspace_command, &args.revisions)?;
+    let missing_pointers: Vec<_> = pointers
+        .into_iter()
+        .map(|pointer| Ok((lfs_store.open(&pointer)?.is_none(), pointer)))
+        .filter_ok(|(is_missing, _)| *is_missing)
+        .map_ok(|(_, pointer)| pointer)
+        .try_collect::<_, _, CommandError>()?;
+    if missing_pointers.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+    } else {
+        client.download(&lfs_store, &missing_pointers)?;
+        writeln!(
+            ui.status(),
+            "Downloaded {} LFS objects",
+            missing_pointers.len()
+        )?;
+    }
+
+    // Replace the pointer files in the working copy. The working-copy commit
+    // doesn't change since the content is recorded as the same pointers.
+    if workspace_command.get_wc_commit_id().is_none() {
+        return Ok(());
+    }
+    let repo = workspace_command.repo().clone();
+    let workspace_root = workspace_command.workspace_root().to_owned();
+    let (locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
+    let tree = wc_commit.tree()?;
+    let mut num_updated = 0;
+    for (path, pointer) in git_lfs::tree_lfs_pointers(&tree, &EverythingMatcher).block_on()? {
+        let disk_path = path.to_fs_path(&workspace_root).map_err(user_error)?;
+        if lfs_store.smudge_file(&pointer, &disk_path)? {
+            num_updated += 1;
+        }
+    }
+    locked_ws.finish(repo.op_id().clone())?;
+    if num_updated > 0 {
+        writeln!(
+            ui.status(),
+            "Updated {num_updated} files in the working copy"
+        )?;
+    }
+    Ok(())
+}
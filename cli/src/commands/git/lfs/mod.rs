@@ -0,0 +1,132 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod fetch;
+mod push;
+
+use clap::Subcommand;
+#[cfg(feature = "git-builtin-transport")]
+use indexmap::IndexSet;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::git;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::git_lfs;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::git_lfs::LfsClient;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::git_lfs::LfsObjectStore;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::git_lfs::LfsPointer;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::matchers::EverythingMatcher;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::ref_name::RemoteName;
+#[cfg(feature = "git-builtin-transport")]
+use jj_lib::repo::Repo as _;
+#[cfg(feature = "git-builtin-transport")]
+use pollster::FutureExt as _;
+
+#[cfg(feature = "git-builtin-transport")]
+use self::fetch::cmd_git_lfs_fetch;
+use self::fetch::GitLfsFetchArgs;
+#[cfg(feature = "git-builtin-transport")]
+use self::push::cmd_git_lfs_push;
+use self::push::GitLfsPushArgs;
+#[cfg(feature = "git-builtin-transport")]
+use super::get_single_remote;
+use crate::cli_util::CommandHelper;
+#[cfg(feature = "git-builtin-transport")]
+use crate::cli_util::RevisionArg;
+#[cfg(feature = "git-builtin-transport")]
+use crate::cli_util::WorkspaceCommandHelper;
+#[cfg(not(feature = "git-builtin-transport"))]
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+#[cfg(feature = "git-builtin-transport")]
+const DEFAULT_REMOTE: &RemoteName = RemoteName::new("origin");
+
+/// Manage Git LFS objects
+///
+/// The content of files with the `filter=lfs` attribute in `.gitattributes`
+/// is stored in the local LFS store, and the files are recorded as LFS pointer
+/// files. When the working copy is updated, the pointer files are replaced by
+/// the content if it's in the local LFS store.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GitLfsCommand {
+    Fetch(GitLfsFetchArgs),
+    Push(GitLfsPushArgs),
+}
+
+#[cfg(feature = "git-builtin-transport")]
+pub fn cmd_git_lfs(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GitLfsCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GitLfsCommand::Fetch(args) => cmd_git_lfs_fetch(ui, command, args),
+        GitLfsCommand::Push(args) => cmd_git_lfs_push(ui, command, args),
+    }
+}
+
+#[cfg(not(feature = "git-builtin-transport"))]
+pub fn cmd_git_lfs(
+    _ui: &mut Ui,
+    _command: &CommandHelper,
+    _subcommand: &GitLfsCommand,
+) -> Result<(), CommandError> {
+    Err(user_error(
+        "Cannot transfer LFS objects because jj was not compiled with the `git-builtin-transport` \
+         feature",
+    ))
+}
+
+/// Returns the local LFS store and the client for the LFS server of the
+/// remote.
+#[cfg(feature = "git-builtin-transport")]
+fn lfs_store_and_client(
+    workspace_command: &WorkspaceCommandHelper,
+    remote: Option<&RemoteName>,
+) -> Result<(LfsObjectStore, LfsClient), CommandError> {
+    let store = workspace_command.repo().store();
+    let git_repo = git::get_git_repo(store)?;
+    let remote = match remote {
+        Some(remote) => remote.to_owned(),
+        None => get_single_remote(store)?.unwrap_or_else(|| DEFAULT_REMOTE.to_owned()),
+    };
+    let client = LfsClient::for_remote(&git_repo, &remote)?;
+    let lfs_store = LfsObjectStore::for_store(store).unwrap();
+    Ok((lfs_store, client))
+}
+
+/// Returns the LFS pointers in the trees of the revisions.
+#[cfg(feature = "git-builtin-transport")]
+fn revisions_lfs_pointers(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    revisions: &[RevisionArg],
+) -> Result<Vec<LfsPointer>, CommandError> {
+    let mut pointers = IndexSet::new();
+    let commits = workspace_command
+        .parse_union_revsets(ui, revisions)?
+        .evaluate_to_commits()?;
+    for commit in commits {
+        let tree = commit?.tree()?;
+        let tree_pointers = git_lfs::tree_lfs_pointers(&tree, &EverythingMatcher).block_on()?;
+        pointers.extend(tree_pointers.into_iter().map(|(_, pointer)| pointer));
+    }
+    Ok(pointers.into_iter().collect())
+}
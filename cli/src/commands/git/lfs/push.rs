@@ -0,0 +1,93 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "git-builtin-transport")]
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use jj_lib::ref_name::RemoteNameBuf;
+
+#[cfg(feature = "git-builtin-transport")]
+use super::lfs_store_and_client;
+#[cfg(feature = "git-builtin-transport")]
+use super::revisions_lfs_pointers;
+#[cfg(feature = "git-builtin-transport")]
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+#[cfg(feature = "git-builtin-transport")]
+use crate::command_error::CommandError;
+use crate::complete;
+#[cfg(feature = "git-builtin-transport")]
+use crate::ui::Ui;
+
+/// Upload Git LFS objects
+///
+/// Uploads the LFS objects referenced by the given revisions to the LFS server
+/// of the remote. Objects are not uploaded by `jj git push`, so this should be
+/// run before pushing the revisions.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitLfsPushArgs {
+    /// The remote to upload to
+    ///
+    /// If there are multiple remotes, the remote named "origin" will be used
+    /// by default.
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<RemoteNameBuf>,
+    /// Upload the objects referenced by these revisions
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+pub fn cmd_git_lfs_push(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitLfsPushArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let (lfs_store, client) = lfs_store_and_client(&workspace_command, args.remote.as_deref())?;
+    let mut pointers = vec![];
+    let mut num_missing = 0;
+    for pointer in revisions_lfs_pointers(ui, &workspace_command, &args.revisions)? {
+        if lfs_store.open(&pointer)?.is_some() {
+            pointers.push(pointer);
+        } else {
+            num_missing += 1;
+        }
+    }
+    if num_missing > 0 {
+        writeln!(
+            ui.warning_default(),
+            "Skipped {num_missing} LFS objects which aren't in the local LFS store"
+        )?;
+    }
+    let num_uploaded = if pointers.is_empty() {
+        0
+    } else {
+        client.upload(&lfs_store, &pointers)?
+    };
+    if num_uploaded == 0 {
+        writeln!(ui.status(), "Nothing changed.")?;
+    } else {
+        writeln!(ui.status(), "Uploaded {num_uploaded} LFS objects")?;
+    }
+    Ok(())
+}
@@ -17,9 +17,11 @@ mod export;
 mod fetch;
 mod import;
 mod init;
+mod lfs;
 mod push;
 mod remote;
 mod root;
+mod submodule;
 
 use std::path::Path;
 
@@ -42,12 +44,16 @@ use self::import::cmd_git_import;
 use self::import::GitImportArgs;
 use self::init::cmd_git_init;
 use self::init::GitInitArgs;
+use self::lfs::cmd_git_lfs;
+use self::lfs::GitLfsCommand;
 use self::push::cmd_git_push;
 use self::push::GitPushArgs;
 use self::remote::cmd_git_remote;
 use self::remote::RemoteCommand;
 use self::root::cmd_git_root;
 use self::root::GitRootArgs;
+use self::submodule::cmd_git_submodule;
+use self::submodule::GitSubmoduleCommand;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::user_error_with_message;
@@ -70,10 +76,14 @@ pub enum GitCommand {
     Fetch(GitFetchArgs),
     Import(GitImportArgs),
     Init(GitInitArgs),
+    #[command(subcommand)]
+    Lfs(GitLfsCommand),
     Push(GitPushArgs),
     #[command(subcommand)]
     Remote(RemoteCommand),
     Root(GitRootArgs),
+    #[command(subcommand)]
+    Submodule(GitSubmoduleCommand),
 }
 
 pub fn cmd_git(
@@ -87,9 +97,11 @@ pub fn cmd_git(
         GitCommand::Fetch(args) => cmd_git_fetch(ui, command, args),
         GitCommand::Import(args) => cmd_git_import(ui, command, args),
         GitCommand::Init(args) => cmd_git_init(ui, command, args),
+        GitCommand::Lfs(args) => cmd_git_lfs(ui, command, args),
         GitCommand::Push(args) => cmd_git_push(ui, command, args),
         GitCommand::Remote(args) => cmd_git_remote(ui, command, args),
         GitCommand::Root(args) => cmd_git_root(ui, command, args),
+        GitCommand::Submodule(args) => cmd_git_submodule(ui, command, args),
     }
 }
 
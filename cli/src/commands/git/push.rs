@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
@@ -31,7 +32,10 @@ use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::git;
 use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitPushStats;
+use jj_lib::git::GitRemoteTag;
+use jj_lib::git::GitTagPushUpdate;
 use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::GitRefNameBuf;
 use jj_lib::ref_name::RefName;
 use jj_lib::ref_name::RefNameBuf;
 use jj_lib::ref_name::RemoteName;
@@ -43,6 +47,7 @@ use jj_lib::refs::BookmarkPushUpdate;
 use jj_lib::refs::LocalAndRemoteRef;
 use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::settings::GitSettings;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::SignBehavior;
 use jj_lib::str_util::StringPattern;
@@ -78,6 +83,8 @@ use crate::ui::Ui;
 /// bookmarks. Use `--remote` to select the remote Git repository by name. There
 /// is no option to push to multiple remotes.
 ///
+/// Use `--tag` to push tags. Tags aren't pushed unless explicitly specified.
+///
 /// Before the command actually moves, creates, or deletes a remote bookmark, it
 /// makes several [safety checks]. If there is a problem, you may need to run
 /// `jj git fetch --remote <remote name>` and/or resolve some [bookmark
@@ -90,7 +97,7 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/bookmarks/#conflicts
 
 #[derive(clap::Args, Clone, Debug)]
-#[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "revisions", "named"]).multiple(true)))]
+#[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "revisions", "named", "tag"]).multiple(true)))]
 #[command(group(ArgGroup::new("what").args(&["all", "tracked"]).conflicts_with("specific")))]
 pub struct GitPushArgs {
     /// The remote to push to (only named remotes are supported)
@@ -188,6 +195,49 @@ pub struct GitPushArgs {
         add = ArgValueCompleter::new(complete::branch_name_equals_any_revision)
     )]
     named: Vec<String>,
+    /// Push only this tag, or tags matching a pattern (can be repeated)
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select tags by [wildcard pattern]. If a tag specified by exact name
+    /// doesn't exist locally, it will be deleted from the remote. Tags on the
+    /// remote pointing to different objects will be overwritten.
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets#string-patterns
+    #[arg(
+        long,
+        value_name = "TAG",
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::local_tags),
+    )]
+    tag: Vec<StringPattern>,
+    /// Also push all Git notes refs (`refs/notes/*`)
+    ///
+    /// The remote notes refs are only updated if that's a fast-forward. If no
+    /// bookmarks or tags are specified, only the notes refs are pushed.
+    #[arg(long, conflicts_with = "to")]
+    notes: bool,
+    /// Push the bookmark to this ref on the remote instead of the bookmark of
+    /// the same name, e.g. '--to refs/for/main'
+    ///
+    /// Requires a single bookmark specified by `--bookmark`. A name not
+    /// starting with `refs/` is taken as a branch name. The remote ref is only
+    /// updated if that's a fast-forward. The remote-tracking bookmark isn't
+    /// updated.
+    #[arg(
+        long,
+        value_name = "REF",
+        requires = "bookmark",
+        conflicts_with_all = ["change", "revisions", "named", "tag"],
+    )]
+    to: Option<String>,
+    /// Transmit the given string to the server as a push option (can be
+    /// repeated)
+    ///
+    /// The options are passed to `git push --push-option`, after the ones
+    /// configured by the `git.push-options` setting.
+    #[arg(long, short = 'o', value_name = "OPTION")]
+    push_option: Vec<String>,
     /// Only display what will change on the remote
     #[arg(long)]
     dry_run: bool,
@@ -200,6 +250,13 @@ fn make_bookmark_term(bookmark_names: &[impl fmt::Display]) -> String {
     }
 }
 
+fn make_tag_term(tag_names: &[impl fmt::Display]) -> String {
+    match tag_names {
+        [tag_name] => format!("tag {tag_name}"),
+        tag_names => format!("tags {}", tag_names.iter().join(", ")),
+    }
+}
+
 const DEFAULT_REMOTE: &RemoteName = RemoteName::new("origin");
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -225,13 +282,19 @@ pub fn cmd_git_push(
     };
 
     let mut tx = workspace_command.start_transaction();
+    if let Some(to) = &args.to {
+        return push_bookmark_to_remote_ref(ui, tx, remote, args, to);
+    }
+    let git_settings = push_git_settings(&tx, args)?;
     let view = tx.repo().view();
     let tx_description;
     let mut bookmark_updates = vec![];
+    let mut tag_updates = vec![];
     if args.all {
         for (name, targets) in view.local_remote_bookmarks(remote) {
             let allow_new = true; // implied by --all
             match classify_bookmark_update(
+                &git_settings,
                 name.to_remote_symbol(remote),
                 targets,
                 allow_new,
@@ -253,6 +316,7 @@ pub fn cmd_git_push(
             }
             let allow_new = false; // doesn't matter
             match classify_bookmark_update(
+                &git_settings,
                 name.to_remote_symbol(remote),
                 targets,
                 allow_new,
@@ -275,6 +339,7 @@ pub fn cmd_git_push(
             let allow_new = false; // doesn't matter
             let allow_delete = true;
             match classify_bookmark_update(
+                &git_settings,
                 name.to_remote_symbol(remote),
                 targets,
                 allow_new,
@@ -318,7 +383,13 @@ pub fn cmd_git_push(
             }
             let allow_new = true; // --change implies creation of remote bookmark
             let allow_delete = false; // doesn't matter
-            match classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete) {
+            match classify_bookmark_update(
+                &git_settings,
+                remote_symbol,
+                targets,
+                allow_new,
+                allow_delete,
+            ) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
                 Ok(None) => writeln!(
                     ui.status(),
@@ -338,7 +409,13 @@ pub fn cmd_git_push(
             }
             let remote_symbol = name.to_remote_symbol(remote);
             let allow_delete = true; // named explicitly, allow delete without --delete
-            match classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete) {
+            match classify_bookmark_update(
+                &git_settings,
+                remote_symbol,
+                targets,
+                allow_new,
+                allow_delete,
+            ) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
                 Ok(None) => writeln!(
                     ui.status(),
@@ -352,7 +429,9 @@ pub fn cmd_git_push(
         let use_default_revset = args.bookmark.is_empty()
             && args.change.is_empty()
             && args.revisions.is_empty()
-            && args.named.is_empty();
+            && args.named.is_empty()
+            && args.tag.is_empty()
+            && !args.notes;
         let bookmarks_targeted = find_bookmarks_targeted_by_revisions(
             ui,
             tx.base_workspace_helper(),
@@ -366,6 +445,7 @@ pub fn cmd_git_push(
             }
             let allow_delete = false;
             match classify_bookmark_update(
+                &git_settings,
                 name.to_remote_symbol(remote),
                 targets,
                 allow_new,
@@ -377,18 +457,43 @@ pub fn cmd_git_push(
             }
         }
 
-        tx_description = format!(
-            "push {names} to git remote {remote}",
-            names = make_bookmark_term(
+        if !args.tag.is_empty() {
+            let remote_tags = git::list_remote_tags(tx.repo(), &git_settings, remote)?;
+            tag_updates = find_tag_updates_to_push(ui, tx.repo(), remote, &args.tag, &remote_tags)?;
+        }
+
+        let mut terms = vec![];
+        if !bookmark_updates.is_empty() {
+            terms.push(make_bookmark_term(
                 &bookmark_updates
                     .iter()
                     .map(|(name, _)| name.as_symbol())
-                    .collect_vec()
-            ),
+                    .collect_vec(),
+            ));
+        }
+        if !tag_updates.is_empty() {
+            terms.push(make_tag_term(
+                &tag_updates
+                    .iter()
+                    .map(|update: &TagPushUpdate| update.git_update.name.as_symbol())
+                    .collect_vec(),
+            ));
+        }
+        if args.notes {
+            terms.push("notes".to_owned());
+        }
+        tx_description = format!(
+            "push {names} to git remote {remote}",
+            names = terms.join(" and "),
             remote = remote.as_symbol()
         );
     }
-    if bookmark_updates.is_empty() {
+    let notes_refs = if args.notes {
+        find_notes_refs_to_push(tx.repo().view())
+    } else {
+        vec![]
+    };
+    if bookmark_updates.is_empty() && tag_updates.is_empty() && notes_refs.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
@@ -398,31 +503,28 @@ pub fn cmd_git_push(
     } else {
         None
     };
+    let bookmark_heads = bookmark_updates
+        .iter()
+        .filter_map(|(_, update)| update.new_target.clone())
+        .collect_vec();
     let commits_to_sign =
-        validate_commits_ready_to_push(ui, &bookmark_updates, remote, &tx, args, sign_behavior)?;
-    if !args.dry_run && !commits_to_sign.is_empty() {
-        if let Some(sign_behavior) = sign_behavior {
-            let num_updated_signatures = commits_to_sign.len();
-            let num_rebased_descendants;
-            (num_rebased_descendants, bookmark_updates) = sign_commits_before_push(
-                &mut tx,
-                commits_to_sign,
-                sign_behavior,
-                bookmark_updates,
-            )?;
-            if let Some(mut formatter) = ui.status_formatter() {
-                writeln!(
-                    formatter,
-                    "Updated signatures of {num_updated_signatures} commits"
-                )?;
-                if num_rebased_descendants > 0 {
-                    writeln!(
-                        formatter,
-                        "Rebased {num_rebased_descendants} descendant commits"
-                    )?;
-                }
-            }
-        }
+        validate_commits_ready_to_push(ui, bookmark_heads, remote, &tx, args, sign_behavior)?;
+    if !tag_updates.is_empty() {
+        // Tagged commits can't be rewritten without moving the tags.
+        let tag_heads = tag_updates
+            .iter()
+            .filter_map(|update| update.new_target.clone())
+            .collect_vec();
+        validate_commits_ready_to_push(ui, tag_heads, remote, &tx, args, None)?;
+    }
+    if !args.dry_run {
+        bookmark_updates = sign_commits_for_push(
+            ui,
+            &mut tx,
+            commits_to_sign,
+            sign_behavior,
+            bookmark_updates,
+        )?;
     }
 
     if let Some(mut formatter) = ui.status_formatter() {
@@ -432,6 +534,15 @@ pub fn cmd_git_push(
             remote = remote.as_symbol()
         )?;
         print_commits_ready_to_push(formatter.as_mut(), tx.repo(), &bookmark_updates)?;
+        print_tags_ready_to_push(formatter.as_mut(), &tag_updates)?;
+        for (full_name, id) in &notes_refs {
+            writeln!(
+                formatter,
+                "  Push notes ref {full_name} at {id}",
+                full_name = full_name.as_str(),
+                id = short_commit_hash(id)
+            )?;
+        }
     }
 
     if args.dry_run {
@@ -439,21 +550,189 @@ pub fn cmd_git_push(
         return Ok(());
     }
 
-    let targets = GitBranchPushTargets {
-        branch_updates: bookmark_updates,
+    // Tags are pushed first because failing to push them after the bookmarks
+    // would leave the pushed bookmarks unrecorded.
+    if !tag_updates.is_empty() {
+        let updates = tag_updates
+            .into_iter()
+            .map(|update| update.git_update)
+            .collect_vec();
+        let push_stats = with_remote_git_callbacks(ui, |cb| {
+            git::push_tags(tx.repo(), &git_settings, remote, &updates, cb)
+        })?;
+        process_push_stats(&push_stats, "tag")?;
+    }
+    if !notes_refs.is_empty() {
+        let push_stats = with_remote_git_callbacks(ui, |cb| {
+            git::push_notes(tx.repo(), &git_settings, remote, cb)
+        })?;
+        process_push_stats(&push_stats, "notes ref")?;
+    }
+    if !bookmark_updates.is_empty() {
+        let targets = GitBranchPushTargets {
+            branch_updates: bookmark_updates,
+        };
+        let push_stats = with_remote_git_callbacks(ui, |cb| {
+            git::push_branches(tx.repo_mut(), &git_settings, remote, &targets, cb)
+        })?;
+        process_push_stats(&push_stats, "bookmark")?;
+    }
+    // Remote tags aren't recorded in the repo view.
+    if tx.repo().has_changes() {
+        tx.finish(ui, tx_description)?;
+    }
+    Ok(())
+}
+
+/// Pushes a single bookmark to the ref specified by `--to`.
+fn push_bookmark_to_remote_ref(
+    ui: &Ui,
+    mut tx: WorkspaceCommandTransaction,
+    remote: &RemoteName,
+    args: &GitPushArgs,
+    to: &str,
+) -> Result<(), CommandError> {
+    let qualified_name: GitRefNameBuf = if to.starts_with("refs/") {
+        to.into()
+    } else {
+        format!("refs/heads/{to}").into()
+    };
+    let view = tx.repo().view();
+    let bookmarks = find_bookmarks_to_push(view, &args.bookmark, remote)?;
+    let (name, local_target) = match &bookmarks[..] {
+        [(name, targets), rest @ ..] if rest.iter().all(|(other, _)| other == name) => {
+            ((*name).to_owned(), targets.local_target)
+        }
+        _ => {
+            return Err(user_error_with_hint(
+                "Can't push multiple bookmarks to a single remote ref",
+                "Specify a single bookmark with --bookmark.",
+            ));
+        }
+    };
+    let git_settings = push_git_settings(&tx, args)?;
+    if !git::is_push_allowed_bookmark(&git_settings, remote, &name) {
+        return Err(user_error_with_hint(
+            format!(
+                "Bookmark {name} is not allowed to be pushed to remote {remote}",
+                name = name.as_symbol(),
+                remote = remote.as_symbol()
+            ),
+            format!(
+                "Update the `git.push-bookmarks.{remote}` setting to allow pushing it.",
+                remote = remote.as_symbol()
+            ),
+        ));
+    }
+    let new_target = match local_target.as_normal() {
+        Some(id) => id.clone(),
+        None if local_target.has_conflict() => {
+            return Err(user_error_with_hint(
+                format!("Bookmark {name} is conflicted", name = name.as_symbol()),
+                "Run `jj bookmark list` to inspect, and use `jj bookmark set` to fix it up.",
+            ));
+        }
+        None => {
+            return Err(user_error(format!(
+                "Bookmark {name} doesn't exist locally",
+                name = name.as_symbol()
+            )));
+        }
+    };
+
+    let sign_behavior = if tx.settings().get_bool("git.sign-on-push")? {
+        Some(SignBehavior::Own)
+    } else {
+        None
+    };
+    let commits_to_sign = validate_commits_ready_to_push(
+        ui,
+        vec![new_target.clone()],
+        remote,
+        &tx,
+        args,
+        sign_behavior,
+    )?;
+    let new_target = if args.dry_run {
+        new_target
+    } else {
+        let update = BookmarkPushUpdate {
+            old_target: None,
+            new_target: Some(new_target),
+        };
+        let bookmark_updates = vec![(name.clone(), update)];
+        let bookmark_updates = sign_commits_for_push(
+            ui,
+            &mut tx,
+            commits_to_sign,
+            sign_behavior,
+            bookmark_updates,
+        )?;
+        let (_, update) = bookmark_updates.into_iter().exactly_one().unwrap();
+        update.new_target.unwrap()
     };
-    let git_settings = tx.settings().git_settings()?;
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(
+            formatter,
+            "Changes to push to {remote}:",
+            remote = remote.as_symbol()
+        )?;
+        writeln!(
+            formatter,
+            "  Push bookmark {name} at {new} to {qualified_name}",
+            name = name.as_symbol(),
+            new = short_commit_hash(&new_target),
+            qualified_name = qualified_name.as_str()
+        )?;
+    }
+
+    if args.dry_run {
+        writeln!(ui.status(), "Dry-run requested, not pushing.")?;
+        return Ok(());
+    }
+
     let push_stats = with_remote_git_callbacks(ui, |cb| {
-        git::push_branches(tx.repo_mut(), &git_settings, remote, &targets, cb)
+        git::push_to_remote_ref(
+            tx.repo(),
+            &git_settings,
+            remote,
+            &qualified_name,
+            &new_target,
+            cb,
+        )
     })?;
-    process_push_stats(&push_stats)?;
-    tx.finish(ui, tx_description)?;
+    process_push_stats(&push_stats, "bookmark")?;
+    // The remote ref isn't recorded in the repo view, but commits may have
+    // been signed.
+    if tx.repo().has_changes() {
+        tx.finish(
+            ui,
+            format!(
+                "push bookmark {name} to {qualified_name} on git remote {remote}",
+                name = name.as_symbol(),
+                qualified_name = qualified_name.as_str(),
+                remote = remote.as_symbol()
+            ),
+        )?;
+    }
     Ok(())
 }
 
-fn process_push_stats(push_stats: &GitPushStats) -> Result<(), CommandError> {
+fn push_git_settings(
+    tx: &WorkspaceCommandTransaction,
+    args: &GitPushArgs,
+) -> Result<GitSettings, CommandError> {
+    let mut git_settings = tx.settings().git_settings()?;
+    git_settings
+        .push_options
+        .extend(args.push_option.iter().cloned());
+    Ok(git_settings)
+}
+
+fn process_push_stats(push_stats: &GitPushStats, ref_kind: &str) -> Result<(), CommandError> {
     if !push_stats.all_ok() {
-        let mut error = user_error("Failed to push some bookmarks");
+        let mut error = user_error(format!("Failed to push some {ref_kind}s"));
         if !push_stats.rejected.is_empty() {
             error.add_formatted_hint_with(|formatter| {
                 writeln!(
@@ -470,10 +749,10 @@ fn process_push_stats(push_stats: &GitPushStats) -> Result<(), CommandError> {
                 }
                 Ok(())
             });
-            error.add_hint(
-                "Try fetching from the remote, then make the bookmark point to where you want it \
-                 to be, and push again.",
-            );
+            error.add_hint(format!(
+                "Try fetching from the remote, then make the {ref_kind} point to where you want \
+                 it to be, and push again."
+            ));
         }
         if !push_stats.remote_rejected.is_empty() {
             error.add_formatted_hint_with(|formatter| {
@@ -488,7 +767,9 @@ fn process_push_stats(push_stats: &GitPushStats) -> Result<(), CommandError> {
                 }
                 Ok(())
             });
-            error.add_hint("Try checking if you have permission to push to all the bookmarks.");
+            error.add_hint(format!(
+                "Try checking if you have permission to push to all the {ref_kind}s."
+            ));
         }
         Err(error)
     } else {
@@ -502,7 +783,7 @@ fn process_push_stats(push_stats: &GitPushStats) -> Result<(), CommandError> {
 /// Returns the list of commits which need to be signed.
 fn validate_commits_ready_to_push(
     ui: &Ui,
-    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+    new_heads: Vec<CommitId>,
     remote: &RemoteName,
     tx: &WorkspaceCommandTransaction,
     args: &GitPushArgs,
@@ -511,10 +792,6 @@ fn validate_commits_ready_to_push(
     let workspace_helper = tx.base_workspace_helper();
     let repo = workspace_helper.repo();
 
-    let new_heads = bookmark_updates
-        .iter()
-        .filter_map(|(_, update)| update.new_target.clone())
-        .collect_vec();
     let old_heads = repo
         .view()
         .remote_bookmarks(remote)
@@ -593,6 +870,41 @@ fn validate_commits_ready_to_push(
     Ok(commits_to_sign)
 }
 
+/// Signs commits before pushing if there are commits to sign.
+///
+/// Returns the updated list of bookmark names and corresponding
+/// [`BookmarkPushUpdate`]s.
+fn sign_commits_for_push(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    commits_to_sign: Vec<Commit>,
+    sign_behavior: Option<SignBehavior>,
+    bookmark_updates: Vec<(RefNameBuf, BookmarkPushUpdate)>,
+) -> Result<Vec<(RefNameBuf, BookmarkPushUpdate)>, CommandError> {
+    let Some(sign_behavior) = sign_behavior else {
+        return Ok(bookmark_updates);
+    };
+    if commits_to_sign.is_empty() {
+        return Ok(bookmark_updates);
+    }
+    let num_updated_signatures = commits_to_sign.len();
+    let (num_rebased_descendants, bookmark_updates) =
+        sign_commits_before_push(tx, commits_to_sign, sign_behavior, bookmark_updates)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(
+            formatter,
+            "Updated signatures of {num_updated_signatures} commits"
+        )?;
+        if num_rebased_descendants > 0 {
+            writeln!(
+                formatter,
+                "Rebased {num_rebased_descendants} descendant commits"
+            )?;
+        }
+    }
+    Ok(bookmark_updates)
+}
+
 /// Signs commits before pushing.
 ///
 /// Returns the number of commits with rebased descendants and the updated list
@@ -706,6 +1018,54 @@ fn print_commits_ready_to_push(
     Ok(())
 }
 
+/// Returns the notes refs to be pushed by `git::push_notes()`.
+fn find_notes_refs_to_push(view: &View) -> Vec<(GitRefNameBuf, CommitId)> {
+    view.notes()
+        .iter()
+        .filter_map(|(name, target)| {
+            let full_name = format!("refs/notes/{name}", name = name.as_str());
+            Some((full_name.into(), target.as_normal()?.clone()))
+        })
+        .collect()
+}
+
+fn print_tags_ready_to_push(
+    formatter: &mut dyn Formatter,
+    tag_updates: &[TagPushUpdate],
+) -> io::Result<()> {
+    for update in tag_updates {
+        let tag_name = update.git_update.name.as_symbol();
+        match (&update.old_target, &update.new_target) {
+            (Some(old_target), Some(new_target)) => {
+                writeln!(
+                    formatter,
+                    "  Move tag {tag_name} from {old} to {new}",
+                    old = short_commit_hash(old_target),
+                    new = short_commit_hash(new_target)
+                )?;
+            }
+            (Some(old_target), None) => {
+                writeln!(
+                    formatter,
+                    "  Delete tag {tag_name} from {old}",
+                    old = short_commit_hash(old_target)
+                )?;
+            }
+            (None, Some(new_target)) => {
+                writeln!(
+                    formatter,
+                    "  Add tag {tag_name} to {new}",
+                    new = short_commit_hash(new_target)
+                )?;
+            }
+            (None, None) => {
+                panic!("Not pushing any change to tag {tag_name}");
+            }
+        }
+    }
+    Ok(())
+}
+
 fn get_default_push_remote(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
@@ -754,6 +1114,7 @@ impl From<RejectedBookmarkUpdateReason> for CommandError {
 }
 
 fn classify_bookmark_update(
+    git_settings: &GitSettings,
     remote_symbol: RemoteRefSymbol<'_>,
     targets: LocalAndRemoteRef,
     allow_new: bool,
@@ -782,6 +1143,25 @@ fn classify_bookmark_update(
                 "Run `jj bookmark track {remote_symbol}` to import the remote bookmark."
             )),
         }),
+        BookmarkPushAction::Update(_)
+            if !git::is_push_allowed_bookmark(
+                git_settings,
+                remote_symbol.remote,
+                remote_symbol.name,
+            ) =>
+        {
+            Err(RejectedBookmarkUpdateReason {
+                message: format!(
+                    "Bookmark {name} is not allowed to be pushed to remote {remote}",
+                    name = remote_symbol.name.as_symbol(),
+                    remote = remote_symbol.remote.as_symbol()
+                ),
+                hint: Some(format!(
+                    "Update the `git.push-bookmarks.{remote}` setting to allow pushing it.",
+                    remote = remote_symbol.remote.as_symbol()
+                )),
+            })
+        }
         BookmarkPushAction::Update(update) if update.old_target.is_none() && !allow_new => {
             Err(RejectedBookmarkUpdateReason {
                 message: format!("Refusing to create new remote bookmark {remote_symbol}"),
@@ -957,6 +1337,91 @@ fn find_bookmarks_to_push<'a>(
     }
 }
 
+/// Tag update to be pushed, with the commits the tag refers to before and
+/// after the update.
+#[derive(Clone, Debug)]
+struct TagPushUpdate {
+    git_update: GitTagPushUpdate,
+    old_target: Option<CommitId>,
+    new_target: Option<CommitId>,
+}
+
+fn find_tag_updates_to_push(
+    ui: &Ui,
+    repo: &dyn Repo,
+    remote: &RemoteName,
+    tag_patterns: &[StringPattern],
+    remote_tags: &BTreeMap<RefNameBuf, GitRemoteTag>,
+) -> Result<Vec<TagPushUpdate>, CommandError> {
+    let view = repo.view();
+    let mut matching_tags = vec![];
+    let mut unmatched_patterns = vec![];
+    for pattern in tag_patterns {
+        let mut matches = view.tags_matching(pattern).peekable();
+        if matches.peek().is_some() {
+            matching_tags.extend(matches);
+        } else if let Some(name) = pattern
+            .as_exact()
+            .map(RefName::new)
+            .filter(|name| remote_tags.contains_key(*name))
+        {
+            // Tag named explicitly, but deleted locally
+            matching_tags.push((name, RefTarget::absent_ref()));
+        } else {
+            unmatched_patterns.push(pattern);
+        }
+    }
+    match &unmatched_patterns[..] {
+        [] => {}
+        [pattern] if pattern.is_exact() => {
+            return Err(user_error(format!("No such tag: {pattern}")));
+        }
+        patterns => {
+            return Err(user_error(format!(
+                "No matching tags for patterns: {}",
+                patterns.iter().join(", ")
+            )));
+        }
+    }
+    matching_tags.sort_unstable_by_key(|(name, _)| *name);
+    matching_tags.dedup_by_key(|(name, _)| *name);
+
+    let mut tag_updates = vec![];
+    for (name, local_target) in matching_tags {
+        if local_target.has_conflict() {
+            return Err(user_error_with_hint(
+                format!("Tag {name} is conflicted", name = name.as_symbol()),
+                "Run `jj tag list` to inspect, and use `jj tag set --allow-move` to fix it up.",
+            ));
+        }
+        let remote_tag = remote_tags.get(name);
+        let new_target = local_target.as_normal();
+        let new_object_id = new_target
+            .map(|id| git::local_tag_object_id(repo, name, id))
+            .transpose()?;
+        let old_object_id = remote_tag.map(|tag| tag.object_id);
+        if new_object_id == old_object_id {
+            writeln!(
+                ui.status(),
+                "Tag {remote_symbol} already matches {name}",
+                remote_symbol = name.to_remote_symbol(remote),
+                name = name.as_symbol()
+            )?;
+            continue;
+        }
+        tag_updates.push(TagPushUpdate {
+            git_update: GitTagPushUpdate {
+                name: name.to_owned(),
+                old_object_id,
+                new_object_id,
+            },
+            old_target: remote_tag.map(|tag| tag.target.clone()),
+            new_target: new_target.cloned(),
+        });
+    }
+    Ok(tag_updates)
+}
+
 fn find_bookmarks_targeted_by_revisions<'a>(
     ui: &Ui,
     workspace_command: &'a WorkspaceCommandHelper,
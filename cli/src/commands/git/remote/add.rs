@@ -15,6 +15,7 @@
 use jj_lib::git;
 use jj_lib::ref_name::RemoteNameBuf;
 use jj_lib::repo::Repo as _;
+use jj_lib::str_util::StringPattern;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -31,6 +32,14 @@ pub struct GitRemoteAddArgs {
     /// Local path will be resolved to absolute form.
     #[arg(value_hint = clap::ValueHint::Url)]
     url: String,
+    /// Fetch only the bookmarks matching the pattern from the remote (can be
+    /// repeated)
+    ///
+    /// By default, all bookmarks are fetched. The patterns are recorded as the
+    /// fetch refspecs of the remote, so only exact names and `glob:` patterns
+    /// with `*` are supported, e.g. `--fetch main --fetch 'glob:team/*'`.
+    #[arg(long, value_name = "PATTERN", value_parser = StringPattern::parse)]
+    fetch: Vec<StringPattern>,
 }
 
 pub fn cmd_git_remote_add(
@@ -40,6 +49,11 @@ pub fn cmd_git_remote_add(
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
     let url = absolute_git_url(command.cwd(), &args.url)?;
-    git::add_remote(workspace_command.repo().store(), &args.remote, &url)?;
+    git::add_remote(
+        workspace_command.repo().store(),
+        &args.remote,
+        &url,
+        &args.fetch,
+    )?;
     Ok(())
 }
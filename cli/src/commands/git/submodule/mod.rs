@@ -0,0 +1,44 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod update;
+
+use clap::Subcommand;
+
+use self::update::cmd_git_submodule_update;
+use self::update::GitSubmoduleUpdateArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage Git submodules
+///
+/// Submodule repositories are stored inside the `.jj/` directory. The commits
+/// recorded in the working-copy commit are checked out when the working copy
+/// is updated, and moving the `HEAD` of a submodule updates the recorded
+/// commit on the next snapshot.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GitSubmoduleCommand {
+    Update(GitSubmoduleUpdateArgs),
+}
+
+pub fn cmd_git_submodule(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GitSubmoduleCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GitSubmoduleCommand::Update(args) => cmd_git_submodule_update(ui, command, args),
+    }
+}
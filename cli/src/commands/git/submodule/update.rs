@@ -0,0 +1,155 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::backend::TreeValue;
+use jj_lib::conflicts::materialize_tree_value;
+use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::git_submodule;
+use jj_lib::git_submodule::GitSubmodule;
+use jj_lib::git_submodule::SubmoduleCheckout;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use pollster::FutureExt as _;
+
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::git_util::with_remote_git_callbacks;
+use crate::ui::Ui;
+
+/// Initialize and check out Git submodules
+///
+/// Submodules declared in the `.gitmodules` file of the working-copy commit
+/// are cloned into the `.jj/` directory if they haven't been initialized yet.
+/// The commits recorded in the working-copy commit are then checked out,
+/// fetching from the submodule remotes if needed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitSubmoduleUpdateArgs {
+    /// Only update submodules matching these prefixes (instead of all
+    /// submodules)
+    #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    paths: Vec<String>,
+}
+
+pub fn cmd_git_submodule_update(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitSubmoduleUpdateArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let commit = repo.store().get_commit(commit_id)?;
+    let tree = commit.tree()?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher()?;
+    let git_settings = workspace_command.settings().git_settings()?;
+
+    let gitmodules_path = RepoPath::from_internal_string(".gitmodules").unwrap();
+    let gitmodules_value = tree.path_value(gitmodules_path)?;
+    let submodules: Vec<GitSubmodule> =
+        match materialize_tree_value(repo.store(), gitmodules_path, gitmodules_value).block_on()? {
+            MaterializedTreeValue::Absent => vec![],
+            MaterializedTreeValue::File(mut file) => {
+                let content = file.read_all(gitmodules_path).block_on()?;
+                git_submodule::parse_gitmodules(&content)?
+            }
+            _ => return Err(user_error(".gitmodules is not a regular file")),
+        };
+
+    let mut num_matched = 0;
+    for submodule in submodules
+        .iter()
+        .filter(|submodule| matcher.matches(&submodule.path))
+    {
+        num_matched += 1;
+        let ui_path = workspace_command.format_file_path(&submodule.path);
+        let commit_id = match tree.path_value(&submodule.path)?.into_resolved() {
+            Ok(Some(TreeValue::GitSubmodule(id))) => id,
+            Ok(_) => {
+                writeln!(
+                    ui.warning_default(),
+                    "No submodule commit recorded at {ui_path}"
+                )?;
+                continue;
+            }
+            Err(_) => {
+                writeln!(ui.warning_default(), "Submodule {ui_path} is conflicted")?;
+                continue;
+            }
+        };
+        let worktree = submodule
+            .path
+            .to_fs_path(workspace_command.workspace_root())
+            .map_err(user_error)?;
+        if git_submodule::submodule_git_dir(&worktree)?.is_none() {
+            writeln!(
+                ui.status(),
+                "Cloning submodule {name} into {ui_path}",
+                name = submodule.name
+            )?;
+            with_remote_git_callbacks(ui, |callbacks| {
+                git_submodule::init_submodule(
+                    repo.as_ref(),
+                    &git_settings,
+                    submodule,
+                    &worktree,
+                    callbacks,
+                )
+            })?;
+        }
+        let old_head_id = git_submodule::submodule_head_id(&worktree)?;
+        let mut checkout = git_submodule::check_out_submodule(&worktree, &commit_id)?;
+        if let SubmoduleCheckout::MissingCommit(_) = checkout {
+            with_remote_git_callbacks(ui, |callbacks| {
+                git_submodule::fetch_submodule(&git_settings, &worktree, callbacks)
+            })?;
+            checkout = git_submodule::check_out_submodule(&worktree, &commit_id)?;
+        }
+        match checkout {
+            SubmoduleCheckout::CheckedOut(_) => {
+                if old_head_id.as_ref() != Some(&commit_id) {
+                    writeln!(
+                        ui.status(),
+                        "Checked out submodule {ui_path} at {hash}",
+                        hash = short_commit_hash(&commit_id)
+                    )?;
+                }
+            }
+            SubmoduleCheckout::MissingCommit(_) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Commit {hash} doesn't exist in submodule {ui_path}",
+                    hash = short_commit_hash(&commit_id)
+                )?;
+            }
+            SubmoduleCheckout::Modified(_) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Submodule {ui_path} has uncommitted changes; not checking out {hash}",
+                    hash = short_commit_hash(&commit_id)
+                )?;
+            }
+            SubmoduleCheckout::Uninitialized => unreachable!(),
+        }
+    }
+    if num_matched == 0 {
+        writeln!(ui.status(), "No submodules to update.")?;
+    }
+    Ok(())
+}
@@ -12,29 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::ops::Range;
+
+use bstr::BStr;
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::annotate::find_line_range_changes;
+use jj_lib::annotate::LineRangeChange;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
-use jj_lib::repo::Repo as _;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt as _;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::format_template;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::diff_util::show_line_range_diff;
 use crate::diff_util::DiffFormatArgs;
+use crate::formatter::Formatter;
 use crate::graphlog::get_graphlog;
 use crate::graphlog::GraphStyle;
 use crate::templater::TemplateRenderer;
@@ -114,10 +127,57 @@ pub(crate) struct LogArgs {
     /// Show patch
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Show revisions modifying the given range of lines
+    ///
+    /// The range is specified as `START,END:PATH`, where `START` and `END` are
+    /// 1-based line numbers in the file at the working-copy commit. `END` can
+    /// also be specified as `+COUNT`. The lines are tracked back through the
+    /// history, and the changes to the lines are shown for each revision.
+    ///
+    /// Combined with `--revisions`, only the matching revisions are shown.
+    #[arg(
+        long = "line-range",
+        short = 'L',
+        value_name = "START,END:PATH",
+        value_parser = parse_line_range,
+        conflicts_with = "paths"
+    )]
+    line_ranges: Vec<LineRangeArg>,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
 
+/// Range of lines in a file, as specified by `--line-range`.
+#[derive(Clone, Debug)]
+struct LineRangeArg {
+    /// 0-based range of the line numbers.
+    line_range: Range<usize>,
+    path: String,
+}
+
+fn parse_line_range(s: &str) -> Result<LineRangeArg, String> {
+    let error = || "Expected a line range of the form `START,END:PATH`".to_owned();
+    let (range, path) = s.split_once(':').ok_or_else(error)?;
+    let (start, end) = range.split_once(',').ok_or_else(error)?;
+    let start: usize = start.parse().map_err(|_| error())?;
+    let end: usize = if let Some(count) = end.strip_prefix('+') {
+        let count: usize = count.parse().map_err(|_| error())?;
+        start + count.saturating_sub(1)
+    } else {
+        end.parse().map_err(|_| error())?
+    };
+    if start == 0 || end < start {
+        return Err(format!("Invalid line range: {range}"));
+    }
+    if path.is_empty() {
+        return Err(error());
+    }
+    Ok(LineRangeArg {
+        line_range: start - 1..end,
+        path: path.to_owned(),
+    })
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_log(
     ui: &mut Ui,
@@ -128,9 +188,27 @@ pub(crate) fn cmd_log(
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let line_range_changes = if args.line_ranges.is_empty() {
+        None
+    } else {
+        Some(find_changes_in_line_ranges(
+            ui,
+            &workspace_command,
+            &args.line_ranges,
+        )?)
+    };
     let revset_expression = {
         // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
+        let mut expression = if let Some(changes) = &line_range_changes {
+            let commit_ids = changes.keys().cloned().collect();
+            let mut expression =
+                workspace_command.attach_revset_evaluator(RevsetExpression::commits(commit_ids));
+            if !args.revisions.is_empty() {
+                let revisions = workspace_command.parse_union_revsets(ui, &args.revisions)?;
+                expression.intersect_with(revisions.expression());
+            }
+            expression
+        } else if args.revisions.is_empty() && args.paths.is_empty() {
             let revset_string = settings.get_string("revsets.log")?;
             workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
         } else if !args.revisions.is_empty() {
@@ -151,7 +229,17 @@ pub(crate) fn cmd_log(
     let prio_revset = workspace_command.parse_revset(ui, &RevisionArg::from(prio_revset))?;
 
     let repo = workspace_command.repo();
-    let matcher = fileset_expression.to_matcher();
+    // File predicates are evaluated against the changes made by each commit.
+    let diff_matcher = |commit: &Commit| -> Result<Box<dyn Matcher>, CommandError> {
+        if !fileset_expression.has_file_predicates() {
+            return Ok(fileset_expression.to_matcher()?);
+        }
+        let from_tree = commit.parent_tree(repo.as_ref())?;
+        let to_tree = commit.tree()?;
+        Ok(fileset_expression
+            .to_matcher_with_tree_diff(&from_tree, &to_tree)
+            .block_on()?)
+    };
     let revset = revset_expression.evaluate()?;
 
     let store = repo.store();
@@ -250,6 +338,7 @@ pub(crate) fn cmd_log(
                 }
                 if let Some(renderer) = &diff_renderer {
                     let mut formatter = ui.new_formatter(&mut buffer);
+                    let matcher = diff_matcher(&commit)?;
                     renderer.show_patch(
                         ui,
                         formatter.as_mut(),
@@ -258,6 +347,13 @@ pub(crate) fn cmd_log(
                         within_graph.width(),
                     )?;
                 }
+                if let Some(changes) = line_range_changes
+                    .as_ref()
+                    .and_then(|changes| changes.get(commit.id()))
+                {
+                    let mut formatter = ui.new_formatter(&mut buffer);
+                    show_line_range_changes(formatter.as_mut(), repo.as_ref(), &commit, changes)?;
+                }
 
                 let node_symbol = format_template(ui, &Some(commit), &node_template);
                 graph.add_node(
@@ -301,8 +397,15 @@ pub(crate) fn cmd_log(
                     .write(formatter, |formatter| template.format(&commit, formatter))?;
                 if let Some(renderer) = &diff_renderer {
                     let width = ui.term_width();
+                    let matcher = diff_matcher(&commit)?;
                     renderer.show_patch(ui, formatter, &commit, matcher.as_ref(), width)?;
                 }
+                if let Some(changes) = line_range_changes
+                    .as_ref()
+                    .and_then(|changes| changes.get(commit.id()))
+                {
+                    show_line_range_changes(formatter, repo.as_ref(), &commit, changes)?;
+                }
             }
         }
     }
@@ -333,3 +436,69 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+/// Finds the revisions modifying the given line ranges, starting from the
+/// working-copy commit.
+fn find_changes_in_line_ranges(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    line_ranges: &[LineRangeArg],
+) -> Result<HashMap<CommitId, Vec<(RepoPathBuf, LineRangeChange)>>, CommandError> {
+    let repo = workspace_command.repo();
+    let starting_commit = workspace_command.resolve_single_rev(ui, &RevisionArg::AT)?;
+    let mut changes_by_commit: HashMap<_, Vec<_>> = HashMap::new();
+    for LineRangeArg { line_range, path } in line_ranges {
+        let file_path = workspace_command.parse_file_path(path)?;
+        let file_value = starting_commit.tree()?.path_value(&file_path)?;
+        let ui_path = workspace_command.format_file_path(&file_path);
+        if file_value.is_absent() {
+            return Err(user_error(format!("No such path: {ui_path}")));
+        }
+        if file_value.is_tree() {
+            return Err(user_error(format!(
+                "Path exists but is not a regular file: {ui_path}"
+            )));
+        }
+        let changes = find_line_range_changes(
+            repo.as_ref(),
+            &starting_commit,
+            &file_path,
+            line_range.clone(),
+            &RevsetExpression::all(),
+        )?;
+        for change in changes {
+            changes_by_commit
+                .entry(change.new.commit_id.clone())
+                .or_default()
+                .push((file_path.clone(), change));
+        }
+    }
+    Ok(changes_by_commit)
+}
+
+fn show_line_range_changes(
+    formatter: &mut dyn Formatter,
+    repo: &dyn Repo,
+    commit: &Commit,
+    changes: &[(RepoPathBuf, LineRangeChange)],
+) -> Result<(), CommandError> {
+    let parent_tree = commit.parent_tree(repo)?;
+    for (path, change) in changes {
+        // The lines may have been added to an existing file
+        let old_text = match &change.old {
+            Some(old) => Some(old.text.as_ref()),
+            None if parent_tree.path_value(path)?.is_present() => Some(BStr::new("")),
+            None => None,
+        };
+        show_line_range_diff(
+            formatter,
+            path.as_internal_file_string(),
+            [old_text, Some(change.new.text.as_ref())],
+            [
+                change.old.as_ref().map_or(0, |old| old.line_range.start),
+                change.new.line_range.start,
+            ],
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,247 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::Signature;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::trailer::parse_trailers;
+use jj_lib::trailer::Trailer;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::description_util::add_trailers_to_description;
+use crate::description_util::remove_trailers_from_description;
+use crate::text_util::parse_author;
+use crate::ui::Ui;
+
+/// Modify the metadata of revisions without changing their content
+///
+/// The author, committer, change ID, and trailers of the revisions can be
+/// updated. Descendants of the modified revisions are rebased onto the new
+/// revisions.
+///
+/// Example: credit a pair programming partner
+///
+/// $ jj metaedit --add-trailer 'Co-authored-by: Foo Bar <foo@bar.com>'
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct MetaeditArgs {
+    /// The revision(s) to modify (default: @)
+    #[arg(
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable)
+    )]
+    revisions_pos: Vec<RevisionArg>,
+    #[arg(
+        short = 'r',
+        hide = true,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable)
+    )]
+    revisions_opt: Vec<RevisionArg>,
+    /// Generate a new change ID
+    ///
+    /// This can be used to turn one of a set of divergent revisions into a
+    /// separate change.
+    #[arg(long)]
+    update_change_id: bool,
+    /// Update the author to the configured user
+    ///
+    /// This updates the author name and email. The author timestamp is
+    /// retained.
+    #[arg(long)]
+    update_author: bool,
+    /// Set author to the provided string
+    ///
+    /// This changes author name and email while retaining author timestamp.
+    #[arg(long, conflicts_with = "update_author", value_parser = parse_author)]
+    author: Option<(String, String)>,
+    /// Update the author timestamp to the current time
+    #[arg(long)]
+    update_author_timestamp: bool,
+    /// Set the author timestamp to the provided RFC 3339 date-time
+    ///
+    /// Example: 2000-01-23T01:23:45+09:00
+    #[arg(
+        long,
+        value_name = "TIMESTAMP",
+        conflicts_with = "update_author_timestamp",
+        value_parser = parse_timestamp
+    )]
+    author_timestamp: Option<Timestamp>,
+    /// Set committer to the provided string
+    ///
+    /// By default, the committer is the configured user.
+    #[arg(long, value_parser = parse_author)]
+    committer: Option<(String, String)>,
+    /// Add a trailer to the description
+    ///
+    /// The trailer is specified as `Key: Value`. It is not added if the same
+    /// trailer is already present.
+    #[arg(long, value_name = "TRAILER", value_parser = parse_trailer)]
+    add_trailer: Vec<Trailer>,
+    /// Remove trailers with the given key from the description
+    ///
+    /// Keys are matched case-insensitively. Trailers are removed before new
+    /// ones are added with `--add-trailer`.
+    #[arg(long, value_name = "KEY")]
+    remove_trailer: Vec<String>,
+}
+
+fn parse_timestamp(s: &str) -> Result<Timestamp, chrono::ParseError> {
+    DateTime::parse_from_rfc3339(s).map(Timestamp::from_datetime)
+}
+
+fn parse_trailer(s: &str) -> Result<Trailer, String> {
+    match parse_trailers(s) {
+        Ok(trailers) if trailers.len() == 1 => Ok(trailers.into_iter().next().unwrap()),
+        _ => Err("Expected a trailer of the form `Key: Value`".to_owned()),
+    }
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_metaedit(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &MetaeditArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commits: Vec<_> = if !args.revisions_pos.is_empty() || !args.revisions_opt.is_empty() {
+        workspace_command
+            .parse_union_revsets(ui, &[&*args.revisions_pos, &*args.revisions_opt].concat())?
+    } else {
+        workspace_command.parse_revset(ui, &RevisionArg::AT)?
+    }
+    .evaluate_to_commits()?
+    .try_collect()?; // in reverse topological order
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to modify.")?;
+        return Ok(());
+    }
+    workspace_command.check_rewritable(commits.iter().ids())?;
+
+    let mut tx = workspace_command.start_transaction();
+    let tx_description = match commits.as_slice() {
+        [] => unreachable!(),
+        [commit] => format!("edit commit metadata for commit {}", commit.id().hex()),
+        [first_commit, remaining_commits @ ..] => {
+            format!(
+                "edit commit metadata for commit {} and {} more",
+                first_commit.id().hex(),
+                remaining_commits.len()
+            )
+        }
+    };
+
+    // Filter out unchanged commits to avoid rebasing descendants in
+    // `transform_descendants` below unnecessarily.
+    let commit_builders: HashMap<_, _> = commits
+        .iter()
+        .map(|commit| {
+            let mut commit_builder = tx.repo_mut().rewrite_commit(commit).detach();
+            if args.update_change_id {
+                commit_builder.generate_new_change_id();
+            }
+            let mut new_author = commit_builder.author().clone();
+            if args.update_author {
+                new_author.name = commit_builder.committer().name.clone();
+                new_author.email = commit_builder.committer().email.clone();
+            }
+            if let Some((name, email)) = &args.author {
+                new_author.name = name.clone();
+                new_author.email = email.clone();
+            }
+            if args.update_author_timestamp {
+                new_author.timestamp = commit_builder.committer().timestamp;
+            }
+            if let Some(timestamp) = args.author_timestamp {
+                new_author.timestamp = timestamp;
+            }
+            commit_builder.set_author(new_author);
+            if let Some((name, email)) = args.committer.clone() {
+                let new_committer = Signature {
+                    name,
+                    email,
+                    timestamp: commit_builder.committer().timestamp,
+                };
+                commit_builder.set_committer(new_committer);
+            }
+            if !args.remove_trailer.is_empty() || !args.add_trailer.is_empty() {
+                let description =
+                    remove_trailers_from_description(commit.description(), &args.remove_trailer);
+                let description = add_trailers_to_description(&description, &args.add_trailer);
+                commit_builder.set_description(description);
+            }
+            (commit, commit_builder)
+        })
+        .filter(|(old_commit, commit_builder)| {
+            old_commit.change_id() != commit_builder.change_id()
+                || old_commit.description() != commit_builder.description()
+                || old_commit.author().name != commit_builder.author().name
+                || old_commit.author().email != commit_builder.author().email
+                || (args.update_author_timestamp || args.author_timestamp.is_some())
+                    && old_commit.author().timestamp != commit_builder.author().timestamp
+                || args.committer.is_some()
+                    && (old_commit.committer().name != commit_builder.committer().name
+                        || old_commit.committer().email != commit_builder.committer().email)
+        })
+        .map(|(old_commit, commit_builder)| (old_commit.id(), commit_builder))
+        .collect();
+
+    let mut num_modified = 0;
+    let mut num_reparented = 0;
+    tx.repo_mut().transform_descendants(
+        commit_builders.keys().map(|&id| id.clone()).collect(),
+        |mut rewriter| {
+            let old_commit_id = rewriter.old_commit().id().clone();
+            if let Some(temp_builder) = commit_builders.get(&old_commit_id) {
+                let old_commit = rewriter.old_commit().clone();
+                let new_parents = rewriter.new_parents().to_vec();
+                let mut_repo = rewriter.mut_repo();
+                let new_commit = mut_repo
+                    .rewrite_commit(&old_commit)
+                    .set_parents(new_parents)
+                    .set_change_id(temp_builder.change_id().clone())
+                    .set_description(temp_builder.description())
+                    .set_author(temp_builder.author().clone())
+                    .set_committer(temp_builder.committer().clone())
+                    .write()?;
+                // Rewrites to a different change ID aren't recorded
+                // automatically.
+                mut_repo.set_rewritten_commit(old_commit_id, new_commit.id().clone());
+                num_modified += 1;
+            } else {
+                rewriter.reparent().write()?;
+                num_reparented += 1;
+            }
+            Ok(())
+        },
+    )?;
+    if num_modified > 0 {
+        writeln!(ui.status(), "Modified {num_modified} commits")?;
+    }
+    if num_reparented > 0 {
+        writeln!(ui.status(), "Rebased {num_reparented} descendant commits")?;
+    }
+    tx.finish(ui, tx_description)?;
+    Ok(())
+}
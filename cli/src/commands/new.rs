@@ -176,8 +176,11 @@ pub(crate) fn cmd_new(
         let should_advance_bookmarks = parent_commits.len() == 1;
         if should_advance_bookmarks {
             advance_bookmarks_target = Some(parent_commit_ids[0].clone());
-            advanceable_bookmarks =
-                workspace_command.get_advanceable_bookmarks(parent_commits[0].parent_ids())?;
+            advanceable_bookmarks = workspace_command.get_advanceable_bookmarks(
+                ui,
+                &parent_commit_ids[0],
+                parent_commits[0].parent_ids(),
+            )?;
         }
     };
 
@@ -0,0 +1,83 @@
+// Copyright 2020-2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::git;
+use jj_lib::object_id::ObjectId as _;
+
+use super::NoteRefArgs;
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::description_util::join_message_paragraphs;
+use crate::ui::Ui;
+
+/// Add a note to a revision
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteAddArgs {
+    /// The revision to add the note to
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+    /// The note message
+    ///
+    /// Multiple messages are combined as separate paragraphs.
+    #[arg(long = "message", short, value_name = "MESSAGE", required = true)]
+    message_paragraphs: Vec<String>,
+    /// Replace the existing note of the revision
+    #[arg(long, short)]
+    force: bool,
+    #[command(flatten)]
+    notes_ref: NoteRefArgs,
+}
+
+pub fn cmd_note_add(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteAddArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let name = &args.notes_ref.name;
+    if !args.force
+        && git::read_note(workspace_command.repo().as_ref(), name, commit.id())?.is_some()
+    {
+        return Err(user_error_with_hint(
+            format!(
+                "Commit {} already has a note",
+                short_commit_hash(commit.id())
+            ),
+            "Use `--force` to replace it.",
+        ));
+    }
+    let note = join_message_paragraphs(&args.message_paragraphs);
+
+    let mut tx = workspace_command.start_transaction();
+    let signature = tx.settings().signature();
+    git::set_note(tx.repo_mut(), name, commit.id(), Some(&note), &signature)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Added note to ")?;
+        tx.write_commit_summary(formatter.as_mut(), &commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(ui, format!("add note to commit {}", commit.id().hex()))?;
+    Ok(())
+}
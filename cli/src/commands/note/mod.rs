@@ -0,0 +1,78 @@
+// Copyright 2020-2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod add;
+mod remove;
+mod show;
+
+use jj_lib::ref_name::RefNameBuf;
+
+use self::add::cmd_note_add;
+use self::add::NoteAddArgs;
+use self::remove::cmd_note_remove;
+use self::remove::NoteRemoveArgs;
+use self::show::cmd_note_show;
+use self::show::NoteShowArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage Git notes attached to revisions
+///
+/// Notes are stored in notes refs of the underlying Git repo, like `git notes`
+/// does. Unlike descriptions, notes can be added or changed without rewriting
+/// the revisions. Like bookmarks, changes to notes refs are recorded in the
+/// operation log and exported to the Git repo. Use `jj git fetch --notes` and
+/// `jj git push --notes` to transfer notes refs.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum NoteCommand {
+    Add(NoteAddArgs),
+    Remove(NoteRemoveArgs),
+    Show(NoteShowArgs),
+}
+
+pub fn cmd_note(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &NoteCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        NoteCommand::Add(args) => cmd_note_add(ui, command, args),
+        NoteCommand::Remove(args) => cmd_note_remove(ui, command, args),
+        NoteCommand::Show(args) => cmd_note_show(ui, command, args),
+    }
+}
+
+#[derive(clap::Args, Clone, Debug)]
+struct NoteRefArgs {
+    /// The notes ref to use
+    ///
+    /// A name not starting with `refs/notes/` is taken relative to it, e.g.
+    /// `--ref ci` means `refs/notes/ci`.
+    #[arg(
+        long = "ref",
+        value_name = "REF",
+        default_value = "commits",
+        value_parser = parse_notes_ref_name,
+    )]
+    name: RefNameBuf,
+}
+
+fn parse_notes_ref_name(name: &str) -> Result<RefNameBuf, String> {
+    let name = name.strip_prefix("refs/notes/").unwrap_or(name);
+    if name.is_empty() {
+        return Err("Notes ref name must not be empty".to_owned());
+    }
+    Ok(name.into())
+}
@@ -15,6 +15,7 @@
 mod abandon;
 mod diff;
 mod log;
+pub mod redo;
 mod restore;
 mod show;
 pub mod undo;
@@ -24,8 +25,13 @@ use abandon::OperationAbandonArgs;
 use clap::Subcommand;
 use diff::cmd_op_diff;
 use diff::OperationDiffArgs;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::operation::Operation;
 use log::cmd_op_log;
 use log::OperationLogArgs;
+use redo::cmd_op_redo;
+use redo::OperationRedoArgs;
 use restore::cmd_op_restore;
 use restore::OperationRestoreArgs;
 use show::cmd_op_show;
@@ -34,6 +40,7 @@ use undo::cmd_op_undo;
 use undo::OperationUndoArgs;
 
 use crate::cli_util::CommandHelper;
+use crate::command_error::internal_error_with_message;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
 
@@ -48,6 +55,7 @@ pub enum OperationCommand {
     Abandon(OperationAbandonArgs),
     Diff(OperationDiffArgs),
     Log(OperationLogArgs),
+    Redo(OperationRedoArgs),
     Restore(OperationRestoreArgs),
     Show(OperationShowArgs),
     Undo(OperationUndoArgs),
@@ -62,6 +70,7 @@ pub fn cmd_operation(
         OperationCommand::Abandon(args) => cmd_op_abandon(ui, command, args),
         OperationCommand::Diff(args) => cmd_op_diff(ui, command, args),
         OperationCommand::Log(args) => cmd_op_log(ui, command, args),
+        OperationCommand::Redo(args) => cmd_op_redo(ui, command, args),
         OperationCommand::Restore(args) => cmd_op_restore(ui, command, args),
         OperationCommand::Show(args) => cmd_op_show(ui, command, args),
         OperationCommand::Undo(args) => cmd_op_undo(ui, command, args),
@@ -100,9 +109,35 @@ fn view_with_desired_portions_restored(
         head_ids: repo_source.head_ids.clone(),
         local_bookmarks: repo_source.local_bookmarks.clone(),
         tags: repo_source.tags.clone(),
+        notes: repo_source.notes.clone(),
         remote_views: remote_source.remote_views.clone(),
         git_refs: current_view.git_refs.clone(),
         git_head: current_view.git_head.clone(),
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
     }
 }
+
+/// Operation tag recording the ID of the operation undone by `jj undo`.
+const UNDO_OF_TAG: &str = "undo-of";
+
+/// Operation tag recording the ID of the undo operation reverted by `jj redo`.
+const REDO_OF_TAG: &str = "redo-of";
+
+/// Loads the operation whose ID is recorded in the `tag` of `op`, if any.
+fn tagged_operation(op: &Operation, tag: &str) -> Result<Option<Operation>, CommandError> {
+    let Some(hex) = op.metadata().tags.get(tag) else {
+        return Ok(None);
+    };
+    let id = OperationId::try_from_hex(hex).ok_or_else(|| {
+        internal_error_with_message(
+            format!(
+                "Invalid operation ID in {tag} tag of operation {}",
+                op.id().hex()
+            ),
+            hex.clone(),
+        )
+    })?;
+    let op_store = op.op_store();
+    let data = op_store.read_operation(&id)?;
+    Ok(Some(Operation::new(op_store, id, data)))
+}
@@ -0,0 +1,93 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+
+use super::tagged_operation;
+use super::view_with_desired_portions_restored;
+use super::UndoWhatToRestore;
+use super::DEFAULT_UNDO_WHAT;
+use super::REDO_OF_TAG;
+use super::UNDO_OF_TAG;
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Redo the most recently undone operation
+///
+/// This reverts the latest `jj undo`. Repeated `jj redo` commands reapply the
+/// operations undone by earlier `jj undo` commands, in reverse order.
+///
+/// Operations can only be redone until a new operation is performed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationRedoArgs {
+    /// What portions of the local state to restore (can be repeated)
+    ///
+    /// This option is EXPERIMENTAL.
+    #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
+    what: Vec<UndoWhatToRestore>,
+}
+
+pub fn cmd_op_redo(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationRedoArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    // Skip over the undo operations that have already been redone to find the
+    // latest one that hasn't.
+    let mut undo_op = workspace_command.repo().operation().clone();
+    while let Some(redone_undo_op) = tagged_operation(&undo_op, REDO_OF_TAG)? {
+        undo_op = match redone_undo_op.parents().exactly_one() {
+            Ok(parent_op) => parent_op?,
+            Err(_) => return Err(user_error("Cannot redo a merge operation")),
+        };
+    }
+    let Some(undone_op) = tagged_operation(&undo_op, UNDO_OF_TAG)? else {
+        return Err(user_error_with_hint(
+            "Nothing to redo",
+            "Only operations undone by the latest `jj undo` commands can be redone.",
+        ));
+    };
+    let parent_of_undo_op = match undo_op.parents().exactly_one() {
+        Ok(parent_op) => parent_op?,
+        Err(_) => return Err(user_error("Cannot redo a merge operation")),
+    };
+
+    let mut tx = workspace_command.start_transaction();
+    let repo_loader = tx.base_repo().loader();
+    let undo_repo = repo_loader.load_at(&undo_op)?;
+    let parent_repo = repo_loader.load_at(&parent_of_undo_op)?;
+    tx.repo_mut().merge(&undo_repo, &parent_repo)?;
+    let new_view = view_with_desired_portions_restored(
+        tx.repo().view().store_view(),
+        tx.base_repo().view().store_view(),
+        &args.what,
+    );
+    tx.repo_mut().set_view(new_view);
+    tx.set_tag(REDO_OF_TAG.to_owned(), undo_op.id().hex());
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Redid operation: ")?;
+        let template = tx.base_workspace_helper().operation_summary_template();
+        template.format(&undone_op, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    tx.finish(ui, format!("redo operation {}", undone_op.id().hex()))?;
+
+    Ok(())
+}
@@ -15,13 +15,15 @@
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId as _;
-use jj_lib::op_store::OpStoreError;
 use jj_lib::operation::Operation;
 use jj_lib::repo::Repo as _;
 
+use super::tagged_operation;
 use super::view_with_desired_portions_restored;
 use super::UndoWhatToRestore;
 use super::DEFAULT_UNDO_WHAT;
+use super::REDO_OF_TAG;
+use super::UNDO_OF_TAG;
 use crate::cli_util::CommandHelper;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
@@ -32,6 +34,10 @@ use crate::ui::Ui;
 ///
 /// This undoes an individual operation by applying the inverse of the
 /// operation.
+///
+/// When run without an operation argument, repeated `jj undo` commands step
+/// further back through the operation log instead of undoing the previous
+/// undo. Use `jj redo` to reapply the undone operations.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationUndoArgs {
     /// The operation to undo
@@ -47,21 +53,28 @@ pub struct OperationUndoArgs {
     what: Vec<UndoWhatToRestore>,
 }
 
-// Checks whether `op` resets the view of `parent_op` to the view of the
-// grandparent op.
-//
-// This is a necessary condition for `op` to be an undo of `parent_op` but is
-// not sufficient. For example, deleting a bookmark also resets the view
-// similarly but is not a literal `undo` operation.
-fn resets_view_of(op: &Operation, parent_op: &Operation) -> Result<bool, OpStoreError> {
-    let Ok(grandparent_op) = parent_op.parents().exactly_one() else {
-        return Ok(false);
-    };
-    Ok(op.view_id() == grandparent_op?.view_id())
-}
-
-fn tx_description(op: &Operation) -> String {
-    format!("undo operation {}", op.id().hex())
+/// Finds the operation whose resulting state `op` is in, looking through the
+/// operations created by `jj undo` and `jj redo`.
+///
+/// For example, if `op` undid `B` whose parent is `A`, the repo at `op` is in
+/// the state after `A`, so the next `jj undo` should undo `A`.
+fn effective_operation(op: &Operation) -> Result<Operation, CommandError> {
+    let mut op = op.clone();
+    loop {
+        if let Some(undone_op) = tagged_operation(&op, UNDO_OF_TAG)? {
+            let Ok(parent_op) = undone_op.parents().exactly_one() else {
+                return Ok(op);
+            };
+            op = parent_op?;
+        } else if let Some(undo_op) = tagged_operation(&op, REDO_OF_TAG)? {
+            let Some(redone_op) = tagged_operation(&undo_op, UNDO_OF_TAG)? else {
+                return Ok(op);
+            };
+            op = redone_op;
+        } else {
+            return Ok(op);
+        }
+    }
 }
 
 pub fn cmd_op_undo(
@@ -70,7 +83,15 @@ pub fn cmd_op_undo(
     args: &OperationUndoArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let bad_op = workspace_command.resolve_single_op(&args.operation)?;
+    let op = workspace_command.resolve_single_op(&args.operation)?;
+    // Undoing the latest operation steps back through the stack of undo
+    // operations. An explicitly specified operation is undone individually.
+    let undo_latest = args.operation == "@";
+    let bad_op = if undo_latest {
+        effective_operation(&op)?
+    } else {
+        op
+    };
     let parent_of_bad_op = match bad_op.parents().at_most_one() {
         Ok(Some(parent_of_bad_op)) => parent_of_bad_op?,
         Ok(None) => return Err(user_error("Cannot undo root operation")),
@@ -88,49 +109,16 @@ pub fn cmd_op_undo(
         &args.what,
     );
     tx.repo_mut().set_view(new_view);
+    if undo_latest {
+        tx.set_tag(UNDO_OF_TAG.to_owned(), bad_op.id().hex());
+    }
     if let Some(mut formatter) = ui.status_formatter() {
         write!(formatter, "Undid operation: ")?;
         let template = tx.base_workspace_helper().operation_summary_template();
         template.format(&bad_op, formatter.as_mut())?;
         writeln!(formatter)?;
     }
-    tx.finish(ui, tx_description(&bad_op))?;
-
-    // Check if the user performed a "double undo", i.e. the current `undo` (C)
-    // reverts an immediately preceding `undo` (B) that is itself an `undo` of the
-    // operation preceding it (A).
-    //
-    //    C (undo of B)
-    // @  B (`bad_op` = undo of A)
-    // ○  A
-    //
-    // An exception is made for when the user specified the immediately preceding
-    // `undo` with an op set. In this situation, the user's intent is clear, so
-    // a warning is not shown.
-    //
-    // Note that undoing an older `undo` does not constitute a "double undo". For
-    // example, the current `undo` (D) here reverts an `undo` B that is not the
-    // immediately preceding operation (C). A warning is not shown in this case.
-    //
-    //    D (undo of B)
-    // @  C (unrelated operation)
-    // ○  B (`bad_op` = undo of A)
-    // ○  A
-    if args.operation == "@"
-        && resets_view_of(&bad_op, &parent_of_bad_op)?
-        && bad_op.metadata().description == tx_description(&parent_of_bad_op)
-    {
-        writeln!(
-            ui.warning_default(),
-            "The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in \
-             the same state as it was before the second-last `jj undo`."
-        )?;
-        writeln!(
-            ui.hint_default(),
-            "To undo multiple operations, use `jj op log` to see past states and `jj op restore` \
-             to restore one of these states."
-        )?;
-    }
+    tx.finish(ui, format!("undo operation {}", bad_op.id().hex()))?;
 
     Ok(())
 }
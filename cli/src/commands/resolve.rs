@@ -12,18 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+use std::io::Write as _;
+use std::iter;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use indexmap::IndexMap;
 use itertools::Itertools as _;
+use jj_lib::gitattributes::GitAttributeState;
+use jj_lib::gitattributes::GitAttributes;
+use jj_lib::gitattributes::GitAttributesError;
+use jj_lib::merged_tree::resolve_file_values;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree::MergedTreeBuilder;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::resolution_cache::RecordedResolution;
+use jj_lib::resolution_cache::ResolutionCache;
+use jj_lib::resolution_cache::ResolutionId;
+use jj_lib::settings::UserSettings;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::print_conflicted_paths;
+use crate::cli_util::short_commit_hash;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::cli_error;
 use crate::command_error::CommandError;
+use crate::commit_templater::Conflict;
 use crate::complete;
+use crate::formatter::Formatter;
+use crate::merge_tools::configured_merge_tools;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// Resolve conflicted files with an external merge tool
@@ -54,21 +78,54 @@ pub(crate) struct ResolveArgs {
     )]
     revision: RevisionArg,
     /// Instead of resolving conflicts, list all the conflicts
-    // TODO: Also have a `--summary` option. `--list` currently acts like
-    // `diff --summary`, but should be more verbose.
     #[arg(long, short)]
     list: bool,
+    /// Instead of resolving conflicts, show the details of each conflict
+    ///
+    /// For each conflicted file, shows the kind of the conflict, and the
+    /// commits which introduced the sides and bases of the conflict.
+    #[arg(long, conflicts_with = "list")]
+    summary: bool,
+    /// Render each conflict using the given template (with `--summary`)
+    ///
+    /// All 0-argument methods of the [`Conflict` type] are available as
+    /// keywords in the template expression. See [`jj help -k templates`] for
+    /// more information.
+    ///
+    /// [`Conflict` type]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#conflict-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T', requires = "summary")]
+    template: Option<String>,
     /// Specify 3-way merge tool to be used
     ///
     /// The built-in merge tools `:ours` and `:theirs` can be used to choose
     /// side #1 and side #2 of the conflict respectively.
+    ///
+    /// If not specified, files with the `merge=<NAME>` attribute in
+    /// `.gitattributes` are resolved by the merge tool of that name if it's
+    /// configured in `merge-tools`, and the other files by `ui.merge-editor`.
     #[arg(
         long,
-        conflicts_with = "list",
+        conflicts_with_all = ["list", "summary"],
         value_name = "NAME",
         add = ArgValueCandidates::new(complete::merge_editors),
     )]
     tool: Option<String>,
+    /// Instead of resolving conflicts, list the recorded conflict resolutions
+    ///
+    /// Resolutions are recorded when `merge.rerere` is enabled. If paths are
+    /// given, only the resolutions recorded at these paths are listed.
+    #[arg(long, conflicts_with_all = ["list", "summary", "tool"])]
+    list_recorded: bool,
+    /// Instead of resolving conflicts, forget the recorded conflict resolutions
+    ///
+    /// If paths are given, only the resolutions recorded at these paths are
+    /// forgotten.
+    #[arg(long, conflicts_with_all = ["list", "summary", "tool", "list_recorded"])]
+    forget_recorded: bool,
     /// Only resolve conflicts in these paths. You can use the `--list` argument
     /// to find paths to use here.
     #[arg(
@@ -88,7 +145,32 @@ pub(crate) fn cmd_resolve(
     let mut workspace_command = command.workspace_helper(ui)?;
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+        .to_matcher()?;
+    if args.list_recorded || args.forget_recorded {
+        let cache = ResolutionCache::load(workspace_command.repo_path());
+        let entries = cache
+            .entries()?
+            .into_iter()
+            .filter(|(_, resolution)| matcher.matches(&resolution.path))
+            .collect_vec();
+        if args.forget_recorded {
+            for (id, _) in &entries {
+                cache.remove(id)?;
+            }
+            writeln!(
+                ui.status(),
+                "Forgot {} recorded conflict resolutions",
+                entries.len()
+            )?;
+        } else {
+            print_recorded_resolutions(
+                &entries,
+                ui.stdout_formatter().as_mut(),
+                &workspace_command,
+            )?;
+        }
+        return Ok(());
+    }
     let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
     let tree = commit.tree()?;
     let conflicts = tree
@@ -109,20 +191,90 @@ pub(crate) fn cmd_resolve(
             &workspace_command,
         );
     };
+    if args.summary {
+        let template: TemplateRenderer<Conflict> = {
+            let language = workspace_command.commit_template_language();
+            let text = match &args.template {
+                Some(value) => value.to_owned(),
+                None => workspace_command
+                    .settings()
+                    .get("templates.resolve_summary")?,
+            };
+            workspace_command
+                .parse_template(ui, &language, &text)?
+                .labeled(["resolve_summary"])
+        };
+        ui.request_pager();
+        let mut formatter = ui.stdout_formatter();
+        for (path, value) in conflicts {
+            let conflict = Conflict {
+                commit: commit.clone(),
+                path,
+                value: value?.simplify(),
+            };
+            template.format(&conflict, formatter.as_mut())?;
+        }
+        return Ok(());
+    }
 
-    let repo_paths = conflicts
-        .iter()
-        .map(|(path, _)| path.as_ref())
-        .collect_vec();
     workspace_command.check_rewritable([commit.id()])?;
-    let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+    // The conflicts may have been recorded with different merge settings, such
+    // as `merge.granularity`, so try to resolve them with the current settings
+    // before running any merge tool.
+    let store = tree.store().clone();
+    let mut tree_builder = MergedTreeBuilder::new(tree.id());
+    let mut repo_paths = vec![];
+    for (path, value) in &conflicts {
+        // Errors are reported when the merge tool reads the file.
+        let Ok(value) = value else {
+            repo_paths.push(path.as_ref());
+            continue;
+        };
+        let value = resolve_file_values(&store, path, value.clone()).block_on()?;
+        if value.is_resolved() {
+            tree_builder.set_or_remove(path.clone(), value);
+        } else {
+            repo_paths.push(path.as_ref());
+        }
+    }
+    let auto_resolved_count = conflicts.len() - repo_paths.len();
+    let tree = if auto_resolved_count > 0 {
+        writeln!(
+            ui.status(),
+            "Resolved conflicts in {auto_resolved_count} files with the configured merge settings"
+        )?;
+        store.get_root_tree(&tree_builder.write_tree(&store)?)?
+    } else {
+        tree
+    };
+    let tool_paths = if let Some(name) = &args.tool {
+        IndexMap::from([(Some(name.clone()), repo_paths)])
+    } else {
+        group_paths_by_merge_attribute(workspace_command.settings(), &tree, repo_paths)?
+    };
+    let merge_editors: Vec<_> = tool_paths
+        .into_iter()
+        .filter(|(_, paths)| !paths.is_empty())
+        .map(|(name, paths)| {
+            let merge_editor = workspace_command.merge_editor(ui, name.as_deref())?;
+            Ok::<_, CommandError>((merge_editor, paths))
+        })
+        .try_collect()?;
     let mut tx = workspace_command.start_transaction();
-    let (new_tree_id, partial_resolution_error) =
-        merge_editor.edit_files(ui, &tree, &repo_paths)?;
+    let mut new_tree = tree;
+    let mut partial_resolution_error = None;
+    for (merge_editor, paths) in &merge_editors {
+        let (new_tree_id, err) = merge_editor.edit_files(ui, &new_tree, paths)?;
+        new_tree = tx.repo().store().get_root_tree(&new_tree_id)?;
+        if err.is_some() {
+            partial_resolution_error = err;
+            break;
+        }
+    }
     let new_commit = tx
         .repo_mut()
         .rewrite_commit(&commit)
-        .set_tree_id(new_tree_id)
+        .set_tree_id(new_tree.id())
         .write()?;
     tx.finish(
         ui,
@@ -151,3 +303,52 @@ pub(crate) fn cmd_resolve(
     }
     Ok(())
 }
+
+fn print_recorded_resolutions(
+    entries: &[(ResolutionId, RecordedResolution)],
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+) -> io::Result<()> {
+    let formatted_paths = entries
+        .iter()
+        .map(|(_, resolution)| workspace_command.format_file_path(&resolution.path))
+        .collect_vec();
+    let max_path_len = formatted_paths.iter().map(|p| p.len()).max().unwrap_or(0);
+    for ((_, resolution), formatted_path) in iter::zip(entries, formatted_paths) {
+        write!(
+            formatter,
+            "{:width$}resolved in commit ",
+            formatted_path,
+            width = max_path_len.min(32) + 3
+        )?;
+        writeln!(
+            formatter.labeled("commit_id"),
+            "{}",
+            short_commit_hash(&resolution.commit_id)
+        )?;
+    }
+    Ok(())
+}
+
+/// Groups the paths by the merge tool specified by the `merge` attribute.
+/// Paths without the attribute, or with a tool which isn't configured (such as
+/// Git's built-in merge drivers), are grouped under `None`.
+fn group_paths_by_merge_attribute<'a>(
+    settings: &UserSettings,
+    tree: &MergedTree,
+    repo_paths: Vec<&'a RepoPath>,
+) -> Result<IndexMap<Option<String>, Vec<&'a RepoPath>>, GitAttributesError> {
+    let configured_tools = configured_merge_tools(settings).collect_vec();
+    let git_attributes = GitAttributes::from_tree(tree.clone());
+    let mut groups = IndexMap::new();
+    for path in repo_paths {
+        let name = match git_attributes.get(path, "merge").block_on()? {
+            GitAttributeState::Value(name) if configured_tools.contains(&name.as_str()) => {
+                Some(name)
+            }
+            _ => None,
+        };
+        groups.entry(name).or_insert_with(Vec::new).push(path);
+    }
+    Ok(groups)
+}
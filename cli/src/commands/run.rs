@@ -14,19 +14,65 @@
 
 //! This file contains the internal implementation of `run`.
 
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::commit::Commit;
+use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::local_working_copy::TreeState;
+use jj_lib::local_working_copy::TreeStateError;
+use jj_lib::local_working_copy::TreeStateSettings;
+use jj_lib::lock::FileLock;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::store::Store;
+use jj_lib::working_copy::CheckoutError;
+use jj_lib::working_copy::CheckoutOptions;
+use jj_lib::working_copy::SnapshotError;
+use jj_lib::working_copy::SnapshotOptions;
+use thiserror::Error;
+use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
+use crate::complete;
 use crate::ui::Ui;
 
-/// (**Stub**, does not work yet) Run a command across a set of revisions.
+/// Run a command across a set of revisions
+///
+/// The command is run through the shell (`sh -c` on Unix, `cmd /c` on
+/// Windows) once for each of the selected revisions, in topological order.
+/// Each invocation runs in a separate working copy in the `.jj/run` directory,
+/// so your own working copy is left untouched and you can keep working while
+/// the command runs. The `JJ_COMMIT_ID` and `JJ_CHANGE_ID` environment
+/// variables are set to the IDs of the revision the command runs on.
 ///
+/// If the command exits successfully and modified files in its working copy,
+/// the changes are recorded in the revision it ran on, and descendants are
+/// rebased on top of it. Changes made by a failing command are discarded.
 ///
-/// All recorded state will be persisted in the `.jj` directory, so occasionally
-/// a `jj run --clean` is needed to clean up disk space.
+/// The working copies in `.jj/run` are reused between invocations, including
+/// ignored files such as build outputs, so incremental builds can be fast.
+/// Occasionally a `jj run --clean` is needed to clean up disk space.
 ///
 /// # Example
 ///
@@ -40,7 +86,13 @@ pub struct RunArgs {
     /// The command to run across all selected revisions.
     shell_command: String,
     /// The revisions to change.
-    #[arg(long, short, default_value = "@", value_name = "REVSETS")]
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
     revisions: Vec<RevisionArg>,
     /// A no-op option to match the interface of `git rebase -x`.
     #[arg(short = 'x', hide = true)]
@@ -48,23 +100,286 @@ pub struct RunArgs {
     /// How many processes should run in parallel, uses by default all cores.
     #[arg(long, short)]
     jobs: Option<usize>,
+    /// Remove the working copies (including ignored files) left by previous
+    /// runs before running the command.
+    #[arg(long)]
+    clean: bool,
+}
+
+#[derive(Debug, Error)]
+enum RunError {
+    #[error("Failed to set up the working copy in {}", path.display())]
+    SetUpDir {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    TreeState(#[from] TreeStateError),
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    #[error("Failed to check out the revision")]
+    Checkout(#[from] CheckoutError),
+    #[error("Failed to start the command")]
+    Spawn(#[source] io::Error),
+    #[error("Failed to snapshot the working copy")]
+    Snapshot(#[from] SnapshotError),
+}
+
+/// Outcome of running the command on a single revision.
+struct RunOutput {
+    status: ExitStatus,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    /// The tree of the working copy after the command finished.
+    tree_id: MergedTreeId,
 }
 
+#[instrument(skip_all)]
 pub fn cmd_run(ui: &mut Ui, command: &CommandHelper, args: &RunArgs) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
-    let _resolved_commits: Vec<_> = workspace_command
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut resolved_commits: Vec<Commit> = workspace_command
         .parse_union_revsets(ui, &args.revisions)?
         .evaluate_to_commits()?
         .try_collect()?;
+    workspace_command.check_rewritable(resolved_commits.iter().ids())?;
+    // Run on ancestors first.
+    resolved_commits.reverse();
     // Jobs are resolved in this order:
     // 1. Commandline argument iff > 0.
     // 2. the amount of cores available.
     // 3. a single job, if all of the above fails.
-    let _jobs = match args.jobs {
+    let jobs = match args.jobs {
         Some(0) | None => std::thread::available_parallelism().map(|t| t.into()).ok(),
         Some(jobs) => Some(jobs),
     }
     // Fallback to a single user-visible job.
     .unwrap_or(1usize);
-    Err(user_error("This is a stub, do not use"))
+
+    let run_dir = workspace_command.workspace_root().join(".jj").join("run");
+    fs::create_dir_all(&run_dir)?;
+    // Concurrent `jj run` invocations would otherwise share the working copies.
+    let _lock = FileLock::lock(run_dir.join("lock"))
+        .map_err(|err| user_error_with_message("Failed to lock the run directory", err))?;
+    if args.clean {
+        for entry in fs::read_dir(&run_dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            }
+        }
+    }
+
+    let store = workspace_command.repo().store().clone();
+    let tree_state_settings = TreeStateSettings::try_from_user_settings(command.settings())?;
+    let checkout_options = workspace_command.checkout_options();
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let snapshot_options = SnapshotOptions {
+        // The scratch working copies aren't watched by the fsmonitor.
+        fsmonitor_settings: FsmonitorSettings::None,
+        ..workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?
+    };
+
+    let num_slots = jobs.min(resolved_commits.len());
+    let pending_commits = Mutex::new(resolved_commits.iter());
+    let mut new_tree_ids: HashMap<CommitId, MergedTreeId> = HashMap::new();
+    let mut failed_commits: Vec<&Commit> = vec![];
+    std::thread::scope(|s| -> Result<(), CommandError> {
+        let (result_tx, result_rx) = channel();
+        for slot in 0..num_slots {
+            let result_tx = result_tx.clone();
+            let pending_commits = &pending_commits;
+            let store = &store;
+            let slot_dir = run_dir.join(slot.to_string());
+            let tree_state_settings = &tree_state_settings;
+            let checkout_options = &checkout_options;
+            let snapshot_options = &snapshot_options;
+            let shell_command = &args.shell_command;
+            s.spawn(move || {
+                let mut tree_state = match load_working_copy(store, &slot_dir, tree_state_settings)
+                {
+                    Ok(tree_state) => tree_state,
+                    Err(err) => {
+                        result_tx.send(Err(err)).ok();
+                        return;
+                    }
+                };
+                loop {
+                    // Release the lock before running the command.
+                    let next_commit = pending_commits.lock().unwrap().next();
+                    let Some(commit) = next_commit else {
+                        break;
+                    };
+                    let result = run_on_commit(
+                        &mut tree_state,
+                        commit,
+                        shell_command,
+                        checkout_options,
+                        snapshot_options,
+                    );
+                    let is_err = result.is_err();
+                    if result_tx
+                        .send(result.map(|output| (commit, output)))
+                        .is_err()
+                        || is_err
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(result_tx);
+
+        for result in result_rx {
+            let (commit, output) = result.map_err(|err| {
+                // Prevent the other threads from picking up new work.
+                pending_commits.lock().unwrap().by_ref().for_each(drop);
+                user_error_with_message("Failed to run the command", err)
+            })?;
+            writeln!(
+                ui.status(),
+                "Ran command on {}:",
+                workspace_command.format_commit_summary(commit)
+            )?;
+            ui.stdout().write_all(&output.stdout)?;
+            ui.stderr().write_all(&output.stderr)?;
+            if !output.status.success() {
+                writeln!(
+                    ui.warning_default(),
+                    "Command failed with {}",
+                    output.status
+                )?;
+                failed_commits.push(commit);
+            } else if output.tree_id != *commit.tree_id() {
+                new_tree_ids.insert(commit.id().clone(), output.tree_id);
+            }
+        }
+        Ok(())
+    })?;
+
+    if new_tree_ids.is_empty() {
+        writeln!(ui.status(), "No revisions were modified.")?;
+    } else {
+        let root_commit_ids = resolved_commits
+            .iter()
+            .ids()
+            .filter(|id| new_tree_ids.contains_key(id))
+            .cloned()
+            .collect_vec();
+        let mut tx = workspace_command.start_transaction();
+        let mut num_modified = 0;
+        let mut num_rebased = 0;
+        tx.repo_mut()
+            .transform_descendants(root_commit_ids, |mut rewriter| {
+                let new_tree_id = new_tree_ids.get(rewriter.old_commit().id());
+                if new_tree_id.is_none() && !rewriter.parents_changed() {
+                    return Ok(());
+                }
+                let store = rewriter.mut_repo().store().clone();
+                let old_tree = rewriter.old_commit().tree()?;
+                let mut builder = rewriter.rebase()?;
+                if let Some(new_tree_id) = new_tree_id {
+                    // Apply the changes made by the command on top of the rebased
+                    // commit, so that changes from rewritten ancestors are kept.
+                    let rebased_tree = store.get_root_tree(builder.tree_id())?;
+                    let new_tree = store.get_root_tree(new_tree_id)?;
+                    let merged_tree = rebased_tree.merge(&old_tree, &new_tree)?;
+                    builder = builder.set_tree_id(merged_tree.id());
+                    num_modified += 1;
+                } else {
+                    num_rebased += 1;
+                }
+                builder.write()?;
+                Ok(())
+            })?;
+        writeln!(ui.status(), "Modified {num_modified} commits.")?;
+        if num_rebased > 0 {
+            writeln!(ui.status(), "Rebased {num_rebased} descendant commits.")?;
+        }
+        tx.finish(
+            ui,
+            format!(
+                "run command '{}' on {} commits",
+                args.shell_command,
+                resolved_commits.len()
+            ),
+        )?;
+    }
+
+    if failed_commits.is_empty() {
+        Ok(())
+    } else {
+        Err(user_error(format!(
+            "Command failed on {} of {} revisions",
+            failed_commits.len(),
+            resolved_commits.len()
+        )))
+    }
+}
+
+/// Loads the working copy in `slot_dir`, creating it if it doesn't exist yet.
+fn load_working_copy(
+    store: &Arc<Store>,
+    slot_dir: &Path,
+    tree_state_settings: &TreeStateSettings,
+) -> Result<TreeState, RunError> {
+    let wc_dir = slot_dir.join("working_copy");
+    let state_dir = slot_dir.join("state");
+    for dir in [&wc_dir, &state_dir] {
+        fs::create_dir_all(dir).map_err(|source| RunError::SetUpDir {
+            path: dir.clone(),
+            source,
+        })?;
+    }
+    Ok(TreeState::load(
+        store.clone(),
+        wc_dir,
+        state_dir,
+        tree_state_settings,
+    )?)
+}
+
+/// Checks out `commit` into the working copy, runs the command in it, and
+/// snapshots the result.
+fn run_on_commit(
+    tree_state: &mut TreeState,
+    commit: &Commit,
+    shell_command: &str,
+    checkout_options: &CheckoutOptions,
+    snapshot_options: &SnapshotOptions,
+) -> Result<RunOutput, RunError> {
+    // Record any leftovers from a previous run so that the checkout below
+    // replaces them instead of failing on unexpected files.
+    tree_state.snapshot(snapshot_options)?;
+    tree_state.check_out(&commit.tree()?, checkout_options)?;
+    tree_state.save()?;
+    let mut cmd = shell_command_for(shell_command);
+    tracing::debug!(?cmd, commit_id = ?commit.id(), "spawning run command");
+    let output = cmd
+        .current_dir(tree_state.working_copy_path())
+        .env("JJ_COMMIT_ID", commit.id().hex())
+        .env("JJ_CHANGE_ID", commit.change_id().reverse_hex())
+        .stdin(Stdio::null())
+        .output()
+        .map_err(RunError::Spawn)?;
+    tree_state.snapshot(snapshot_options)?;
+    tree_state.save()?;
+    Ok(RunOutput {
+        status: output.status,
+        stdout: output.stdout,
+        stderr: output.stderr,
+        tree_id: tree_state.current_tree_id().clone(),
+    })
+}
+
+fn shell_command_for(shell_command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/c").arg(shell_command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(shell_command);
+        cmd
+    }
 }
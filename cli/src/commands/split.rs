@@ -29,6 +29,7 @@ use jj_lib::rewrite::MoveCommitsTarget;
 use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::compute_commit_location;
@@ -159,7 +160,11 @@ impl SplitArgs {
         workspace_command.check_rewritable([target_commit.id()])?;
         let matcher = workspace_command
             .parse_file_patterns(ui, &self.paths)?
-            .to_matcher();
+            .to_matcher_with_tree_diff(
+                &target_commit.parent_tree(workspace_command.repo().as_ref())?,
+                &target_commit.tree()?,
+            )
+            .block_on()?;
         let diff_selector = workspace_command.diff_selector(
             ui,
             self.tool.as_deref(),
@@ -23,6 +23,7 @@ use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite;
 use jj_lib::rewrite::CommitWithSelection;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -158,9 +159,15 @@ pub(crate) fn cmd_squash(
         destination = parents.pop().unwrap();
     }
 
+    let mut source_trees = vec![];
+    for source in &sources {
+        source_trees.push(source.parent_tree(workspace_command.repo().as_ref())?);
+        source_trees.push(source.tree()?);
+    }
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
-        .to_matcher();
+        .to_matcher_with_trees(&source_trees)
+        .block_on()?;
     let diff_selector =
         workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let text_editor = workspace_command.text_editor()?;
@@ -0,0 +1,160 @@
+// Copyright 2020-2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod create;
+mod delete;
+mod list;
+mod set;
+
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::str_util::StringPattern;
+use jj_lib::view::View;
+
+use self::create::cmd_tag_create;
+use self::create::TagCreateArgs;
+use self::delete::cmd_tag_delete;
+use self::delete::TagDeleteArgs;
+use self::list::cmd_tag_list;
+use self::list::TagListArgs;
+use self::set::cmd_tag_set;
+use self::set::TagSetArgs;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage tags.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum TagCommand {
+    #[command(visible_alias("c"))]
+    Create(TagCreateArgs),
+    #[command(visible_alias("d"))]
+    Delete(TagDeleteArgs),
+    #[command(visible_alias("l"))]
+    List(TagListArgs),
+    #[command(visible_alias("s"))]
+    Set(TagSetArgs),
+}
+
+pub fn cmd_tag(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &TagCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        TagCommand::Create(args) => cmd_tag_create(ui, command, args),
+        TagCommand::Delete(args) => cmd_tag_delete(ui, command, args),
+        TagCommand::List(args) => cmd_tag_list(ui, command, args),
+        TagCommand::Set(args) => cmd_tag_set(ui, command, args),
+    }
+}
+
+/// Options for creating annotated tags
+#[derive(clap::Args, Clone, Debug)]
+struct TagAnnotationArgs {
+    /// Create an annotated tag with the given message
+    ///
+    /// Without this option, a lightweight tag is created.
+    #[arg(long, short, value_name = "MESSAGE")]
+    message: Option<String>,
+    /// Sign the annotated tag
+    ///
+    /// The tag is signed with the configured signing backend and key.
+    #[arg(long, requires = "message")]
+    sign: bool,
+}
+
+/// Points the tags to the commit. Annotated tag objects are written to the
+/// underlying Git repo if a message is specified.
+fn set_tag_targets(
+    tx: &mut WorkspaceCommandTransaction,
+    names: &[RefNameBuf],
+    target_commit: &Commit,
+    annotation: &TagAnnotationArgs,
+) -> Result<(), CommandError> {
+    let Some(message) = &annotation.message else {
+        for name in names {
+            tx.repo_mut()
+                .set_tag_target(name, RefTarget::normal(target_commit.id().clone()));
+        }
+        return Ok(());
+    };
+    #[cfg(feature = "git")]
+    {
+        use jj_lib::backend::SigningFn;
+        use jj_lib::repo::Repo as _;
+
+        let store = tx.repo().store().clone();
+        if annotation.sign && !store.signer().can_sign() {
+            return Err(crate::command_error::user_error_with_hint(
+                "No signing backend configured",
+                "For configuring a signing backend, see https://jj-vcs.github.io/jj/latest/config/#commit-signing",
+            ));
+        }
+        let tagger = tx.settings().signature();
+        let sign_key = tx.settings().sign_settings().key;
+        let mut sign_fn = |data: &[u8]| store.signer().sign(data, sign_key.as_deref());
+        for name in names {
+            let sign_with = annotation.sign.then_some(&mut sign_fn as &mut SigningFn);
+            jj_lib::git::create_annotated_tag(
+                tx.repo_mut(),
+                name,
+                target_commit.id(),
+                message,
+                &tagger,
+                sign_with,
+            )?;
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "git"))]
+    {
+        let _ = message;
+        Err(user_error(
+            "Annotated tags can only be created in Git-backed repos",
+        ))
+    }
+}
+
+fn find_local_tags<'a>(
+    view: &'a View,
+    name_patterns: &[StringPattern],
+) -> Result<Vec<(&'a RefName, &'a RefTarget)>, CommandError> {
+    let mut matching_tags = vec![];
+    let mut unmatched_patterns = vec![];
+    for pattern in name_patterns {
+        let mut matches = view.tags_matching(pattern).peekable();
+        if matches.peek().is_none() {
+            unmatched_patterns.push(pattern);
+        }
+        matching_tags.extend(matches);
+    }
+    match &unmatched_patterns[..] {
+        [] => {
+            matching_tags.sort_unstable_by_key(|(name, _)| *name);
+            matching_tags.dedup_by_key(|(name, _)| *name);
+            Ok(matching_tags)
+        }
+        [pattern] if pattern.is_exact() => Err(user_error(format!("No such tag: {pattern}"))),
+        patterns => Err(user_error(format!(
+            "No matching tags for patterns: {}",
+            patterns.iter().join(", ")
+        ))),
+    }
+}
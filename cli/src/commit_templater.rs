@@ -40,14 +40,20 @@ use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
+#[cfg(feature = "git")]
+use jj_lib::git;
+use jj_lib::gitattributes::GitAttributes;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::id_prefix::IdPrefixIndex;
 use jj_lib::matchers::Matcher;
+use jj_lib::merge::Merge;
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
+#[cfg(feature = "git")]
+use jj_lib::ref_name::RefNameBuf;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo;
@@ -57,6 +63,9 @@ use jj_lib::revset;
 use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetContainingFn;
 use jj_lib::revset::RevsetDiagnostics;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::revset::RevsetIteratorExt as _;
 use jj_lib::revset::RevsetModifier;
 use jj_lib::revset::RevsetParseContext;
 use jj_lib::revset::UserRevsetExpression;
@@ -309,6 +318,26 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(self, diagnostics, build_ctx, property, function)
             }
+            CommitTemplatePropertyKind::Conflict(property) => {
+                let table = &self.build_fn_table.conflict_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::ConflictList(property) => {
+                let table = &self.build_fn_table.conflict_list_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::ConflictSide(property) => {
+                let table = &self.build_fn_table.conflict_side_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::ConflictSideList(property) => {
+                let table = &self.build_fn_table.conflict_side_list_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
             CommitTemplatePropertyKind::DiffStats(property) => {
                 let table = &self.build_fn_table.diff_stats_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -386,6 +415,10 @@ pub enum CommitTemplatePropertyKind<'repo> {
     TreeDiffEntry(BoxedTemplateProperty<'repo, TreeDiffEntry>),
     TreeDiffEntryList(BoxedTemplateProperty<'repo, Vec<TreeDiffEntry>>),
     TreeEntry(BoxedTemplateProperty<'repo, TreeEntry>),
+    Conflict(BoxedTemplateProperty<'repo, Conflict>),
+    ConflictList(BoxedTemplateProperty<'repo, Vec<Conflict>>),
+    ConflictSide(BoxedTemplateProperty<'repo, ConflictSide>),
+    ConflictSideList(BoxedTemplateProperty<'repo, Vec<ConflictSide>>),
     DiffStats(BoxedTemplateProperty<'repo, DiffStatsFormatted<'repo>>),
     CryptographicSignatureOpt(BoxedTemplateProperty<'repo, Option<CryptographicSignature>>),
     AnnotationLine(BoxedTemplateProperty<'repo, AnnotationLine>),
@@ -415,6 +448,10 @@ template_builder::impl_property_wrappers!(<'repo> CommitTemplatePropertyKind<'re
     TreeDiffEntry(TreeDiffEntry),
     TreeDiffEntryList(Vec<TreeDiffEntry>),
     TreeEntry(TreeEntry),
+    Conflict(Conflict),
+    ConflictList(Vec<Conflict>),
+    ConflictSide(ConflictSide),
+    ConflictSideList(Vec<ConflictSide>),
     DiffStats(DiffStatsFormatted<'repo>),
     CryptographicSignatureOpt(Option<CryptographicSignature>),
     AnnotationLine(AnnotationLine),
@@ -454,6 +491,10 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::TreeDiffEntry(_) => "TreeDiffEntry",
             Self::TreeDiffEntryList(_) => "List<TreeDiffEntry>",
             Self::TreeEntry(_) => "TreeEntry",
+            Self::Conflict(_) => "Conflict",
+            Self::ConflictList(_) => "List<Conflict>",
+            Self::ConflictSide(_) => "ConflictSide",
+            Self::ConflictSideList(_) => "List<ConflictSide>",
             Self::DiffStats(_) => "DiffStats",
             Self::CryptographicSignatureOpt(_) => "Option<CryptographicSignature>",
             Self::AnnotationLine(_) => "AnnotationLine",
@@ -487,6 +528,10 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::TreeDiffEntry(_) => None,
             Self::TreeDiffEntryList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
             Self::TreeEntry(_) => None,
+            Self::Conflict(_) => None,
+            Self::ConflictList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
+            Self::ConflictSide(_) => None,
+            Self::ConflictSideList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
             Self::DiffStats(_) => None,
             Self::CryptographicSignatureOpt(property) => {
                 Some(property.map(|sig| sig.is_some()).into_dyn())
@@ -544,6 +589,10 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::TreeDiffEntry(_) => None,
             Self::TreeDiffEntryList(_) => None,
             Self::TreeEntry(_) => None,
+            Self::Conflict(_) => None,
+            Self::ConflictList(_) => None,
+            Self::ConflictSide(_) => None,
+            Self::ConflictSideList(_) => None,
             Self::DiffStats(_) => None,
             Self::CryptographicSignatureOpt(_) => None,
             Self::AnnotationLine(_) => None,
@@ -575,6 +624,10 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::TreeDiffEntry(_) => None,
             Self::TreeDiffEntryList(_) => None,
             Self::TreeEntry(_) => None,
+            Self::Conflict(_) => None,
+            Self::ConflictList(_) => None,
+            Self::ConflictSide(_) => None,
+            Self::ConflictSideList(_) => None,
             Self::DiffStats(property) => Some(property.into_template()),
             Self::CryptographicSignatureOpt(_) => None,
             Self::AnnotationLine(_) => None,
@@ -636,6 +689,10 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::TreeDiffEntry(_), _) => None,
             (Self::TreeDiffEntryList(_), _) => None,
             (Self::TreeEntry(_), _) => None,
+            (Self::Conflict(_), _) => None,
+            (Self::ConflictList(_), _) => None,
+            (Self::ConflictSide(_), _) => None,
+            (Self::ConflictSideList(_), _) => None,
             (Self::DiffStats(_), _) => None,
             (Self::CryptographicSignatureOpt(_), _) => None,
             (Self::AnnotationLine(_), _) => None,
@@ -668,6 +725,10 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::TreeDiffEntry(_), _) => None,
             (Self::TreeDiffEntryList(_), _) => None,
             (Self::TreeEntry(_), _) => None,
+            (Self::Conflict(_), _) => None,
+            (Self::ConflictList(_), _) => None,
+            (Self::ConflictSide(_), _) => None,
+            (Self::ConflictSideList(_), _) => None,
             (Self::DiffStats(_), _) => None,
             (Self::CryptographicSignatureOpt(_), _) => None,
             (Self::AnnotationLine(_), _) => None,
@@ -698,6 +759,10 @@ pub struct CommitTemplateBuildFnTable<'repo> {
     pub tree_diff_entry_methods: CommitTemplateBuildMethodFnMap<'repo, TreeDiffEntry>,
     pub tree_diff_entry_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<TreeDiffEntry>>,
     pub tree_entry_methods: CommitTemplateBuildMethodFnMap<'repo, TreeEntry>,
+    pub conflict_methods: CommitTemplateBuildMethodFnMap<'repo, Conflict>,
+    pub conflict_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<Conflict>>,
+    pub conflict_side_methods: CommitTemplateBuildMethodFnMap<'repo, ConflictSide>,
+    pub conflict_side_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<ConflictSide>>,
     pub diff_stats_methods: CommitTemplateBuildMethodFnMap<'repo, DiffStats>,
     pub cryptographic_signature_methods:
         CommitTemplateBuildMethodFnMap<'repo, CryptographicSignature>,
@@ -725,6 +790,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_methods: builtin_tree_diff_entry_methods(),
             tree_diff_entry_list_methods: template_builder::builtin_unformattable_list_methods(),
             tree_entry_methods: builtin_tree_entry_methods(),
+            conflict_methods: builtin_conflict_methods(),
+            conflict_list_methods: template_builder::builtin_unformattable_list_methods(),
+            conflict_side_methods: builtin_conflict_side_methods(),
+            conflict_side_list_methods: template_builder::builtin_unformattable_list_methods(),
             diff_stats_methods: builtin_diff_stats_methods(),
             cryptographic_signature_methods: builtin_cryptographic_signature_methods(),
             annotation_line_methods: builtin_annotation_line_methods(),
@@ -750,6 +819,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_methods: HashMap::new(),
             tree_diff_entry_list_methods: HashMap::new(),
             tree_entry_methods: HashMap::new(),
+            conflict_methods: HashMap::new(),
+            conflict_list_methods: HashMap::new(),
+            conflict_side_methods: HashMap::new(),
+            conflict_side_list_methods: HashMap::new(),
             diff_stats_methods: HashMap::new(),
             cryptographic_signature_methods: HashMap::new(),
             annotation_line_methods: HashMap::new(),
@@ -775,6 +848,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_methods,
             tree_diff_entry_list_methods,
             tree_entry_methods,
+            conflict_methods,
+            conflict_list_methods,
+            conflict_side_methods,
+            conflict_side_list_methods,
             diff_stats_methods,
             cryptographic_signature_methods,
             annotation_line_methods,
@@ -806,6 +883,13 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_list_methods,
         );
         merge_fn_map(&mut self.tree_entry_methods, tree_entry_methods);
+        merge_fn_map(&mut self.conflict_methods, conflict_methods);
+        merge_fn_map(&mut self.conflict_list_methods, conflict_list_methods);
+        merge_fn_map(&mut self.conflict_side_methods, conflict_side_methods);
+        merge_fn_map(
+            &mut self.conflict_side_list_methods,
+            conflict_side_list_methods,
+        );
         merge_fn_map(&mut self.diff_stats_methods, diff_stats_methods);
         merge_fn_map(
             &mut self.cryptographic_signature_methods,
@@ -1046,6 +1130,26 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    #[cfg(feature = "git")]
+    map.insert(
+        "notes",
+        |language, diagnostics, _build_ctx, self_property, function| {
+            let ([], [name_node]) = function.expect_arguments()?;
+            let name: RefNameBuf = if let Some(node) = name_node {
+                template_parser::catch_aliases(diagnostics, node, |_diagnostics, node| {
+                    let name = template_parser::expect_string_literal(node)?;
+                    Ok(name.strip_prefix("refs/notes/").unwrap_or(name).into())
+                })?
+            } else {
+                git::DEFAULT_NOTES_REF_NAME.to_owned()
+            };
+            let repo = language.repo;
+            let out_property = self_property.and_then(move |commit| {
+                Ok(git::read_note(repo, &name, commit.id())?.unwrap_or_default())
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "divergent",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1104,6 +1208,34 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "conflicts",
+        |language, diagnostics, _build_ctx, self_property, function| {
+            let ([], [files_node]) = function.expect_arguments()?;
+            let files = if let Some(node) = files_node {
+                expect_fileset_literal(diagnostics, node, language.path_converter)?
+            } else {
+                FilesetExpression::all()
+            };
+            let matcher = fileset_to_matcher(&files, function.args_span)?;
+            let out_property = self_property.and_then(move |commit| {
+                let tree = commit.tree()?;
+                let conflicts: Vec<_> = tree
+                    .conflicts()
+                    .filter(|(path, _)| matcher.matches(path))
+                    .map(|(path, value)| {
+                        BackendResult::Ok(Conflict {
+                            commit: commit.clone(),
+                            path,
+                            value: value?.simplify(),
+                        })
+                    })
+                    .try_collect()?;
+                Ok(conflicts)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "empty",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1125,7 +1257,7 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
                 FilesetExpression::all()
             };
             let repo = language.repo;
-            let matcher: Rc<dyn Matcher> = files.to_matcher().into();
+            let matcher = fileset_to_matcher(&files, function.args_span)?;
             let out_property = self_property
                 .and_then(move |commit| Ok(TreeDiff::from_commit(repo, &commit, matcher.clone())?));
             Ok(out_property.into_dyn_wrapped())
@@ -1177,6 +1309,16 @@ fn expect_fileset_literal(
     })
 }
 
+fn fileset_to_matcher(
+    expression: &FilesetExpression,
+    span: pest::Span<'_>,
+) -> Result<Rc<dyn Matcher>, TemplateParseError> {
+    let matcher = expression.to_matcher().map_err(|err| {
+        TemplateParseError::expression("In fileset expression", span).with_source(err)
+    })?;
+    Ok(matcher.into())
+}
+
 fn evaluate_revset_expression<'repo>(
     language: &CommitTemplateLanguage<'repo>,
     span: pest::Span<'_>,
@@ -1966,7 +2108,12 @@ impl TreeDiff {
 
     fn into_formatted<F, E>(self, show: F) -> TreeDiffFormatted<F>
     where
-        F: Fn(&mut dyn Formatter, &Store, BoxStream<CopiesTreeDiffEntry>) -> Result<(), E>,
+        F: Fn(
+            &mut dyn Formatter,
+            &Store,
+            &GitAttributes,
+            BoxStream<CopiesTreeDiffEntry>,
+        ) -> Result<(), E>,
         E: Into<TemplatePropertyError>,
     {
         TreeDiffFormatted { diff: self, show }
@@ -1981,14 +2128,21 @@ struct TreeDiffFormatted<F> {
 
 impl<F, E> Template for TreeDiffFormatted<F>
 where
-    F: Fn(&mut dyn Formatter, &Store, BoxStream<CopiesTreeDiffEntry>) -> Result<(), E>,
+    F: Fn(
+        &mut dyn Formatter,
+        &Store,
+        &GitAttributes,
+        BoxStream<CopiesTreeDiffEntry>,
+    ) -> Result<(), E>,
     E: Into<TemplatePropertyError>,
 {
     fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
         let show = &self.show;
         let store = self.diff.from_tree.store();
+        let git_attributes = GitAttributes::from_tree(self.diff.to_tree.clone());
         let tree_diff = self.diff.diff_stream();
-        show(formatter.as_mut(), store, tree_diff).or_else(|err| formatter.handle_error(err.into()))
+        show(formatter.as_mut(), store, &git_attributes, tree_diff)
+            .or_else(|err| formatter.handle_error(err.into()))
     }
 }
 
@@ -2034,11 +2188,12 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
                     if let Some(context) = context {
                         options.context = context;
                     }
-                    diff.into_formatted(move |formatter, store, tree_diff| {
+                    diff.into_formatted(move |formatter, store, git_attributes, tree_diff| {
                         diff_util::show_color_words_diff(
                             formatter,
                             store,
                             tree_diff,
+                            git_attributes,
                             path_converter,
                             &options,
                             conflict_marker_style,
@@ -2076,11 +2231,12 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
                     if let Some(context) = context {
                         options.context = context;
                     }
-                    diff.into_formatted(move |formatter, store, tree_diff| {
+                    diff.into_formatted(move |formatter, store, git_attributes, tree_diff| {
                         diff_util::show_git_diff(
                             formatter,
                             store,
                             tree_diff,
+                            git_attributes,
                             &options,
                             conflict_marker_style,
                         )
@@ -2132,7 +2288,7 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
             let path_converter = language.path_converter;
             let template = self_property
                 .map(move |diff| {
-                    diff.into_formatted(move |formatter, _store, tree_diff| {
+                    diff.into_formatted(move |formatter, _store, _git_attributes, tree_diff| {
                         diff_util::show_diff_summary(formatter, tree_diff, path_converter)
                             .block_on()
                     })
@@ -2292,6 +2448,197 @@ fn is_executable_file(value: &MergedTreeValue) -> Option<bool> {
     conflicts::resolve_file_executable(&executable)
 }
 
+/// File conflict in a commit.
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    /// Commit containing the conflict.
+    pub commit: Commit,
+    pub path: RepoPathBuf,
+    /// Simplified conflict value.
+    pub value: MergedTreeValue,
+}
+
+impl Conflict {
+    /// Returns the conflict category, in order of precedence if the conflict
+    /// falls into more than one.
+    pub fn kind(&self) -> &'static str {
+        if self
+            .value
+            .iter()
+            .flatten()
+            .any(|value| matches!(value, TreeValue::Tree(_)))
+        {
+            "file-vs-directory"
+        } else if self.value.adds().any(|value| value.is_none()) {
+            "deletion"
+        } else if let Some(file_ids) = self.value.to_file_merge() {
+            if file_ids.resolve_trivial().is_some() {
+                "executable"
+            } else {
+                "content"
+            }
+        } else {
+            "other"
+        }
+    }
+
+    /// Returns the sides of the conflict with the commits which introduced
+    /// them.
+    pub fn sides(&self, repo: &dyn Repo) -> Result<Vec<ConflictSide>, TemplatePropertyError> {
+        self.find_term_commits(repo, self.value.adds())
+    }
+
+    /// Returns the bases of the conflict with the commits which introduced
+    /// them.
+    pub fn bases(&self, repo: &dyn Repo) -> Result<Vec<ConflictSide>, TemplatePropertyError> {
+        self.find_term_commits(repo, self.value.removes())
+    }
+
+    /// Attributes each term to the closest ancestor which changed the path to
+    /// that value. Terms that don't appear in the ancestors were introduced by
+    /// the commit itself, e.g. by rebasing it.
+    fn find_term_commits<'a>(
+        &self,
+        repo: &dyn Repo,
+        terms: impl IntoIterator<Item = &'a Option<TreeValue>>,
+    ) -> Result<Vec<ConflictSide>, TemplatePropertyError> {
+        let mut sides = terms
+            .into_iter()
+            .map(|value| ConflictSide {
+                value: value.clone(),
+                commit: None,
+            })
+            .collect_vec();
+        let ancestors = RevsetExpression::commit(self.commit.id().clone())
+            .parents()
+            .ancestors()
+            .filtered(RevsetFilterPredicate::File(FilesetExpression::file_path(
+                self.path.clone(),
+            )))
+            .evaluate(repo)?;
+        for ancestor in ancestors.iter().commits(repo.store()) {
+            if sides.iter().all(|side| side.commit.is_some()) {
+                break;
+            }
+            let ancestor = ancestor?;
+            let value = ancestor.tree()?.path_value(&self.path)?;
+            // Conflicted values are attributed to further ancestors.
+            let Some(value) = value.as_resolved() else {
+                continue;
+            };
+            for side in &mut sides {
+                if side.commit.is_none() && side.value == *value {
+                    side.commit = Some(ancestor.clone());
+                }
+            }
+        }
+        for side in &mut sides {
+            if side.commit.is_none() && side.value.is_some() {
+                side.commit = Some(self.commit.clone());
+            }
+        }
+        Ok(sides)
+    }
+}
+
+fn builtin_conflict_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Conflict> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<Conflict>::new();
+    map.insert(
+        "path",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|conflict| conflict.path);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "num_sides",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|conflict| Ok(i64::try_from(conflict.value.num_sides())?));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "kind",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|conflict| conflict.kind().to_owned());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "sides",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.and_then(|conflict| conflict.sides(repo));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "bases",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.and_then(|conflict| conflict.bases(repo));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
+/// Side or base of a [`Conflict`].
+#[derive(Clone, Debug)]
+pub struct ConflictSide {
+    pub value: Option<TreeValue>,
+    /// Commit which introduced the value, if known.
+    pub commit: Option<Commit>,
+}
+
+fn builtin_conflict_side_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, ConflictSide> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<ConflictSide>::new();
+    map.insert(
+        "file_type",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property
+                .map(|side| describe_file_type(&Merge::resolved(side.value)).to_owned());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "executable",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|side| {
+                matches!(
+                    side.value,
+                    Some(TreeValue::File {
+                        executable: true,
+                        ..
+                    })
+                )
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "commit",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|side| side.commit);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
 /// [`DiffStats`] with rendering parameters.
 #[derive(Clone, Debug)]
 pub struct DiffStatsFormatted<'a> {
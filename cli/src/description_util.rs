@@ -18,8 +18,10 @@ use jj_lib::config::ConfigGetError;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::file_util::PathError;
 use jj_lib::settings::UserSettings;
+use jj_lib::trailer::locate_description_trailers;
 use jj_lib::trailer::parse_description_trailers;
 use jj_lib::trailer::parse_trailers;
+use jj_lib::trailer::Trailer;
 use thiserror::Error;
 
 use crate::cli_util::short_commit_hash;
@@ -397,27 +399,88 @@ pub fn add_trailers_with_template(
     template: &TemplateRenderer<'_, Commit>,
     commit: &Commit,
 ) -> Result<String, CommandError> {
-    let trailers = parse_description_trailers(commit.description());
     let trailer_lines = template
         .format_plain_text(commit)
         .into_string()
         .map_err(|_| user_error("Trailers should be valid utf-8"))?;
     let new_trailers = parse_trailers(&trailer_lines)?;
-    let mut description = commit.description().to_owned();
+    Ok(add_trailers_to_description(
+        commit.description(),
+        &new_trailers,
+    ))
+}
+
+/// Add the `new_trailers` in the last paragraph of the description
+///
+/// Trailers which are already in the description are skipped.
+pub fn add_trailers_to_description(description: &str, new_trailers: &[Trailer]) -> String {
+    let trailers = parse_description_trailers(description);
+    let newline = line_ending(description);
+    let mut description = description.to_owned();
     if trailers.is_empty() && !new_trailers.is_empty() {
         if description.is_empty() {
             // a first empty line where the user will edit the commit summary
-            description.push('\n');
+            description.push_str(newline);
         }
         // create a new paragraph for the trailer
-        description.push('\n');
+        description.push_str(newline);
     }
     for new_trailer in new_trailers {
-        if !trailers.contains(&new_trailer) {
-            description.push_str(&format!("{}: {}\n", new_trailer.key, new_trailer.value));
+        if !trailers.contains(new_trailer) {
+            description.push_str(&format!(
+                "{}: {}{newline}",
+                new_trailer.key, new_trailer.value
+            ));
         }
     }
-    Ok(description)
+    description
+}
+
+/// Remove the trailers with any of the given `keys` from the last paragraph of
+/// the description
+///
+/// Keys are matched case-insensitively. The trailer paragraph is removed if
+/// it becomes empty.
+pub fn remove_trailers_from_description(description: &str, keys: &[String]) -> String {
+    let Some(paragraph) = locate_description_trailers(description) else {
+        return description.to_owned();
+    };
+    let trimmed = description.trim_ascii_end();
+    // The body includes the blank line before the trailer paragraph.
+    let body = &trimmed[..paragraph.start];
+    let mut kept = String::new();
+    let mut pos = paragraph.start;
+    for (trailer, range) in &paragraph.trailers {
+        if keys
+            .iter()
+            .any(|key| key.eq_ignore_ascii_case(&trailer.key))
+        {
+            kept.push_str(&trimmed[pos..range.start]);
+            pos = range.end;
+        }
+    }
+    kept.push_str(&trimmed[pos..]);
+    let newline = line_ending(description);
+    if kept.trim_ascii().is_empty() {
+        let body = body.trim_ascii_end();
+        if body.is_empty() {
+            String::new()
+        } else {
+            format!("{body}{newline}")
+        }
+    } else {
+        format!("{body}{}{newline}", kept.trim_ascii_end())
+    }
+}
+
+/// Returns the line ending used in the description, which is `\n` unless it
+/// uses `\r\n`.
+fn line_ending(description: &str) -> &'static str {
+    if description.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
 }
 
 /// Add the trailers from `templates.commit_trailers` in the last paragraph of
@@ -464,9 +527,12 @@ pub fn description_template(
 mod tests {
     use indexmap::indexmap;
     use indoc::indoc;
+    use jj_lib::trailer::Trailer;
     use maplit::hashmap;
 
+    use super::add_trailers_to_description;
     use super::parse_bulk_edit_message;
+    use super::remove_trailers_from_description;
     use crate::description_util::ParseBulkEditMessageError;
 
     #[test]
@@ -619,4 +685,92 @@ mod tests {
         assert!(result.duplicates.is_empty());
         assert!(result.unexpected.is_empty());
     }
+
+    #[test]
+    fn test_add_trailers_to_description() {
+        let trailers = [Trailer {
+            key: "Signed-off-by".to_owned(),
+            value: "Foo".to_owned(),
+        }];
+        assert_eq!(
+            add_trailers_to_description("", &trailers),
+            "\n\nSigned-off-by: Foo\n"
+        );
+        assert_eq!(
+            add_trailers_to_description("subject\n", &trailers),
+            "subject\n\nSigned-off-by: Foo\n"
+        );
+        assert_eq!(
+            add_trailers_to_description("subject\n\nReviewed-by: Bar\n", &trailers),
+            "subject\n\nReviewed-by: Bar\nSigned-off-by: Foo\n"
+        );
+
+        // The line ending of the description is preserved
+        assert_eq!(
+            add_trailers_to_description("subject\r\n", &trailers),
+            "subject\r\n\r\nSigned-off-by: Foo\r\n"
+        );
+        assert_eq!(
+            add_trailers_to_description("subject\r\n\r\nReviewed-by: Bar\r\n", &trailers),
+            "subject\r\n\r\nReviewed-by: Bar\r\nSigned-off-by: Foo\r\n"
+        );
+    }
+
+    #[test]
+    fn test_remove_trailers_from_description() {
+        let keys = ["signed-off-by".to_owned()];
+        // No trailers
+        assert_eq!(remove_trailers_from_description("", &keys), "");
+        assert_eq!(
+            remove_trailers_from_description("Signed-off-by: Foo\n", &keys),
+            "Signed-off-by: Foo\n"
+        );
+
+        // Multi-line trailers are removed entirely
+        let description = indoc! {"
+            subject
+
+            Signed-off-by: Foo
+              continued
+            Reviewed-by: Bar
+        "};
+        assert_eq!(
+            remove_trailers_from_description(description, &keys),
+            indoc! {"
+                subject
+
+                Reviewed-by: Bar
+            "}
+        );
+
+        // Whitespace-only lines separate the trailer paragraph
+        let description = "subject\n\t\nSigned-off-by: Foo\n";
+        assert_eq!(
+            remove_trailers_from_description(description, &keys),
+            "subject\n"
+        );
+        let description = "subject\r\n\r\nSigned-off-by: Foo\r\nReviewed-by: Bar\r\n";
+        assert_eq!(
+            remove_trailers_from_description(description, &keys),
+            "subject\r\n\r\nReviewed-by: Bar\r\n"
+        );
+
+        // The trailer paragraph is removed if it becomes empty
+        let description = indoc! {"
+            subject
+
+            body
+
+            Signed-off-by: Foo
+            signed-off-by: Bar
+        "};
+        assert_eq!(
+            remove_trailers_from_description(description, &keys),
+            indoc! {"
+                subject
+
+                body
+            "}
+        );
+    }
 }
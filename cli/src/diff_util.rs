@@ -58,6 +58,9 @@ use jj_lib::files::ConflictDiffHunk;
 use jj_lib::files::DiffLineHunkSide;
 use jj_lib::files::DiffLineIterator;
 use jj_lib::files::DiffLineNumber;
+use jj_lib::gitattributes::GitAttributeState;
+use jj_lib::gitattributes::GitAttributes;
+use jj_lib::gitattributes::GitAttributesError;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Merge;
 use jj_lib::merge::MergedTreeValue;
@@ -381,6 +384,8 @@ pub enum DiffRenderError {
     #[error(transparent)]
     InvalidRepoPath(#[from] InvalidRepoPathError),
     #[error(transparent)]
+    GitAttributes(#[from] GitAttributesError),
+    #[error(transparent)]
     Io(#[from] io::Error),
 }
 
@@ -446,6 +451,17 @@ impl<'a> DiffRenderer<'a> {
     ) -> Result<(), DiffRenderError> {
         let store = self.repo.store();
         let path_converter = self.path_converter;
+        let git_attributes = GitAttributes::from_tree(to_tree.clone());
+        let needs_contents = self.formats.iter().any(|format| {
+            !matches!(
+                format,
+                DiffFormat::Summary | DiffFormat::Types | DiffFormat::NameOnly
+            )
+        });
+        if needs_contents {
+            // Fetch the remotely stored files at once rather than one by one.
+            from_tree.prefetch_diff_files(to_tree, matcher).await?;
+        }
         for format in &self.formats {
             match format {
                 DiffFormat::Summary => {
@@ -478,6 +494,7 @@ impl<'a> DiffRenderer<'a> {
                         formatter,
                         store,
                         tree_diff,
+                        &git_attributes,
                         options,
                         self.conflict_marker_style,
                     )
@@ -490,6 +507,7 @@ impl<'a> DiffRenderer<'a> {
                         formatter,
                         store,
                         tree_diff,
+                        &git_attributes,
                         path_converter,
                         options,
                         self.conflict_marker_style,
@@ -1174,6 +1192,21 @@ fn file_content_for_diff<T>(
     })
 }
 
+/// Overrides the binary detection with the `diff` attribute of the `path`.
+/// Files marked as `binary` have the `diff` attribute unset.
+async fn apply_diff_attribute<T>(
+    content: &mut FileContent<T>,
+    git_attributes: &GitAttributes,
+    path: &RepoPath,
+) -> Result<(), GitAttributesError> {
+    match git_attributes.get(path, "diff").await? {
+        GitAttributeState::Set => content.is_binary = false,
+        GitAttributeState::Unset => content.is_binary = true,
+        GitAttributeState::Value(_) | GitAttributeState::Unspecified => {}
+    }
+    Ok(())
+}
+
 fn diff_content(
     path: &RepoPath,
     value: MaterializedTreeValue,
@@ -1262,6 +1295,7 @@ pub async fn show_color_words_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
     tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    git_attributes: &GitAttributes,
     path_converter: &RepoPathUiConverter,
     options: &ColorWordsDiffOptions,
     conflict_marker_style: ConflictMarkerStyle,
@@ -1300,7 +1334,8 @@ pub async fn show_color_words_diff(
                 formatter.labeled("header"),
                 "Added {description} {right_ui_path}:"
             )?;
-            let right_content = diff_content_as_merge(right_path, right_value)?;
+            let mut right_content = diff_content_as_merge(right_path, right_value)?;
+            apply_diff_attribute(&mut right_content, git_attributes, right_path).await?;
             if right_content.is_empty() {
                 writeln!(formatter.labeled("empty"), "    (empty)")?;
             } else if right_content.is_binary {
@@ -1357,8 +1392,10 @@ pub async fn show_color_words_diff(
                     )
                 }
             };
-            let left_content = diff_content_as_merge(left_path, left_value)?;
-            let right_content = diff_content_as_merge(right_path, right_value)?;
+            let mut left_content = diff_content_as_merge(left_path, left_value)?;
+            let mut right_content = diff_content_as_merge(right_path, right_value)?;
+            apply_diff_attribute(&mut left_content, git_attributes, left_path).await?;
+            apply_diff_attribute(&mut right_content, git_attributes, right_path).await?;
             if left_path == right_path {
                 writeln!(
                     formatter.labeled("header"),
@@ -1386,7 +1423,8 @@ pub async fn show_color_words_diff(
                 formatter.labeled("header"),
                 "Removed {description} {right_ui_path}:"
             )?;
-            let left_content = diff_content_as_merge(left_path, left_value)?;
+            let mut left_content = diff_content_as_merge(left_path, left_value)?;
+            apply_diff_attribute(&mut left_content, git_attributes, left_path).await?;
             if left_content.is_empty() {
                 writeln!(formatter.labeled("empty"), "    (empty)")?;
             } else if left_content.is_binary {
@@ -1520,12 +1558,12 @@ fn git_diff_part(
             };
         }
         MaterializedTreeValue::GitSubmodule(id) => {
-            // TODO: What should we actually do here?
-            mode = "040000";
+            // Git renders the submodule pointer as a one-line file.
+            mode = "160000";
             hash = id.hex();
             content = FileContent {
                 is_binary: false,
-                contents: BString::default(),
+                contents: format!("Subproject commit {hash}\n").into(),
             };
         }
         MaterializedTreeValue::FileConflict(file) => {
@@ -1748,6 +1786,13 @@ fn show_unified_diff_hunks(
     formatter: &mut dyn Formatter,
     contents: [&BStr; 2],
     options: &UnifiedDiffOptions,
+) -> io::Result<()> {
+    show_unified_diff_hunk_lines(formatter, unified_diff_hunks(contents, options))
+}
+
+fn show_unified_diff_hunk_lines<'content>(
+    formatter: &mut dyn Formatter,
+    hunks: impl IntoIterator<Item = UnifiedDiffHunk<'content>>,
 ) -> io::Result<()> {
     // "If the chunk size is 0, the first number is one lower than one would
     // expect." - https://www.artima.com/weblogs/viewpost.jsp?thread=164293
@@ -1764,7 +1809,7 @@ fn show_unified_diff_hunks(
         }
     }
 
-    for hunk in unified_diff_hunks(contents, options) {
+    for hunk in hunks {
         writeln!(
             formatter.labeled("hunk_header"),
             "@@ -{},{} +{},{} @@",
@@ -1811,6 +1856,7 @@ pub async fn show_git_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
     tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    git_attributes: &GitAttributes,
     options: &UnifiedDiffOptions,
     conflict_marker_style: ConflictMarkerStyle,
 ) -> Result<(), DiffRenderError> {
@@ -1822,8 +1868,10 @@ pub async fn show_git_diff(
         let right_path_string = right_path.as_internal_file_string();
         let (left_value, right_value) = values?;
 
-        let left_part = git_diff_part(left_path, left_value, conflict_marker_style)?;
-        let right_part = git_diff_part(right_path, right_value, conflict_marker_style)?;
+        let mut left_part = git_diff_part(left_path, left_value, conflict_marker_style)?;
+        let mut right_part = git_diff_part(right_path, right_value, conflict_marker_style)?;
+        apply_diff_attribute(&mut left_part.content, git_attributes, left_path).await?;
+        apply_diff_attribute(&mut right_part.content, git_attributes, right_path).await?;
 
         formatter.with_label("file_header", |formatter| {
             writeln!(
@@ -1900,6 +1948,46 @@ pub async fn show_git_diff(
     Ok(())
 }
 
+/// Shows changes to a range of lines in the Git diff format.
+///
+/// `contents` are the lines in the range before and after the change, and
+/// `line_starts` are the 0-based line numbers of the first lines. `None`
+/// content means that the file didn't exist. All lines in the range are shown
+/// as context.
+pub fn show_line_range_diff(
+    formatter: &mut dyn Formatter,
+    path_string: &str,
+    contents: [Option<&BStr>; 2],
+    line_starts: [usize; 2],
+) -> io::Result<()> {
+    let [left_path, right_path] =
+        [("a", contents[0]), ("b", contents[1])].map(|(prefix, content)| match content {
+            Some(_) => format!("{prefix}/{path_string}"),
+            None => "/dev/null".to_owned(),
+        });
+    formatter.with_label("file_header", |formatter| {
+        writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
+        writeln!(formatter, "--- {left_path}")?;
+        writeln!(formatter, "+++ {right_path}")
+    })?;
+    let contents = contents.map(|content| content.unwrap_or_default());
+    let options = UnifiedDiffOptions {
+        context: usize::MAX,
+        line_diff: LineDiffOptions::default(),
+    };
+    let hunks = unified_diff_hunks(contents, &options)
+        .into_iter()
+        .map(|mut hunk| {
+            let [left_start, right_start] = line_starts;
+            hunk.left_line_range =
+                hunk.left_line_range.start + left_start..hunk.left_line_range.end + left_start;
+            hunk.right_line_range =
+                hunk.right_line_range.start + right_start..hunk.right_line_range.end + right_start;
+            hunk
+        });
+    show_unified_diff_hunk_lines(formatter, hunks)
+}
+
 #[instrument(skip_all)]
 pub async fn show_diff_summary(
     formatter: &mut dyn Formatter,
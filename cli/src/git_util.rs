@@ -515,7 +515,7 @@ impl RefStatus {
                     TrackingStatus::Untracked
                 }
             }
-            GitRefKind::Tag => TrackingStatus::NotApplicable,
+            GitRefKind::Tag | GitRefKind::Note => TrackingStatus::NotApplicable,
         };
 
         let import_status = match (remote_ref.target.is_absent(), ref_target.is_absent()) {
@@ -552,6 +552,7 @@ impl RefStatus {
         let label = match self.ref_kind {
             GitRefKind::Bookmark => "bookmark",
             GitRefKind::Tag => "tag",
+            GitRefKind::Note => "note",
         };
 
         write!(out, "{label}: ")?;
@@ -563,7 +564,7 @@ impl RefStatus {
 enum TrackingStatus {
     Tracked,
     Untracked,
-    NotApplicable, // for tags
+    NotApplicable, // for tags and notes
 }
 
 enum ImportStatus {
@@ -598,6 +599,29 @@ export or their "parent" bookmarks."#,
             )?;
         }
     }
+    if !stats.failed_tags.is_empty() {
+        writeln!(ui.warning_default(), "Failed to export some tags:")?;
+        let mut formatter = ui.stderr_formatter();
+        for (name, reason) in &stats.failed_tags {
+            write!(formatter, "  ")?;
+            write!(formatter.labeled("tag"), "{}", name.as_symbol())?;
+            for err in iter::successors(Some(reason as &dyn error::Error), |err| err.source()) {
+                write!(formatter, ": {err}")?;
+            }
+            writeln!(formatter)?;
+        }
+    }
+    if !stats.failed_notes.is_empty() {
+        writeln!(ui.warning_default(), "Failed to export some notes refs:")?;
+        let mut formatter = ui.stderr_formatter();
+        for (name, reason) in &stats.failed_notes {
+            write!(formatter, "  refs/notes/{}", name.as_str())?;
+            for err in iter::successors(Some(reason as &dyn error::Error), |err| err.source()) {
+                write!(formatter, ": {err}")?;
+            }
+            writeln!(formatter)?;
+        }
+    }
     Ok(())
 }
 
@@ -557,10 +557,40 @@ pub fn edit_diff_builtin(
     Ok(tree_id)
 }
 
-fn make_merge_sections(
-    merge_result: MergeResult,
-) -> Result<Vec<scm_record::Section<'static>>, BuiltinToolError> {
-    let mut sections = Vec::new();
+/// Sections of a conflicted file to be shown in the builtin merge editor.
+#[derive(Debug)]
+struct MergeSections {
+    sections: Vec<scm_record::Section<'static>>,
+    /// Indices of the sections which only label the terms of conflicting
+    /// hunks. They must be removed before applying the selected changes.
+    label_indices: Vec<usize>,
+}
+
+impl MergeSections {
+    fn push_label(&mut self, lines: Vec<String>) {
+        self.label_indices.push(self.sections.len());
+        self.sections.push(scm_record::Section::Unchanged {
+            lines: lines.into_iter().map(Cow::Owned).collect(),
+        });
+    }
+}
+
+fn make_conflict_label(kind: char, text: &str) -> String {
+    format!(
+        "{} {text}\n",
+        kind.to_string().repeat(MIN_CONFLICT_MARKER_LEN)
+    )
+}
+
+/// Splits the merge result into sections. Each term of a conflicting hunk is
+/// shown as a separate section of added lines, labeled in the same way as
+/// conflict markers, so the user can pick one side of the hunk by selecting
+/// the section, or combine lines from several terms.
+fn make_merge_sections(merge_result: MergeResult) -> Result<MergeSections, BuiltinToolError> {
+    let mut merge_sections = MergeSections {
+        sections: Vec::new(),
+        label_indices: Vec::new(),
+    };
     match merge_result {
         MergeResult::Resolved(buf) => {
             let contents = buf_to_file_contents(None, buf.into());
@@ -584,12 +614,14 @@ fn make_merge_sections(
                 }),
             };
             if let Some(section) = section {
-                sections.push(section);
+                merge_sections.sections.push(section);
             }
         }
         MergeResult::Conflict(hunks) => {
+            let num_conflicts = hunks.iter().filter(|hunk| !hunk.is_resolved()).count();
+            let mut conflict_index = 0;
             for hunk in hunks {
-                let section = match hunk.into_resolved() {
+                let merge = match hunk.into_resolved() {
                     Ok(contents) => {
                         let contents = std::str::from_utf8(&contents).map_err(|err| {
                             BuiltinToolError::DecodeUtf8 {
@@ -597,50 +629,66 @@ fn make_merge_sections(
                                 item: "unchanged hunk",
                             }
                         })?;
-                        scm_record::Section::Unchanged {
-                            lines: contents
-                                .split_inclusive('\n')
-                                .map(|line| Cow::Owned(line.to_owned()))
-                                .collect(),
+                        merge_sections
+                            .sections
+                            .push(scm_record::Section::Unchanged {
+                                lines: contents
+                                    .split_inclusive('\n')
+                                    .map(|line| Cow::Owned(line.to_owned()))
+                                    .collect(),
+                            });
+                        continue;
+                    }
+                    Err(merge) => merge,
+                };
+                conflict_index += 1;
+                let conflict_info = format!("Conflict {conflict_index} of {num_conflicts}");
+                let num_bases = merge.removes().len();
+                for (term_index, contents) in merge.iter().enumerate() {
+                    let contents = std::str::from_utf8(contents).map_err(|err| {
+                        BuiltinToolError::DecodeUtf8 {
+                            source: err,
+                            item: "conflicting hunk",
                         }
+                    })?;
+                    let mut label = if term_index % 2 == 0 {
+                        format!("Contents of side #{}", term_index / 2 + 1)
+                    } else if num_bases == 1 {
+                        "Contents of base".to_owned()
+                    } else {
+                        format!("Contents of base #{}", term_index / 2 + 1)
+                    };
+                    if contents.is_empty() {
+                        label.push_str(" (empty)");
                     }
-                    Err(merge) => {
-                        let lines: Vec<scm_record::SectionChangedLine> = merge
-                            .iter()
-                            .zip(
-                                [
-                                    scm_record::ChangeType::Added,
-                                    scm_record::ChangeType::Removed,
-                                ]
-                                .into_iter()
-                                .cycle(),
-                            )
-                            .map(|(contents, change_type)| -> Result<_, BuiltinToolError> {
-                                let contents = std::str::from_utf8(contents).map_err(|err| {
-                                    BuiltinToolError::DecodeUtf8 {
-                                        source: err,
-                                        item: "conflicting hunk",
-                                    }
-                                })?;
-                                let changed_lines =
-                                    make_section_changed_lines(contents, change_type);
-                                Ok(changed_lines)
-                            })
-                            .flatten_ok()
-                            .try_collect()?;
-                        scm_record::Section::Changed { lines }
+                    let kind = if term_index % 2 == 0 { '+' } else { '-' };
+                    let mut label_lines = vec![];
+                    if term_index == 0 {
+                        label_lines.push(make_conflict_label('<', &conflict_info));
                     }
-                };
-                sections.push(section);
+                    label_lines.push(make_conflict_label(kind, &label));
+                    merge_sections.push_label(label_lines);
+                    if !contents.is_empty() {
+                        let lines =
+                            make_section_changed_lines(contents, scm_record::ChangeType::Added);
+                        merge_sections
+                            .sections
+                            .push(scm_record::Section::Changed { lines });
+                    }
+                }
+                merge_sections.push_label(vec![make_conflict_label(
+                    '>',
+                    &format!("{conflict_info} ends"),
+                )]);
             }
         }
     }
-    Ok(sections)
+    Ok(merge_sections)
 }
 
 fn make_merge_file(
     merge_tool_file: &MergeToolFile,
-) -> Result<scm_record::File<'static>, BuiltinToolError> {
+) -> Result<(scm_record::File<'static>, Vec<usize>), BuiltinToolError> {
     let file = &merge_tool_file.file;
     let file_mode = if file.executable.expect("should have been resolved") {
         mode::EXECUTABLE
@@ -650,8 +698,11 @@ fn make_merge_file(
     // TODO: Maybe we should test binary contents here, and generate per-file
     // Binary section to select either "our" or "their" file.
     let merge_result = files::merge_hunks(&file.contents);
-    let sections = make_merge_sections(merge_result)?;
-    Ok(scm_record::File {
+    let MergeSections {
+        sections,
+        label_indices,
+    } = make_merge_sections(merge_result)?;
+    let file = scm_record::File {
         old_path: None,
         // Path for displaying purposes, not for file access.
         path: Cow::Owned(
@@ -661,7 +712,18 @@ fn make_merge_file(
         ),
         file_mode,
         sections,
-    })
+    };
+    Ok((file, label_indices))
+}
+
+/// Removes the sections which only label conflicting hunks.
+fn remove_label_sections(file: &mut scm_record::File, label_indices: &[usize]) {
+    let mut index = 0;
+    file.sections.retain(|_| {
+        let is_label = label_indices.contains(&index);
+        index += 1;
+        !is_label
+    });
 }
 
 pub fn edit_merge_builtin(
@@ -669,15 +731,30 @@ pub fn edit_merge_builtin(
     merge_tool_files: &[MergeToolFile],
 ) -> Result<MergedTreeId, BuiltinToolError> {
     let mut input = scm_record::helpers::CrosstermInput;
+    edit_merge_builtin_with_input(tree, merge_tool_files, &mut input)
+}
+
+fn edit_merge_builtin_with_input(
+    tree: &MergedTree,
+    merge_tool_files: &[MergeToolFile],
+    input: &mut dyn scm_record::RecordInput,
+) -> Result<MergedTreeId, BuiltinToolError> {
+    let (files, label_indices): (Vec<_>, Vec<_>) = merge_tool_files
+        .iter()
+        .map(make_merge_file)
+        .process_results(|iter| iter.unzip())?;
     let recorder = scm_record::Recorder::new(
         scm_record::RecordState {
             is_read_only: false,
-            files: merge_tool_files.iter().map(make_merge_file).try_collect()?,
+            files,
             commits: Default::default(),
         },
-        &mut input,
+        input,
     );
-    let state = recorder.run()?;
+    let mut state = recorder.run()?;
+    for (file, label_indices) in state.files.iter_mut().zip(&label_indices) {
+        remove_label_sections(file, label_indices);
+    }
 
     let store = tree.store();
     let mut tree_builder = MergedTreeBuilder::new(tree.id().clone());
@@ -1798,58 +1875,244 @@ mod tests {
             .block_on()
             .unwrap();
         let merge_result = files::merge_hunks(&content);
-        let sections = make_merge_sections(merge_result).unwrap();
-        insta::assert_debug_snapshot!(sections, @r#"
-        [
-            Changed {
-                lines: [
-                    SectionChangedLine {
-                        is_checked: false,
-                        change_type: Added,
-                        line: "left 1\n",
-                    },
-                    SectionChangedLine {
-                        is_checked: false,
-                        change_type: Removed,
-                        line: "base 1\n",
-                    },
-                    SectionChangedLine {
-                        is_checked: false,
-                        change_type: Added,
-                        line: "right 1\n",
-                    },
-                ],
-            },
-            Unchanged {
-                lines: [
-                    "base 2\n",
-                    "base 3\n",
-                    "base 4\n",
-                ],
-            },
-            Changed {
-                lines: [
-                    SectionChangedLine {
-                        is_checked: false,
-                        change_type: Added,
-                        line: "left 5\n",
-                    },
-                    SectionChangedLine {
-                        is_checked: false,
-                        change_type: Removed,
-                        line: "base 5\n",
-                    },
-                    SectionChangedLine {
-                        is_checked: false,
-                        change_type: Added,
-                        line: "right 5\n",
-                    },
-                ],
-            },
-        ]
+        let merge_sections = make_merge_sections(merge_result).unwrap();
+        insta::assert_debug_snapshot!(merge_sections, @r#"
+        MergeSections {
+            sections: [
+                Unchanged {
+                    lines: [
+                        "<<<<<<< Conflict 1 of 2\n",
+                        "+++++++ Contents of side #1\n",
+                    ],
+                },
+                Changed {
+                    lines: [
+                        SectionChangedLine {
+                            is_checked: false,
+                            change_type: Added,
+                            line: "left 1\n",
+                        },
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        "------- Contents of base\n",
+                    ],
+                },
+                Changed {
+                    lines: [
+                        SectionChangedLine {
+                            is_checked: false,
+                            change_type: Added,
+                            line: "base 1\n",
+                        },
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        "+++++++ Contents of side #2\n",
+                    ],
+                },
+                Changed {
+                    lines: [
+                        SectionChangedLine {
+                            is_checked: false,
+                            change_type: Added,
+                            line: "right 1\n",
+                        },
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        ">>>>>>> Conflict 1 of 2 ends\n",
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        "base 2\n",
+                        "base 3\n",
+                        "base 4\n",
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        "<<<<<<< Conflict 2 of 2\n",
+                        "+++++++ Contents of side #1\n",
+                    ],
+                },
+                Changed {
+                    lines: [
+                        SectionChangedLine {
+                            is_checked: false,
+                            change_type: Added,
+                            line: "left 5\n",
+                        },
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        "------- Contents of base\n",
+                    ],
+                },
+                Changed {
+                    lines: [
+                        SectionChangedLine {
+                            is_checked: false,
+                            change_type: Added,
+                            line: "base 5\n",
+                        },
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        "+++++++ Contents of side #2\n",
+                    ],
+                },
+                Changed {
+                    lines: [
+                        SectionChangedLine {
+                            is_checked: false,
+                            change_type: Added,
+                            line: "right 5\n",
+                        },
+                    ],
+                },
+                Unchanged {
+                    lines: [
+                        ">>>>>>> Conflict 2 of 2 ends\n",
+                    ],
+                },
+            ],
+            label_indices: [
+                0,
+                2,
+                4,
+                6,
+                8,
+                10,
+                12,
+                14,
+            ],
+        }
         "#);
     }
 
+    #[test]
+    fn test_make_merge_sections_multiple_sides() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let path = repo_path("file");
+        let file_id = |contents: &str| {
+            let tree = testutils::create_tree(&test_repo.repo, &[(path, contents)]);
+            match tree.path_value(path).unwrap().into_resolved() {
+                Ok(Some(TreeValue::File { id, .. })) => Some(id),
+                other => panic!("merge should have been a FileId: {other:?}"),
+            }
+        };
+        let merge = Merge::from_vec(vec![
+            file_id("a 1\nbase 2\nbase 3\n"),
+            file_id("base 1\nbase 2\nbase 3\n"),
+            file_id("b 1\nbase 2\nb 3\n"),
+            file_id("base 1\nbase 2\nbase 3\n"),
+            file_id("c 1\nbase 2\n"),
+        ]);
+        let content = extract_as_single_hunk(&merge, store, path)
+            .block_on()
+            .unwrap();
+        let merge_result = files::merge_hunks(&content);
+        let MergeSections {
+            sections,
+            label_indices,
+        } = make_merge_sections(merge_result).unwrap();
+        let mut file = scm_record::File {
+            old_path: None,
+            path: Cow::Borrowed(Path::new("file")),
+            file_mode: mode::NORMAL,
+            sections,
+        };
+        let labels = label_indices
+            .iter()
+            .map(|&index| match &file.sections[index] {
+                scm_record::Section::Unchanged { lines } => lines.concat(),
+                section => panic!("label should be an unchanged section: {section:?}"),
+            })
+            .collect_vec();
+        insta::assert_snapshot!(labels.concat(), @r"
+        <<<<<<< Conflict 1 of 2
+        +++++++ Contents of side #1
+        ------- Contents of base #1
+        +++++++ Contents of side #2
+        ------- Contents of base #2
+        +++++++ Contents of side #3
+        >>>>>>> Conflict 1 of 2 ends
+        <<<<<<< Conflict 2 of 2
+        +++++++ Contents of side #1
+        ------- Contents of base #1
+        +++++++ Contents of side #2
+        ------- Contents of base #2
+        +++++++ Contents of side #3 (empty)
+        >>>>>>> Conflict 2 of 2 ends
+        ");
+
+        // Pick side #3 of the first conflict, and combine sides #1 and #2 of the
+        // second conflict.
+        let mut changed_sections = file
+            .sections
+            .iter_mut()
+            .filter_map(|section| match section {
+                scm_record::Section::Changed { lines } => Some(lines),
+                _ => None,
+            })
+            .collect_vec();
+        for index in [4, 5, 7] {
+            for line in changed_sections[index].iter_mut() {
+                line.is_checked = true;
+            }
+        }
+        remove_label_sections(&mut file, &label_indices);
+        let (selected, _unselected) = file.get_selected_contents();
+        insta::assert_debug_snapshot!(selected.contents, @r#"
+        Text {
+            contents: "c 1\nbase 2\nbase 3\nb 3\n",
+        }
+        "#);
+    }
+
+    #[test]
+    fn test_edit_merge_builtin_multiple_sides() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let file_path = repo_path("file");
+        let tree = {
+            let base = testutils::create_single_tree(&test_repo.repo, &[(file_path, "base\n")]);
+            let side1 = testutils::create_single_tree(&test_repo.repo, &[(file_path, "1\n")]);
+            let side2 = testutils::create_single_tree(&test_repo.repo, &[(file_path, "2\n")]);
+            let side3 = testutils::create_single_tree(&test_repo.repo, &[(file_path, "3\n")]);
+            MergedTree::new(Merge::from_vec(vec![
+                side1,
+                base.clone(),
+                side2,
+                base,
+                side3,
+            ]))
+        };
+        let merge_tool_file = MergeToolFile::from_tree_and_path(&tree, file_path).unwrap();
+        assert_eq!(merge_tool_file.file.ids.num_sides(), 3);
+
+        // Select the contents of all terms, and accept
+        let mut input = scm_record::helpers::TestingInput::new(
+            80,
+            24,
+            [scm_record::Event::ToggleAll, scm_record::Event::QuitAccept],
+        );
+        let tree_id = edit_merge_builtin_with_input(&tree, &[merge_tool_file], &mut input).unwrap();
+        let expected_tree =
+            testutils::create_tree(&test_repo.repo, &[(file_path, "1\nbase\n2\nbase\n3\n")]);
+        assert_tree_eq!(&tree_id, &expected_tree.id(), store);
+    }
+
     prop_state_machine! {
         #[test]
         fn test_edit_diff_builtin_all_or_nothing_proptest(
@@ -343,13 +343,6 @@ impl MergeToolFile {
                 path: repo_path.to_owned(),
                 summary: conflict.describe(),
             })?;
-        // We only support conflicts with 2 sides (3-way conflicts)
-        if file.ids.num_sides() > 2 {
-            return Err(ConflictResolveError::ConflictTooComplicated {
-                path: repo_path.to_owned(),
-                sides: file.ids.num_sides(),
-            });
-        };
         if file.executable.is_none() {
             return Err(ConflictResolveError::ExecutableConflict {
                 path: repo_path.to_owned(),
@@ -362,6 +355,19 @@ impl MergeToolFile {
             file,
         })
     }
+
+    /// Checks that the conflict can be passed to a tool which only supports
+    /// 3-way merges.
+    fn check_two_sided(&self) -> Result<(), ConflictResolveError> {
+        let sides = self.file.ids.num_sides();
+        if sides > 2 {
+            return Err(ConflictResolveError::ConflictTooComplicated {
+                path: self.repo_path.clone(),
+                sides,
+            });
+        }
+        Ok(())
+    }
 }
 
 /// Configured 3-way merge editor.
@@ -432,6 +438,12 @@ impl MergeEditor {
             .iter()
             .map(|&repo_path| MergeToolFile::from_tree_and_path(tree, repo_path))
             .try_collect()?;
+        // Only the builtin tool supports conflicts with more than 2 sides
+        if !matches!(self.tool, MergeTool::Builtin) {
+            for file in &merge_tool_files {
+                file.check_two_sided()?;
+            }
+        }
 
         match &self.tool {
             MergeTool::Builtin => {
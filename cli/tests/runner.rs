@@ -14,6 +14,7 @@ mod test_acls;
 mod test_advance_bookmarks;
 mod test_alias;
 mod test_backout_command;
+mod test_bisect_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
 mod test_commit_command;
@@ -40,20 +41,27 @@ mod test_generate_md_cli_help;
 mod test_git_clone;
 mod test_git_colocated;
 mod test_git_fetch;
+#[cfg(feature = "git-builtin-transport")]
+mod test_git_http;
 mod test_git_import_export;
 mod test_git_init;
+#[cfg(feature = "git-builtin-transport")]
+mod test_git_lfs;
 mod test_git_private_commits;
 mod test_git_push;
 mod test_git_remotes;
 mod test_git_root;
+mod test_git_submodule;
 mod test_gitignores;
 mod test_global_opts;
 mod test_help_command;
 mod test_immutable_commits;
 mod test_interdiff_command;
 mod test_log_command;
+mod test_metaedit_command;
 mod test_new_command;
 mod test_next_prev_commands;
+mod test_note_command;
 mod test_operations;
 mod test_parallelize_command;
 mod test_rebase_command;
@@ -63,6 +71,7 @@ mod test_restore_command;
 mod test_revert_command;
 mod test_revset_output;
 mod test_root;
+mod test_run_command;
 mod test_show_command;
 mod test_sign_unsign_commands;
 mod test_simplify_parents_command;
@@ -310,6 +310,94 @@ fn test_advance_bookmarks_multiple_bookmarks(make_commit: CommitFn) {
     }
 }
 
+// Check that bookmarks in `revsets.bookmark-advance-from` are advanced when
+// advance-from-revset is enabled.
+#[test_case(commit_cmd ; "commit")]
+#[test_case(describe_new_cmd; "new")]
+fn test_advance_bookmarks_from_revset(make_commit: CommitFn) {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    set_advance_bookmarks(&test_env, true);
+    test_env.add_config("experimental-advance-branches.advance-from-revset = true");
+
+    make_commit(&work_dir, "first");
+    make_commit(&work_dir, "second");
+    work_dir
+        .run_jj(["bookmark", "create", "-r", "@--", "test_bookmark"])
+        .success();
+
+    // Bookmarks outside `revsets.bookmark-advance-from` are not advanced.
+    test_env.add_config(r#"revsets.bookmark-advance-from = "none()""#);
+    make_commit(&work_dir, "third");
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
+    @  bookmarks{} desc:
+    ○  bookmarks{} desc: third
+    ○  bookmarks{} desc: second
+    ○  bookmarks{test_bookmark} desc: first
+    ◆  bookmarks{} desc:
+    [EOF]
+    ");
+    }
+
+    // Bookmarks on ancestors of the working-copy parent are advanced.
+    test_env.add_config(r#"revsets.bookmark-advance-from = "heads(::@- & bookmarks())""#);
+    make_commit(&work_dir, "fourth");
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
+    @  bookmarks{} desc:
+    ○  bookmarks{test_bookmark} desc: fourth
+    ○  bookmarks{} desc: third
+    ○  bookmarks{} desc: second
+    ○  bookmarks{} desc: first
+    ◆  bookmarks{} desc:
+    [EOF]
+    ");
+    }
+}
+
+// Check that `jj new <rev>` doesn't advance bookmarks in
+// `revsets.bookmark-advance-from`, which is relative to the working copy.
+#[test]
+fn test_new_advance_bookmarks_from_revset_other_revision() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    set_advance_bookmarks(&test_env, true);
+    test_env.add_config("experimental-advance-branches.advance-from-revset = true");
+
+    work_dir.run_jj(["describe", "-m", "main"]).success();
+    work_dir.run_jj(["new", "-m", "feature1"]).success();
+    work_dir.run_jj(["new", "-m", "feature2"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r", "@--", "main"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r", "@", "feature"])
+        .success();
+    work_dir.run_jj(["new", "main"]).success();
+    insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
+    @  bookmarks{} desc:
+    │ ○  bookmarks{feature} desc: feature2
+    │ ○  bookmarks{} desc: feature1
+    ├─╯
+    ○  bookmarks{main} desc: main
+    ◆  bookmarks{} desc:
+    [EOF]
+    ");
+
+    work_dir.run_jj(["new", "feature"]).success();
+    insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
+    @  bookmarks{} desc:
+    ○  bookmarks{feature} desc: feature2
+    ○  bookmarks{} desc: feature1
+    ○  bookmarks{main} desc: main
+    ◆  bookmarks{} desc:
+    [EOF]
+    ");
+}
+
 // Call `jj new` on an interior commit and see that the bookmark pointing to its
 // parent's parent is advanced.
 #[test]
@@ -0,0 +1,268 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(unix)]
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+/// Creates a linear history of commits "1" to "6", where commit "4" is the
+/// first one containing "bad" in `file`.
+fn create_linear_history(work_dir: &TestWorkDir) {
+    for i in 1..=6 {
+        let contents = if i < 4 { "good\n" } else { "bad\n" };
+        work_dir.run_jj(["new", "-m", &i.to_string()]).success();
+        work_dir.write_file("file", contents);
+        work_dir.write_file("number", format!("{i}\n"));
+    }
+    work_dir.run_jj(["new", "root()"]).success();
+}
+
+#[test]
+fn test_bisect_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_linear_history(&work_dir);
+
+    let output = work_dir.run_jj([
+        "bisect",
+        "run",
+        "--range=description(1)::description(6)",
+        "--",
+        "sh",
+        "-c",
+        "! grep -q bad file",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Search complete. The first bad revision is: mzvwutvl 1bd00865 4
+    [EOF]
+    ------- stderr -------
+    Now evaluating: zsuskuln 426d8652 3
+    Working copy  (@) now at: yostqsxw dca4f9a9 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 426d8652 3
+    Added 2 files, modified 0 files, removed 0 files
+    The revision is good.
+
+    Now evaluating: royxmykx 568ccd8b 5
+    Working copy  (@) now at: wmwvqwsz 411a4fc1 (empty) (no description set)
+    Parent commit (@-)      : royxmykx 568ccd8b 5
+    Added 0 files, modified 2 files, removed 0 files
+    The revision is bad.
+
+    Now evaluating: mzvwutvl 1bd00865 4
+    Working copy  (@) now at: xpvxrnms 86cbeea8 (empty) (no description set)
+    Parent commit (@-)      : mzvwutvl 1bd00865 4
+    Added 0 files, modified 1 files, removed 0 files
+    The revision is bad.
+
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bisect_run_skip() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_linear_history(&work_dir);
+
+    // Commits "3" and "4" can't be evaluated
+    let output = work_dir.run_jj([
+        "bisect",
+        "run",
+        "--range=description(1)::description(6)",
+        "--",
+        "sh",
+        "-c",
+        "grep -q '^[34]$' number && exit 125; ! grep -q bad file",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Search complete. Because of skipped revisions, the first bad revision could be any of:
+      royxmykx 568ccd8b 5
+      mzvwutvl 1bd00865 4
+      zsuskuln 426d8652 3
+    [EOF]
+    ------- stderr -------
+    Now evaluating: zsuskuln 426d8652 3
+    Working copy  (@) now at: yostqsxw dca4f9a9 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 426d8652 3
+    Added 2 files, modified 0 files, removed 0 files
+    The revision is skipped.
+
+    Now evaluating: mzvwutvl 1bd00865 4
+    Working copy  (@) now at: wmwvqwsz d361f94f (empty) (no description set)
+    Parent commit (@-)      : mzvwutvl 1bd00865 4
+    Added 0 files, modified 2 files, removed 0 files
+    The revision is skipped.
+
+    Now evaluating: kkmpptxz df300647 2
+    Working copy  (@) now at: xpvxrnms dc57b95b (empty) (no description set)
+    Parent commit (@-)      : kkmpptxz df300647 2
+    Added 0 files, modified 2 files, removed 0 files
+    The revision is good.
+
+    Now evaluating: royxmykx 568ccd8b 5
+    Working copy  (@) now at: oluvmlzw 65ce368f (empty) (no description set)
+    Parent commit (@-)      : royxmykx 568ccd8b 5
+    Added 0 files, modified 2 files, removed 0 files
+    The revision is bad.
+
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bisect_run_no_bad_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_linear_history(&work_dir);
+
+    let output = work_dir.run_jj(["bisect", "run", "--range=description(1)::", "true"]);
+    insta::assert_snapshot!(output, @r"
+    Search complete. No bad revisions found in the range.
+    [EOF]
+    ------- stderr -------
+    Now evaluating: zsuskuln 426d8652 3
+    Working copy  (@) now at: yostqsxw dca4f9a9 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 426d8652 3
+    Added 2 files, modified 0 files, removed 0 files
+    The revision is good.
+
+    Now evaluating: royxmykx 568ccd8b 5
+    Working copy  (@) now at: wmwvqwsz 411a4fc1 (empty) (no description set)
+    Parent commit (@-)      : royxmykx 568ccd8b 5
+    Added 0 files, modified 2 files, removed 0 files
+    The revision is good.
+
+    Now evaluating: yqosqzyt bfc34d3c 6
+    Working copy  (@) now at: xpvxrnms 8234e15a (empty) (no description set)
+    Parent commit (@-)      : yqosqzyt bfc34d3c 6
+    Added 0 files, modified 1 files, removed 0 files
+    The revision is good.
+
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bisect_run_abort() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_linear_history(&work_dir);
+
+    let output = work_dir.run_jj([
+        "bisect",
+        "run",
+        "--range=description(1)::description(6)",
+        "--",
+        "sh",
+        "-c",
+        "exit 200",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Now evaluating: zsuskuln 426d8652 3
+    Working copy  (@) now at: yostqsxw dca4f9a9 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 426d8652 3
+    Added 2 files, modified 0 files, removed 0 files
+    Error: Aborting bisection: the command failed with exit status: 200
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["bisect", "run", "--range=none()", "true"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The range to bisect is empty
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_bisect_interactive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_linear_history(&work_dir);
+
+    let output = work_dir.run_jj(["bisect", "good"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No bisection in progress
+    Hint: Use `jj bisect start` to start one.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["bisect", "start", "--range=description(1)::description(6)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Now evaluating: zsuskuln 426d8652 3
+    Working copy  (@) now at: znkkpsqq feff5035 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 426d8652 3
+    Added 2 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bisect", "start", "--range=description(1)::description(6)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: A bisection is already in progress
+    Hint: Use `jj bisect reset` to abandon it.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Mark the revision being evaluated
+    let output = work_dir.run_jj(["bisect", "good"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Now evaluating: royxmykx 568ccd8b 5
+    Working copy  (@) now at: kmkuslsw f006e266 (empty) (no description set)
+    Parent commit (@-)      : royxmykx 568ccd8b 5
+    Added 0 files, modified 2 files, removed 0 files
+    [EOF]
+    ");
+
+    // Mark an explicit revision
+    let output = work_dir.run_jj(["bisect", "bad", "-r=description(4)"]);
+    insta::assert_snapshot!(output, @r"
+    Search complete. The first bad revision is: mzvwutvl 1bd00865 4
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["bisect", "good"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No revision is being evaluated
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["bisect", "reset"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Bisection reset.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bisect", "reset"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No bisection in progress.
+    [EOF]
+    ");
+}
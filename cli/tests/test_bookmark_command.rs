@@ -574,6 +574,106 @@ fn test_bookmark_move_conflicting() {
     ");
 }
 
+#[test]
+fn test_bookmark_advance() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "a1", "a2"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "b1"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["new"]).success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @   facfd221db13
+    ○   b4bed0ec2b43
+    ○  b1 1c1c95df80e5
+    ○  a1 a2 e8849ae12c70
+    ◆   000000000000
+    [EOF]
+    ");
+
+    // Only the closest bookmarks are advanced
+    let output = work_dir.run_jj(["bookmark", "advance"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Advanced 1 bookmarks to royxmykx facfd221 b1 | (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  b1 facfd221db13
+    ○   b4bed0ec2b43
+    ○   1c1c95df80e5
+    ○  a1 a2 e8849ae12c70
+    ◆   000000000000
+    [EOF]
+    ");
+
+    // Filter by name
+    let output = work_dir.run_jj(["bookmark", "advance", "b1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No bookmarks to advance.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "advance", "glob:x*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No matching bookmarks for patterns: x*
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "advance", "a1", "--to=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Advanced 1 bookmarks to mzvwutvl b4bed0ec a1 | (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  b1 facfd221db13
+    ○  a1 b4bed0ec2b43
+    ○   1c1c95df80e5
+    ○  a2 e8849ae12c70
+    ◆   000000000000
+    [EOF]
+    ");
+
+    // Bookmarks are never moved backwards or sideways
+    let output = work_dir.run_jj(["bookmark", "advance", "--to=root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Not advancing bookmarks that would move backwards or sideways: a1
+    No bookmarks to advance.
+    [EOF]
+    ");
+
+    // The source and the target can be configured
+    let output = work_dir.run_jj([
+        "bookmark",
+        "advance",
+        "--config=revsets.bookmark-advance-from='a1 | a2'",
+        "--config=revsets.bookmark-advance-to='b1'",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Advanced 2 bookmarks to royxmykx facfd221 a1 a2 b1 | (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  a1 a2 b1 facfd221db13
+    ○   b4bed0ec2b43
+    ○   1c1c95df80e5
+    ○   e8849ae12c70
+    ◆   000000000000
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_rename() {
     let test_env = TestEnvironment::default();
@@ -927,59 +927,60 @@ fn test_operations() {
         .split('\t')
         .next()
         .unwrap();
-    insta::assert_snapshot!(add_workspace_id, @"12f7cbba4278");
+    insta::assert_snapshot!(add_workspace_id, @"ff6e30d5ab07");
 
-    let output = work_dir.complete_fish(["op", "show", "8"]);
+    let output = work_dir.complete_fish(["op", "show", "6"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
-    8f47435a3990	(2001-02-03 08:05:07) add workspace 'default'
+    60cbb7330e2d	(2001-02-03 08:05:12) describe commit 96157804fd41363cb2ff8ff957ff1df1a2a1109a
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
+    66bf0ddd9238	(2001-02-03 08:05:09) describe commit 3ae22e7f50a15d393e412cca72d09a61165d0c84
     [EOF]
     ");
     // make sure global --at-op flag is respected
-    let output = work_dir.complete_fish(["--at-op", "8ed8c16786e6", "op", "show", "8"]);
+    let output = work_dir.complete_fish(["--at-op", "639c59cf9a5b", "op", "show", "6"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
-    8f47435a3990	(2001-02-03 08:05:07) add workspace 'default'
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
+    66bf0ddd9238	(2001-02-03 08:05:09) describe commit 3ae22e7f50a15d393e412cca72d09a61165d0c84
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["--at-op", "8e"]);
+    let output = work_dir.complete_fish(["--at-op", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "abandon", "8e"]);
+    let output = work_dir.complete_fish(["op", "abandon", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "diff", "--op", "8e"]);
+    let output = work_dir.complete_fish(["op", "diff", "--op", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
-    let output = work_dir.complete_fish(["op", "diff", "--from", "8e"]);
+    let output = work_dir.complete_fish(["op", "diff", "--from", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
-    let output = work_dir.complete_fish(["op", "diff", "--to", "8e"]);
+    let output = work_dir.complete_fish(["op", "diff", "--to", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "restore", "8e"]);
+    let output = work_dir.complete_fish(["op", "restore", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "undo", "8e"]);
+    let output = work_dir.complete_fish(["op", "undo", "63"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    639c59cf9a5b	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
 }
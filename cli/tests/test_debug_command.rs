@@ -295,7 +295,7 @@ fn test_debug_operation_id() {
     let work_dir = test_env.work_dir("repo");
     let output = work_dir.run_jj(["debug", "operation", "--display", "id"]);
     assert_snapshot!(filter_index_stats(output), @r"
-    8f47435a3990362feaf967ca6de2eb0a31c8b883dfcb66fba5c22200d12bbe61e3dc8bc855f1f6879285fcafaf85ac792f9a43bcc36e57d28737d18347d5e752
+    f045ea2142cf29f80725fef67591309ca80f6c3e2dfb9065b7717be89fa8c1def2d389ebcb1c8524e201a1976221d08334e72909734ea6370c1c11d26976d08d
     [EOF]
     ");
 }
@@ -659,6 +659,81 @@ fn test_diff_name_only() {
     ");
 }
 
+#[test]
+fn test_diff_file_predicates() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("small", "small\n");
+    work_dir.write_file("large.BIN", "x".repeat(2000));
+    work_dir.write_file("script", "#!/bin/sh\n");
+    work_dir.run_jj(["file", "chmod", "x", "script"]).success();
+
+    let output = work_dir.run_jj(["diff", "--name-only", "executable()"]);
+    insta::assert_snapshot!(output, @r"
+    script
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--name-only", "larger_than(1KB)"]);
+    insta::assert_snapshot!(output, @r"
+    large.BIN
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--name-only", "~larger_than(1KB) ~ executable()"]);
+    insta::assert_snapshot!(output, @r"
+    small
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--name-only", "glob-i:*.bin"]);
+    insta::assert_snapshot!(output, @r"
+    large.BIN
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--name-only", "larger_than(1XB)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse fileset: Invalid file size: 1XB
+    Caused by:  --> 1:13
+      |
+    1 | larger_than(1XB)
+      |             ^-^
+      |
+      = Invalid file size: 1XB
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // A file which became small still matches because it was large before
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("large.BIN", "x\n");
+    let output = work_dir.run_jj(["diff", "--name-only", "larger_than(1KB)"]);
+    insta::assert_snapshot!(output, @r"
+    large.BIN
+    [EOF]
+    ");
+
+    // Conflicted files
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file", "b\n");
+    // Merge all children of the root commit
+    work_dir.run_jj(["new", "root()+"]).success();
+    let output = work_dir.run_jj(["file", "list", "conflicted()"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list", "~conflicted()"]);
+    insta::assert_snapshot!(output, @r"
+    large.BIN
+    script
+    small
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_renamed_file_and_dir() {
     let test_env = TestEnvironment::default();
@@ -3760,6 +3835,46 @@ fn test_diff_binary() {
     ");
 }
 
+#[test]
+fn test_diff_binary_gitattributes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file(".gitattributes", "*.dat binary\n*.txt diff\n");
+    work_dir.write_file("data.dat", "foo\n");
+    work_dir.write_file("text.txt", "foo\0\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("data.dat", "bar\n");
+    work_dir.write_file("text.txt", "bar\0\n");
+
+    let output = work_dir.run_jj(["diff"]);
+    insta::assert_snapshot!(
+        output.normalize_stdout_with(|s| s.replace('\0', "<NUL>")), @r"
+    Modified regular file data.dat:
+        (binary)
+    Modified regular file text.txt:
+       1    1: foobar<NUL>
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(
+        output.normalize_stdout_with(|s| s.replace('\0', "<NUL>")), @r"
+    diff --git a/data.dat b/data.dat
+    index 257cc5642c..5716ca5987 100644
+    Binary files a/data.dat and b/data.dat differ
+    diff --git a/text.txt b/text.txt
+    index 4af7a02cfb..080090e709 100644
+    --- a/text.txt
+    +++ b/text.txt
+    @@ -1,1 +1,1 @@
+    -foo<NUL>
+    +bar<NUL>
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_revisions() {
     let test_env = TestEnvironment::default();
@@ -212,6 +212,54 @@ fn test_annotate_abandoned() {
     ");
 }
 
+#[test]
+fn test_annotate_rename() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("old.txt", "line1\nline2\nline3\n");
+    work_dir.run_jj(["commit", "-m=initial"]).success();
+    work_dir.remove_file("old.txt");
+    work_dir.write_file("new.txt", "line1\nline2\nline3\nline4\n");
+    work_dir.run_jj(["commit", "-m=rename"]).success();
+    work_dir.write_file("other.txt", "moved1\nmoved2\n");
+    work_dir.run_jj(["commit", "-m=add other"]).success();
+    work_dir.write_file("new.txt", "line1\nmoved1\nmoved2\nline2\nline3\nline4\n");
+    work_dir.write_file("other.txt", "");
+    work_dir.run_jj(["describe", "-m=move lines"]).success();
+
+    let template = r#"commit.description().first_line() ++ ": " ++ content"#;
+    let output = work_dir.run_jj(["file", "annotate", "new.txt", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    initial: line1
+    move lines: moved1
+    move lines: moved2
+    initial: line2
+    initial: line3
+    rename: line4
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "file",
+        "annotate",
+        "new.txt",
+        "-T",
+        template,
+        "--detect-moved-lines",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    initial: line1
+    add other: moved1
+    add other: moved2
+    initial: line2
+    initial: line3
+    rename: line4
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_annotate_with_template() {
     let test_env = TestEnvironment::default();
@@ -263,3 +311,20 @@ fn test_annotate_with_template() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_annotate_file_predicate() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file.txt", "x".repeat(2000));
+
+    let output = work_dir.run_jj(["file", "annotate", "larger_than(1KB)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such path: larger_than(1KB)
+    [EOF]
+    [exit status: 1]
+    ");
+}
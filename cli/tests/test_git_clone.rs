@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
 use std::path;
 
 use indoc::formatdoc;
@@ -601,7 +602,7 @@ fn test_git_clone_ignore_working_copy() {
     let output = clone_dir.run_jj(["status"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: The working copy is stale (not updated since operation 8f47435a3990).
+    Error: The working copy is stale (not updated since operation f045ea2142cf).
     Hint: Run `jj workspace update-stale` to update it.
     See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy for more information.
     [EOF]
@@ -844,6 +845,115 @@ fn test_git_clone_with_depth() {
     ");
 }
 
+#[test]
+fn test_git_clone_with_filter() {
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    let clone_dir = test_env.work_dir("clone");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(&git_repo_path);
+    let mut parents = vec![];
+    for content in ["1\n", "2\n", "3\n"] {
+        let commit_id = git::add_commit(
+            &git_repo,
+            "refs/heads/main",
+            "file",
+            content.as_bytes(),
+            &format!("content {content}"),
+            &parents,
+        )
+        .commit_id;
+        parents = vec![commit_id];
+    }
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(git_repo_path.join(".git").join("config"))
+        .unwrap();
+    writeln!(config, "[uploadpack]\n\tallowFilter = true").unwrap();
+
+    let output = root_dir.run_jj(["git", "clone", "--filter=blob:none", "source", "clone"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone"
+    bookmark: main@origin [new] tracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Working copy  (@) now at: sqpuoqvx 7952d8a7 (empty) (no description set)
+    Parent commit (@-)      : qzrkqxzt 7656126c main | content 3
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    let git_config =
+        std::fs::read_to_string(clone_dir.root().join(".jj/repo/store/git/config")).unwrap();
+    assert!(git_config.contains("partialclonefilter = blob:none"));
+
+    // Missing file contents are fetched on demand
+    let output = clone_dir.run_jj(["file", "show", "-r", "main-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    2
+    [EOF]
+    ");
+
+    // Reading missing file contents fails if the remote isn't reachable
+    std::fs::rename(&git_repo_path, test_env.env_root().join("moved")).unwrap();
+    let output = clone_dir.run_jj(["file", "show", "-r", "main--", "file"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Error when reading object d00491fd7e5bb6fa28c517a0bb32b8b506539d4d of type file
+    Caused by:
+    1: Failed to fetch missing object from the promisor remote 'origin' of the partial clone
+    2: Git process failed: External git program failed:
+    fatal: '$TEST_ENV/source' does not appear to be a git repository
+    fatal: Could not read from remote repository.
+
+    Please make sure you have the correct access rights
+    and the repository exists.
+    fatal: could not fetch d00491fd7e5bb6fa28c517a0bb32b8b506539d4d from promisor remote
+
+    Hint: The repo is a partial clone. Missing file contents are fetched from the remote on demand, so the remote has to be reachable.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Missing file contents are fetched at once before checking out or
+    // showing a diff
+    let output = clone_dir.run_jj(["diff", "--git", "-r", "main--"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to fetch missing object from the promisor remote 'origin' of the partial clone
+    Caused by: Git process failed: External git program failed:
+    fatal: '$TEST_ENV/source' does not appear to be a git repository
+    fatal: Could not read from remote repository.
+
+    Please make sure you have the correct access rights
+    and the repository exists.
+
+    Hint: The repo is a partial clone. Missing file contents are fetched from the remote on demand, so the remote has to be reachable.
+    [EOF]
+    [exit status: 1]
+    ");
+    std::fs::rename(test_env.env_root().join("moved"), &git_repo_path).unwrap();
+    let output = clone_dir.run_jj(["new", "main--"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: mzvwutvl 5a327e3f (empty) (no description set)
+    Parent commit (@-)      : vvqtvlwt 5de12ed3 content 1
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = clone_dir.run_jj(["diff", "--git", "-r", "main-"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file b/file
+    index d00491fd7e..0cfbf08886 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -1
+    +2
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_clone_invalid_immutable_heads() {
     let test_env = TestEnvironment::default();
@@ -898,7 +1008,7 @@ fn test_git_clone_malformed() {
     let output = clone_dir.run_jj(["status"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: The working copy is stale (not updated since operation 01e1362cd2e1).
+    Error: The working copy is stale (not updated since operation 3cec3643be87).
     Hint: Run `jj workspace update-stale` to update it.
     See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy for more information.
     [EOF]
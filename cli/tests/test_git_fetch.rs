@@ -110,6 +110,139 @@ fn test_git_fetch_with_default_config() {
     ");
 }
 
+#[test]
+fn test_git_fetch_deepen() {
+    let test_env = TestEnvironment::default();
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(&git_repo_path);
+    let mut parents = vec![];
+    for message in ["first", "second", "third"] {
+        let commit_id = git::add_commit(
+            &git_repo,
+            "refs/heads/main",
+            "file",
+            message.as_bytes(),
+            message,
+            &parents,
+        )
+        .commit_id;
+        parents = vec![commit_id];
+    }
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    test_env
+        .run_jj_in(".", ["git", "clone", "--depth=1", "source", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r#"
+    @  4086d89a76a5 ""
+    ◆  d60f49650c28 "third" main
+    ◆  000000000000 ""
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["git", "fetch", "--deepen=1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r#"
+    @  4086d89a76a5 ""
+    ◆  d60f49650c28 "third" main
+    ◆  4f7f5dd3d811 "second"
+    ◆  000000000000 ""
+    [EOF]
+    "#);
+
+    // The history can be deepened further
+    work_dir.run_jj(["git", "fetch", "--deepen=1"]).success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r#"
+    @  4086d89a76a5 ""
+    ◆  d60f49650c28 "third" main
+    ◆  4f7f5dd3d811 "second"
+    ◆  0cd610e3a6b7 "first"
+    ◆  000000000000 ""
+    [EOF]
+    "#);
+}
+
+#[cfg(not(feature = "git-builtin-transport"))]
+#[test]
+fn test_git_fetch_builtin_transport_unavailable() {
+    let test_env = TestEnvironment::default();
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(&git_repo_path);
+    add_commit_to_branch(&git_repo, "main");
+    test_env
+        .run_jj_in(".", ["git", "clone", "source", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let output = work_dir.run_jj(["git", "fetch", "--config=git.transport='builtin'"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The builtin Git transport is unavailable because jj was not compiled with the `git-builtin-transport` feature
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(feature = "git-builtin-transport")]
+#[test]
+fn test_git_fetch_builtin_transport() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.transport = 'builtin'");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(&git_repo_path);
+    add_commit_to_branch(&git_repo, "main");
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    test_env
+        .run_jj_in(".", ["git", "clone", "source", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+
+    add_commit_to_branch(&git_repo, "feature");
+    let output = work_dir.run_jj(["git", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: feature@origin [new] untracked
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    feature@origin: srwrtuky 16ec9ef2 message
+    main: kmpysrkw 0130f303 message
+      @origin: kmpysrkw 0130f303 message
+    [EOF]
+    ");
+
+    // Bookmarks deleted on the remote are pruned
+    git_repo
+        .find_reference("refs/heads/feature")
+        .unwrap()
+        .delete()
+        .unwrap();
+    let output = work_dir.run_jj(["git", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: feature@origin [deleted] untracked
+    Abandoned 1 commits that are no longer reachable.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main: kmpysrkw 0130f303 message
+      @origin: kmpysrkw 0130f303 message
+    [EOF]
+    ");
+
+    // Shallow history isn't supported
+    let output = work_dir.run_jj(["git", "fetch", "--deepen=1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Shallow fetch is not supported by the builtin Git transport
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_fetch_default_remote() {
     let test_env = TestEnvironment::default();
@@ -1134,6 +1267,69 @@ fn test_git_fetch_bookmarks_missing_with_subprocess_localized_message() {
 
 // See `test_undo_restore_commands.rs` for fetch-undo-push and fetch-undo-fetch
 // of the same bookmarks for various kinds of undo.
+#[test]
+fn test_git_fetch_bookmarks_from_remote_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let source_repo = init_git_remote(&test_env, "origin");
+    for branch in ["main", "team/a", "team/b", "other"] {
+        add_commit_to_branch(&source_repo, branch);
+    }
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "origin",
+            "../origin",
+            "--fetch=main",
+            "--fetch=glob:team/*",
+        ])
+        .success();
+
+    // Only the bookmarks specified by the remote's refspecs are fetched
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main@origin: kmpysrkw 0130f303 message
+    team/a@origin: olzosvmx 72ff3ac5 message
+    team/b@origin: srytypmy 5525a219 message
+    [EOF]
+    ");
+
+    // The setting takes precedence over the refspecs
+    test_env.add_config(r#"git.fetch-bookmarks.origin = ["other"]"#);
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main@origin: kmpysrkw 0130f303 message
+    other@origin: qtzmqslk 8820d88a message
+    team/a@origin: olzosvmx 72ff3ac5 message
+    team/b@origin: srytypmy 5525a219 message
+    [EOF]
+    ");
+
+    // Explicitly specified bookmarks take precedence over the default
+    work_dir
+        .run_jj(["git", "fetch", "--branch=origin"])
+        .success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main@origin: kmpysrkw 0130f303 message
+    origin@origin: qmyrypzk ab8b299e message
+    other@origin: qtzmqslk 8820d88a message
+    team/a@origin: olzosvmx 72ff3ac5 message
+    team/b@origin: srytypmy 5525a219 message
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "remote", "add", "bad", "../origin", "--fetch=*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid bookmark pattern provided: '*'
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_fetch_undo() {
     let test_env = TestEnvironment::default();
@@ -1185,7 +1381,7 @@ fn test_git_fetch_undo() {
     let output = target_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: 158b589e0e15 (2001-02-03 08:05:18) fetch from git remote(s) origin
+    Undid operation: c47099255a64 (2001-02-03 08:05:18) fetch from git remote(s) origin
     [EOF]
     ");
     // The undo works as expected
@@ -1274,7 +1470,7 @@ fn test_fetch_undo_what() {
     let output = work_dir.run_jj(["op", "restore", "--what", "repo", &base_operation_id]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -1305,7 +1501,7 @@ fn test_fetch_undo_what() {
     ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -0,0 +1,273 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The test server runs `git http-backend`.
+#![cfg(unix)]
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+
+use bstr::ByteSlice;
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+/// Basic authentication of alice:secret
+const AUTHORIZATION: &str = "Basic YWxpY2U6c2VjcmV0";
+
+/// Smart HTTP server running `git http-backend` for the repos in a directory.
+struct GitHttpServer {
+    addr: SocketAddr,
+}
+
+impl GitHttpServer {
+    fn start(root: &Path) -> Self {
+        Self::start_with_auth(root, None)
+    }
+
+    /// Starts a server which requires the `authorization` header.
+    fn start_with_auth(root: &Path, authorization: Option<&'static str>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let root = root.to_owned();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                handle_request(stream.unwrap(), &root, authorization);
+            }
+        });
+        GitHttpServer { addr }
+    }
+
+    fn url(&self, repo: &str) -> String {
+        format!("http://{}/{repo}", self.addr)
+    }
+}
+
+fn handle_request(mut stream: TcpStream, root: &Path, required_authorization: Option<&str>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut content_length = 0;
+    let mut chunked = false;
+    let mut content_type = String::new();
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line.trim_end().is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').unwrap();
+        let value = value.trim().to_owned();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().unwrap();
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("content-type") {
+            content_type = value;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value);
+        }
+    }
+    let body = if chunked {
+        read_chunked_body(&mut reader)
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        body
+    };
+
+    if required_authorization.is_some() && authorization.as_deref() != required_authorization {
+        write!(
+            stream,
+            "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic \
+             realm=\"git\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        return;
+    }
+
+    let mut parts = request_line.split(' ');
+    let method = parts.next().unwrap();
+    let target = parts.next().unwrap();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut child = Command::new("git")
+        .arg("http-backend")
+        .env("GIT_CONFIG_GLOBAL", "/dev/null")
+        .env("GIT_CONFIG_SYSTEM", "/dev/null")
+        .env("GIT_PROJECT_ROOT", root)
+        .env("GIT_HTTP_EXPORT_ALL", "1")
+        // Allow anonymous pushes
+        .env("GIT_CONFIG_COUNT", "1")
+        .env("GIT_CONFIG_KEY_0", "http.receivepack")
+        .env("GIT_CONFIG_VALUE_0", "true")
+        .env("REMOTE_ADDR", "127.0.0.1")
+        .env("REQUEST_METHOD", method)
+        .env("PATH_INFO", path)
+        .env("QUERY_STRING", query)
+        .env("CONTENT_TYPE", content_type)
+        .env("CONTENT_LENGTH", body.len().to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&body).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    // Convert the CGI response to an HTTP response
+    let (headers, response) = output.stdout.split_once_str("\r\n\r\n").unwrap();
+    let mut status = "200 OK".to_owned();
+    let mut response_headers = String::new();
+    for line in ByteSlice::lines(headers) {
+        let line = line.to_str().unwrap();
+        if let Some(value) = line.strip_prefix("Status: ") {
+            status = value.to_owned();
+        } else {
+            response_headers.push_str(line);
+            response_headers.push_str("\r\n");
+        }
+    }
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{response_headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.len()
+    )
+    .unwrap();
+    stream.write_all(response).unwrap();
+}
+
+/// Reads a body sent with `Transfer-Encoding: chunked`, as pushes are.
+fn read_chunked_body(reader: &mut impl BufRead) -> Vec<u8> {
+    let mut body = vec![];
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line).unwrap();
+        let size = usize::from_str_radix(size_line.trim_end(), 16).unwrap();
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).unwrap();
+        if size == 0 {
+            return body;
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+#[test]
+fn test_git_http_builtin_transport() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.transport = 'builtin'");
+    let git_repo = git::init_bare(test_env.env_root().join("origin.git"));
+    git::add_commit(&git_repo, "refs/heads/main", "file", b"main", "main", &[]);
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    let server = GitHttpServer::start(test_env.env_root());
+    let url = server.url("origin.git");
+    // Drop the progress messages of the server, which depend on the Git version
+    let normalize = |text: String| {
+        text.replace(&server.addr.to_string(), "$SERVER")
+            .split_inclusive('\n')
+            .filter(|line| !line.starts_with("remote: "))
+            .collect()
+    };
+
+    let output = test_env.run_jj_in(".", ["git", "clone", &url, "repo"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize), @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/repo"
+    bookmark: main@origin [new] tracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Working copy  (@) now at: sqpuoqvx 0b107cda (empty) (no description set)
+    Parent commit (@-)      : knulxszy 33b53f99 main | main
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    let work_dir = test_env.work_dir("repo");
+
+    git::add_commit(
+        &git_repo,
+        "refs/heads/feature",
+        "file",
+        b"feature",
+        "feature",
+        &[],
+    );
+    let output = work_dir.run_jj(["git", "fetch"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize), @r"
+    ------- stderr -------
+    bookmark: feature@origin [new] untracked
+    [EOF]
+    ");
+
+    // Push a new bookmark
+    work_dir.run_jj(["new", "main", "-m", "local"]).success();
+    work_dir.write_file("file", "local");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "local"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--allow-new", "-b", "local"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize), @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark local to 6e225c468107
+    [EOF]
+    ");
+    let git_repo = git::open(test_env.env_root().join("origin.git"));
+    let pushed_commit = git_repo
+        .find_reference("refs/heads/local")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(pushed_commit.message_raw().unwrap(), "local\n");
+}
+
+#[test]
+fn test_git_http_builtin_transport_no_plain_http_credentials() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.transport = 'builtin'");
+    let git_repo = git::init_bare(test_env.env_root().join("origin.git"));
+    git::add_commit(&git_repo, "refs/heads/main", "file", b"main", "main", &[]);
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    let server = GitHttpServer::start_with_auth(test_env.env_root(), Some(AUTHORIZATION));
+    let url = server.url("origin.git");
+    let normalize = |text: String| text.replace(&server.addr.to_string(), "$SERVER");
+
+    // Without credentials, the server rejects the request
+    let output = test_env.run_jj_in(".", ["git", "clone", &url, "repo"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize), @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/repo"
+    Error: Authentication failed for http://$SERVER/origin.git
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    // Credentials aren't sent over plain HTTP
+    let url_with_credentials = url.replace("http://", "http://alice:secret@");
+    let output = test_env.run_jj_in(".", ["git", "clone", &url_with_credentials, "repo"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize), @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/repo"
+    Error: Failed to connect to http://$SERVER/origin.git
+    Caused by: The transport layer refuses to use a given identity: Will not send credentials in clear text over http
+    [EOF]
+    [exit status: 1]
+    "#);
+}
@@ -0,0 +1,356 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt as _;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::common::TestEnvironment;
+
+type LfsObjects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// Minimal LFS server serving the batch API at `/repo.git/info/lfs`.
+struct LfsServer {
+    addr: SocketAddr,
+    objects: LfsObjects,
+}
+
+impl LfsServer {
+    fn start() -> Self {
+        Self::start_with_auth(None)
+    }
+
+    /// Starts a server which requires the `authorization` header in batch API
+    /// requests. Object transfers are authorized by a token passed in the
+    /// headers of the batch response.
+    fn start_with_auth(authorization: Option<&'static str>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let objects = LfsObjects::default();
+        let server_objects = objects.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                handle_request(stream.unwrap(), addr, &server_objects, authorization);
+            }
+        });
+        LfsServer { addr, objects }
+    }
+
+    fn repo_url(&self) -> String {
+        format!("http://{}/repo.git", self.addr)
+    }
+}
+
+fn handle_request(
+    mut stream: TcpStream,
+    addr: SocketAddr,
+    objects: &LfsObjects,
+    authorization: Option<&str>,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut content_length = 0;
+    let mut request_authorization = None;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line.trim_end().is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').unwrap();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().unwrap();
+        } else if name.eq_ignore_ascii_case("authorization") {
+            request_authorization = Some(value.trim().to_owned());
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    let mut objects = objects.lock().unwrap();
+    let (method, path) = request_line.split_once(' ').unwrap();
+    let path = path.split_once(' ').unwrap().0;
+    let action_authorization = authorization.map(|_| "RemoteAuth token");
+    let (status, response) = if let Some(oid) = path.strip_prefix("/objects/") {
+        if request_authorization.as_deref() != action_authorization {
+            ("401 Unauthorized", vec![])
+        } else {
+            match method {
+                "GET" => match objects.get(oid) {
+                    Some(content) => ("200 OK", content.clone()),
+                    None => ("404 Not Found", vec![]),
+                },
+                "PUT" => {
+                    objects.insert(oid.to_owned(), body);
+                    ("200 OK", vec![])
+                }
+                _ => ("405 Method Not Allowed", vec![]),
+            }
+        }
+    } else if path == "/repo.git/info/lfs/objects/batch"
+        && request_authorization.as_deref() != authorization
+    {
+        ("401 Unauthorized", vec![])
+    } else if path == "/repo.git/info/lfs/objects/batch" {
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let operation = request["operation"].as_str().unwrap();
+        let response_objects: Vec<_> = request["objects"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|object| {
+                let oid = object["oid"].as_str().unwrap();
+                let href = format!("http://{addr}/objects/{oid}");
+                let header = match action_authorization {
+                    Some(value) => serde_json::json!({ "Authorization": value }),
+                    None => serde_json::json!({}),
+                };
+                match (operation, objects.contains_key(oid)) {
+                    ("download", true) | ("upload", false) => serde_json::json!({
+                        "oid": oid,
+                        "size": object["size"],
+                        "actions": { operation: { "href": href, "header": header } },
+                    }),
+                    ("download", false) => serde_json::json!({
+                        "oid": oid,
+                        "size": object["size"],
+                        "error": { "code": 404, "message": "Object does not exist" },
+                    }),
+                    _ => object.clone(),
+                }
+            })
+            .collect();
+        let response = serde_json::json!({ "transfer": "basic", "objects": response_objects });
+        ("200 OK", serde_json::to_vec(&response).unwrap())
+    } else {
+        ("404 Not Found", vec![])
+    };
+    let authenticate = if status.starts_with("401") {
+        "WWW-Authenticate: Basic realm=\"lfs\"\r\n"
+    } else {
+        ""
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{authenticate}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.len()
+    )
+    .unwrap();
+    stream.write_all(&response).unwrap();
+}
+
+#[test]
+fn test_git_lfs_push_and_fetch() {
+    let test_env = TestEnvironment::default();
+    let server = LfsServer::start();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", &server.repo_url()])
+        .success();
+    work_dir.write_file(".gitattributes", "*.bin filter=lfs\n");
+    work_dir.write_file("asset.bin", "asset content\n");
+
+    // The file is recorded as a pointer to the LFS object
+    let output = work_dir.run_jj(["file", "show", "asset.bin"]);
+    insta::assert_snapshot!(output, @r"
+    version https://git-lfs.github.com/spec/v1
+    oid sha256:5f30e1c6d1ef785ed8c4b312e0aaed02c29c84cf4e4a590138bcb88284c554f2
+    size 14
+    [EOF]
+    ");
+    work_dir.run_jj(["commit", "-m", "asset"]).success();
+
+    let output = work_dir.run_jj(["git", "lfs", "push", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Uploaded 1 LFS objects
+    [EOF]
+    ");
+    assert_eq!(server.objects.lock().unwrap().len(), 1);
+    let output = work_dir.run_jj(["git", "lfs", "push", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Without the local LFS object, the pointer file is checked out
+    work_dir.remove_dir_all(".jj/repo/store/git/lfs");
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.run_jj(["new", "description(asset)"]).success();
+    insta::assert_snapshot!(work_dir.read_file("asset.bin"), @r"
+    version https://git-lfs.github.com/spec/v1
+    oid sha256:5f30e1c6d1ef785ed8c4b312e0aaed02c29c84cf4e4a590138bcb88284c554f2
+    size 14
+    ");
+
+    // Fetching the object replaces the pointer file
+    let output = work_dir.run_jj(["git", "lfs", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Downloaded 1 LFS objects
+    Updated 1 files in the working copy
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("asset.bin"), @"asset content");
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : vruxwmqv e9dbfe1a (empty) (no description set)
+    Parent commit (@-): qpvuntsm 7aaca5ae asset
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "lfs", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
+
+// The credential helper is a shell script.
+#[cfg(unix)]
+#[test]
+fn test_git_lfs_credentials() {
+    let test_env = TestEnvironment::default();
+    // Basic authentication of alice:secret
+    let server = LfsServer::start_with_auth(Some("Basic YWxpY2U6c2VjcmV0"));
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", &server.repo_url()])
+        .success();
+    work_dir.write_file(".gitattributes", "*.bin filter=lfs\n");
+    work_dir.write_file("asset.bin", "asset content\n");
+    work_dir.run_jj(["commit", "-m", "asset"]).success();
+    let normalize = |text: String| text.replace(&server.addr.to_string(), "$SERVER");
+
+    // Without credentials, the server rejects the request
+    let output = work_dir.run_jj(["git", "lfs", "push", "-r", "@-"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize), @r"
+    ------- stderr -------
+    Error: Failed to transfer Git LFS objects
+    Caused by: Authentication failed for http://$SERVER/repo.git/info/lfs
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Credentials embedded in the LFS URL are used
+    let url = format!("http://alice:secret@{}/repo.git/info/lfs", server.addr);
+    let git_config_path = work_dir.root().join(".jj/repo/store/git/config");
+    let mut git_config = std::fs::read_to_string(&git_config_path).unwrap();
+    let original_git_config = git_config.clone();
+    git_config.push_str(&format!("[lfs]\n\turl = {url}\n"));
+    std::fs::write(&git_config_path, &git_config).unwrap();
+    let output = work_dir.run_jj(["git", "lfs", "push", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Uploaded 1 LFS objects
+    [EOF]
+    ");
+    assert_eq!(server.objects.lock().unwrap().len(), 1);
+
+    // Credentials are looked up with the credential helpers
+    let helper_path = test_env.env_root().join("credential-helper");
+    std::fs::write(
+        &helper_path,
+        "#!/bin/sh\necho username=alice\necho password=secret\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&helper_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let mut git_config = original_git_config;
+    git_config.push_str(&format!(
+        "[credential]\n\thelper = {}\n",
+        helper_path.display()
+    ));
+    std::fs::write(&git_config_path, &git_config).unwrap();
+    work_dir.remove_dir_all(".jj/repo/store/git/lfs");
+    let output = work_dir.run_jj(["git", "lfs", "fetch", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Downloaded 1 LFS objects
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_lfs_fetch_missing_object() {
+    let test_env = TestEnvironment::default();
+    let server = LfsServer::start();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", &server.repo_url()])
+        .success();
+    work_dir.write_file(".gitattributes", "*.bin filter=lfs\n");
+    work_dir.write_file("asset.bin", "asset content\n");
+    work_dir.run_jj(["commit", "-m", "asset"]).success();
+    work_dir.remove_dir_all(".jj/repo/store/git/lfs");
+
+    let output = work_dir.run_jj(["git", "lfs", "fetch", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to transfer Git LFS objects
+    Caused by: LFS server rejected object 5f30e1c6d1ef785ed8c4b312e0aaed02c29c84cf4e4a590138bcb88284c554f2: Object does not exist
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The objects that aren't in the local store can't be pushed
+    let output = work_dir.run_jj(["git", "lfs", "push", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Skipped 1 LFS objects which aren't in the local LFS store
+    Nothing changed.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_lfs_no_endpoint() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../other"])
+        .success();
+
+    let output = work_dir.run_jj(["git", "lfs", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to transfer Git LFS objects
+    Caused by: Cannot determine the LFS server of remote 'origin'. Set `lfs.url` in the Git config
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "lfs", "push", "--remote", "upstream"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to transfer Git LFS objects
+    Caused by: No git remote named 'upstream'
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -282,6 +282,77 @@ fn test_git_push_other_remote_has_bookmark() {
     ");
 }
 
+#[cfg(feature = "git-builtin-transport")]
+#[test]
+fn test_git_push_builtin_transport() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("git.transport = 'builtin'");
+    let work_dir = test_env.work_dir("local");
+
+    // Move bookmark1 forward, delete bookmark2, and create a new bookmark
+    work_dir.run_jj(["new", "bookmark1", "-m=local"]).success();
+    work_dir.write_file("local", "local");
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark2"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-bookmark"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--all", "--deleted"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 1cd6269d7d57
+      Delete bookmark bookmark2 from 38a204733702
+      Add bookmark my-bookmark to 1cd6269d7d57
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: vruxwmqv 1cd6269d local
+      @origin: vruxwmqv 1cd6269d local
+    my-bookmark: vruxwmqv 1cd6269d local
+      @origin: vruxwmqv 1cd6269d local
+    [EOF]
+    ");
+
+    // The remote-tracking refs were updated in the Git repo too
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Move bookmark1 on the remote, and try to push it sideways
+    let origin_dir = test_env.work_dir("origin");
+    origin_dir.run_jj(["git", "import"]).success();
+    origin_dir.run_jj(["new", "root()", "-m=remote"]).success();
+    origin_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@", "--allow-backwards"])
+        .success();
+    origin_dir.run_jj(["git", "export"]).success();
+    work_dir.run_jj(["new", "root()", "-m=sideways"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@", "--allow-backwards"])
+        .success();
+    let output = work_dir.run_jj(["git", "push"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move sideways bookmark bookmark1 from 1cd6269d7d57 to a37f9ecdd3c6
+    Error: Failed to push some bookmarks
+    Hint: The following references unexpectedly moved on the remote:
+      refs/heads/bookmark1 (reason: stale info)
+    Hint: Try fetching from the remote, then make the bookmark point to where you want it to be, and push again.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_push_forward_unexpectedly_moved() {
     let test_env = TestEnvironment::default();
@@ -1713,6 +1784,53 @@ fn test_git_push_deleted() {
     ");
 }
 
+#[test]
+fn test_git_push_bookmarks_from_config() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    test_env.add_config(r#"git.push-bookmarks.origin = ["glob:bookmark2"]"#);
+
+    work_dir
+        .run_jj(["describe", "bookmark1", "-m", "modified bookmark1 commit"])
+        .success();
+    work_dir
+        .run_jj(["describe", "bookmark2", "-m", "modified bookmark2 commit"])
+        .success();
+
+    // Bookmarks not allowed to be pushed are skipped
+    let output = work_dir.run_jj(["git", "push", "--all", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Bookmark bookmark1 is not allowed to be pushed to remote origin
+    Hint: Update the `git.push-bookmarks.origin` setting to allow pushing it.
+    Changes to push to origin:
+      Move sideways bookmark bookmark2 from 38a204733702 to eb5bbacb3a5f
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // Pushing them explicitly fails
+    let output = work_dir.run_jj(["git", "push", "--bookmark=bookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmark bookmark1 is not allowed to be pushed to remote origin
+    Hint: Update the `git.push-bookmarks.origin` setting to allow pushing it.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Pushing them to another ref fails too
+    let output = work_dir.run_jj(["git", "push", "--bookmark=bookmark1", "--to=other"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmark bookmark1 is not allowed to be pushed to remote origin
+    Hint: Update the `git.push-bookmarks.origin` setting to allow pushing it.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_push_conflicting_bookmarks() {
     let test_env = TestEnvironment::default();
@@ -2244,6 +2362,306 @@ fn test_git_push_rejected_by_remote() {
     });
 }
 
+#[test]
+fn test_git_push_tags() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let origin_dir = test_env.work_dir("origin");
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["tag", "create", "-r=bookmark1", "v1"])
+        .success();
+    work_dir
+        .run_jj(["tag", "create", "-r=bookmark2", "v2"])
+        .success();
+    work_dir
+        .run_jj(["tag", "create", "-r=bookmark2", "w1"])
+        .success();
+
+    // Tags aren't pushed by default
+    let output = work_dir.run_jj(["git", "push", "--all", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "push", "--tag=glob:v*", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add tag v1 to 9b2e76de3920
+      Add tag v2 to 38a204733702
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "push", "--tag=glob:v*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add tag v1 to 9b2e76de3920
+      Add tag v2 to 38a204733702
+    [EOF]
+    ");
+    origin_dir.run_jj(["git", "import"]).success();
+    let output = origin_dir.run_jj(["tag", "list"]);
+    insta::assert_snapshot!(output, @r"
+    v1: qpvuntsm 9b2e76de (empty) description 1
+    v2: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+
+    // Tags already on the remote are skipped
+    let output = work_dir.run_jj(["git", "push", "--tag=v1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Tag v1@origin already matches v1
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Move and delete tags
+    work_dir
+        .run_jj(["tag", "set", "--allow-move", "-r=bookmark2", "v1"])
+        .success();
+    work_dir.run_jj(["tag", "delete", "v2"]).success();
+    let output = work_dir.run_jj(["git", "push", "--tag=v1", "--tag=v2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move tag v1 from 9b2e76de3920 to 38a204733702
+      Delete tag v2 from 38a204733702
+    [EOF]
+    ");
+    origin_dir.run_jj(["git", "import"]).success();
+    let output = origin_dir.run_jj(["tag", "list"]);
+    insta::assert_snapshot!(output, @r"
+    v1: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+
+    // Tags that exist neither locally nor on the remote
+    let output = work_dir.run_jj(["git", "push", "--tag=v2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such tag: v2
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--tag=glob:x*", "--tag=y"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No matching tags for patterns: x*, y
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_git_push_tags_with_bookmarks() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let origin_dir = test_env.work_dir("origin");
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark1", "-m=release"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "move", "bookmark1", "--to=@"])
+        .success();
+    work_dir
+        .run_jj(["tag", "create", "-m=Release 1.0", "v1.0"])
+        .success();
+
+    let output = work_dir.run_jj(["git", "push", "-bbookmark1", "--tag=v1.0"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 994c652e707a
+      Add tag v1.0 to 994c652e707a
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @  push bookmark bookmark1 and tag v1.0 to git remote origin
+    [EOF]
+    ");
+
+    // The annotated tag object is pushed
+    let origin_git_repo = git::open(git_repo_dir_for_jj_repo(&origin_dir));
+    let tag_ref = origin_git_repo.find_reference("refs/tags/v1.0").unwrap();
+    let tag_object = origin_git_repo.find_object(tag_ref.target().id()).unwrap();
+    assert_eq!(tag_object.kind, gix::object::Kind::Tag);
+    assert_eq!(
+        tag_object.into_tag().decode().unwrap().message,
+        "Release 1.0\n"
+    );
+
+    let output = work_dir.run_jj(["git", "push", "--tag=v1.0"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Tag v1.0@origin already matches v1.0
+    Nothing changed.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_push_to_remote_ref() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let origin_dir = test_env.work_dir("origin");
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark1", "-m=for review"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-feature"])
+        .success();
+
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "-bmy-feature",
+        "--to=refs/for/bookmark1",
+        "--dry-run",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push bookmark my-feature at ef9667aa0368 to refs/for/bookmark1
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "push", "-bmy-feature", "--to=refs/for/bookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push bookmark my-feature at ef9667aa0368 to refs/for/bookmark1
+    [EOF]
+    ");
+    let origin_git_repo = git::open(git_repo_dir_for_jj_repo(&origin_dir));
+    let pushed_ref = origin_git_repo
+        .find_reference("refs/for/bookmark1")
+        .unwrap();
+    let output = work_dir.run_jj(["log", "-r@", "-Tcommit_id", "--no-graph"]);
+    assert_eq!(pushed_ref.target().id().to_string(), output.stdout.raw());
+
+    // The bookmark isn't tracked on the remote
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    my-feature: vruxwmqv ef9667aa (empty) for review
+    [EOF]
+    ");
+
+    // Branch names are qualified, and only fast-forwards are allowed
+    let output = work_dir.run_jj(["git", "push", "-bmy-feature", "--to=bookmark2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push bookmark my-feature at ef9667aa0368 to refs/heads/bookmark2
+    Error: Failed to push some bookmarks
+    Hint: The following references unexpectedly moved on the remote:
+      refs/heads/bookmark2 (reason: non-fast-forward)
+    Hint: Try fetching from the remote, then make the bookmark point to where you want it to be, and push again.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "push", "-bmy-feature", "--to=bookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push bookmark my-feature at ef9667aa0368 to refs/heads/bookmark1
+    [EOF]
+    ");
+
+    // A single bookmark must be specified
+    let output = work_dir.run_jj(["git", "push", "-bglob:*", "--to=refs/for/bookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Can't push multiple bookmarks to a single remote ref
+    Hint: Specify a single bookmark with --bookmark.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--to=refs/for/bookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --bookmark <BOOKMARK>
+
+    Usage: jj git push --bookmark <BOOKMARK> --to <REF>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_git_push_options() {
+    use std::io::Write as _;
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    let origin_git_repo_path = git_repo_dir_for_jj_repo(&test_env.work_dir("origin"));
+
+    // Make the remote accept push options and record them
+    let mut config_file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(origin_git_repo_path.join("config"))
+        .unwrap();
+    writeln!(config_file, "[receive]\n\tadvertisePushOptions = true").unwrap();
+    let hook_path = origin_git_repo_path.join("hooks").join("pre-receive");
+    std::fs::write(
+        &hook_path,
+        indoc::indoc! {r#"
+            #!/bin/sh
+            i=0
+            while [ "$i" -lt "${GIT_PUSH_OPTION_COUNT:-0}" ]; do
+                eval "echo \"push option: \$GIT_PUSH_OPTION_$i\"" >&2
+                i=$((i + 1))
+            done
+        "#},
+    )
+    .unwrap();
+    std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o700)).unwrap();
+
+    work_dir.run_jj(["new", "bookmark1", "-m=update"]).success();
+    work_dir
+        .run_jj(["bookmark", "move", "bookmark1", "--to=@"])
+        .success();
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "-bbookmark1",
+        "--config=git.push-options=['ci.skip']",
+        "-otopic=foo",
+        "--push-option=merge_request.create",
+    ]);
+    let mut settings = insta::Settings::clone_current();
+    settings.add_filter(r"\s*\n", "\n");
+    settings.bind(|| {
+        insta::assert_snapshot!(output, @r"
+        ------- stderr -------
+        Changes to push to origin:
+          Move forward bookmark bookmark1 from 9b2e76de3920 to ac223b435b62
+        remote: push option: ci.skip
+        remote: push option: topic=foo
+        remote: push option: merge_request.create
+        [EOF]
+        ");
+    });
+}
+
 #[must_use]
 fn get_bookmark_output(work_dir: &TestWorkDir) -> CommandOutput {
     // --quiet to suppress deleted bookmarks hint
@@ -122,6 +122,53 @@ fn test_git_remote_add() {
     ");
 }
 
+#[test]
+fn test_git_remote_add_with_fetch_bookmarks() {
+    let test_env = TestEnvironment::default();
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let output = work_dir.run_jj([
+        "git",
+        "remote",
+        "add",
+        "foo",
+        "http://example.com/repo/foo",
+        "--fetch=main",
+        "--fetch=glob:team/*",
+    ]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj([
+        "git",
+        "remote",
+        "add",
+        "bar",
+        "http://example.com/repo/bar",
+        "--fetch=glob:team/*/*",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid bookmark pattern provided: 'team/*/*'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The bookmark patterns are preserved when renaming the remote
+    work_dir
+        .run_jj(["git", "remote", "rename", "foo", "baz"])
+        .success();
+    insta::assert_snapshot!(read_git_config(work_dir.root()), @r#"
+    [core]
+    	repositoryformatversion = 0
+    	bare = true
+    	logallrefupdates = false
+    [remote "baz"]
+    	url = http://example.com/repo/foo
+    	fetch = +refs/heads/main:refs/remotes/baz/main
+    	fetch = +refs/heads/team/*:refs/remotes/baz/team/*
+    "#);
+}
+
 #[test]
 fn test_git_remote_set_url() {
     let test_env = TestEnvironment::default();
@@ -0,0 +1,215 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+/// Creates a superproject Git repo at `super` whose `main` branch records the
+/// first commit of the submodule repo at `sub-origin`. Returns the submodule
+/// commits.
+fn set_up_superproject(test_env: &TestEnvironment) -> (gix::ObjectId, gix::ObjectId) {
+    let sub_repo = git::init(test_env.env_root().join("sub-origin"));
+    let sub_commit1 =
+        git::add_commit(&sub_repo, "refs/heads/main", "file", b"1\n", "sub1", &[]).commit_id;
+    let sub_commit2 = git::add_commit(
+        &sub_repo,
+        "refs/heads/main",
+        "added",
+        b"added\n",
+        "sub2",
+        &[sub_commit1],
+    )
+    .commit_id;
+    git::set_symbolic_reference(&sub_repo, "HEAD", "refs/heads/main");
+
+    let super_repo = git::init(test_env.env_root().join("super"));
+    // The relative URL is resolved against the remote of the superproject
+    let gitmodules = "[submodule \"sub\"]\n\tpath = sub\n\turl = ../sub-origin\n";
+    let gitmodules_id = super_repo
+        .write_blob(gitmodules.as_bytes())
+        .unwrap()
+        .detach();
+    let mut tree_editor = super_repo
+        .edit_tree(gix::ObjectId::empty_tree(super_repo.object_hash()))
+        .unwrap();
+    tree_editor
+        .upsert(
+            ".gitmodules",
+            gix::object::tree::EntryKind::Blob,
+            gitmodules_id,
+        )
+        .unwrap();
+    tree_editor
+        .upsert("sub", gix::object::tree::EntryKind::Commit, sub_commit1)
+        .unwrap();
+    let tree_id = tree_editor.write().unwrap().detach();
+    git::write_commit(&super_repo, "refs/heads/main", tree_id, "super", &[]);
+    git::set_symbolic_reference(&super_repo, "HEAD", "refs/heads/main");
+    (sub_commit1, sub_commit2)
+}
+
+#[test]
+fn test_git_submodule_update() {
+    let test_env = TestEnvironment::default();
+    let (sub_commit1, sub_commit2) = set_up_superproject(&test_env);
+    test_env
+        .run_jj_in(".", ["git", "clone", "super", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let submodule_dir = work_dir.root().join("sub");
+
+    // The submodule isn't checked out until it's initialized
+    assert!(!submodule_dir.join("file").exists());
+    let output = work_dir.run_jj(["git", "submodule", "update"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Cloning submodule sub into sub
+    Checked out submodule sub at b7340920248e
+    [EOF]
+    ");
+    assert_eq!(
+        std::fs::read_to_string(submodule_dir.join("file")).unwrap(),
+        "1\n"
+    );
+    assert!(!submodule_dir.join("added").exists());
+
+    // The submodule repository is stored in the repo
+    assert!(work_dir
+        .root()
+        .join(".jj/repo/submodule_store/git/sub")
+        .is_dir());
+
+    // Nothing to do if the submodule is up to date
+    let output = work_dir.run_jj(["git", "submodule", "update"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : sqpuoqvx e5084fd0 (empty) (no description set)
+    Parent commit (@-): xtqtotlr 3aec5624 main | super
+    [EOF]
+    ");
+
+    // Moving the submodule HEAD is recorded in the working-copy commit. Set
+    // the mtime explicitly in case the file system timestamp is coarse.
+    let sub_repo = git::open(&submodule_dir);
+    git::set_head_to_id(&sub_repo, sub_commit2);
+    std::fs::File::options()
+        .write(true)
+        .open(sub_repo.path().join("HEAD"))
+        .unwrap()
+        .set_modified(UNIX_EPOCH + Duration::from_secs(1))
+        .unwrap();
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M sub
+    Working copy  (@) : sqpuoqvx d961216e (no description set)
+    Parent commit (@-): xtqtotlr 3aec5624 main | super
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/sub b/sub
+    index b734092024..26fba1d1b9 160000
+    --- a/sub
+    +++ b/sub
+    @@ -1,1 +1,1 @@
+    -Subproject commit b7340920248ef18877fe9f2bdc20086733bb6f7e
+    +Subproject commit 26fba1d1b92e1e8de49dd493eb0aaefd72182c3d
+    [EOF]
+    ");
+
+    // Checking out the parent commit moves the submodule HEAD back
+    work_dir.run_jj(["new", "main"]).success();
+    let sub_repo = git::open(&submodule_dir);
+    assert_eq!(sub_repo.head_id().unwrap().detach(), sub_commit1);
+    assert!(!submodule_dir.join("added").exists());
+}
+
+#[test]
+fn test_git_submodule_update_keeps_local_changes() {
+    let test_env = TestEnvironment::default();
+    let (sub_commit1, sub_commit2) = set_up_superproject(&test_env);
+    test_env
+        .run_jj_in(".", ["git", "clone", "super", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let submodule_dir = work_dir.root().join("sub");
+    work_dir.run_jj(["git", "submodule", "update"]).success();
+
+    // Record the second submodule commit in a working-copy commit
+    let sub_repo = git::open(&submodule_dir);
+    git::set_head_to_id(&sub_repo, sub_commit2);
+    std::fs::File::options()
+        .write(true)
+        .open(sub_repo.path().join("HEAD"))
+        .unwrap()
+        .set_modified(UNIX_EPOCH + Duration::from_secs(1))
+        .unwrap();
+    work_dir.run_jj(["describe", "-m=sub2"]).success();
+    work_dir.run_jj(["new", "main"]).success();
+    let sub_repo = git::open(&submodule_dir);
+    assert_eq!(sub_repo.head_id().unwrap().detach(), sub_commit1);
+
+    // An untracked file which would be overwritten isn't discarded
+    std::fs::write(submodule_dir.join("added"), "local\n").unwrap();
+    work_dir.run_jj(["new", "description(sub2)"]).success();
+    let sub_repo = git::open(&submodule_dir);
+    assert_eq!(sub_repo.head_id().unwrap().detach(), sub_commit1);
+    assert_eq!(
+        std::fs::read_to_string(submodule_dir.join("added")).unwrap(),
+        "local\n"
+    );
+    let output = work_dir.run_jj(["git", "submodule", "update"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Submodule sub has uncommitted changes; not checking out 26fba1d1b92e
+    [EOF]
+    ");
+
+    // Local changes to files which don't differ between the commits are kept
+    std::fs::remove_file(submodule_dir.join("added")).unwrap();
+    std::fs::write(submodule_dir.join("file"), "local\n").unwrap();
+    let output = work_dir.run_jj(["git", "submodule", "update"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Checked out submodule sub at 26fba1d1b92e
+    [EOF]
+    ");
+    let sub_repo = git::open(&submodule_dir);
+    assert_eq!(sub_repo.head_id().unwrap().detach(), sub_commit2);
+    assert_eq!(
+        std::fs::read_to_string(submodule_dir.join("file")).unwrap(),
+        "local\n"
+    );
+}
+
+#[test]
+fn test_git_submodule_update_no_submodules() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["git", "submodule", "update"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No submodules to update.
+    [EOF]
+    ");
+}
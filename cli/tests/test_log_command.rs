@@ -927,6 +927,189 @@ fn test_log_filtered_by_path() {
     ");
 }
 
+#[test]
+fn test_log_filtered_by_file_predicate() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("large", "x".repeat(2000));
+    work_dir.run_jj(["commit", "-m=add large"]).success();
+    work_dir.write_file("small", "small\n");
+    work_dir.run_jj(["commit", "-m=add small"]).success();
+    work_dir.write_file("large", "x\n");
+    work_dir.run_jj(["describe", "-m=shrink large"]).success();
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-r", "files(larger_than(1KB))"]);
+    insta::assert_snapshot!(output, @r"
+    @  shrink large
+    ~  (elided revisions)
+    ○  add large
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "larger_than(1KB)"]);
+    insta::assert_snapshot!(output, @r"
+    @  shrink large
+    │  M large
+    ~  (elided revisions)
+    ○  add large
+    │  A large
+    ~
+    [EOF]
+    ");
+
+    // Templates have no trees to evaluate file predicates against
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "-T",
+        "self.diff('larger_than(1KB)').summary()",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: In fileset expression
+    Caused by:
+    1:  --> 1:11
+      |
+    1 | self.diff('larger_than(1KB)').summary()
+      |           ^----------------^
+      |
+      = In fileset expression
+    2: Function `larger_than` can't be used here because it needs file contents
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_log_line_range() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\nb\nc\nd\n");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir.write_file("file1", "a\nB\nc\nd\n");
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    work_dir.write_file("file1", "a\nB\nc\nD\n");
+    work_dir.write_file("file2", "foo\n");
+    work_dir.run_jj(["new", "-m", "fourth"]).success();
+    work_dir.write_file("file1", "x\na\nB\nnew\nc\nD\n");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-L", "3,5:file1"]);
+    insta::assert_snapshot!(output, @r"
+    @  fourth
+    │  diff --git a/file1 b/file1
+    │  --- a/file1
+    │  +++ b/file1
+    │  @@ -2,2 +3,3 @@
+    │   B
+    │  +new
+    │   c
+    ~  (elided revisions)
+    ○  second
+    │  diff --git a/file1 b/file1
+    │  --- a/file1
+    │  +++ b/file1
+    │  @@ -2,2 +2,2 @@
+    │  -b
+    │  +B
+    │   c
+    ○  first
+    │  diff --git a/file1 b/file1
+    ~  --- /dev/null
+       +++ b/file1
+       @@ -0,0 +2,2 @@
+       +b
+       +c
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--no-graph",
+        "-L=6,+1:file1",
+        "-L=1,1:file2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    third
+    diff --git a/file1 b/file1
+    --- a/file1
+    +++ b/file1
+    @@ -4,1 +4,1 @@
+    -d
+    +D
+    diff --git a/file2 b/file2
+    --- /dev/null
+    +++ b/file2
+    @@ -0,0 +1,1 @@
+    +foo
+    first
+    diff --git a/file1 b/file1
+    --- /dev/null
+    +++ b/file1
+    @@ -0,0 +4,1 @@
+    +d
+    [EOF]
+    ");
+
+    // Lines added to an existing file are shown against the old path
+    let output = work_dir.run_jj(["log", "-T", "description", "--no-graph", "-L4,4:file1"]);
+    insta::assert_snapshot!(output, @r"
+    fourth
+    diff --git a/file1 b/file1
+    --- a/file1
+    +++ b/file1
+    @@ -0,0 +4,1 @@
+    +new
+    [EOF]
+    ");
+
+    // Revisions are filtered by --revisions
+    let output = work_dir.run_jj(["log", "-T", "description", "-L3,5:file1", "-r=::@-"]);
+    insta::assert_snapshot!(output, @r"
+    ○  second
+    │  diff --git a/file1 b/file1
+    │  --- a/file1
+    │  +++ b/file1
+    │  @@ -2,2 +2,2 @@
+    │  -b
+    │  +B
+    │   c
+    ○  first
+    │  diff --git a/file1 b/file1
+    ~  --- /dev/null
+       +++ b/file1
+       @@ -0,0 +2,2 @@
+       +b
+       +c
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-L", "5,3:file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value '5,3:file1' for '--line-range <START,END:PATH>': Invalid line range: 5,3
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["log", "-L", "1,2:nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such path: nonexistent
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_log_limit() {
     let test_env = TestEnvironment::default();
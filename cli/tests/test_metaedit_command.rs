@@ -0,0 +1,224 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+#[must_use]
+fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
+    let template = r#"
+    separate(" ",
+      change_id.short(),
+      commit_id.short(),
+      author.name(),
+      author.email(),
+      author.timestamp(),
+      "/",
+      committer.name(),
+      committer.email(),
+      description.first_line(),
+    ) ++ "\n"
+    "#;
+    work_dir.run_jj(["log", "-r=all()", "-T", template])
+}
+
+#[test]
+fn test_metaedit() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.run_jj(["new", "-m", "c"]).success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr 7794eb03e998 Test User test.user@example.com 2001-02-03 04:05:10.000 +07:00 / Test User test.user@example.com c
+    ○  kkmpptxzrspx 74bc80726640 Test User test.user@example.com 2001-02-03 04:05:09.000 +07:00 / Test User test.user@example.com b
+    ○  qpvuntsmwlqt b86e28cd6862 Test User test.user@example.com 2001-02-03 04:05:08.000 +07:00 / Test User test.user@example.com a
+    ◆  zzzzzzzzzzzz 000000000000 1970-01-01 00:00:00.000 +00:00 /
+    [EOF]
+    ");
+
+    // Nothing to change
+    let output = work_dir.run_jj(["metaedit", "-r=description(b)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Set the author and the committer; descendants are rebased
+    let output = work_dir.run_jj([
+        "metaedit",
+        "-r=description(b)",
+        "--author=Foo Bar <foo@bar.com>",
+        "--author-timestamp=2000-01-23T01:23:45+09:00",
+        "--committer=Baz Qux <baz@qux.com>",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Modified 1 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: zsuskuln 070bd952 (empty) c
+    Parent commit (@-)      : kkmpptxz 3608f8b3 (empty) b
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr 070bd952ea48 Test User test.user@example.com 2001-02-03 04:05:10.000 +07:00 / Test User test.user@example.com c
+    ○  kkmpptxzrspx 3608f8b33114 Foo Bar foo@bar.com 2000-01-23 01:23:45.000 +09:00 / Baz Qux baz@qux.com b
+    ○  qpvuntsmwlqt b86e28cd6862 Test User test.user@example.com 2001-02-03 04:05:08.000 +07:00 / Test User test.user@example.com a
+    ◆  zzzzzzzzzzzz 000000000000 1970-01-01 00:00:00.000 +00:00 /
+    [EOF]
+    ");
+
+    // Reset the author to the configured user
+    let output = work_dir.run_jj([
+        "metaedit",
+        "-r=description(b)",
+        "--update-author",
+        "--update-author-timestamp",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Modified 1 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: zsuskuln fa7c29fa (empty) c
+    Parent commit (@-)      : kkmpptxz 7326b747 (empty) b
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  zsuskulnrvyr fa7c29fa85fb Test User test.user@example.com 2001-02-03 04:05:10.000 +07:00 / Test User test.user@example.com c
+    ○  kkmpptxzrspx 7326b747ccaa Test User test.user@example.com 2001-02-03 04:05:15.000 +07:00 / Test User test.user@example.com b
+    ○  qpvuntsmwlqt b86e28cd6862 Test User test.user@example.com 2001-02-03 04:05:08.000 +07:00 / Test User test.user@example.com a
+    ◆  zzzzzzzzzzzz 000000000000 1970-01-01 00:00:00.000 +00:00 /
+    [EOF]
+    ");
+
+    // Generate a new change ID for multiple revisions
+    let output = work_dir.run_jj(["metaedit", "--update-change-id", "description(a)|@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Modified 2 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: kpqxywon 73db0ea6 (empty) c
+    Parent commit (@-)      : kkmpptxz 915fa91d (empty) b
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  kpqxywonksrl 73db0ea67f70 Test User test.user@example.com 2001-02-03 04:05:10.000 +07:00 / Test User test.user@example.com c
+    ○  kkmpptxzrspx 915fa91d415d Test User test.user@example.com 2001-02-03 04:05:15.000 +07:00 / Test User test.user@example.com b
+    ○  vzqnnsmrxxkw 2f57c82cf093 Test User test.user@example.com 2001-02-03 04:05:08.000 +07:00 / Test User test.user@example.com a
+    ◆  zzzzzzzzzzzz 000000000000 1970-01-01 00:00:00.000 +00:00 /
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["metaedit", "root()", "--update-author"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The root commit 000000000000 is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_metaedit_trailers() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "subject\n\nbody\n\nReviewed-by: Foo <foo@bar.com>",
+        ])
+        .success();
+
+    let output = work_dir.run_jj([
+        "metaedit",
+        "--add-trailer=Co-authored-by: Baz <baz@qux.com>",
+        "--add-trailer=Reviewed-by: Foo <foo@bar.com>",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Modified 1 commits
+    Working copy  (@) now at: qpvuntsm 299ea4d1 (empty) subject
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @r"
+    subject
+
+    body
+
+    Reviewed-by: Foo <foo@bar.com>
+    Co-authored-by: Baz <baz@qux.com>
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "metaedit",
+        "--remove-trailer=reviewed-by",
+        "--remove-trailer=Co-authored-by",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Modified 1 commits
+    Working copy  (@) now at: qpvuntsm deb72aac (empty) subject
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @r"
+    subject
+
+    body
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["metaedit", "--add-trailer=not a trailer"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'not a trailer' for '--add-trailer <TRAILER>': Expected a trailer of the form `Key: Value`
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_metaedit_remove_trailer_after_whitespace_line() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // A whitespace-only line separates the trailer paragraph
+    work_dir
+        .run_jj(["describe", "-m", "subject\n\t\nFoo: bar\nBaz: qux"])
+        .success();
+    work_dir
+        .run_jj(["metaedit", "--remove-trailer=foo"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @r"
+    subject
+    	
+    Baz: qux
+    [EOF]
+    ");
+}
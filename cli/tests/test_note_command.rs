@@ -0,0 +1,220 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_note_add_show_remove() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-mcommit1"]).success();
+    work_dir.run_jj(["new", "-mcommit2"]).success();
+
+    let output = work_dir.run_jj(["note", "show"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit ae3ed07032e1 has no note
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["note", "add", "-r@-", "-mfirst", "-msecond"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added note to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["note", "show", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    first
+
+    second
+    [EOF]
+    ");
+
+    // The note can only be replaced with --force
+    let output = work_dir.run_jj(["note", "add", "-r@-", "-mreplaced"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit b876c5f49546 already has a note
+    Hint: Use `--force` to replace it.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["note", "add", "-r@-", "-mreplaced", "--force"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added note to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+
+    // Notes of another notes ref are independent
+    work_dir
+        .run_jj(["note", "add", "-mreview", "--ref=refs/notes/review"])
+        .success();
+
+    let template = r#"description.first_line() ++ ": " ++ self.notes() ++ "/" ++ self.notes("review") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  commit2: /review
+    │
+    ○  commit1: replaced
+    │  /
+    ◆  : /
+    [EOF]
+    ");
+
+    // Notes refs aren't included in git_refs()
+    let output = work_dir.run_jj(["log", "-rgit_refs()"]);
+    insta::assert_snapshot!(output, @"");
+
+    let output = work_dir.run_jj(["note", "remove", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Removed note from qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["note", "remove", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit b876c5f49546 has no note
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  commit2: /review
+    │
+    ○  commit1: /
+    ◆  : /
+    [EOF]
+    ");
+
+    // Notes changes are recorded in the operation log, so they can be undone
+    let output = work_dir.run_jj(["op", "log", "-n3", "-Tdescription ++ '\n'"]);
+    insta::assert_snapshot!(output, @r"
+    @  remove note from commit b876c5f49546badadb52858bbe7aace109ac8a7b
+    ○  add note to commit ae3ed07032e12bcd765dbeea26745fca58f4cb94
+    ○  add note to commit b876c5f49546badadb52858bbe7aace109ac8a7b
+    [EOF]
+    ");
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["note", "show", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    replaced
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_note_fetch_push() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "origin"]).success();
+    let origin_dir = test_env.work_dir("origin");
+    origin_dir.run_jj(["describe", "-mcommit1"]).success();
+    origin_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    origin_dir
+        .run_jj(["note", "add", "-mfrom origin"])
+        .success();
+    origin_dir.run_jj(["new"]).success();
+    origin_dir.run_jj(["git", "export"]).success();
+
+    test_env
+        .run_jj_in(
+            ".",
+            [
+                "git",
+                "clone",
+                "--config=git.auto-local-bookmark=true",
+                "origin/.jj/repo/store/git",
+                "local",
+            ],
+        )
+        .success();
+    let work_dir = test_env.work_dir("local");
+
+    // Notes aren't fetched by default
+    let output = work_dir.run_jj(["note", "show", "-rmain"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit b876c5f49546 has no note
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "fetch", "--notes"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["note", "show", "-rmain"]);
+    insta::assert_snapshot!(output, @r"
+    from origin
+    [EOF]
+    ");
+
+    // Nothing to push without --notes
+    work_dir
+        .run_jj(["note", "add", "-rmain", "-mfrom local", "--force"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "-bmain"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Bookmark main@origin already matches main
+    Nothing changed.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--notes", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push notes ref refs/notes/commits at 9092a4b2d044
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--notes"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push notes ref refs/notes/commits at 9092a4b2d044
+    [EOF]
+    ");
+
+    origin_dir.run_jj(["git", "import"]).success();
+    let output = origin_dir.run_jj(["note", "show", "-rmain"]);
+    insta::assert_snapshot!(output, @r"
+    from local
+    [EOF]
+    ");
+
+    // The remote notes ref is only updated if that's a fast-forward
+    origin_dir
+        .run_jj(["note", "add", "-rmain", "-mdiverged", "--force"])
+        .success();
+    origin_dir.run_jj(["git", "export"]).success();
+    work_dir
+        .run_jj(["note", "add", "-rmain", "-mfrom local again", "--force"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--notes"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Push notes ref refs/notes/commits at d2916884873c
+    Error: Failed to push some notes refs
+    Hint: The following references unexpectedly moved on the remote:
+      refs/notes/commits (reason: fetch first)
+    Hint: Try fetching from the remote, then make the notes ref point to where you want it to be, and push again.
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -623,7 +623,8 @@ fn test_baseless_conflict_input_files() {
 
 #[test]
 fn test_too_many_parents() {
-    let test_env = TestEnvironment::default();
+    let mut test_env = TestEnvironment::default();
+    let _editor_script = test_env.set_up_fake_editor();
     test_env.run_jj_in(".", ["git", "init", "repo"]).success();
     let work_dir = test_env.work_dir("repo");
 
@@ -642,16 +643,24 @@ fn test_too_many_parents() {
     [EOF]
     ");
 
+    // External tools only support 2-sided conflicts
     let output = work_dir.run_jj(["resolve"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
-    Hint: Using default editor ':builtin'; run `jj config set --user ui.merge-editor :builtin` to disable this message.
     Error: Failed to resolve conflicts
     Caused by: The conflict at "file" has 3 sides. At most 2 sides are supported.
-    Hint: Edit the conflict markers manually to resolve this.
+    Hint: Edit the conflict markers manually or use the builtin merge editor (`--tool :builtin`) to resolve this.
     [EOF]
     [exit status: 1]
     "#);
+
+    // The builtin tool supports conflicts with any number of sides. There's no
+    // terminal to open it in here, but it should get past the check above.
+    let output = work_dir.run_jj(["resolve", "--tool=:builtin"]);
+    assert!(!output
+        .stderr
+        .raw()
+        .contains("At most 2 sides are supported"));
 }
 
 #[test]
@@ -2091,3 +2100,273 @@ fn test_resolve_with_contents_of_side() {
     [exit status: 2]
     "#);
 }
+
+#[test]
+fn test_resolve_with_merge_attribute() {
+    let mut test_env = TestEnvironment::default();
+    let editor_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let gitattributes = "*.dat merge=fake-editor\n*.txt merge=text\n";
+    create_commit_with_files(
+        &work_dir,
+        "base",
+        &[],
+        &[
+            (".gitattributes", gitattributes),
+            ("file.txt", "base\n"),
+            ("file.dat", "base\n"),
+        ],
+    );
+    create_commit_with_files(
+        &work_dir,
+        "a",
+        &["base"],
+        &[("file.txt", "a\n"), ("file.dat", "a\n")],
+    );
+    create_commit_with_files(
+        &work_dir,
+        "b",
+        &["base"],
+        &[("file.txt", "b\n"), ("file.dat", "b\n")],
+    );
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    // The files with the configured tool in the `merge` attribute are resolved
+    // by that tool, and the others by the default tool.
+    std::fs::write(&editor_script, "write\nresolution\n").unwrap();
+    let output = work_dir.run_jj(["resolve", "--config=ui.merge-editor=':ours'"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Resolving conflicts in: file.dat
+    Working copy  (@) now at: vruxwmqv e3eb94c9 conflict | conflict
+    Parent commit (@-)      : zsuskuln 44700d49 a | a
+    Parent commit (@-)      : royxmykx 59fb46c3 b | b
+    Added 0 files, modified 2 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file.txt"), @"a");
+    insta::assert_snapshot!(work_dir.read_file("file.dat"), @"resolution");
+
+    // The --tool argument takes precedence over the attribute
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["resolve", "--tool=:theirs"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv ed338340 conflict | conflict
+    Parent commit (@-)      : zsuskuln 44700d49 a | a
+    Parent commit (@-)      : royxmykx 59fb46c3 b | b
+    Added 0 files, modified 2 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file.txt"), @"b");
+    insta::assert_snapshot!(work_dir.read_file("file.dat"), @"b");
+}
+
+#[test]
+fn test_resolve_with_word_granularity() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "a b c\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a b2 c\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "a b c2\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+    work_dir.run_jj(["new", "base"]).success();
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list", "-r=conflict"]), @r"
+    file    2-sided conflict
+    [EOF]
+    ");
+
+    // The conflict recorded with line granularity is resolved without running
+    // the merge tool
+    let output = work_dir.run_jj(["resolve", "-r=conflict", "--config=merge.granularity=word"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Resolved conflicts in 1 files with the configured merge settings
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=conflict", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a b2 c2
+    [EOF]
+    ");
+
+    // New merges are resolved word by word
+    let output = work_dir.run_jj(["new", "a", "b", "--config=merge.granularity=word"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: lylxulpl 77478ad2 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 0ddc394b a | a
+    Parent commit (@-)      : royxmykx c3859b9d b | b
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @"a b2 c2");
+}
+
+#[test]
+fn test_resolve_with_recorded_resolution() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("merge.rerere = true");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list-recorded"]), @"");
+
+    // Resolving the conflict records the resolution
+    work_dir.write_file("file", "resolved\n");
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list-recorded"]), @r"
+    file   resolved in commit 03b7f88ed0fd
+    [EOF]
+    ");
+
+    // The recorded resolution is reused for the same conflict
+    let output = work_dir.run_jj(["new", "a", "b"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: kmkuslsw adeeffc4 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 45537d53 a | a
+    Parent commit (@-)      : royxmykx 89d1b299 b | b
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @"resolved");
+
+    // Recorded resolutions can be forgotten
+    let output = work_dir.run_jj(["resolve", "--forget-recorded", "other"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Forgot 0 recorded conflict resolutions
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["resolve", "--forget-recorded"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Forgot 1 recorded conflict resolutions
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list-recorded"]), @"");
+    work_dir.run_jj(["new", "a", "b"]).success();
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list"]), @r"
+    file    2-sided conflict
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_summary() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(
+        &work_dir,
+        "base",
+        &[],
+        &[("content", "base\n"), ("deleted", "base\n")],
+    );
+    create_commit_with_files(
+        &work_dir,
+        "a",
+        &["base"],
+        &[("content", "a\n"), ("deleted", "a\n")],
+    );
+    create_commit_with_files(&work_dir, "b", &["base"], &[("content", "b\n")]);
+    work_dir.remove_file("deleted");
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    let output = work_dir.run_jj(["resolve", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    content: 2-sided content conflict
+      - file from rlvkpnrz 577420fa base | base
+      + file from zsuskuln ab37a5ea a | a
+      + file from royxmykx cb3396ff b | b
+    deleted: 2-sided deletion conflict
+      - file from rlvkpnrz 577420fa base | base
+      + file from zsuskuln ab37a5ea a | a
+      + absent from royxmykx cb3396ff b | b
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "resolve",
+        "--summary",
+        "-T",
+        r#"path ++ " " ++ kind ++ " " ++ sides.map(|s| s.commit().description().first_line()) ++ "\n""#,
+        "content",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    content content a b
+    [EOF]
+    ");
+
+    // The conflicts are available in commit templates
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        r#"description.first_line() ++ ": " ++ self.conflicts().map(|c| c.path() ++ " " ++ c.num_sides()) ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @    conflict: content 2 deleted 2
+    ├─╮
+    │ ○  b:
+    ○ │  a:
+    ├─╯
+    ○  base:
+    ◆  :
+    [EOF]
+    ");
+
+    // A side which doesn't exist in the ancestors was introduced by the
+    // conflicted commit itself
+    work_dir.run_jj(["rebase", "-r=b", "-d=a"]).success();
+    let output = work_dir.run_jj(["resolve", "--summary", "-r=b", "content"]);
+    insta::assert_snapshot!(output, @r"
+    content: 2-sided content conflict
+      - file from rlvkpnrz 577420fa base | base
+      + file from zsuskuln ab37a5ea a | a
+      + file from royxmykx 823ec21f b | (conflict) b
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_summary_add_add() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // The file is absent in the base, but isn't deleted on either side
+    create_commit_with_files(&work_dir, "base", &[], &[]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    let output = work_dir.run_jj(["resolve", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    file: 2-sided content conflict
+      - absent
+      + file from zsuskuln e3c7222d a | a
+      + file from royxmykx 1f2c13ec b | b
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r=@",
+        "-T",
+        r#"self.conflicts().map(|c| c.path() ++ " " ++ c.kind()) ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  file content
+    │
+    ~
+    [EOF]
+    ");
+}
@@ -0,0 +1,203 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(unix)]
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+#[must_use]
+fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
+    work_dir.run_jj(["log", "-T", "description", "--summary"])
+}
+
+#[test]
+fn test_run_modifies_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.write_file("file", "a\nb\n");
+    work_dir.run_jj(["new", "-m", "c"]).success();
+    work_dir.write_file("other", "c\n");
+
+    let output = work_dir.run_jj([
+        "run",
+        "-j1",
+        "-r=description(b)::",
+        "sed 's/b/B/' file > file.tmp && mv file.tmp file",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Ran command on kkmpptxz 382c8b43 b:
+    Ran command on zsuskuln f5626455 c:
+    Modified 2 commits.
+    Working copy  (@) now at: zsuskuln 6fa5351d c
+    Parent commit (@-)      : kkmpptxz 8eb1ebc1 b
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  c
+    │  A other
+    ○  b
+    │  M file
+    ○  a
+    │  A file
+    ◆
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=description(b)", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    B
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    a
+    B
+    ");
+}
+
+#[test]
+fn test_run_rebases_descendants() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.write_file("other", "b\n");
+
+    // Only the parent is modified, the change is rebased into the child.
+    let output = work_dir.run_jj(["run", "-r=description(a)", "echo generated > generated"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Ran command on qpvuntsm 7febbd38 a:
+    Modified 1 commits.
+    Rebased 1 descendant commits.
+    Working copy  (@) now at: kkmpptxz adfc0fbb b
+    Parent commit (@-)      : qpvuntsm 1d318e86 a
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  b
+    │  A other
+    ○  a
+    │  A file
+    │  A generated
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_run_output_and_environment() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    let output = work_dir.run_jj(["run", "echo $JJ_CHANGE_ID; echo to-stderr >&2"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu
+    [EOF]
+    ------- stderr -------
+    Ran command on qpvuntsm b86e28cd (empty) a:
+    to-stderr
+    No revisions were modified.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_run_failure_discards_changes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    let op_id_before = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["run", "echo b > file; exit 1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Ran command on qpvuntsm 7febbd38 a:
+    Warning: Command failed with exit status: 1
+    No revisions were modified.
+    Error: Command failed on 1 of 1 revisions
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.current_operation_id(), op_id_before);
+    insta::assert_snapshot!(work_dir.read_file("file"), @"a");
+}
+
+#[test]
+fn test_run_reuses_working_copies() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file(".gitignore", "cache\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+
+    // Ignored files are kept between runs, but not after --clean
+    let command = "test -f cache && echo found; touch cache";
+    let output = work_dir.run_jj(["run", command]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Ran command on qpvuntsm a33e1bf4 a:
+    No revisions were modified.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["run", command]);
+    insta::assert_snapshot!(output, @r"
+    found
+    [EOF]
+    ------- stderr -------
+    Ran command on qpvuntsm a33e1bf4 a:
+    No revisions were modified.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["run", "--clean", command]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Ran command on qpvuntsm a33e1bf4 a:
+    No revisions were modified.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_run_immutable() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["run", "-r=root()", "true"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The root commit 000000000000 is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -181,6 +181,39 @@ fn test_split_by_paths() {
     ");
 }
 
+#[test]
+fn test_split_by_file_predicate() {
+    let mut test_env = TestEnvironment::default();
+    let _edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("small", "small\n");
+    work_dir.write_file("large", "x".repeat(2000));
+    work_dir.run_jj(["new"]).success();
+
+    let output = work_dir.run_jj(["split", "-r@-", "larger_than(1KB)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Selected changes : qpvuntsm 11685b6c (no description set)
+    Remaining changes: kkmpptxz eb095a65 (no description set)
+    Working copy  (@) now at: rlvkpnrz bf7180c4 (empty) (no description set)
+    Parent commit (@-)      : kkmpptxz eb095a65 (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○
+    │  A small
+    ○
+    │  A large
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_split_with_non_empty_description() {
     let mut test_env = TestEnvironment::default();
@@ -393,16 +426,16 @@ fn test_split_with_descendants() {
     insta::assert_snapshot!(evolog_1, @r"
     ○  qpvuntsm test.user@example.com 2001-02-03 08:05:12 74306e35
     │  Add file1
-    │  -- operation 994b490f285d (2001-02-03 08:05:12) split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
+    │  -- operation 1e9d8f148fc0 (2001-02-03 08:05:12) split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 1d2499e7
     │  Add file1 & file2
-    │  -- operation adf4f33386c9 (2001-02-03 08:05:08) commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 57291803fa5f (2001-02-03 08:05:08) commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 78ead2155fcc (2001-02-03 08:05:08) snapshot working copy
+    │  -- operation 49a0649b33fa (2001-02-03 08:05:08) snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+       -- operation f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
 
@@ -412,16 +445,16 @@ fn test_split_with_descendants() {
     insta::assert_snapshot!(evolog_2, @r"
     ○  royxmykx test.user@example.com 2001-02-03 08:05:12 0a37745e
     │  Add file2
-    │  -- operation 994b490f285d (2001-02-03 08:05:12) split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
+    │  -- operation 1e9d8f148fc0 (2001-02-03 08:05:12) split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 1d2499e7
     │  Add file1 & file2
-    │  -- operation adf4f33386c9 (2001-02-03 08:05:08) commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 57291803fa5f (2001-02-03 08:05:08) commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 78ead2155fcc (2001-02-03 08:05:08) snapshot working copy
+    │  -- operation 49a0649b33fa (2001-02-03 08:05:08) snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+       -- operation f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
 }
@@ -555,13 +588,13 @@ fn test_split_parallel_no_descendants() {
     insta::assert_snapshot!(evolog_1, @r#"
     ○  qpvuntsm test.user@example.com 2001-02-03 08:05:09 7bcd474c
     │  TESTED=TODO
-    │  -- operation 2b21c33e1596 (2001-02-03 08:05:09) split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation e384849b2792 (2001-02-03 08:05:09) split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 1663cd1cc445 (2001-02-03 08:05:08) snapshot working copy
+    │  -- operation 1ff06018488a (2001-02-03 08:05:08) snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+       -- operation f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ------- stderr -------
     Warning: Deprecated user-level config: ui.default-description is updated to template-aliases.default_commit_description = '"\n\nTESTED=TODO\n"'
@@ -574,13 +607,13 @@ fn test_split_parallel_no_descendants() {
     insta::assert_snapshot!(evolog_2, @r#"
     @  kkmpptxz test.user@example.com 2001-02-03 08:05:09 431886f6
     │  (no description set)
-    │  -- operation 2b21c33e1596 (2001-02-03 08:05:09) split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation e384849b2792 (2001-02-03 08:05:09) split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 1663cd1cc445 (2001-02-03 08:05:08) snapshot working copy
+    │  -- operation 1ff06018488a (2001-02-03 08:05:08) snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+       -- operation f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ------- stderr -------
     Warning: Deprecated user-level config: ui.default-description is updated to template-aliases.default_commit_description = '"\n\nTESTED=TODO\n"'
@@ -930,7 +963,7 @@ fn test_split_with_multiple_workspaces_same_working_copy() {
     secondary_dir
         .run_jj(["edit", "-r", "description(first-commit)"])
         .success();
-    // Check the working-copy commit in each workspace in the log output. The "@"
+    // Check the working-copy commit in each workspace in the log output. The "@r"
     // node in the graph indicates the current workspace's working-copy commit.
     insta::assert_snapshot!(get_workspace_log_output(&main_dir), @r"
     @  qpvuntsmwlqt default@ second@ first-commit
@@ -987,7 +1020,7 @@ fn test_split_with_multiple_workspaces_different_working_copy() {
     main_dir
         .run_jj(["workspace", "add", "--name", "second", "../secondary"])
         .success();
-    // Check the working-copy commit in each workspace in the log output. The "@"
+    // Check the working-copy commit in each workspace in the log output. The "@r"
     // node in the graph indicates the current workspace's working-copy commit.
     insta::assert_snapshot!(get_workspace_log_output(&main_dir), @r"
     @  qpvuntsmwlqt default@ first-commit
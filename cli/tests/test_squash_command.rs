@@ -317,7 +317,6 @@ fn test_squash_partial() {
     ");
 
     // We get a warning if we pass a positional argument that looks like a revset
-    work_dir.run_jj(["undo"]).success();
     let output = work_dir.run_jj(["squash", "b"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
@@ -327,24 +326,23 @@ fn test_squash_partial() {
     "#);
 
     // we can use --interactive and fileset together
-    work_dir.run_jj(["undo"]).success();
     work_dir.write_file("file3", "foo\n");
     std::fs::write(&edit_script, "reset file1").unwrap();
     let output = work_dir.run_jj(["squash", "-i", "file1", "file3"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Rebased 1 descendant commits
-    Working copy  (@) now at: mzvwutvl 69c58f86 c | (no description set)
-    Parent commit (@-)      : kkmpptxz 0f38c564 b | (no description set)
+    Working copy  (@) now at: mzvwutvl f660ff29 c | (no description set)
+    Parent commit (@-)      : kkmpptxz 8395a652 b | (no description set)
     [EOF]
     ");
     let output = work_dir.run_jj(["log", "-s"]);
     insta::assert_snapshot!(output, @r"
-    @  mzvwutvl test.user@example.com 2001-02-03 08:05:36 c 69c58f86
+    @  mzvwutvl test.user@example.com 2001-02-03 08:05:34 c f660ff29
     │  (no description set)
     │  M file1
     │  M file2
-    ○  kkmpptxz test.user@example.com 2001-02-03 08:05:36 b 0f38c564
+    ○  kkmpptxz test.user@example.com 2001-02-03 08:05:34 b 8395a652
     │  (no description set)
     │  M file1
     │  M file2
@@ -1175,15 +1173,15 @@ fn test_squash_from_multiple_partial_no_op() {
     ]);
     insta::assert_snapshot!(output, @r"
     @    6dfc239e2ba3 d
-    ├─╮  -- operation b7394e553191 (2001-02-03 08:05:13) squash commits into fdb92bc249a019337e7fa3f6c6fa74a762dd20b5
+    ├─╮  -- operation 695094012e18 (2001-02-03 08:05:13) squash commits into fdb92bc249a019337e7fa3f6c6fa74a762dd20b5
     │ ○  b1a17f79a1a5 b
-    │ │  -- operation 853cf887ea1b (2001-02-03 08:05:10) snapshot working copy
+    │ │  -- operation d41486a2f3fb (2001-02-03 08:05:10) snapshot working copy
     │ ○  d8b7d57239ca b
-    │    -- operation a7f388a190d3 (2001-02-03 08:05:09) new empty commit
+    │    -- operation fae9d83e0cab (2001-02-03 08:05:09) new empty commit
     ○  fdb92bc249a0 d
-    │  -- operation 2a8d2002ac46 (2001-02-03 08:05:12) snapshot working copy
+    │  -- operation 581dbaa90140 (2001-02-03 08:05:12) snapshot working copy
     ○  af709ccc1ca9 d
-       -- operation 5aefb1c40e7d (2001-02-03 08:05:11) new empty commit
+       -- operation d2ea6316c6e0 (2001-02-03 08:05:11) new empty commit
     [EOF]
     ");
 
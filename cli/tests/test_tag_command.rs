@@ -125,3 +125,191 @@ fn test_tag_list() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_tag_create_set_delete() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::open(work_dir.root());
+
+    work_dir.run_jj(["commit", "-mcommit1"]).success();
+    work_dir.run_jj(["commit", "-mcommit2"]).success();
+
+    let output = work_dir.run_jj(["tag", "create", "-r@--", "v1", "v2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 2 tags pointing to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "v1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Tag already exists: v1
+    Hint: Use `jj tag set` to update it.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "v1@origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'v1@origin' for '<NAMES>...': Failed to parse tag name: Syntax error
+
+    For more information, try '--help'.
+    Caused by:  --> 1:3
+      |
+    1 | v1@origin
+      |   ^---
+      |
+      = expected <EOI>
+    Hint: See https://jj-vcs.github.io/jj/latest/revsets/ or use `jj help -k revsets` for how to quote symbols.
+    [EOF]
+    [exit status: 2]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["tag", "list"]), @r"
+    v1: qpvuntsm b876c5f4 (empty) commit1
+    v2: qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_repo.find_reference("refs/tags/v2").unwrap().id()
+    );
+
+    // Moving tags requires --allow-move
+    let output = work_dir.run_jj(["tag", "set", "-r@-", "v1", "v3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to move tag: v1
+    Hint: Use --allow-move to allow it.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["tag", "set", "-r@-", "--allow-move", "v1", "v3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 tags pointing to rlvkpnrz 3f672e72 (empty) commit2
+    Moved 1 tags to rlvkpnrz 3f672e72 (empty) commit2
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["tag", "list"]), @r"
+    v1: rlvkpnrz 3f672e72 (empty) commit2
+    v2: qpvuntsm b876c5f4 (empty) commit1
+    v3: rlvkpnrz 3f672e72 (empty) commit2
+    [EOF]
+    ");
+    assert_ne!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_repo.find_reference("refs/tags/v2").unwrap().id()
+    );
+
+    let output = work_dir.run_jj(["tag", "delete", "glob:v[12]", "v4"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such tag: v4
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["tag", "delete", "glob:v[12]"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Deleted 2 tags.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["tag", "list"]), @r"
+    v3: rlvkpnrz 3f672e72 (empty) commit2
+    [EOF]
+    ");
+    assert!(git_repo
+        .try_find_reference("refs/tags/v1")
+        .unwrap()
+        .is_none());
+    assert!(git_repo
+        .try_find_reference("refs/tags/v2")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_tag_create_annotated() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = {
+        let mut git_repo_path = work_dir.root().to_owned();
+        git_repo_path.extend([".jj", "repo", "store", "git"]);
+        git::open(git_repo_path)
+    };
+    work_dir.run_jj(["commit", "-mcommit1"]).success();
+
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "--sign", "v1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --message <MESSAGE>
+
+    Usage: jj tag create --message <MESSAGE> --revision <REVSET> --sign <NAMES>...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "-mRelease", "--sign", "v1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No signing backend configured
+    Hint: For configuring a signing backend, see https://jj-vcs.github.io/jj/latest/config/#commit-signing
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj([
+        "tag",
+        "create",
+        "-r@-",
+        "-mRelease 1",
+        "--sign",
+        "--config=signing.backend=test",
+        "v1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 tags pointing to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["tag", "list"]), @r"
+    v1: qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+
+    // The annotated tag is written to the Git repo even if it isn't colocated
+    let git_tag = git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .id()
+        .object()
+        .unwrap()
+        .into_tag();
+    let git_tag = git_tag.decode().unwrap();
+    // The test signing backend doesn't produce a PGP signature, so the
+    // signature is parsed as a part of the message.
+    insta::assert_snapshot!(git_tag.message, @r"
+    Release 1
+    --- JJ-TEST-SIGNATURE ---
+    KEY: 
+    cf3ed179320873276b1f0b6c7a047ab76a15779df6edcd2bffd7ff59e73c3d71125ffc0dcbd5579a0e95a3f5dae3f4e94f2e3e5c2d5a5dc68cac0a259ba95a8d
+    ");
+
+    // The annotated tag isn't replaced by export
+    work_dir.run_jj(["git", "export"]).success();
+    assert!(git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .id()
+        .object()
+        .unwrap()
+        .try_into_tag()
+        .is_ok());
+}
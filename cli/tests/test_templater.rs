@@ -48,16 +48,16 @@ fn test_templater_parse_error() {
     'format_id(id)' = 'id.sort()'
     "###,
     );
-    insta::assert_snapshot!(render(r#"conflicts"#), @r"
+    insta::assert_snapshot!(render(r#"conflicted"#), @r"
     ------- stderr -------
-    Error: Failed to parse template: Keyword `conflicts` doesn't exist
+    Error: Failed to parse template: Keyword `conflicted` doesn't exist
     Caused by:  --> 1:1
       |
-    1 | conflicts
-      | ^-------^
+    1 | conflicted
+      | ^--------^
       |
-      = Keyword `conflicts` doesn't exist
-    Hint: Did you mean `conflict`, `conflicting`?
+      = Keyword `conflicted` doesn't exist
+    Hint: Did you mean `conflict`, `conflicting`, `conflicts`, `contained_in`?
     [EOF]
     [exit status: 1]
     ");
@@ -443,8 +443,9 @@ fn test_bookmark_track_untrack_undo() {
     work_dir.run_jj(["undo"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-    feature1@origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+      @origin: qpvuntsm bab5b5ef (empty) commit
+    feature2: qpvuntsm bab5b5ef (empty) commit
+      @origin: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
@@ -454,50 +455,174 @@ fn test_bookmark_track_untrack_undo() {
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
       @origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+    feature2: qpvuntsm bab5b5ef (empty) commit
+      @origin: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
     work_dir.run_jj(["undo"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-    feature1@origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+    feature2: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 }
 
 #[test]
-fn test_undo_latest_undo_implicitly() {
+fn test_undo_repeatedly() {
     let test_env = TestEnvironment::default();
     test_env.run_jj_in(".", ["git", "init", "repo"]).success();
     let work_dir = test_env.work_dir("repo");
 
-    // Double-undo creation of child
-    work_dir.run_jj(["new"]).success();
-    work_dir.run_jj(["undo"]).success();
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["describe", "-m", "b"]).success();
+    work_dir.run_jj(["describe", "-m", "c"]).success();
+
+    // Each undo steps further back
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: 6de77c1b46a3 (2001-02-03 08:05:09) undo operation dbcb2561b6fee72ea6de79511b6b62f1fff2424f79d16dd30339f94621100f77c86ca7450f7b1ec1bd95d4d56b7a54fe3f3e612353e62cedc682366211b4144e
-    Working copy  (@) now at: rlvkpnrz 43444d88 (empty) (no description set)
-    Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
+    Undid operation: 1aa23523e6d6 (2001-02-03 08:05:10) describe commit 9f43967b1cdbce4ab322cb7b4636fc0362c38373
+    Working copy  (@) now at: qpvuntsm 9f43967b (empty) b
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["undo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Undid operation: 5704e62c17b3 (2001-02-03 08:05:09) describe commit b86e28cd6862624ad77e1aaf31e34b2c7545bebd
+    Working copy  (@) now at: qpvuntsm b86e28cd (empty) a
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_description(&work_dir), @r"
+    a
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_redo() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["redo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Nothing to redo
+    Hint: Only operations undone by the latest `jj undo` commands can be redone.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["describe", "-m", "b"]).success();
+    work_dir.run_jj(["describe", "-m", "c"]).success();
+    work_dir.run_jj(["undo"]).success();
+    work_dir.run_jj(["undo"]).success();
+    insta::assert_snapshot!(get_description(&work_dir), @r"
+    a
+    [EOF]
+    ");
+
+    // Each redo reapplies the operation undone by an earlier undo
+    let output = work_dir.run_jj(["redo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Redid operation: 3b4947e3aec4 (2001-02-03 08:05:10) describe commit 99025a24f7f3d4b2ff35ef4ba94b7350c1ce36a2
+    Working copy  (@) now at: qpvuntsm 097e596c (empty) b
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_description(&work_dir), @r"
+    b
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "redo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Redid operation: 314e4dce6189 (2001-02-03 08:05:11) describe commit 097e596ca5a28677c520edeee32cbd8c8abf9c8c
+    Working copy  (@) now at: qpvuntsm 81447e31 (empty) c
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_description(&work_dir), @r"
+    c
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["redo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Nothing to redo
+    Hint: Only operations undone by the latest `jj undo` commands can be redone.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Undo after redo steps back again
+    work_dir.run_jj(["undo"]).success();
+    work_dir.run_jj(["undo"]).success();
+    insta::assert_snapshot!(get_description(&work_dir), @r"
+    a
+    [EOF]
+    ");
+    work_dir.run_jj(["redo"]).success();
+    insta::assert_snapshot!(get_description(&work_dir), @r"
+    b
+    [EOF]
+    ");
+
+    // A new operation clears the redo stack
+    work_dir.run_jj(["describe", "-m", "d"]).success();
+    let output = work_dir.run_jj(["redo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Nothing to redo
+    Hint: Only operations undone by the latest `jj undo` commands can be redone.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "-n3"]);
+    insta::assert_snapshot!(output, @r"
+    @  837f4bafc67c test-username@host.example.com 2001-02-03 04:05:25.000 +07:00 - 2001-02-03 04:05:25.000 +07:00
+    │  describe commit 097e596ca5a28677c520edeee32cbd8c8abf9c8c
+    │  args: jj describe -m d
+    ○  2750b83e518e test-username@host.example.com 2001-02-03 04:05:23.000 +07:00 - 2001-02-03 04:05:23.000 +07:00
+    │  redo operation 3b4947e3aec454e01f93823009b0afa6157da1f2d0f2a99b74c42910ffde5d31392f8d2322bbf489f7b557e467bc8f6da3c79968c643f3d6979ba8520d283281
+    │  args: jj redo
+    │  redo-of: 0e68429d493baacfc5e28097383ab137fa2fe12270f532d08cc612481eb4dad333722e5c4c111f83a0756edc324be586879cc3d36ce0b9a30bf0db4b0c8da020
+    ○  0e68429d493b test-username@host.example.com 2001-02-03 04:05:21.000 +07:00 - 2001-02-03 04:05:21.000 +07:00
+    │  undo operation 3b4947e3aec454e01f93823009b0afa6157da1f2d0f2a99b74c42910ffde5d31392f8d2322bbf489f7b557e467bc8f6da3c79968c643f3d6979ba8520d283281
+    │  args: jj undo
+    │  undo-of: 3b4947e3aec454e01f93823009b0afa6157da1f2d0f2a99b74c42910ffde5d31392f8d2322bbf489f7b557e467bc8f6da3c79968c643f3d6979ba8520d283281
     [EOF]
     ");
+}
 
-    // Double-undo creation of sibling
+#[test]
+fn test_undo_latest_undo_implicitly() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // The second undo undoes the creation of the child, not the first undo
+    work_dir.run_jj(["new"]).success();
     work_dir.run_jj(["new", "@-"]).success();
     work_dir.run_jj(["undo"]).success();
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: b77c991c5a2f (2001-02-03 08:05:12) undo operation 58f57841c00da755413d291ed9e1a1d9a58dd4311b5000a8703f1bf93339dd12cdbc2c6e1c8cd5f43cb584cabddcf8366153a007c244d89ee80a4e42e513058d
-    Working copy  (@) now at: mzvwutvl 8afc18ff (empty) (no description set)
-    Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
+    Undid operation: 886e0b3d495f (2001-02-03 08:05:08) new empty commit
+    Working copy  (@) now at: qpvuntsm e8849ae1 (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T=change_id.short()"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsmwlqt
+    ◆  zzzzzzzzzzzz
     [EOF]
     ");
 }
@@ -517,22 +642,21 @@ fn test_undo_latest_undo_explicitly() {
     let output = work_dir.run_jj(["undo", op_id_hex]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: 6de77c1b46a3 (2001-02-03 08:05:09) undo operation dbcb2561b6fee72ea6de79511b6b62f1fff2424f79d16dd30339f94621100f77c86ca7450f7b1ec1bd95d4d56b7a54fe3f3e612353e62cedc682366211b4144e
+    Undid operation: a10d17e86ff5 (2001-02-03 08:05:09) undo operation 886e0b3d495f7590a0d1ff17b5c51dcd4876d2800e302693429fc310c306c3dc6416d7f183cf6390b13008bac74ec0fe4904f0a18f8590ba61b0d69d95c0b993
     Working copy  (@) now at: rlvkpnrz 43444d88 (empty) (no description set)
     Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 
+    // `@` steps back through the undo operations like the default
     work_dir.run_jj(["new"]).success();
     work_dir.run_jj(["undo"]).success();
     let output = work_dir.run_jj(["undo", "@"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: c99ea8473832 (2001-02-03 08:05:13) undo operation 09cbd5c994ee9e437950a49fa4400d3c6e9d5c1e44ea55b38806a9692317716647578328c53b887a758a40270ad8ab6d5b67c1675d088281879cc7c74a2da6cc
-    Working copy  (@) now at: royxmykx ba0e5dca (empty) (no description set)
-    Parent commit (@-)      : rlvkpnrz 43444d88 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
+    Undid operation: 21092ec1e38b (2001-02-03 08:05:11) undo operation a10d17e86ff54f8aff8b6f8b8ba67df7c4f07ea86eddbbb0d7376b2c5a0846286d6703d32bd07f20ef729aed2860a1a39ed8f78a6eea47466f1b22a74f6a670a
+    Working copy  (@) now at: qpvuntsm e8849ae1 (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
     [EOF]
     ");
 }
@@ -554,7 +678,7 @@ fn test_undo_an_older_undo() {
     let output = work_dir.run_jj(["undo", op_id_hex]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: 6de77c1b46a3 (2001-02-03 08:05:09) undo operation dbcb2561b6fee72ea6de79511b6b62f1fff2424f79d16dd30339f94621100f77c86ca7450f7b1ec1bd95d4d56b7a54fe3f3e612353e62cedc682366211b4144e
+    Undid operation: a10d17e86ff5 (2001-02-03 08:05:09) undo operation 886e0b3d495f7590a0d1ff17b5c51dcd4876d2800e302693429fc310c306c3dc6416d7f183cf6390b13008bac74ec0fe4904f0a18f8590ba61b0d69d95c0b993
     [EOF]
     ");
 
@@ -565,13 +689,13 @@ fn test_undo_an_older_undo() {
     let output = work_dir.run_jj(["undo", "@-"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: ffea82d2bf89 (2001-02-03 08:05:14) undo operation 2b3480d4ece49d59f5da51e1284b742773e6b858935f122bebab625430137f9aae1aaebf7a8c081e0715e9cd24deee9d0d039743fbb1dd61804c633c9c7f17a2
+    Undid operation: b309a4ae4333 (2001-02-03 08:05:14) undo operation e43fdeb00d298931788df6bb481b6d3b642d1e59f9918d544312fa4475f90c96793ae54eadfe15d7223da8b43a4815d2db52e4fc12b7d4148dbf06bf9d1db7ef
     [EOF]
     ");
 }
 
 #[test]
-fn test_undo_an_undo_multiple_times() {
+fn test_undo_until_root_operation() {
     let test_env = TestEnvironment::default();
     test_env.run_jj_in(".", ["git", "init", "repo"]).success();
     let work_dir = test_env.work_dir("repo");
@@ -581,45 +705,15 @@ fn test_undo_an_undo_multiple_times() {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: 6de77c1b46a3 (2001-02-03 08:05:09) undo operation dbcb2561b6fee72ea6de79511b6b62f1fff2424f79d16dd30339f94621100f77c86ca7450f7b1ec1bd95d4d56b7a54fe3f3e612353e62cedc682366211b4144e
-    Working copy  (@) now at: rlvkpnrz 43444d88 (empty) (no description set)
-    Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
+    Undid operation: f045ea2142cf (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: f17b43a603fd (2001-02-03 08:05:10) undo operation 6de77c1b46a39115938845d2fffb5780a49a4991d1f8f99b60509705faa38d496b06cdbb427a497ff22d1f2b81613ac80a8dd6b97ca2fa35d7cc66d2a33059e4
-    Working copy  (@) now at: qpvuntsm e8849ae1 (empty) (no description set)
-    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
-    [EOF]
-    ");
-
-    work_dir.run_jj(["new"]).success();
-    work_dir.run_jj(["undo"]).success();
-    let output = work_dir.run_jj(["undo"]);
-    insta::assert_snapshot!(output, @r"
-    ------- stderr -------
-    Undid operation: 1eee1feca2d3 (2001-02-03 08:05:13) undo operation 18ee4719e7c7a2da3fbeec66678dfb03c4f3deebbe887dbc46270102c86894615a07a223713981d12b3ddc757e5104c7e9f7b391202747cd1868e0d2ac01f35d
-    Working copy  (@) now at: royxmykx e7d0d5fd (empty) (no description set)
-    Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
-    [EOF]
-    ");
-    let output = work_dir.run_jj(["undo", "@"]);
-    insta::assert_snapshot!(output, @r"
-    ------- stderr -------
-    Undid operation: 079cb7121929 (2001-02-03 08:05:14) undo operation 1eee1feca2d334e1c7c956048eec1e54a35d58b39aaec88c8a48ce7ce216b30e287f49c0305c51ad86292c6a2e11dd98ed97d8ef0a497e7114cf2aea99d4e71d
-    Working copy  (@) now at: qpvuntsm e8849ae1 (empty) (no description set)
-    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
-    Warning: The second-last `jj undo` was reverted by the latest `jj undo`. The repo is now in the same state as it was before the second-last `jj undo`.
-    Hint: To undo multiple operations, use `jj op log` to see past states and `jj op restore` to restore one of these states.
+    Error: Cannot undo root operation
     [EOF]
+    [exit status: 1]
     ");
 }
 
@@ -636,11 +730,16 @@ fn test_undo_bookmark_deletion() {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Undid operation: e1bcf7cd8080 (2001-02-03 08:05:09) delete bookmark foo
+    Undid operation: 078b38780f91 (2001-02-03 08:05:09) delete bookmark foo
     [EOF]
     ");
 }
 
+#[must_use]
+fn get_description(work_dir: &TestWorkDir) -> CommandOutput {
+    work_dir.run_jj(["log", "--no-graph", "-r=@", "-T=description"])
+}
+
 #[must_use]
 fn get_bookmark_output(work_dir: &TestWorkDir) -> CommandOutput {
     // --quiet to suppress deleted bookmarks hint
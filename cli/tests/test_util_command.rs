@@ -94,7 +94,7 @@ fn test_gc_operation_log() {
     let output = work_dir.run_jj(["debug", "operation", &op_to_remove]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
-    Error: No operation ID matching "b50d0a8f111a9d30d45d429d62c8e54016cc7c891706921a6493756c8074e883671cf3dac0ac9f94ef0fa8c79738a3dfe38c3e1f6c5e1a4a4d0857d266ef2040"
+    Error: No operation ID matching "6c63e4b57aeba908a150495c0d8ccd2663d3b49568559e8388d5682b7707eaeece82ddb6620722c35e4f106f0401b2f2c9411bc74ac89aef9af2304ac8023f79"
     [EOF]
     [exit status: 1]
     "#);
@@ -25,6 +25,7 @@ use futures::StreamExt as _;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::annotate::CopyTracking;
 use crate::annotate::FileAnnotator;
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
@@ -135,6 +136,8 @@ pub async fn split_hunks_to_trees(
         // Compute annotation of parent (= left) content to map right hunks
         let mut annotator =
             FileAnnotator::with_file_content(source.commit.id(), left_path, left_text.clone());
+        // Hunks are absorbed into the same file path.
+        annotator.set_copy_tracking(CopyTracking::None);
         annotator.compute(repo, destinations)?;
         let annotation = annotator.to_annotation();
         let annotation_ranges = annotation
@@ -20,12 +20,17 @@
 
 use std::collections::hash_map;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::iter;
+use std::mem;
 use std::ops::Range;
 use std::rc::Rc;
 
 use bstr::BStr;
 use bstr::BString;
+use bstr::ByteSlice as _;
+use futures::StreamExt as _;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 
@@ -37,11 +42,13 @@ use crate::conflicts::materialize_merge_result_to_bytes;
 use crate::conflicts::materialize_tree_value;
 use crate::conflicts::ConflictMarkerStyle;
 use crate::conflicts::MaterializedTreeValue;
+use crate::copies::CopyRecords;
 use crate::diff::Diff;
 use crate::diff::DiffHunkKind;
 use crate::fileset::FilesetExpression;
 use crate::graph::GraphEdge;
 use crate::graph::GraphEdgeType;
+use crate::matchers::EverythingMatcher;
 use crate::merged_tree::MergedTree;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
@@ -121,12 +128,25 @@ impl FileAnnotation {
     }
 }
 
+/// How lines are followed across files.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CopyTracking {
+    /// Lines are followed within the same file path.
+    None,
+    /// Lines are also followed across copies and renames of the file recorded
+    /// by the backend.
+    #[default]
+    Files,
+    /// In addition to copies and renames, lines moved or copied from other
+    /// files modified in the same commit are detected.
+    Lines,
+}
+
 /// Annotation process for a specific file.
 #[derive(Clone, Debug)]
 pub struct FileAnnotator {
-    // If we add copy-tracing support, file_path might be tracked by state.
-    file_path: RepoPathBuf,
     original_text: BString,
+    copy_tracking: CopyTracking,
     state: AnnotationState,
 }
 
@@ -161,29 +181,38 @@ impl FileAnnotator {
     ) -> Self {
         source.fill_line_map();
         let original_text = source.text.clone();
+        let original_line_map = vec![Err(starting_commit_id.clone()); source.line_map.len()];
+        let sources = HashMap::from([(file_path.to_owned(), source)]);
         let state = AnnotationState {
-            original_line_map: vec![Err(starting_commit_id.clone()); source.line_map.len()],
-            commit_source_map: HashMap::from([(starting_commit_id.clone(), source)]),
-            num_unresolved_roots: 0,
+            original_line_map,
+            commit_source_map: HashMap::from([(starting_commit_id.clone(), sources)]),
+            unresolved_roots: HashSet::new(),
         };
         FileAnnotator {
-            file_path: file_path.to_owned(),
             original_text,
+            copy_tracking: CopyTracking::default(),
             state,
         }
     }
 
+    /// Sets how lines are followed across files. Defaults to
+    /// [`CopyTracking::Files`].
+    pub fn set_copy_tracking(&mut self, copy_tracking: CopyTracking) {
+        self.copy_tracking = copy_tracking;
+    }
+
     /// Computes line-by-line annotation within the `domain`.
     ///
     /// The `domain` expression narrows the range of ancestors to search. It
-    /// will be intersected as `domain & ::pending_commits & files(file_path)`.
-    /// The `pending_commits` is assumed to be included in the `domain`.
+    /// will be intersected as `domain & ::pending_commits & files(file_paths)`,
+    /// where `file_paths` include the sources of copies and renames found so
+    /// far. The `pending_commits` is assumed to be included in the `domain`.
     pub fn compute(
         &mut self,
         repo: &dyn Repo,
         domain: &Rc<ResolvedRevsetExpression>,
     ) -> Result<(), RevsetEvaluationError> {
-        process_commits(repo, &mut self.state, domain, &self.file_path)
+        process_commits(repo, &mut self.state, domain, self.copy_tracking)
     }
 
     /// Remaining commit ids to visit from.
@@ -203,14 +232,191 @@ impl FileAnnotator {
     }
 }
 
+/// Lines of a file at a certain commit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineRangeContent {
+    /// Commit where the lines exist.
+    pub commit_id: CommitId,
+    /// 0-based range of the line numbers in the file.
+    pub line_range: Range<usize>,
+    /// Contents of the lines, including newline characters.
+    pub text: BString,
+}
+
+/// Change to a range of lines in a file made by a commit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineRangeChange {
+    /// Lines at the commit which made the change.
+    pub new: LineRangeContent,
+    /// Corresponding lines at the parent commit. `None` if all lines were
+    /// added by the commit.
+    pub old: Option<LineRangeContent>,
+}
+
+/// Finds commits that modified the given range of lines in a file, starting
+/// from the `starting_commit`.
+///
+/// The lines are tracked back through the history in the same way as
+/// [`FileAnnotator`]. If lines are inserted within the range, the range at the
+/// ancestor commits shrinks accordingly. The `domain` expression narrows the
+/// range of ancestors to search.
+///
+/// Changes are returned in reverse topological order.
+pub fn find_line_range_changes(
+    repo: &dyn Repo,
+    starting_commit: &Commit,
+    file_path: &RepoPath,
+    line_range: Range<usize>,
+    domain: &Rc<ResolvedRevsetExpression>,
+) -> Result<Vec<LineRangeChange>, RevsetEvaluationError> {
+    let mut source = Source::load(starting_commit, file_path)?;
+    source.fill_line_map();
+    let line_range = line_range.start..line_range.end.min(source.line_map.len());
+    if line_range.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut pending_ranges = HashMap::from([(starting_commit.id().clone(), (source, line_range))]);
+
+    let predicate = RevsetFilterPredicate::File(FilesetExpression::file_path(file_path.to_owned()));
+    let heads = RevsetExpression::commit(starting_commit.id().clone());
+    let revset = heads
+        .union(&domain.intersection(&heads.ancestors()).filtered(predicate))
+        .evaluate(repo)?;
+
+    let mut changes = vec![];
+    for node in revset.iter_graph() {
+        let (commit_id, edge_list) = node?;
+        let Some((source, line_range)) = pending_ranges.remove(&commit_id) else {
+            continue;
+        };
+        let mut modified = true;
+        let mut old = None;
+        for parent_edge in &edge_list {
+            let parent_commit_id = &parent_edge.target;
+            let parent_source = match pending_ranges.get(parent_commit_id) {
+                Some((parent_source, _)) => parent_source.clone(),
+                None => {
+                    let commit = repo.store().get_commit(parent_commit_id)?;
+                    Source::load(&commit, file_path)?
+                }
+            };
+            let Some(parent_range) = map_line_range(&source.text, &parent_source.text, &line_range)
+            else {
+                continue;
+            };
+            if parent_range.len() == line_range.len()
+                && slice_lines(&parent_source.text, &parent_range)
+                    == slice_lines(&source.text, &line_range)
+            {
+                // The lines weren't modified by this commit.
+                modified = false;
+            }
+            if old.is_none() {
+                old = Some(LineRangeContent {
+                    commit_id: parent_commit_id.clone(),
+                    line_range: parent_range.clone(),
+                    text: slice_lines(&parent_source.text, &parent_range).into(),
+                });
+            }
+            // If an omitted parent had the lines, stop tracking them there.
+            if parent_edge.edge_type == GraphEdgeType::Missing {
+                continue;
+            }
+            match pending_ranges.entry(parent_commit_id.clone()) {
+                hash_map::Entry::Occupied(mut entry) => {
+                    let (_, range) = entry.get_mut();
+                    *range = range.start.min(parent_range.start)..range.end.max(parent_range.end);
+                }
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert((parent_source, parent_range));
+                }
+            }
+        }
+        if modified {
+            changes.push(LineRangeChange {
+                new: LineRangeContent {
+                    commit_id,
+                    line_range: line_range.clone(),
+                    text: slice_lines(&source.text, &line_range).into(),
+                },
+                old,
+            });
+        }
+        if pending_ranges.is_empty() {
+            break;
+        }
+    }
+    Ok(changes)
+}
+
+/// Maps the `line_range` in the current file to the corresponding range in the
+/// parent file. Lines modified at the boundaries of the range are included.
+/// Returns `None` if all lines in the range were added.
+fn map_line_range(
+    current_contents: &[u8],
+    parent_contents: &[u8],
+    line_range: &Range<usize>,
+) -> Option<Range<usize>> {
+    let diff = Diff::by_line([current_contents, parent_contents]);
+    let mut current_line_counter: usize = 0;
+    let mut parent_line_counter: usize = 0;
+    let mut parent_start = None;
+    let mut parent_end = None;
+    for hunk in diff.hunks() {
+        let current_count = hunk.contents[0].split_inclusive(|b| *b == b'\n').count();
+        let parent_count = hunk.contents[1].split_inclusive(|b| *b == b'\n').count();
+        let current_lines = current_line_counter..current_line_counter + current_count;
+        match hunk.kind {
+            DiffHunkKind::Matching => {
+                if current_lines.contains(&line_range.start) {
+                    parent_start =
+                        Some(parent_line_counter + (line_range.start - current_line_counter));
+                }
+                if current_lines.contains(&(line_range.end - 1)) {
+                    parent_end =
+                        Some(parent_line_counter + (line_range.end - current_line_counter));
+                }
+            }
+            DiffHunkKind::Different => {
+                if current_lines.contains(&line_range.start) {
+                    parent_start = Some(parent_line_counter);
+                }
+                if current_lines.contains(&(line_range.end - 1)) {
+                    parent_end = Some(parent_line_counter + parent_count);
+                }
+            }
+        }
+        current_line_counter += current_count;
+        parent_line_counter += parent_count;
+    }
+    let parent_range = parent_start?..parent_end?;
+    (!parent_range.is_empty()).then_some(parent_range)
+}
+
+/// Returns the contents of the lines in the `line_range`.
+fn slice_lines<'a>(text: &'a [u8], line_range: &Range<usize>) -> &'a [u8] {
+    let mut offset = 0;
+    let mut start = text.len();
+    for (i, line) in text.split_inclusive(|b| *b == b'\n').enumerate() {
+        if i == line_range.start {
+            start = offset;
+        }
+        if i == line_range.end {
+            return &text[start..offset];
+        }
+        offset += line.len();
+    }
+    &text[start..]
+}
+
 /// Intermediate state of file annotation.
 #[derive(Clone, Debug)]
 struct AnnotationState {
     original_line_map: OriginalLineMap,
-    /// Commits to file line mappings and contents.
-    commit_source_map: HashMap<CommitId, Source>,
-    /// Number of unresolved root commits in `commit_source_map`.
-    num_unresolved_roots: usize,
+    /// Commits to file line mappings and contents, keyed by file paths.
+    commit_source_map: HashMap<CommitId, HashMap<RepoPathBuf, Source>>,
+    /// Unresolved root commits in `commit_source_map`.
+    unresolved_roots: HashSet<CommitId>,
 }
 
 /// Line mapping and file content at a certain commit.
@@ -237,6 +443,14 @@ impl Source {
         Ok(Self::new(text))
     }
 
+    fn merge_line_map(&mut self, line_map: Vec<(usize, usize)>) {
+        self.line_map = if self.line_map.is_empty() {
+            line_map
+        } else {
+            itertools::merge(self.line_map.iter().copied(), line_map).collect()
+        };
+    }
+
     fn fill_line_map(&mut self) {
         let lines = self.text.split_inclusive(|b| *b == b'\n');
         self.line_map = lines.enumerate().map(|(i, _)| (i, i)).collect();
@@ -253,108 +467,386 @@ fn process_commits(
     repo: &dyn Repo,
     state: &mut AnnotationState,
     domain: &Rc<ResolvedRevsetExpression>,
-    file_name: &RepoPath,
+    copy_tracking: CopyTracking,
 ) -> Result<(), RevsetEvaluationError> {
-    let predicate = RevsetFilterPredicate::File(FilesetExpression::file_path(file_name.to_owned()));
-    // TODO: If the domain isn't a contiguous range, changes masked out by it
-    // might not be caught by the closest ancestor revision. For example,
-    // domain=merges() would pick up almost nothing because merge revisions
-    // are usually empty. Perhaps, we want to query `files(file_path,
-    // within_sub_graph=domain)`, not `domain & files(file_path)`.
-    let heads = RevsetExpression::commits(state.commit_source_map.keys().cloned().collect());
-    let revset = heads
-        .union(&domain.intersection(&heads.ancestors()).filtered(predicate))
-        .evaluate(repo)?;
-
-    state.num_unresolved_roots = 0;
-    for node in revset.iter_graph() {
-        let (commit_id, edge_list) = node?;
-        process_commit(repo, file_name, state, &commit_id, &edge_list)?;
-        if state.commit_source_map.len() == state.num_unresolved_roots {
-            // No more lines to propagate to ancestors.
-            break;
+    state.unresolved_roots.clear();
+    loop {
+        let file_paths = state
+            .commit_source_map
+            .values()
+            .flat_map(|sources| sources.keys())
+            .unique()
+            .cloned()
+            .collect_vec();
+        // The index may prove that commits don't touch the files without
+        // diffing their trees, if changed paths are indexed.
+        let predicate = RevsetFilterPredicate::File(FilesetExpression::union_all(
+            file_paths
+                .iter()
+                .cloned()
+                .map(FilesetExpression::file_path)
+                .collect(),
+        ));
+        // TODO: If the domain isn't a contiguous range, changes masked out by
+        // it might not be caught by the closest ancestor revision. For example,
+        // domain=merges() would pick up almost nothing because merge revisions
+        // are usually empty. Perhaps, we want to query `files(file_path,
+        // within_sub_graph=domain)`, not `domain & files(file_path)`.
+        let heads = RevsetExpression::commits(
+            state
+                .commit_source_map
+                .keys()
+                .filter(|id| !state.unresolved_roots.contains(*id))
+                .cloned()
+                .collect(),
+        );
+        let revset = heads
+            .union(&domain.intersection(&heads.ancestors()).filtered(predicate))
+            .evaluate(repo)?;
+
+        let mut found_new_paths = false;
+        for node in revset.iter_graph() {
+            let (commit_id, edge_list) = node?;
+            found_new_paths |= process_commit(
+                repo,
+                state,
+                domain,
+                copy_tracking,
+                &file_paths,
+                &commit_id,
+                &edge_list,
+            )?;
+            if state.commit_source_map.len() == state.unresolved_roots.len() {
+                // No more lines to propagate to ancestors.
+                return Ok(());
+            }
+            if found_new_paths {
+                // Start over from the pending commits so that changes to the
+                // new paths are also caught.
+                break;
+            }
+        }
+        if !found_new_paths {
+            return Ok(());
         }
     }
-    Ok(())
 }
 
 /// For a given commit, for each parent, we compare the version in the parent
 /// tree with the current version, updating the mappings for any lines in
 /// common. If the parent doesn't have the file, we skip it.
+///
+/// Returns true if lines were propagated to file paths other than the
+/// `tracked_paths`.
 fn process_commit(
     repo: &dyn Repo,
-    file_name: &RepoPath,
     state: &mut AnnotationState,
+    domain: &Rc<ResolvedRevsetExpression>,
+    copy_tracking: CopyTracking,
+    tracked_paths: &[RepoPathBuf],
     current_commit_id: &CommitId,
     edges: &[GraphEdge<CommitId>],
-) -> Result<(), BackendError> {
-    let Some(mut current_source) = state.commit_source_map.remove(current_commit_id) else {
-        return Ok(());
+) -> Result<bool, RevsetEvaluationError> {
+    let Some(current_sources) = state.commit_source_map.remove(current_commit_id) else {
+        return Ok(false);
     };
 
-    for parent_edge in edges {
-        let parent_commit_id = &parent_edge.target;
-        let parent_source = match state.commit_source_map.entry(parent_commit_id.clone()) {
-            hash_map::Entry::Occupied(entry) => entry.into_mut(),
-            hash_map::Entry::Vacant(entry) => {
-                let commit = repo.store().get_commit(entry.key())?;
-                entry.insert(Source::load(&commit, file_name)?)
-            }
-        };
+    let mut found_new_paths = false;
+    for (file_path, mut current_source) in current_sources {
+        let parents = find_parent_paths(
+            repo,
+            domain,
+            copy_tracking,
+            current_commit_id,
+            &file_path,
+            edges,
+        )?;
+        for (parent_commit_id, parent_path, edge_type) in parents {
+            let parent_source = match state
+                .commit_source_map
+                .entry(parent_commit_id.clone())
+                .or_default()
+                .entry(parent_path.clone())
+            {
+                hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                hash_map::Entry::Vacant(entry) => {
+                    let commit = repo.store().get_commit(&parent_commit_id)?;
+                    entry.insert(Source::load(&commit, &parent_path)?)
+                }
+            };
+
+            // For two versions of the same file, for all the lines in common,
+            // overwrite the new mapping in the results for the new commit.
+            // Let's say I have a file in commit A and commit B. We know that
+            // according to local line_map, in commit A, line 3 corresponds to
+            // line 7 of the original file. Now, line 3 in Commit A corresponds
+            // to line 6 in commit B. Then, we update local line_map to say that
+            // "Commit B line 6 goes to line 7 of the original file". We repeat
+            // this for all lines in common in the two commits.
+            let mut current_lines = current_source.line_map.iter().copied().peekable();
+            let mut new_current_line_map = Vec::new();
+            let mut new_parent_line_map = Vec::new();
+            copy_same_lines_with(
+                &current_source.text,
+                &parent_source.text,
+                |current_start, parent_start, count| {
+                    new_current_line_map
+                        .extend(current_lines.peeking_take_while(|&(cur, _)| cur < current_start));
+                    while let Some((current, original)) =
+                        current_lines.next_if(|&(cur, _)| cur < current_start + count)
+                    {
+                        let parent = parent_start + (current - current_start);
+                        new_parent_line_map.push((parent, original));
+                    }
+                },
+            );
+            new_current_line_map.extend(current_lines);
+            current_source.line_map = new_current_line_map;
+            parent_source.merge_line_map(new_parent_line_map);
+            found_new_paths |= update_parent_source(
+                state,
+                tracked_paths,
+                current_commit_id,
+                &parent_commit_id,
+                &parent_path,
+                edge_type,
+            );
+        }
 
-        // For two versions of the same file, for all the lines in common,
-        // overwrite the new mapping in the results for the new commit. Let's
-        // say I have a file in commit A and commit B. We know that according to
-        // local line_map, in commit A, line 3 corresponds to line 7 of the
-        // original file. Now, line 3 in Commit A corresponds to line 6 in
-        // commit B. Then, we update local line_map to say that "Commit B line 6
-        // goes to line 7 of the original file". We repeat this for all lines in
-        // common in the two commits.
-        let mut current_lines = current_source.line_map.iter().copied().peekable();
-        let mut new_current_line_map = Vec::new();
-        let mut new_parent_line_map = Vec::new();
-        copy_same_lines_with(
-            &current_source.text,
-            &parent_source.text,
-            |current_start, parent_start, count| {
-                new_current_line_map
-                    .extend(current_lines.peeking_take_while(|&(cur, _)| cur < current_start));
-                while let Some((current, original)) =
-                    current_lines.next_if(|&(cur, _)| cur < current_start + count)
+        if copy_tracking == CopyTracking::Lines && !current_source.line_map.is_empty() {
+            let moved_sources =
+                find_moved_lines(repo, current_commit_id, &file_path, &mut current_source)?;
+            for (parent_commit_id, parent_path, source) in moved_sources {
+                let edge_type = if is_in_domain(repo, domain, &parent_commit_id)? {
+                    GraphEdgeType::Direct
+                } else {
+                    GraphEdgeType::Missing
+                };
+                match state
+                    .commit_source_map
+                    .entry(parent_commit_id.clone())
+                    .or_default()
+                    .entry(parent_path.clone())
                 {
-                    let parent = parent_start + (current - current_start);
-                    new_parent_line_map.push((parent, original));
+                    hash_map::Entry::Occupied(entry) => {
+                        entry.into_mut().merge_line_map(source.line_map);
+                    }
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(source);
+                    }
                 }
-            },
-        );
-        new_current_line_map.extend(current_lines);
-        current_source.line_map = new_current_line_map;
-        parent_source.line_map = if parent_source.line_map.is_empty() {
-            new_parent_line_map
-        } else {
-            itertools::merge(parent_source.line_map.iter().copied(), new_parent_line_map).collect()
-        };
-        if parent_source.line_map.is_empty() {
-            state.commit_source_map.remove(parent_commit_id);
-        } else if parent_edge.edge_type == GraphEdgeType::Missing {
-            // If an omitted parent had the file, leave these lines unresolved.
-            // The origin of the unresolved lines is represented as
-            // Err(root_commit_id).
-            for &(_, original_line_number) in &parent_source.line_map {
-                state.original_line_map[original_line_number] = Err(current_commit_id.clone());
+                found_new_paths |= update_parent_source(
+                    state,
+                    tracked_paths,
+                    current_commit_id,
+                    &parent_commit_id,
+                    &parent_path,
+                    edge_type,
+                );
             }
-            state.num_unresolved_roots += 1;
+        }
+
+        // Once we've looked at all parents of a commit, any leftover lines
+        // must be original to the current commit, so we save this information
+        // in original_line_map.
+        for (_, original_line_number) in current_source.line_map {
+            state.original_line_map[original_line_number] = Ok(current_commit_id.clone());
         }
     }
 
-    // Once we've looked at all parents of a commit, any leftover lines must be
-    // original to the current commit, so we save this information in
-    // original_line_map.
-    for (_, original_line_number) in current_source.line_map {
-        state.original_line_map[original_line_number] = Ok(current_commit_id.clone());
+    Ok(found_new_paths)
+}
+
+/// Returns the parent commits, file paths, and edge types to compare the file
+/// at the current commit with.
+///
+/// If the file was copied or renamed by the current commit, the source of the
+/// copy in the direct parent is returned instead of the graph edges.
+fn find_parent_paths(
+    repo: &dyn Repo,
+    domain: &Rc<ResolvedRevsetExpression>,
+    copy_tracking: CopyTracking,
+    current_commit_id: &CommitId,
+    file_path: &RepoPath,
+    edges: &[GraphEdge<CommitId>],
+) -> Result<Vec<(CommitId, RepoPathBuf, GraphEdgeType)>, RevsetEvaluationError> {
+    let edge_parents = || {
+        edges
+            .iter()
+            .map(|edge| (edge.target.clone(), file_path.to_owned(), edge.edge_type))
+            .collect()
+    };
+    if copy_tracking == CopyTracking::None {
+        return Ok(edge_parents());
+    }
+    let commit = repo.store().get_commit(current_commit_id)?;
+    let parents: Vec<_> = commit.parents().try_collect()?;
+    for parent in &parents {
+        if !parent.tree()?.path_value(file_path)?.is_absent() {
+            return Ok(edge_parents());
+        }
     }
 
-    Ok(())
+    // The file was added by this commit. Look for the source of the copy.
+    let mut parent_paths = vec![];
+    for parent in &parents {
+        let records = repo
+            .store()
+            .get_copy_records(Some(&[file_path.to_owned()]), parent.id(), commit.id())?
+            .collect::<Vec<_>>()
+            .block_on();
+        let mut copy_records = CopyRecords::default();
+        copy_records.add_records(records)?;
+        if let Some(record) = copy_records.for_target(file_path) {
+            let edge_type = if is_in_domain(repo, domain, parent.id())? {
+                GraphEdgeType::Direct
+            } else {
+                GraphEdgeType::Missing
+            };
+            parent_paths.push((parent.id().clone(), record.source.clone(), edge_type));
+        }
+    }
+    if parent_paths.is_empty() {
+        Ok(edge_parents())
+    } else {
+        Ok(parent_paths)
+    }
+}
+
+/// Maps lines which aren't found in the parent file to lines removed from
+/// other files in the same commit. Returns the parent sources having the moved
+/// lines.
+///
+/// Lines consisting only of whitespace are ignored. Moves in merge commits
+/// aren't detected.
+fn find_moved_lines(
+    repo: &dyn Repo,
+    current_commit_id: &CommitId,
+    file_path: &RepoPath,
+    current_source: &mut Source,
+) -> BackendResult<Vec<(CommitId, RepoPathBuf, Source)>> {
+    let store = repo.store();
+    let commit = store.get_commit(current_commit_id)?;
+    let [parent_id] = commit.parent_ids() else {
+        return Ok(vec![]);
+    };
+    let parent_tree = store.get_commit(parent_id)?.tree()?;
+    let tree = commit.tree()?;
+    let diff_entries: Vec<_> = parent_tree
+        .diff_stream(&tree, &EverythingMatcher)
+        .collect()
+        .block_on();
+
+    // Index the lines removed from the other files by content.
+    let mut parent_sources = vec![];
+    let mut removed_lines: HashMap<BString, VecDeque<(usize, usize)>> = HashMap::new();
+    for entry in diff_entries {
+        entry.values?;
+        if entry.path.as_ref() == file_path {
+            continue;
+        }
+        let parent_text = get_file_contents(store, &entry.path, &parent_tree).block_on()?;
+        let text = get_file_contents(store, &entry.path, &tree).block_on()?;
+        let source_index = parent_sources.len();
+        parent_sources.push((entry.path, Source::new(parent_text)));
+        let diff = Diff::by_line([&parent_sources[source_index].1.text, &text]);
+        let mut parent_line_counter: usize = 0;
+        for hunk in diff.hunks() {
+            let lines = hunk.contents[0].split_inclusive(|b| *b == b'\n');
+            match hunk.kind {
+                DiffHunkKind::Matching => parent_line_counter += lines.count(),
+                DiffHunkKind::Different => {
+                    for line in lines {
+                        if !line.trim().is_empty() {
+                            removed_lines
+                                .entry(line.into())
+                                .or_default()
+                                .push_back((source_index, parent_line_counter));
+                        }
+                        parent_line_counter += 1;
+                    }
+                }
+            }
+        }
+    }
+    if removed_lines.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let lines = current_source
+        .text
+        .split_inclusive(|b| *b == b'\n')
+        .collect_vec();
+    let mut new_current_line_map = Vec::new();
+    for (current, original) in mem::take(&mut current_source.line_map) {
+        let moved = removed_lines
+            .get_mut(lines[current])
+            .and_then(|candidates| candidates.pop_front());
+        if let Some((source_index, parent)) = moved {
+            parent_sources[source_index]
+                .1
+                .line_map
+                .push((parent, original));
+        } else {
+            new_current_line_map.push((current, original));
+        }
+    }
+    current_source.line_map = new_current_line_map;
+    Ok(parent_sources
+        .into_iter()
+        .filter(|(_, source)| !source.line_map.is_empty())
+        .map(|(path, mut source)| {
+            source.line_map.sort_unstable();
+            (parent_id.clone(), path, source)
+        })
+        .collect())
+}
+
+/// Removes the parent source if no lines were propagated to it, or marks the
+/// lines unresolved if the parent is outside of the domain.
+///
+/// Returns true if lines were propagated to a path other than the
+/// `tracked_paths`.
+fn update_parent_source(
+    state: &mut AnnotationState,
+    tracked_paths: &[RepoPathBuf],
+    current_commit_id: &CommitId,
+    parent_commit_id: &CommitId,
+    parent_path: &RepoPath,
+    edge_type: GraphEdgeType,
+) -> bool {
+    let parent_sources = state.commit_source_map.get_mut(parent_commit_id).unwrap();
+    let parent_source = &parent_sources[parent_path];
+    if parent_source.line_map.is_empty() {
+        parent_sources.remove(parent_path);
+        if parent_sources.is_empty() {
+            state.commit_source_map.remove(parent_commit_id);
+        }
+        return false;
+    }
+    if edge_type == GraphEdgeType::Missing {
+        // If an omitted parent had the file, leave these lines unresolved.
+        // The origin of the unresolved lines is represented as
+        // Err(root_commit_id).
+        for &(_, original_line_number) in &parent_source.line_map {
+            state.original_line_map[original_line_number] = Err(current_commit_id.clone());
+        }
+        state.unresolved_roots.insert(parent_commit_id.clone());
+    }
+    !tracked_paths
+        .iter()
+        .any(|path| path.as_ref() == parent_path)
+}
+
+fn is_in_domain(
+    repo: &dyn Repo,
+    domain: &Rc<ResolvedRevsetExpression>,
+    commit_id: &CommitId,
+) -> Result<bool, RevsetEvaluationError> {
+    let revset = RevsetExpression::commit(commit_id.clone())
+        .intersection(domain)
+        .evaluate(repo)?;
+    Ok(!revset.is_empty())
 }
 
 /// For two files, calls `copy(current_start, parent_start, count)` for each
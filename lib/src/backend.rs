@@ -497,6 +497,30 @@ pub trait Backend: Send + Sync + Debug {
         id: &FileId,
     ) -> BackendResult<Pin<Box<dyn AsyncRead + Send>>>;
 
+    /// Returns the size of the file contents in bytes.
+    ///
+    /// The default implementation reads the whole file. Backends should
+    /// override it if the size is available without reading the contents.
+    async fn file_size(&self, path: &RepoPath, id: &FileId) -> BackendResult<u64> {
+        let mut reader = self.read_file(path, id).await?;
+        tokio::io::copy(&mut reader, &mut tokio::io::sink())
+            .await
+            .map_err(|err| BackendError::ReadFile {
+                path: path.to_owned(),
+                id: id.clone(),
+                source: err.into(),
+            })
+    }
+
+    /// Fetches the contents of the files at once if they're stored remotely.
+    ///
+    /// This is a hint for backends that fetch file contents lazily, and the
+    /// files can be read without calling it. The default implementation does
+    /// nothing.
+    async fn prefetch_files(&self, _ids: &[FileId]) -> BackendResult<()> {
+        Ok(())
+    }
+
     async fn write_file(
         &self,
         path: &RepoPath,
@@ -0,0 +1,187 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bisect a range of commits to find the first commit that introduced a
+//! change in behavior.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use itertools::Itertools as _;
+use thiserror::Error;
+
+use crate::backend::BackendError;
+use crate::backend::CommitId;
+use crate::commit::Commit;
+use crate::repo::Repo;
+use crate::revset::ResolvedRevsetExpression;
+use crate::revset::RevsetEvaluationError;
+use crate::revset::RevsetExpression;
+
+/// Error that may occur during bisection.
+#[derive(Debug, Error)]
+pub enum BisectionError {
+    /// Error while contacting the Backend.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    /// Error evaluating the commit range.
+    #[error(transparent)]
+    RevsetEvaluation(#[from] RevsetEvaluationError),
+}
+
+/// Result of evaluating a commit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Evaluation {
+    /// The commit doesn't have the change in behavior.
+    Good,
+    /// The commit has the change in behavior.
+    Bad,
+    /// The commit can't be evaluated.
+    Skip,
+}
+
+/// What to do next in the bisection.
+#[derive(Clone, Debug)]
+pub enum NextStep {
+    /// The commit should be evaluated.
+    Evaluate(Commit),
+    /// The bisection is complete.
+    Done(BisectionResult),
+}
+
+/// Result of a complete bisection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BisectionResult {
+    /// No bad commit was found in the range.
+    Indeterminate,
+    /// The first bad commit is one of these. There's more than one candidate
+    /// if commits were skipped.
+    Found(Vec<Commit>),
+}
+
+/// Keeps track of evaluated commits and finds the next commit to evaluate.
+///
+/// Ancestors of good commits are assumed to be good, and descendants of bad
+/// commits are assumed to be bad.
+pub struct Bisector<'repo> {
+    repo: &'repo dyn Repo,
+    input_range: Rc<ResolvedRevsetExpression>,
+    good_commits: HashSet<CommitId>,
+    bad_commits: HashSet<CommitId>,
+    skipped_commits: HashSet<CommitId>,
+}
+
+impl<'repo> Bisector<'repo> {
+    /// Creates bisector that searches the commits in `input_range`.
+    pub fn new(repo: &'repo dyn Repo, input_range: Rc<ResolvedRevsetExpression>) -> Self {
+        Bisector {
+            repo,
+            input_range,
+            good_commits: HashSet::new(),
+            bad_commits: HashSet::new(),
+            skipped_commits: HashSet::new(),
+        }
+    }
+
+    /// Records the result of evaluating the commit.
+    pub fn mark(&mut self, id: CommitId, evaluation: Evaluation) {
+        self.good_commits.remove(&id);
+        self.bad_commits.remove(&id);
+        self.skipped_commits.remove(&id);
+        match evaluation {
+            Evaluation::Good => self.good_commits.insert(id),
+            Evaluation::Bad => self.bad_commits.insert(id),
+            Evaluation::Skip => self.skipped_commits.insert(id),
+        };
+    }
+
+    /// Marks the commit as good.
+    pub fn mark_good(&mut self, id: CommitId) {
+        self.mark(id, Evaluation::Good);
+    }
+
+    /// Marks the commit as bad.
+    pub fn mark_bad(&mut self, id: CommitId) {
+        self.mark(id, Evaluation::Bad);
+    }
+
+    /// Marks the commit as skipped.
+    pub fn mark_skipped(&mut self, id: CommitId) {
+        self.mark(id, Evaluation::Skip);
+    }
+
+    /// Commits marked as good.
+    pub fn good_commits(&self) -> &HashSet<CommitId> {
+        &self.good_commits
+    }
+
+    /// Commits marked as bad.
+    pub fn bad_commits(&self) -> &HashSet<CommitId> {
+        &self.bad_commits
+    }
+
+    /// Commits marked as skipped.
+    pub fn skipped_commits(&self) -> &HashSet<CommitId> {
+        &self.skipped_commits
+    }
+
+    /// Finds the next commit to evaluate, or the result if all relevant
+    /// commits have been evaluated.
+    pub fn next_step(&self) -> Result<NextStep, BisectionError> {
+        let good_expression =
+            RevsetExpression::commits(self.good_commits.iter().cloned().collect());
+        let bad_expression = RevsetExpression::commits(self.bad_commits.iter().cloned().collect());
+        let skipped_expression =
+            RevsetExpression::commits(self.skipped_commits.iter().cloned().collect());
+
+        let to_evaluate = self
+            .input_range
+            .minus(&good_expression.ancestors())
+            .minus(&bad_expression.descendants())
+            .minus(&skipped_expression);
+        let candidate_ids: Vec<CommitId> = to_evaluate.evaluate(self.repo)?.iter().try_collect()?;
+        if !candidate_ids.is_empty() {
+            // The candidates are in reverse topological order, so the middle
+            // commit splits a linear range in half.
+            let id = &candidate_ids[candidate_ids.len() / 2];
+            let commit = self.repo.store().get_commit(id)?;
+            return Ok(NextStep::Evaluate(commit));
+        }
+
+        // The first bad commits are the earliest bad commits in the range, or
+        // the skipped commits between them and the good commits.
+        let first_bad = self
+            .input_range
+            .intersection(&bad_expression.descendants())
+            .roots();
+        let skipped_before_first_bad = self
+            .input_range
+            .intersection(&skipped_expression)
+            .intersection(&first_bad.ancestors())
+            .minus(&good_expression.ancestors());
+        let result_ids: Vec<CommitId> = first_bad
+            .union(&skipped_before_first_bad)
+            .evaluate(self.repo)?
+            .iter()
+            .try_collect()?;
+        if result_ids.is_empty() {
+            return Ok(NextStep::Done(BisectionResult::Indeterminate));
+        }
+        let commits: Vec<_> = result_ids
+            .iter()
+            .map(|id| self.repo.store().get_commit(id))
+            .try_collect()?;
+        Ok(NextStep::Done(BisectionResult::Found(commits)))
+    }
+}
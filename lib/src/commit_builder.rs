@@ -29,6 +29,7 @@ use crate::commit::is_backend_commit_empty;
 use crate::commit::Commit;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
+use crate::resolution_cache::ResolutionCache;
 use crate::settings::JJRng;
 use crate::settings::SignSettings;
 use crate::settings::UserSettings;
@@ -362,6 +363,9 @@ impl DetachedCommitBuilder {
             ));
         }
         mut_repo.add_head(&commit)?;
+        if let Some(cache) = &self.store.merge_options().resolution_cache {
+            record_resolved_conflicts(cache, mut_repo, &commit, self.rewrite_source.as_ref())?;
+        }
         mut_repo.set_predecessors(commit.id().clone(), predecessors);
         if let Some(rewrite_source) = self.rewrite_source {
             if rewrite_source.change_id() == commit.change_id() {
@@ -396,6 +400,29 @@ impl DetachedCommitBuilder {
     }
 }
 
+/// Records the resolutions of the conflicts which the parents or the rewritten
+/// commit had, but the new `commit` doesn't.
+fn record_resolved_conflicts(
+    cache: &ResolutionCache,
+    repo: &dyn Repo,
+    commit: &Commit,
+    rewrite_source: Option<&Commit>,
+) -> BackendResult<()> {
+    let new_tree = commit.tree()?;
+    let parent_tree = commit.parent_tree(repo)?;
+    if parent_tree.has_conflict() {
+        cache.record_resolved_conflicts(&parent_tree, &new_tree, commit.id())?;
+    }
+    // If the commit was rebased, the old conflicts may have been resolved by
+    // the new parents rather than by the user.
+    if let Some(source) = rewrite_source {
+        if source.parent_ids() == commit.parent_ids() && source.has_conflict()? {
+            cache.record_resolved_conflicts(&source.tree()?, &new_tree, commit.id())?;
+        }
+    }
+    Ok(())
+}
+
 fn write_to_store(
     store: &Arc<Store>,
     mut commit: backend::Commit,
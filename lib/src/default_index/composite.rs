@@ -47,6 +47,7 @@ use crate::index::IndexError;
 use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId as _;
 use crate::object_id::PrefixResolution;
+use crate::repo_path::RepoPath;
 use crate::revset::ResolvedExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetEvaluationError;
@@ -91,6 +92,14 @@ pub(super) trait IndexSegment: Send + Sync {
     fn num_parents(&self, local_pos: LocalPosition) -> u32;
 
     fn parent_positions(&self, local_pos: LocalPosition) -> SmallIndexPositionsVec;
+
+    /// Returns paths changed by the commit, or `None` if the changed paths of
+    /// the commit aren't indexed.
+    ///
+    /// The paths are computed from the unresolved diff from the parents, so
+    /// they may include paths whose contents turn out to be unchanged once
+    /// conflicts are resolved.
+    fn changed_paths(&self, local_pos: LocalPosition) -> Option<Vec<&RepoPath>>;
 }
 
 pub(super) type DynIndexSegment = dyn IndexSegment;
@@ -26,6 +26,7 @@ use super::composite::DynIndexSegment;
 use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::object_id::ObjectId as _;
+use crate::repo_path::RepoPath;
 
 /// Global index position.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
@@ -114,6 +115,10 @@ impl<'a> IndexEntry<'a> {
         self.source.parent_positions(self.local_pos)
     }
 
+    pub fn changed_paths(&self) -> Option<Vec<&'a RepoPath>> {
+        self.source.changed_paths(self.local_pos)
+    }
+
     pub fn parents(&self) -> impl ExactSizeIterator<Item = IndexEntry<'a>> + use<'a> {
         let composite = CompositeIndex::new(self.source);
         self.parent_positions()
@@ -71,6 +71,7 @@ mod tests {
     use crate::index::Index as _;
     use crate::object_id::HexPrefix;
     use crate::object_id::PrefixResolution;
+    use crate::repo_path::RepoPathBuf;
     use crate::tests::new_temp_dir;
 
     const TEST_FIELD_LENGTHS: FieldLengths = FieldLengths {
@@ -353,6 +354,125 @@ mod tests {
         assert_eq!(entry_6.generation_number(), 2);
     }
 
+    #[test_case(false, false; "full in memory")]
+    #[test_case(false, true; "full on disk")]
+    #[test_case(true, false; "incremental in memory")]
+    #[test_case(true, true; "incremental on disk")]
+    fn index_changed_paths(incremental: bool, on_disk: bool) {
+        let temp_dir = new_temp_dir();
+        let mut new_change_id = change_id_generator();
+        let mut mutable_segment = MutableIndexSegment::full(TEST_FIELD_LENGTHS);
+        let path = |name| RepoPathBuf::from_internal_string(name).unwrap();
+        // 4 [dir/b, c]
+        // |
+        // 3 []
+        // |
+        // 2 (not indexed)
+        // |
+        // 1 [a, dir/b]
+        // |
+        // 0 []
+        let id_0 = CommitId::from_hex("000000");
+        let id_1 = CommitId::from_hex("111111");
+        let id_2 = CommitId::from_hex("222222");
+        let id_3 = CommitId::from_hex("333333");
+        let id_4 = CommitId::from_hex("444444");
+        mutable_segment.add_commit_data_with_changed_paths(
+            id_0.clone(),
+            new_change_id(),
+            &[],
+            Some(vec![]),
+        );
+        mutable_segment.add_commit_data_with_changed_paths(
+            id_1.clone(),
+            new_change_id(),
+            &[id_0.clone()],
+            Some(vec![path("dir/b"), path("a")]),
+        );
+        mutable_segment.add_commit_data(id_2.clone(), new_change_id(), &[id_1.clone()]);
+
+        // If testing incremental indexing, write the first three commits to one file
+        // now and build the remainder as another segment on top.
+        if incremental {
+            let initial_file = mutable_segment.save_in(temp_dir.path()).unwrap();
+            mutable_segment = MutableIndexSegment::incremental(initial_file);
+        }
+
+        mutable_segment.add_commit_data_with_changed_paths(
+            id_3.clone(),
+            new_change_id(),
+            &[id_2.clone()],
+            Some(vec![]),
+        );
+        mutable_segment.add_commit_data_with_changed_paths(
+            id_4.clone(),
+            new_change_id(),
+            &[id_3.clone()],
+            Some(vec![path("dir/b"), path("c")]),
+        );
+        let index_segment: Box<DynIndexSegment> = if on_disk {
+            let saved_index = mutable_segment.save_in(temp_dir.path()).unwrap();
+            Box::new(Arc::try_unwrap(saved_index).unwrap())
+        } else {
+            Box::new(mutable_segment)
+        };
+        let index = CompositeIndex::new(index_segment.as_ref());
+
+        let changed_paths = |id| {
+            let entry = index.entry_by_id(id).unwrap();
+            let paths = entry.changed_paths()?;
+            Some(
+                paths
+                    .iter()
+                    .map(|path| path.as_internal_file_string())
+                    .collect_vec(),
+            )
+        };
+        assert_eq!(changed_paths(&id_0), Some(vec![]));
+        assert_eq!(changed_paths(&id_1), Some(vec!["dir/b", "a"]));
+        assert_eq!(changed_paths(&id_2), None);
+        assert_eq!(changed_paths(&id_3), Some(vec![]));
+        assert_eq!(changed_paths(&id_4), Some(vec!["dir/b", "c"]));
+    }
+
+    #[test]
+    fn index_changed_paths_squashed() {
+        let temp_dir = new_temp_dir();
+        let mut new_change_id = change_id_generator();
+        let path = |name| RepoPathBuf::from_internal_string(name).unwrap();
+        let id_0 = CommitId::from_hex("000000");
+        let id_1 = CommitId::from_hex("111111");
+        let id_2 = CommitId::from_hex("222222");
+
+        let mut index = DefaultMutableIndex::full(TEST_FIELD_LENGTHS);
+        index.add_commit_data_with_changed_paths(id_0.clone(), new_change_id(), &[], vec![]);
+        index.add_commit_data(id_1.clone(), new_change_id(), &[id_0.clone()]);
+        let initial_file = index.squash_and_save_in(temp_dir.path()).unwrap();
+
+        // The new segment is as large as its parent, so they'll be squashed.
+        let mut index = DefaultMutableIndex::incremental(initial_file);
+        index.add_commit_data_with_changed_paths(
+            id_2.clone(),
+            new_change_id(),
+            &[id_1.clone()],
+            vec![path("a")],
+        );
+        let index_file = index.squash_and_save_in(temp_dir.path()).unwrap();
+        assert!(index_file
+            .as_composite()
+            .ancestor_files_without_local()
+            .next()
+            .is_none());
+
+        let index = index_file.as_composite();
+        let entry_0 = index.entry_by_id(&id_0).unwrap();
+        assert_eq!(entry_0.changed_paths(), Some(vec![]));
+        let entry_1 = index.entry_by_id(&id_1).unwrap();
+        assert_eq!(entry_1.changed_paths(), None);
+        let entry_2 = index.entry_by_id(&id_2).unwrap();
+        assert_eq!(entry_2.changed_paths(), Some(vec![path("a").as_ref()]));
+    }
+
     #[test]
     fn resolve_commit_id_prefix() {
         let temp_dir = new_temp_dir();
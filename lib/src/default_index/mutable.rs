@@ -17,6 +17,7 @@
 use std::any::Any;
 use std::cmp::max;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::io;
 use std::io::Write as _;
@@ -27,7 +28,9 @@ use std::sync::Arc;
 
 use blake2::Blake2b512;
 use digest::Digest as _;
+use futures::StreamExt as _;
 use itertools::Itertools as _;
+use pollster::FutureExt as _;
 use smallvec::smallvec;
 use smallvec::SmallVec;
 use tempfile::NamedTempFile;
@@ -46,6 +49,7 @@ use super::readonly::FieldLengths;
 use super::readonly::ReadonlyIndexSegment;
 use super::readonly::INDEX_SEGMENT_FILE_FORMAT_VERSION;
 use super::readonly::OVERFLOW_FLAG;
+use crate::backend::BackendResult;
 use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::commit::Commit;
@@ -57,12 +61,16 @@ use crate::index::Index;
 use crate::index::IndexError;
 use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
+use crate::matchers::EverythingMatcher;
 use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId;
 use crate::object_id::PrefixResolution;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
 use crate::revset::ResolvedExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetEvaluationError;
+use crate::rewrite;
 use crate::store::Store;
 
 #[derive(Debug)]
@@ -71,6 +79,8 @@ struct MutableGraphEntry {
     change_id: ChangeId,
     generation_number: u32,
     parent_positions: SmallIndexPositionsVec,
+    /// Paths changed by the commit, or `None` if unknown.
+    changed_paths: Option<Vec<RepoPathBuf>>,
 }
 
 pub(super) struct MutableIndexSegment {
@@ -80,6 +90,7 @@ pub(super) struct MutableIndexSegment {
     graph: Vec<MutableGraphEntry>,
     commit_lookup: BTreeMap<CommitId, LocalPosition>,
     change_lookup: BTreeMap<ChangeId, SmallLocalPositionsVec>,
+    index_changed_paths: bool,
 }
 
 impl MutableIndexSegment {
@@ -91,12 +102,18 @@ impl MutableIndexSegment {
             graph: vec![],
             commit_lookup: BTreeMap::new(),
             change_lookup: BTreeMap::new(),
+            index_changed_paths: false,
         }
     }
 
     pub(super) fn incremental(parent_file: Arc<ReadonlyIndexSegment>) -> Self {
         let num_parent_commits = parent_file.as_composite().num_commits();
         let field_lengths = parent_file.field_lengths();
+        // Keep indexing changed paths if the parent index does.
+        let index_changed_paths = num_parent_commits.checked_sub(1).is_some_and(|pos| {
+            let entry = parent_file.as_composite().entry_by_pos(IndexPosition(pos));
+            entry.changed_paths().is_some()
+        });
         Self {
             parent_file: Some(parent_file),
             num_parent_commits,
@@ -104,6 +121,7 @@ impl MutableIndexSegment {
             graph: vec![],
             commit_lookup: BTreeMap::new(),
             change_lookup: BTreeMap::new(),
+            index_changed_paths,
         }
     }
 
@@ -111,11 +129,22 @@ impl MutableIndexSegment {
         CompositeIndex::new(self)
     }
 
+    #[cfg(test)]
     pub(super) fn add_commit_data(
         &mut self,
         commit_id: CommitId,
         change_id: ChangeId,
         parent_ids: &[CommitId],
+    ) {
+        self.add_commit_data_with_changed_paths(commit_id, change_id, parent_ids, None);
+    }
+
+    pub(super) fn add_commit_data_with_changed_paths(
+        &mut self,
+        commit_id: CommitId,
+        change_id: ChangeId,
+        parent_ids: &[CommitId],
+        changed_paths: Option<Vec<RepoPathBuf>>,
     ) {
         if self.as_composite().has_id(&commit_id) {
             return;
@@ -125,6 +154,7 @@ impl MutableIndexSegment {
             change_id,
             generation_number: 0,
             parent_positions: SmallVec::new(),
+            changed_paths,
         };
         for parent_id in parent_ids {
             let parent_entry = self
@@ -153,7 +183,15 @@ impl MutableIndexSegment {
         for pos in other_segment.num_parent_commits()..other.num_commits() {
             let entry = other.entry_by_pos(IndexPosition(pos));
             let parent_ids = entry.parents().map(|entry| entry.commit_id()).collect_vec();
-            self.add_commit_data(entry.commit_id(), entry.change_id(), &parent_ids);
+            let changed_paths = entry
+                .changed_paths()
+                .map(|paths| paths.into_iter().map(ToOwned::to_owned).collect());
+            self.add_commit_data_with_changed_paths(
+                entry.commit_id(),
+                entry.change_id(),
+                &parent_ids,
+                changed_paths,
+            );
         }
     }
 
@@ -211,6 +249,10 @@ impl MutableIndexSegment {
         buf.extend(0_u32.to_le_bytes());
         let change_overflow_offset = buf.len();
         buf.extend(0_u32.to_le_bytes());
+        let changed_path_positions_offset = buf.len();
+        buf.extend(0_u32.to_le_bytes());
+        let changed_paths_offset = buf.len();
+        buf.extend(0_u32.to_le_bytes());
 
         // Positions of change ids in the sorted table
         let change_id_pos_map: HashMap<&ChangeId, u32> = self
@@ -298,6 +340,52 @@ impl MutableIndexSegment {
         for LocalPosition(pos) in change_overflow {
             buf.extend(pos.to_le_bytes());
         }
+
+        // Positions of changed paths in the sorted table
+        let changed_path_pos_map: BTreeMap<&RepoPath, u32> = self
+            .graph
+            .iter()
+            .filter_map(|entry| entry.changed_paths.as_ref())
+            .flatten()
+            .map(AsRef::as_ref)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, path)| (path, u32::try_from(i).unwrap()))
+            .collect();
+
+        let mut changed_path_positions = vec![];
+        for entry in &self.graph {
+            if let Some(paths) = &entry.changed_paths {
+                changed_path_positions
+                    .extend(paths.iter().map(|path| changed_path_pos_map[&**path]));
+                let end_pos = u32::try_from(changed_path_positions.len()).unwrap();
+                assert!(end_pos < OVERFLOW_FLAG);
+                buf.extend(end_pos.to_le_bytes());
+            } else {
+                let end_pos = u32::try_from(changed_path_positions.len()).unwrap();
+                assert!(end_pos < OVERFLOW_FLAG);
+                buf.extend((end_pos | OVERFLOW_FLAG).to_le_bytes());
+            }
+        }
+
+        let num_changed_path_positions = u32::try_from(changed_path_positions.len()).unwrap();
+        buf[changed_path_positions_offset..][..4]
+            .copy_from_slice(&num_changed_path_positions.to_le_bytes());
+        for pos in changed_path_positions {
+            buf.extend(pos.to_le_bytes());
+        }
+
+        let num_changed_paths = u32::try_from(changed_path_pos_map.len()).unwrap();
+        buf[changed_paths_offset..][..4].copy_from_slice(&num_changed_paths.to_le_bytes());
+        let mut name_end = 0_u32;
+        for path in changed_path_pos_map.keys() {
+            name_end += u32::try_from(path.as_internal_file_string().len()).unwrap();
+            buf.extend(name_end.to_le_bytes());
+        }
+        for path in changed_path_pos_map.keys() {
+            buf.extend_from_slice(path.as_internal_file_string().as_bytes());
+        }
     }
 
     /// If the MutableIndex has more than half the commits of its parent
@@ -438,6 +526,11 @@ impl IndexSegment for MutableIndexSegment {
     fn parent_positions(&self, local_pos: LocalPosition) -> SmallIndexPositionsVec {
         self.graph[local_pos.0 as usize].parent_positions.clone()
     }
+
+    fn changed_paths(&self, local_pos: LocalPosition) -> Option<Vec<&RepoPath>> {
+        let paths = self.graph[local_pos.0 as usize].changed_paths.as_ref()?;
+        Some(paths.iter().map(AsRef::as_ref).collect())
+    }
 }
 
 /// In-memory mutable records for the on-disk commit index backend.
@@ -459,6 +552,12 @@ impl DefaultMutableIndex {
         self.0.as_composite().num_commits()
     }
 
+    /// Enables indexing of paths changed by commits to be added.
+    pub(super) fn enable_changed_paths(&mut self) {
+        self.0.index_changed_paths = true;
+    }
+
+    #[cfg(test)]
     pub(super) fn add_commit_data(
         &mut self,
         commit_id: CommitId,
@@ -468,6 +567,22 @@ impl DefaultMutableIndex {
         self.0.add_commit_data(commit_id, change_id, parent_ids);
     }
 
+    #[cfg(test)]
+    pub(super) fn add_commit_data_with_changed_paths(
+        &mut self,
+        commit_id: CommitId,
+        change_id: ChangeId,
+        parent_ids: &[CommitId],
+        changed_paths: Vec<RepoPathBuf>,
+    ) {
+        self.0.add_commit_data_with_changed_paths(
+            commit_id,
+            change_id,
+            parent_ids,
+            Some(changed_paths),
+        );
+    }
+
     pub(super) fn squash_and_save_in(self, dir: &Path) -> io::Result<Arc<ReadonlyIndexSegment>> {
         self.0.maybe_squash_with_ancestors().save_in(dir)
     }
@@ -544,10 +659,28 @@ impl MutableIndex for DefaultMutableIndex {
     }
 
     fn add_commit(&mut self, commit: &Commit) {
-        self.add_commit_data(
+        let changed_paths = if self.0.index_changed_paths && !self.has_id(commit.id()) {
+            match collect_changed_paths(self.as_composite(), commit).block_on() {
+                Ok(paths) => Some(paths),
+                Err(err) => {
+                    // Index the changed paths as unknown (not as empty), so
+                    // the revset engine will diff the trees instead.
+                    tracing::warn!(
+                        ?err,
+                        commit_id = %commit.id(),
+                        "Failed to collect changed paths; indexing them as unknown"
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        self.0.add_commit_data_with_changed_paths(
             commit.id().clone(),
             commit.change_id().clone(),
             commit.parent_ids(),
+            changed_paths,
         );
     }
 
@@ -560,6 +693,27 @@ impl MutableIndex for DefaultMutableIndex {
     }
 }
 
+/// Collects paths that differ between the commit and its parents.
+///
+/// The parent trees are merged without resolving conflicts, so the result may
+/// include paths whose resolved contents are unchanged.
+async fn collect_changed_paths(
+    index: &CompositeIndex,
+    commit: &Commit,
+) -> BackendResult<Vec<RepoPathBuf>> {
+    let parents: Vec<_> = commit.parents().try_collect()?;
+    let from_tree =
+        rewrite::merge_commit_trees_no_resolve_without_repo(commit.store(), &index, &parents)
+            .await?;
+    let to_tree = commit.tree_async().await?;
+    let paths = from_tree
+        .diff_stream(&to_tree, &EverythingMatcher)
+        .map(|entry| entry.path)
+        .collect()
+        .await;
+    Ok(paths)
+}
+
 fn resolve_neighbor_ids<'a, K: Ord, V>(
     lookup_table: &'a BTreeMap<K, V>,
     id: &K,
@@ -22,6 +22,7 @@ use std::fs::File;
 use std::io;
 use std::io::Read;
 use std::path::Path;
+use std::str;
 use std::sync::Arc;
 
 use smallvec::smallvec;
@@ -51,6 +52,7 @@ use crate::index::ReadonlyIndex;
 use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId;
 use crate::object_id::PrefixResolution;
+use crate::repo_path::RepoPath;
 use crate::revset::ResolvedExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetEvaluationError;
@@ -108,7 +110,7 @@ impl ReadonlyIndexLoadError {
 }
 
 /// Current format version of the index segment file.
-pub(super) const INDEX_SEGMENT_FILE_FORMAT_VERSION: u32 = 6;
+pub(super) const INDEX_SEGMENT_FILE_FORMAT_VERSION: u32 = 7;
 
 /// If set, the value is stored in the overflow table.
 pub(super) const OVERFLOW_FLAG: u32 = 0x8000_0000;
@@ -141,6 +143,21 @@ impl ChangeLocalPosition {
     }
 }
 
+/// End position in the changed path positions table, flagged if the changed
+/// paths of the entry aren't indexed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct ChangedPathEndPosition(u32);
+
+impl ChangedPathEndPosition {
+    fn as_indexed(self) -> Option<u32> {
+        (self.0 & OVERFLOW_FLAG == 0).then_some(self.0)
+    }
+
+    fn position(self) -> u32 {
+        self.0 & !OVERFLOW_FLAG
+    }
+}
+
 /// Lengths of fields to be serialized.
 #[derive(Clone, Copy, Debug)]
 pub(super) struct FieldLengths {
@@ -197,6 +214,8 @@ impl CommitGraphEntry<'_> {
 /// u32: number of local change ids
 /// u32: number of overflow parent entries
 /// u32: number of overflow change id positions
+/// u32: number of changed path positions
+/// u32: number of changed paths
 /// for each entry, in some topological order with parents first:
 ///   u32: generation number
 ///   if number of parents <= 2:
@@ -222,6 +241,15 @@ impl CommitGraphEntry<'_> {
 ///   u32: global index position
 /// for each overflow change id entry:
 ///   u32: local position in the graph entries table
+/// for each entry, in the same order as the graph entries:
+///   u32: (< 0x8000_0000) end position in the changed path positions table
+///        (>=0x8000_0000) end position with the flag set, which means the
+///                        changed paths of the entry aren't indexed
+/// for each changed path position:
+///   u32: position in the sorted changed paths table
+/// for each changed path, sorted:
+///   u32: end offset in the changed path names
+/// <bytes>: changed path names, concatenated in the sorted order
 /// ```
 ///
 /// Note that u32 fields are 4-byte aligned so long as the parent file name
@@ -244,6 +272,10 @@ pub(super) struct ReadonlyIndexSegment {
     change_pos_table_base: usize,
     parent_overflow_base: usize,
     change_overflow_base: usize,
+    changed_path_end_base: usize,
+    changed_path_pos_base: usize,
+    changed_path_name_end_base: usize,
+    changed_path_name_base: usize,
     data: Vec<u8>,
 }
 
@@ -325,6 +357,8 @@ impl ReadonlyIndexSegment {
         let num_local_change_ids = read_u32(file)?;
         let num_parent_overflow_entries = read_u32(file)?;
         let num_change_overflow_entries = read_u32(file)?;
+        let num_changed_path_positions = read_u32(file)?;
+        let num_changed_paths = read_u32(file)?;
         let mut data = vec![];
         file.read_to_end(&mut data).map_err(from_io_err)?;
 
@@ -335,6 +369,9 @@ impl ReadonlyIndexSegment {
         let change_pos_table_size = (num_local_change_ids as usize) * 4;
         let parent_overflow_size = (num_parent_overflow_entries as usize) * 4;
         let change_overflow_size = (num_change_overflow_entries as usize) * 4;
+        let changed_path_end_size = (num_local_commits as usize) * 4;
+        let changed_path_pos_size = (num_changed_path_positions as usize) * 4;
+        let changed_path_name_end_size = (num_changed_paths as usize) * 4;
 
         let graph_base = 0;
         let commit_lookup_base = graph_base + graph_size;
@@ -342,7 +379,20 @@ impl ReadonlyIndexSegment {
         let change_pos_table_base = change_id_table_base + change_id_table_size;
         let parent_overflow_base = change_pos_table_base + change_pos_table_size;
         let change_overflow_base = parent_overflow_base + parent_overflow_size;
-        let expected_size = change_overflow_base + change_overflow_size;
+        let changed_path_end_base = change_overflow_base + change_overflow_size;
+        let changed_path_pos_base = changed_path_end_base + changed_path_end_size;
+        let changed_path_name_end_base = changed_path_pos_base + changed_path_pos_size;
+        let changed_path_name_base = changed_path_name_end_base + changed_path_name_end_size;
+        // The names table is variable-length. Its size is the last end offset.
+        let changed_path_name_size = if num_changed_paths > 0 {
+            data.get(changed_path_name_base - 4..changed_path_name_base)
+                .map_or(0, |bytes| {
+                    u32::from_le_bytes(bytes.try_into().unwrap()) as usize
+                })
+        } else {
+            0
+        };
+        let expected_size = changed_path_name_base + changed_path_name_size;
 
         if data.len() != expected_size {
             return Err(ReadonlyIndexLoadError::invalid_data(
@@ -364,6 +414,10 @@ impl ReadonlyIndexSegment {
             change_pos_table_base,
             parent_overflow_base,
             change_overflow_base,
+            changed_path_end_base,
+            changed_path_pos_base,
+            changed_path_name_end_base,
+            changed_path_name_base,
             data,
         }))
     }
@@ -427,13 +481,41 @@ impl ReadonlyIndexSegment {
         &self,
         overflow_pos: u32,
     ) -> impl Iterator<Item = LocalPosition> + use<'_> {
-        let table = &self.data[self.change_overflow_base..];
+        let table = &self.data[self.change_overflow_base..self.changed_path_end_base];
         let offset = (overflow_pos as usize) * 4;
         table[offset..]
             .chunks_exact(4)
             .map(|chunk| LocalPosition(u32::from_le_bytes(chunk.try_into().unwrap())))
     }
 
+    fn changed_path_end(&self, local_pos: LocalPosition) -> ChangedPathEndPosition {
+        let table = &self.data[self.changed_path_end_base..self.changed_path_pos_base];
+        let offset = (local_pos.0 as usize) * 4;
+        ChangedPathEndPosition(u32::from_le_bytes(table[offset..][..4].try_into().unwrap()))
+    }
+
+    fn changed_path_pos(&self, pos: u32) -> u32 {
+        let table = &self.data[self.changed_path_pos_base..self.changed_path_name_end_base];
+        let offset = (pos as usize) * 4;
+        u32::from_le_bytes(table[offset..][..4].try_into().unwrap())
+    }
+
+    fn changed_path_name_end(&self, path_pos: u32) -> usize {
+        let table = &self.data[self.changed_path_name_end_base..self.changed_path_name_base];
+        let offset = (path_pos as usize) * 4;
+        u32::from_le_bytes(table[offset..][..4].try_into().unwrap()) as usize
+    }
+
+    fn changed_path(&self, path_pos: u32) -> &RepoPath {
+        let start = path_pos
+            .checked_sub(1)
+            .map_or(0, |prev_pos| self.changed_path_name_end(prev_pos));
+        let end = self.changed_path_name_end(path_pos);
+        let names = &self.data[self.changed_path_name_base..];
+        let name = str::from_utf8(&names[start..end]).expect("changed path should be UTF-8");
+        RepoPath::from_internal_string(name).expect("changed path should be valid")
+    }
+
     /// Binary searches commit id by `prefix`. Returns the lookup position.
     fn commit_id_byte_prefix_to_lookup_pos(&self, prefix: &[u8]) -> PositionLookupResult {
         binary_search_pos_by(self.num_local_commits, |pos| {
@@ -578,6 +660,17 @@ impl IndexSegment for ReadonlyIndexSegment {
             self.overflow_parents(overflow_pos, num_parents)
         }
     }
+
+    fn changed_paths(&self, local_pos: LocalPosition) -> Option<Vec<&RepoPath>> {
+        let end = self.changed_path_end(local_pos).as_indexed()?;
+        let start = local_pos.0.checked_sub(1).map_or(0, |prev_pos| {
+            self.changed_path_end(LocalPosition(prev_pos)).position()
+        });
+        let paths = (start..end)
+            .map(|pos| self.changed_path(self.changed_path_pos(pos)))
+            .collect();
+        Some(paths)
+    }
 }
 
 /// Commit index backend which stores data on local disk.
@@ -609,6 +702,13 @@ impl DefaultReadonlyIndex {
         Some(entry.generation_number())
     }
 
+    /// Looks up paths changed by the specified commit. Returns `None` if the
+    /// changed paths of the commit aren't indexed.
+    pub fn changed_paths(&self, commit_id: &CommitId) -> Option<Vec<&RepoPath>> {
+        let entry = self.0.as_composite().entry_by_id(commit_id)?;
+        entry.changed_paths()
+    }
+
     #[doc(hidden)] // for tests
     pub fn evaluate_revset_impl(
         &self,
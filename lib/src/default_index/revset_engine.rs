@@ -34,6 +34,7 @@ use pollster::FutureExt as _;
 
 use super::composite::AsCompositeIndex;
 use super::composite::CompositeIndex;
+use super::entry::IndexEntry;
 use super::entry::IndexPosition;
 use super::rev_walk::EagerRevWalk;
 use super::rev_walk::PeekableRevWalk;
@@ -50,11 +51,14 @@ use crate::conflicts::MaterializedTreeValue;
 use crate::diff::Diff;
 use crate::diff::DiffHunkKind;
 use crate::files;
+use crate::fileset::FilesetExpression;
 use crate::graph::GraphNode;
+use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
 use crate::matchers::Visit;
 use crate::merge::Merge;
 use crate::merged_tree::resolve_file_values;
+use crate::merged_tree::MergedTree;
 use crate::object_id::ObjectId as _;
 use crate::repo_path::RepoPath;
 use crate::revset::ResolvedExpression;
@@ -1257,28 +1261,28 @@ fn build_predicate_fn(
             })
         }
         RevsetFilterPredicate::File(expr) => {
-            let matcher: Rc<dyn Matcher> = expr.to_matcher().into();
+            let files = DiffFilesMatcher::new(expr);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_have_diff_from_parent(&entry, files.candidates()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
-                Ok(has_diff_from_parent(&store, index, &commit, &*matcher).block_on()?)
+                Ok(has_diff_from_parent(&store, index, &commit, &files).block_on()?)
             })
         }
         RevsetFilterPredicate::DiffContains { text, files } => {
             let text_pattern = text.clone();
-            let files_matcher: Rc<dyn Matcher> = files.to_matcher().into();
+            let files = DiffFilesMatcher::new(files);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_have_diff_from_parent(&entry, files.candidates()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(
-                    matches_diff_from_parent(
-                        &store,
-                        index,
-                        &commit,
-                        &text_pattern,
-                        &*files_matcher,
-                    )
-                    .block_on()?,
+                    matches_diff_from_parent(&store, index, &commit, &text_pattern, &files)
+                        .block_on()?,
                 )
             })
         }
@@ -1303,17 +1307,73 @@ fn build_predicate_fn(
     }
 }
 
+/// Matcher of the files in a diff.
+///
+/// File predicates are evaluated against the trees of each diff.
+#[derive(Clone)]
+enum DiffFilesMatcher {
+    Paths(Rc<dyn Matcher>),
+    WithPredicates(FilesetExpression),
+}
+
+impl DiffFilesMatcher {
+    fn new(expression: &FilesetExpression) -> Self {
+        match expression.to_matcher() {
+            Ok(matcher) => DiffFilesMatcher::Paths(matcher.into()),
+            Err(_) => DiffFilesMatcher::WithPredicates(expression.clone()),
+        }
+    }
+
+    /// Matcher which matches at least all files the diff matcher may match.
+    fn candidates(&self) -> &dyn Matcher {
+        match self {
+            DiffFilesMatcher::Paths(matcher) => matcher.as_ref(),
+            DiffFilesMatcher::WithPredicates(_) => &EverythingMatcher,
+        }
+    }
+
+    async fn to_diff_matcher(
+        &self,
+        from_tree: &MergedTree,
+        to_tree: &MergedTree,
+    ) -> BackendResult<Rc<dyn Matcher>> {
+        match self {
+            DiffFilesMatcher::Paths(matcher) => Ok(matcher.clone()),
+            DiffFilesMatcher::WithPredicates(expression) => {
+                let matcher = expression
+                    .to_matcher_with_tree_diff(from_tree, to_tree)
+                    .await?;
+                Ok(matcher.into())
+            }
+        }
+    }
+}
+
+/// Returns false if the indexed changed paths prove that the commit has no
+/// diff matching the `matcher`.
+fn may_have_diff_from_parent(entry: &IndexEntry, matcher: &dyn Matcher) -> bool {
+    // Tree ids are cheaper to compare than the changed paths
+    if matcher.visit(RepoPath::root()) == Visit::AllRecursively {
+        return true;
+    }
+    entry
+        .changed_paths()
+        .is_none_or(|paths| paths.iter().any(|path| matcher.matches(path)))
+}
+
 async fn has_diff_from_parent(
     store: &Arc<Store>,
     index: &CompositeIndex,
     commit: &Commit,
-    matcher: &dyn Matcher,
+    files: &DiffFilesMatcher,
 ) -> BackendResult<bool> {
     let parents: Vec<_> = commit.parents().try_collect()?;
     if let [parent] = parents.as_slice() {
         // Fast path: no need to load the root tree
         let unchanged = commit.tree_id() == parent.tree_id();
-        if matcher.visit(RepoPath::root()) == Visit::AllRecursively {
+        if matches!(files, DiffFilesMatcher::Paths(matcher)
+            if matcher.visit(RepoPath::root()) == Visit::AllRecursively)
+        {
             return Ok(!unchanged);
         } else if unchanged {
             return Ok(false);
@@ -1324,8 +1384,9 @@ async fn has_diff_from_parent(
     let from_tree =
         rewrite::merge_commit_trees_no_resolve_without_repo(store, &index, &parents).await?;
     let to_tree = commit.tree_async().await?;
+    let matcher = files.to_diff_matcher(&from_tree, &to_tree).await?;
     // TODO: handle copy tracking
-    let mut tree_diff = from_tree.diff_stream(&to_tree, matcher);
+    let mut tree_diff = from_tree.diff_stream(&to_tree, &*matcher);
     // TODO: Resolve values concurrently
     while let Some(entry) = tree_diff.next().await {
         let (from_value, to_value) = entry.values?;
@@ -1343,15 +1404,16 @@ async fn matches_diff_from_parent(
     index: &CompositeIndex,
     commit: &Commit,
     text_pattern: &StringPattern,
-    files_matcher: &dyn Matcher,
+    files: &DiffFilesMatcher,
 ) -> BackendResult<bool> {
     let parents: Vec<_> = commit.parents().try_collect()?;
     // Conflict resolution is expensive, try that only for matched files.
     let from_tree =
         rewrite::merge_commit_trees_no_resolve_without_repo(store, &index, &parents).await?;
     let to_tree = commit.tree_async().await?;
+    let files_matcher = files.to_diff_matcher(&from_tree, &to_tree).await?;
     // TODO: handle copy tracking
-    let mut tree_diff = from_tree.diff_stream(&to_tree, files_matcher);
+    let mut tree_diff = from_tree.diff_stream(&to_tree, &*files_matcher);
     // TODO: Resolve values concurrently
     while let Some(entry) = tree_diff.next().await {
         let (left_value, right_value) = entry.values?;
@@ -95,6 +95,7 @@ pub enum DefaultIndexStoreError {
 #[derive(Debug)]
 pub struct DefaultIndexStore {
     dir: PathBuf,
+    index_changed_paths: bool,
 }
 
 impl DefaultIndexStore {
@@ -105,6 +106,7 @@ impl DefaultIndexStore {
     pub fn init(dir: &Path) -> Result<Self, DefaultIndexStoreInitError> {
         let store = DefaultIndexStore {
             dir: dir.to_owned(),
+            index_changed_paths: false,
         };
         store.ensure_base_dirs()?;
         Ok(store)
@@ -113,9 +115,20 @@ impl DefaultIndexStore {
     pub fn load(dir: &Path) -> DefaultIndexStore {
         DefaultIndexStore {
             dir: dir.to_owned(),
+            index_changed_paths: false,
         }
     }
 
+    /// Sets whether to record paths changed by each commit when building
+    /// index.
+    ///
+    /// Once enabled, changed paths will continue to be indexed for new
+    /// commits. Use `reinit()` to rebuild index without changed paths.
+    pub fn with_changed_paths(mut self, enabled: bool) -> Self {
+        self.index_changed_paths = enabled;
+        self
+    }
+
     pub fn reinit(&self) -> Result<(), DefaultIndexStoreInitError> {
         // Create base directories in case the store was initialized by old jj.
         self.ensure_base_dirs()?;
@@ -237,6 +250,9 @@ impl DefaultIndexStore {
                 mutable_index = DefaultMutableIndex::incremental(parent_file);
             }
         }
+        if self.index_changed_paths {
+            mutable_index.enable_changed_paths();
+        }
 
         tracing::info!(
             ?maybe_parent_file,
@@ -21,7 +21,6 @@ use crate::submodule_store::SubmoduleStore;
 
 #[derive(Debug)]
 pub struct DefaultSubmoduleStore {
-    #[expect(dead_code)]
     path: PathBuf,
 }
 
@@ -48,4 +47,8 @@ impl SubmoduleStore for DefaultSubmoduleStore {
     fn name(&self) -> &str {
         Self::name()
     }
+
+    fn git_dir(&self, name: &str) -> PathBuf {
+        self.path.join("git").join(name)
+    }
 }
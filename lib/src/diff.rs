@@ -643,6 +643,28 @@ impl<'input> Diff<'input> {
         )
     }
 
+    /// Compares `inputs` split into the given `token_ranges`, one list of
+    /// ranges per input.
+    pub(crate) fn for_token_ranges<T: AsRef<[u8]> + ?Sized + 'input>(
+        inputs: impl IntoIterator<Item = &'input T>,
+        token_ranges: &[Vec<Range<usize>>],
+        compare: impl CompareBytes,
+    ) -> Self {
+        let mut inputs = inputs.into_iter().map(BStr::new);
+        let base_input = inputs.next().expect("inputs must not be empty");
+        let other_inputs: SmallVec<[&BStr; 1]> = inputs.collect();
+        let (base_token_ranges, other_token_ranges) = token_ranges
+            .split_first()
+            .expect("token ranges must not be empty");
+        Self::with_inputs_and_token_ranges(
+            base_input,
+            other_inputs,
+            base_token_ranges,
+            other_token_ranges,
+            compare,
+        )
+    }
+
     fn with_inputs_and_token_ranges(
         base_input: &'input BStr,
         other_inputs: SmallVec<[&'input BStr; 1]>,
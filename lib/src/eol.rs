@@ -19,7 +19,11 @@ use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt as _;
 
 use crate::config::ConfigGetError;
+use crate::gitattributes::GitAttributeState;
+use crate::gitattributes::GitAttributes;
+use crate::gitattributes::GitAttributesError;
 use crate::local_working_copy::TreeStateSettings;
+use crate::repo_path::RepoPath;
 use crate::settings::UserSettings;
 
 pub(crate) fn create_target_eol_strategy(
@@ -37,6 +41,59 @@ fn is_binary(bytes: &[u8]) -> bool {
     bytes.contains(&b'\0')
 }
 
+/// The `text` attribute of a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum TextAttribute {
+    /// `text`: the file is always converted.
+    Set,
+    /// `-text` or `binary`: the file is never converted.
+    Unset,
+    /// `text=auto`: the file is converted if it doesn't look like binary.
+    Auto,
+    /// The `working-copy.eol-conversion` setting decides.
+    #[default]
+    Unspecified,
+}
+
+/// EOL conversion attributes of a file, resolved from `.gitattributes`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EolAttributes {
+    text: TextAttribute,
+    eol: Option<TargetEol>,
+}
+
+impl EolAttributes {
+    /// Looks up the `text` and `eol` attributes of the `path`.
+    pub(crate) async fn resolve(
+        git_attributes: &GitAttributes,
+        path: &RepoPath,
+    ) -> Result<Self, GitAttributesError> {
+        let text = match git_attributes.get(path, "text").await? {
+            GitAttributeState::Set => TextAttribute::Set,
+            GitAttributeState::Unset => TextAttribute::Unset,
+            GitAttributeState::Value(value) if value == "auto" => TextAttribute::Auto,
+            GitAttributeState::Value(_) | GitAttributeState::Unspecified => {
+                TextAttribute::Unspecified
+            }
+        };
+        let eol = match git_attributes.get(path, "eol").await?.as_value() {
+            Some("lf") => Some(TargetEol::Lf),
+            Some("crlf") => Some(TargetEol::Crlf),
+            _ => None,
+        };
+        Ok(EolAttributes { text, eol })
+    }
+
+    fn text(&self) -> TextAttribute {
+        // Like Git, setting the `eol` attribute implies `text` unless the
+        // `text` attribute is specified.
+        match (self.text, self.eol) {
+            (TextAttribute::Unspecified, Some(_)) => TextAttribute::Set,
+            (text, _) => text,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct TargetEolStrategy {
     eol_conversion_mode: EolConversionMode,
@@ -48,50 +105,66 @@ impl TargetEolStrategy {
 
     pub(crate) async fn convert_eol_for_snapshot<'a>(
         &self,
-        mut contents: impl AsyncRead + Send + Unpin + 'a,
+        attributes: &EolAttributes,
+        contents: impl AsyncRead + Send + Unpin + 'a,
     ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
-        match self.eol_conversion_mode {
-            EolConversionMode::None => Ok(Box::new(contents)),
-            EolConversionMode::Input | EolConversionMode::InputOutput => {
-                let mut peek = vec![];
-                (&mut contents)
-                    .take(Self::PROBE_LIMIT)
-                    .read_to_end(&mut peek)
-                    .await?;
-                let target_eol = if is_binary(&peek) {
-                    TargetEol::PassThrough
-                } else {
-                    TargetEol::Lf
-                };
-                let peek = Cursor::new(peek);
-                let contents = peek.chain(contents);
-                convert_eol(contents, target_eol).await
-            }
-        }
+        let probe = match attributes.text() {
+            TextAttribute::Set => false,
+            TextAttribute::Unset => return Ok(Box::new(contents)),
+            TextAttribute::Auto => true,
+            TextAttribute::Unspecified => match self.eol_conversion_mode {
+                EolConversionMode::None => return Ok(Box::new(contents)),
+                EolConversionMode::Input | EolConversionMode::InputOutput => true,
+            },
+        };
+        Self::convert_eol_unless_binary(contents, TargetEol::Lf, probe).await
     }
 
     pub(crate) async fn convert_eol_for_update<'a>(
         &self,
+        attributes: &EolAttributes,
+        contents: impl AsyncRead + Send + Unpin + 'a,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
+        let default_eol = match self.eol_conversion_mode {
+            EolConversionMode::None | EolConversionMode::Input => TargetEol::PassThrough,
+            EolConversionMode::InputOutput => TargetEol::Crlf,
+        };
+        // The contents in the store are expected to use LF already.
+        let target_eol = match attributes.eol.unwrap_or(default_eol) {
+            TargetEol::Lf | TargetEol::PassThrough => return Ok(Box::new(contents)),
+            TargetEol::Crlf => TargetEol::Crlf,
+        };
+        let probe = match attributes.text() {
+            TextAttribute::Set => false,
+            TextAttribute::Unset => return Ok(Box::new(contents)),
+            TextAttribute::Auto | TextAttribute::Unspecified => true,
+        };
+        Self::convert_eol_unless_binary(contents, target_eol, probe).await
+    }
+
+    /// Converts the EOL of the `contents`. If `probe` is true, the contents
+    /// are passed through if they look like binary.
+    async fn convert_eol_unless_binary<'a>(
         mut contents: impl AsyncRead + Send + Unpin + 'a,
+        target_eol: TargetEol,
+        probe: bool,
     ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
-        match self.eol_conversion_mode {
-            EolConversionMode::None | EolConversionMode::Input => Ok(Box::new(contents)),
-            EolConversionMode::InputOutput => {
-                let mut peek = vec![];
-                (&mut contents)
-                    .take(Self::PROBE_LIMIT)
-                    .read_to_end(&mut peek)
-                    .await?;
-                let target_eol = if is_binary(&peek) {
-                    TargetEol::PassThrough
-                } else {
-                    TargetEol::Crlf
-                };
-                let peek = Cursor::new(peek);
-                let contents = peek.chain(contents);
-                convert_eol(contents, target_eol).await
-            }
+        if !probe {
+            return convert_eol(contents, target_eol).await;
         }
+        let mut peek = vec![];
+        (&mut contents)
+            .take(Self::PROBE_LIMIT)
+            .read_to_end(&mut peek)
+            .await?;
+        let target_eol = if is_binary(&peek) {
+            TargetEol::PassThrough
+        } else {
+            target_eol
+        };
+        let peek = Cursor::new(peek);
+        let contents = peek.chain(contents);
+        convert_eol(contents, target_eol).await
     }
 }
 
@@ -273,7 +346,7 @@ mod tests {
     ) {
         let mut actual_output = vec![];
         strategy
-            .convert_eol_for_snapshot(contents)
+            .convert_eol_for_snapshot(&EolAttributes::default(), contents)
             .await
             .unwrap()
             .read_to_end(&mut actual_output)
@@ -305,7 +378,81 @@ mod tests {
     ) {
         let mut actual_output = vec![];
         strategy
-            .convert_eol_for_update(contents)
+            .convert_eol_for_update(&EolAttributes::default(), contents)
+            .await
+            .unwrap()
+            .read_to_end(&mut actual_output)
+            .await
+            .unwrap();
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[tokio::main(flavor = "current_thread")]
+    #[test_case(EolConversionMode::Input, TextAttribute::Unset, None,
+        b"\r\n", b"\r\n"; "-text overrides settings")]
+    #[test_case(EolConversionMode::None, TextAttribute::Set, None,
+        b"\r\n", b"\n"; "text overrides settings")]
+    #[test_case(EolConversionMode::None, TextAttribute::Set, None,
+        b"\0\r\n", b"\0\n"; "text converts binary input")]
+    #[test_case(EolConversionMode::None, TextAttribute::Auto, None,
+        b"\r\n", b"\n"; "text=auto text input")]
+    #[test_case(EolConversionMode::None, TextAttribute::Auto, None,
+        b"\0\r\n", b"\0\r\n"; "text=auto binary input")]
+    #[test_case(EolConversionMode::None, TextAttribute::Unspecified, Some(TargetEol::Crlf),
+        b"\r\n", b"\n"; "eol=crlf implies text")]
+    #[test_case(EolConversionMode::Input, TextAttribute::Unspecified, None,
+        b"\r\n", b"\n"; "unspecified uses settings")]
+    async fn test_eol_attributes_convert_eol_for_snapshot(
+        eol_conversion_mode: EolConversionMode,
+        text: TextAttribute,
+        eol: Option<TargetEol>,
+        contents: &[u8],
+        expected_output: &[u8],
+    ) {
+        let strategy = TargetEolStrategy {
+            eol_conversion_mode,
+        };
+        let attributes = EolAttributes { text, eol };
+        let mut actual_output = vec![];
+        strategy
+            .convert_eol_for_snapshot(&attributes, contents)
+            .await
+            .unwrap()
+            .read_to_end(&mut actual_output)
+            .await
+            .unwrap();
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[tokio::main(flavor = "current_thread")]
+    #[test_case(EolConversionMode::InputOutput, TextAttribute::Unset, None,
+        b"\n", b"\n"; "-text overrides settings")]
+    #[test_case(EolConversionMode::None, TextAttribute::Set, None,
+        b"\n", b"\n"; "text without eol uses settings")]
+    #[test_case(EolConversionMode::InputOutput, TextAttribute::Set, None,
+        b"\n", b"\r\n"; "text with input output settings")]
+    #[test_case(EolConversionMode::None, TextAttribute::Unspecified, Some(TargetEol::Crlf),
+        b"\n", b"\r\n"; "eol=crlf overrides settings")]
+    #[test_case(EolConversionMode::InputOutput, TextAttribute::Unspecified, Some(TargetEol::Lf),
+        b"\n", b"\n"; "eol=lf overrides settings")]
+    #[test_case(EolConversionMode::None, TextAttribute::Set, Some(TargetEol::Crlf),
+        b"\0\n", b"\0\r\n"; "text eol=crlf converts binary input")]
+    #[test_case(EolConversionMode::None, TextAttribute::Auto, Some(TargetEol::Crlf),
+        b"\0\n", b"\0\n"; "text=auto eol=crlf binary input")]
+    async fn test_eol_attributes_convert_eol_for_update(
+        eol_conversion_mode: EolConversionMode,
+        text: TextAttribute,
+        eol: Option<TargetEol>,
+        contents: &[u8],
+        expected_output: &[u8],
+    ) {
+        let strategy = TargetEolStrategy {
+            eol_conversion_mode,
+        };
+        let attributes = EolAttributes { text, eol };
+        let mut actual_output = vec![];
+        strategy
+            .convert_eol_for_update(&attributes, contents)
             .await
             .unwrap()
             .read_to_end(&mut actual_output)
@@ -18,15 +18,21 @@ use std::borrow::Borrow;
 use std::collections::VecDeque;
 use std::iter;
 use std::mem;
+use std::sync::Arc;
 
 use bstr::BStr;
 use bstr::BString;
 use itertools::Itertools as _;
 
+use crate::config::ConfigGetError;
 use crate::diff::Diff;
 use crate::diff::DiffHunk;
 use crate::diff::DiffHunkKind;
 use crate::merge::Merge;
+use crate::repo_path::RepoPath;
+use crate::resolution_cache::ResolutionCache;
+use crate::settings::UserSettings;
+use crate::structural_merge::StructuralMerger;
 
 /// A diff line which may contain small hunks originating from both sides.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -269,6 +275,43 @@ pub enum MergeResult {
     Conflict(Vec<Merge<BString>>),
 }
 
+/// Granularity of the hunks that conflict when merging file contents.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeGranularity {
+    /// Changes to the same or adjacent lines conflict.
+    #[default]
+    Line,
+    /// Changes to the same or adjacent lines are merged word by word.
+    Word,
+}
+
+/// Options for merging file contents.
+#[derive(Clone, Debug, Default)]
+pub struct MergeOptions {
+    /// Granularity of the hunks that conflict.
+    pub granularity: MergeGranularity,
+    /// Syntax-aware merger to try if the contents can't be merged line by
+    /// line.
+    pub structural_merger: Option<Arc<StructuralMerger>>,
+    /// Recorded conflict resolutions to reuse if the contents can't be merged.
+    pub resolution_cache: Option<Arc<ResolutionCache>>,
+}
+
+impl MergeOptions {
+    /// Loads merge options from `settings`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let structural_merger = settings
+            .get_bool("merge.structural")?
+            .then(|| Arc::new(StructuralMerger::with_builtin_languages()));
+        Ok(MergeOptions {
+            granularity: settings.get("merge.granularity")?,
+            structural_merger,
+            resolution_cache: None,
+        })
+    }
+}
+
 /// Splits `inputs` into hunks, resolves trivial merge conflicts for each.
 ///
 /// Returns either fully-resolved content or list of partially-resolved hunks.
@@ -293,6 +336,51 @@ pub fn try_merge<T: AsRef<[u8]>>(inputs: &Merge<T>) -> Option<BString> {
     merge_inner(inputs)
 }
 
+/// Attempts to merge the contents of the file at `path` according to the
+/// `options`.
+///
+/// The `inputs` are merged line by line first. If that fails, the structural
+/// merger is tried, and then the word-level merge if enabled.
+pub fn try_merge_file<T: AsRef<[u8]>>(
+    path: &RepoPath,
+    inputs: &Merge<T>,
+    options: &MergeOptions,
+) -> Option<BString> {
+    if let Some(content) = try_merge(inputs) {
+        return Some(content);
+    }
+    if let Some(merger) = &options.structural_merger {
+        if let Some(content) = merger.try_merge(path, inputs) {
+            return Some(content);
+        }
+    }
+    match options.granularity {
+        MergeGranularity::Line => None,
+        MergeGranularity::Word => try_merge_by_word(inputs),
+    }
+}
+
+/// Splits `inputs` into line hunks, and attempts to resolve the conflicting
+/// hunks word by word.
+///
+/// If all hunks can be merged successfully, returns the merged content.
+pub fn try_merge_by_word<T: AsRef<[u8]>>(inputs: &Merge<T>) -> Option<BString> {
+    let num_diffs = inputs.removes().len();
+    let diff = Diff::by_line(inputs.removes().chain(inputs.adds()));
+    let mut merged = BString::default();
+    for hunk in resolve_diff_hunks(&diff, num_diffs) {
+        match hunk.into_resolved() {
+            Ok(content) => merged.extend_from_slice(content),
+            Err(conflict) => {
+                let diff = Diff::by_word(conflict.removes().chain(conflict.adds()));
+                let content = collect_resolved(resolve_diff_hunks(&diff, num_diffs))?;
+                merged.extend_from_slice(&content);
+            }
+        }
+    }
+    Some(merged)
+}
+
 fn merge_inner<'input, T: AsRef<[u8]>, B: FromMergeHunks<'input>>(inputs: &'input Merge<T>) -> B {
     // TODO: Using the first remove as base (first in the inputs) is how it's
     // usually done for 3-way conflicts. Are there better heuristics when there are
@@ -987,4 +1075,41 @@ mod tests {
         "};
         assert_eq!(merge(&conflict([left, base, right])), resolved(merged));
     }
+
+    #[test]
+    fn test_try_merge_by_word() {
+        // Two sides changed different words of the same line
+        let inputs = conflict([b"a b2 c\nd\n", b"a b c\nd\n", b"a b c2\nd\n"]);
+        assert_eq!(try_merge(&inputs), None);
+        assert_eq!(try_merge_by_word(&inputs), Some(hunk(b"a b2 c2\nd\n")));
+
+        // Two sides changed adjacent lines
+        let inputs = conflict([b"a2\nb\n", b"a\nb\n", b"a\nb2\n"]);
+        assert_eq!(try_merge(&inputs), None);
+        assert_eq!(try_merge_by_word(&inputs), Some(hunk(b"a2\nb2\n")));
+
+        // Two sides changed the same word
+        let inputs = conflict([b"a b1 c\n", b"a b c\n", b"a b2 c\n"]);
+        assert_eq!(try_merge_by_word(&inputs), None);
+
+        // One hunk can be merged by word, the other can't
+        let inputs = conflict([b"a1 x\nb\nc1\n", b"a x\nb\nc\n", b"a x1\nb\nc2\n"]);
+        assert_eq!(try_merge_by_word(&inputs), None);
+    }
+
+    #[test]
+    fn test_try_merge_file() {
+        let path = RepoPath::from_internal_string("file").unwrap();
+        let inputs = conflict([b"a b2 c\n", b"a b c\n", b"a b c2\n"]);
+        let line_options = MergeOptions::default();
+        assert_eq!(try_merge_file(path, &inputs, &line_options), None);
+        let word_options = MergeOptions {
+            granularity: MergeGranularity::Word,
+            ..MergeOptions::default()
+        };
+        assert_eq!(
+            try_merge_file(path, &inputs, &word_options),
+            Some(hunk(b"a b2 c2\n"))
+        );
+    }
 }
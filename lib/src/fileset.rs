@@ -15,14 +15,19 @@
 //! Functional language for selecting a set of paths.
 
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::iter;
 use std::path;
 use std::slice;
 use std::sync::LazyLock;
 
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::backend::BackendResult;
+use crate::backend::TreeValue;
 use crate::dsl_util::collect_similar;
 use crate::fileset_parser;
 use crate::fileset_parser::BinaryOp;
@@ -43,11 +48,14 @@ use crate::matchers::Matcher;
 use crate::matchers::NothingMatcher;
 use crate::matchers::PrefixMatcher;
 use crate::matchers::UnionMatcher;
+use crate::merge::MergedTreeValue;
+use crate::merged_tree::MergedTree;
 use crate::repo_path::RelativePathParseError;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathUiConverter;
 use crate::repo_path::UiPathParseError;
+use crate::store::Store;
 
 /// Error occurred during file pattern parsing.
 #[derive(Debug, Error)]
@@ -80,6 +88,12 @@ pub enum FilePattern {
         /// Glob pattern relative to `dir`.
         pattern: glob::Pattern,
     },
+    /// Matches file (or exact) path with case-insensitive glob pattern.
+    FileGlobI {
+        /// Glob pattern relative to the workspace root. The directory part is
+        /// also matched case-insensitively.
+        pattern: glob::Pattern,
+    },
     // TODO: add more patterns:
     // - FilesInPath: files in directory, non-recursively?
     // - NameGlob or SuffixGlob: file name with glob?
@@ -105,14 +119,17 @@ impl FilePattern {
         // * string pattern syntax (+ case sensitivity?)
         //   * path: literal path (default) (default anchor: prefix)
         //   * glob: glob pattern (default anchor: file)
+        //   * glob-i: case-insensitive glob pattern (default anchor: file)
         //   * regex?
         match kind {
             "cwd" => Self::cwd_prefix_path(path_converter, input),
             "cwd-file" | "file" => Self::cwd_file_path(path_converter, input),
             "cwd-glob" | "glob" => Self::cwd_file_glob(path_converter, input),
+            "cwd-glob-i" | "glob-i" => Self::cwd_file_glob_i(path_converter, input),
             "root" => Self::root_prefix_path(input),
             "root-file" => Self::root_file_path(input),
             "root-glob" => Self::root_file_glob(input),
+            "root-glob-i" => Self::root_file_glob_i(input),
             _ => Err(FilePatternParseError::InvalidKind(kind.to_owned())),
         }
     }
@@ -145,6 +162,16 @@ impl FilePattern {
         Self::file_glob_at(dir, pattern)
     }
 
+    /// Pattern that matches cwd-relative file path glob case-insensitively.
+    pub fn cwd_file_glob_i(
+        path_converter: &RepoPathUiConverter,
+        input: impl AsRef<str>,
+    ) -> Result<Self, FilePatternParseError> {
+        let (dir, pattern) = split_glob_path(input.as_ref());
+        let dir = path_converter.parse_file_path(dir)?;
+        Self::file_glob_i_at(dir, pattern)
+    }
+
     /// Pattern that matches workspace-relative file (or exact) path.
     pub fn root_file_path(input: impl AsRef<str>) -> Result<Self, FilePatternParseError> {
         // TODO: Let caller pass in converter for root-relative paths too
@@ -165,6 +192,29 @@ impl FilePattern {
         Self::file_glob_at(dir, pattern)
     }
 
+    /// Pattern that matches workspace-relative file path glob
+    /// case-insensitively.
+    pub fn root_file_glob_i(input: impl AsRef<str>) -> Result<Self, FilePatternParseError> {
+        let (dir, pattern) = split_glob_path(input.as_ref());
+        let dir = RepoPathBuf::from_relative_path(dir)?;
+        Self::file_glob_i_at(dir, pattern)
+    }
+
+    fn file_glob_i_at(dir: RepoPathBuf, input: &str) -> Result<Self, FilePatternParseError> {
+        // The literal directory path is also matched case-insensitively, so
+        // it's escaped and concatenated with the glob pattern.
+        let mut pattern = glob::Pattern::escape(dir.as_internal_file_string());
+        if !input.is_empty() {
+            let normalized = RepoPathBuf::from_relative_path(input)?;
+            if !pattern.is_empty() {
+                pattern.push('/');
+            }
+            pattern.push_str(normalized.as_internal_file_string());
+        }
+        let pattern = glob::Pattern::new(&pattern)?;
+        Ok(FilePattern::FileGlobI { pattern })
+    }
+
     fn file_glob_at(dir: RepoPathBuf, input: &str) -> Result<Self, FilePatternParseError> {
         if input.is_empty() {
             return Ok(FilePattern::FilePath(dir));
@@ -181,7 +231,7 @@ impl FilePattern {
         match self {
             FilePattern::FilePath(path) => Some(path),
             FilePattern::PrefixPath(path) => Some(path),
-            FilePattern::FileGlob { .. } => None,
+            FilePattern::FileGlob { .. } | FilePattern::FileGlobI { .. } => None,
         }
     }
 }
@@ -197,6 +247,69 @@ fn split_glob_path(input: &str) -> (&str, &str) {
     input.split_at(prefix_len)
 }
 
+/// Predicate on the value of a file in a tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FilePredicate {
+    /// Matches conflicted files.
+    Conflicted,
+    /// Matches executable files.
+    Executable,
+    /// Matches symlinks.
+    Symlink,
+    /// Matches files larger than the given number of bytes.
+    LargerThan(u64),
+}
+
+impl FilePredicate {
+    /// Returns true if the `value` of the file at `path` satisfies this
+    /// predicate.
+    pub async fn matches_value(
+        &self,
+        store: &Store,
+        path: &RepoPath,
+        value: &MergedTreeValue,
+    ) -> BackendResult<bool> {
+        match self {
+            FilePredicate::Conflicted => Ok(!value.is_resolved()),
+            FilePredicate::Executable => Ok(matches!(
+                value.as_resolved(),
+                Some(Some(TreeValue::File {
+                    executable: true,
+                    ..
+                }))
+            )),
+            FilePredicate::Symlink => Ok(matches!(
+                value.as_resolved(),
+                Some(Some(TreeValue::Symlink(_)))
+            )),
+            FilePredicate::LargerThan(size) => {
+                let Some(Some(TreeValue::File { id, .. })) = value.as_resolved() else {
+                    return Ok(false);
+                };
+                Ok(store.file_size(path, id).await? > *size)
+            }
+        }
+    }
+
+    /// Name of the fileset function which constructs this predicate.
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            FilePredicate::Conflicted => "conflicted",
+            FilePredicate::Executable => "executable",
+            FilePredicate::Symlink => "symlink",
+            FilePredicate::LargerThan(_) => "larger_than",
+        }
+    }
+}
+
+/// Error occurred when a file predicate is evaluated without trees.
+#[derive(Debug, Error)]
+#[error("Function `{name}` can't be used here because it needs file contents")]
+pub struct FilePredicateUnsupportedError {
+    /// Name of the fileset function.
+    pub name: &'static str,
+}
+
 /// AST-level representation of the fileset expression.
 #[derive(Clone, Debug)]
 pub enum FilesetExpression {
@@ -206,6 +319,12 @@ pub enum FilesetExpression {
     All,
     /// Matches basic pattern.
     Pattern(FilePattern),
+    /// Matches files satisfying the predicate in a tree.
+    ///
+    /// The predicate can only be evaluated by
+    /// `FilesetExpression::to_matcher_with_trees()` or
+    /// `FilesetExpression::to_matcher_with_tree_diff()`.
+    FilePredicate(FilePredicate),
     /// Matches any of the expressions.
     ///
     /// Use `FilesetExpression::union_all()` to construct a union expression.
@@ -278,7 +397,8 @@ impl FilesetExpression {
             match expr {
                 FilesetExpression::None
                 | FilesetExpression::All
-                | FilesetExpression::Pattern(_) => {}
+                | FilesetExpression::Pattern(_)
+                | FilesetExpression::FilePredicate(_) => {}
                 FilesetExpression::UnionAll(exprs) => stack.extend(exprs.iter().rev()),
                 FilesetExpression::Intersection(expr1, expr2)
                 | FilesetExpression::Difference(expr1, expr2) => {
@@ -303,9 +423,142 @@ impl FilesetExpression {
         })
     }
 
+    /// Returns true if this expression contains file predicates, which need
+    /// trees to be evaluated.
+    pub fn has_file_predicates(&self) -> bool {
+        self.dfs_pre()
+            .any(|expr| matches!(expr, FilesetExpression::FilePredicate(_)))
+    }
+
     /// Transforms the expression tree to `Matcher` object.
-    pub fn to_matcher(&self) -> Box<dyn Matcher> {
-        build_union_matcher(self.as_union_all())
+    ///
+    /// Returns an error if the expression contains file predicates, which
+    /// can't be evaluated without trees.
+    pub fn to_matcher(&self) -> Result<Box<dyn Matcher>, FilePredicateUnsupportedError> {
+        build_union_matcher(self.as_union_all(), &|predicate| {
+            Err(FilePredicateUnsupportedError {
+                name: predicate.function_name(),
+            })
+        })
+    }
+
+    /// Transforms the expression tree to `Matcher` object, evaluating file
+    /// predicates against the `trees`.
+    ///
+    /// A file satisfies a predicate if it does in any of the `trees`. The
+    /// predicates are evaluated only for the files which the other parts of
+    /// the expression may match.
+    pub async fn to_matcher_with_trees(
+        &self,
+        trees: &[MergedTree],
+    ) -> BackendResult<Box<dyn Matcher>> {
+        if !self.has_file_predicates() {
+            return Ok(self.to_matcher_without_predicates());
+        }
+        let candidates_matcher = self
+            .to_candidates_expression()
+            .to_matcher_without_predicates();
+        let mut paths = Vec::new();
+        for tree in trees {
+            for (path, value) in tree.entries_matching(&*candidates_matcher) {
+                value?;
+                paths.push(path);
+            }
+        }
+        paths.sort_unstable();
+        paths.dedup();
+        self.to_matcher_with_paths(trees, &paths).await
+    }
+
+    /// Transforms the expression tree to `Matcher` object, evaluating file
+    /// predicates against the files that differ between `from_tree` and
+    /// `to_tree`.
+    ///
+    /// A file satisfies a predicate if it does in either tree. Unchanged files
+    /// never satisfy predicates.
+    pub async fn to_matcher_with_tree_diff(
+        &self,
+        from_tree: &MergedTree,
+        to_tree: &MergedTree,
+    ) -> BackendResult<Box<dyn Matcher>> {
+        if !self.has_file_predicates() {
+            return Ok(self.to_matcher_without_predicates());
+        }
+        let candidates_matcher = self
+            .to_candidates_expression()
+            .to_matcher_without_predicates();
+        let paths: Vec<_> = from_tree
+            .diff_stream(to_tree, &*candidates_matcher)
+            .map(|entry| entry.values.map(|_| entry.path))
+            .try_collect()
+            .await?;
+        let trees = [from_tree.clone(), to_tree.clone()];
+        self.to_matcher_with_paths(&trees, &paths).await
+    }
+
+    /// Builds `Matcher` which evaluates each file predicate to the set of
+    /// `paths` satisfying it in any of the `trees`.
+    async fn to_matcher_with_paths(
+        &self,
+        trees: &[MergedTree],
+        paths: &[RepoPathBuf],
+    ) -> BackendResult<Box<dyn Matcher>> {
+        let mut predicate_paths: Vec<(&FilePredicate, Vec<&RepoPath>)> = Vec::new();
+        for expr in self.dfs_pre() {
+            if let FilesetExpression::FilePredicate(predicate) = expr {
+                if !predicate_paths.iter().any(|(p, _)| *p == predicate) {
+                    predicate_paths.push((predicate, Vec::new()));
+                }
+            }
+        }
+        for path in paths {
+            for tree in trees {
+                let value = tree.path_value_async(path).await?;
+                for (predicate, matched_paths) in &mut predicate_paths {
+                    if matched_paths.last() != Some(&path.as_ref())
+                        && predicate.matches_value(tree.store(), path, &value).await?
+                    {
+                        matched_paths.push(path);
+                    }
+                }
+            }
+        }
+        let matcher = build_union_matcher(self.as_union_all(), &|predicate| {
+            let (_, paths) = predicate_paths
+                .iter()
+                .find(|(p, _)| *p == predicate)
+                .expect("predicate should have been evaluated");
+            Ok::<_, Infallible>(Box::new(FilesMatcher::new(paths)))
+        });
+        Ok(matcher.unwrap_or_else(|err| match err {}))
+    }
+
+    fn to_matcher_without_predicates(&self) -> Box<dyn Matcher> {
+        self.to_matcher()
+            .expect("expression shouldn't contain file predicates")
+    }
+
+    /// Replaces file predicates so the expression matches all the files the
+    /// original expression may match, whatever the predicates evaluate to.
+    fn to_candidates_expression(&self) -> FilesetExpression {
+        match self {
+            FilesetExpression::None | FilesetExpression::All | FilesetExpression::Pattern(_) => {
+                self.clone()
+            }
+            FilesetExpression::FilePredicate(_) => FilesetExpression::All,
+            FilesetExpression::UnionAll(exprs) => FilesetExpression::UnionAll(
+                exprs
+                    .iter()
+                    .map(FilesetExpression::to_candidates_expression)
+                    .collect(),
+            ),
+            FilesetExpression::Intersection(expr1, expr2) => FilesetExpression::Intersection(
+                Box::new(expr1.to_candidates_expression()),
+                Box::new(expr2.to_candidates_expression()),
+            ),
+            // The second expression can only exclude files.
+            FilesetExpression::Difference(expr1, _) => expr1.to_candidates_expression(),
+        }
     }
 }
 
@@ -313,10 +566,14 @@ impl FilesetExpression {
 ///
 /// Since `Matcher` typically accepts a set of patterns to be OR-ed, this
 /// function takes a list of union `expressions` as input.
-fn build_union_matcher(expressions: &[FilesetExpression]) -> Box<dyn Matcher> {
+fn build_union_matcher<E>(
+    expressions: &[FilesetExpression],
+    predicate_matcher: &impl Fn(&FilePredicate) -> Result<Box<dyn Matcher>, E>,
+) -> Result<Box<dyn Matcher>, E> {
     let mut file_paths = Vec::new();
     let mut prefix_paths = Vec::new();
     let mut file_globs = Vec::new();
+    let mut file_globs_i = Vec::new();
     let mut matchers: Vec<Option<Box<dyn Matcher>>> = Vec::new();
     for expr in expressions {
         let matcher: Box<dyn Matcher> = match expr {
@@ -330,19 +587,21 @@ fn build_union_matcher(expressions: &[FilesetExpression]) -> Box<dyn Matcher> {
                     FilePattern::FileGlob { dir, pattern } => {
                         file_globs.push((dir, pattern.clone()));
                     }
+                    FilePattern::FileGlobI { pattern } => file_globs_i.push(pattern.clone()),
                 }
                 continue;
             }
+            FilesetExpression::FilePredicate(predicate) => predicate_matcher(predicate)?,
             // UnionAll is supposed to be flattened by caller.
-            FilesetExpression::UnionAll(exprs) => build_union_matcher(exprs),
+            FilesetExpression::UnionAll(exprs) => build_union_matcher(exprs, predicate_matcher)?,
             FilesetExpression::Intersection(expr1, expr2) => {
-                let m1 = build_union_matcher(expr1.as_union_all());
-                let m2 = build_union_matcher(expr2.as_union_all());
+                let m1 = build_union_matcher(expr1.as_union_all(), predicate_matcher)?;
+                let m2 = build_union_matcher(expr2.as_union_all(), predicate_matcher)?;
                 Box::new(IntersectionMatcher::new(m1, m2))
             }
             FilesetExpression::Difference(expr1, expr2) => {
-                let m1 = build_union_matcher(expr1.as_union_all());
-                let m2 = build_union_matcher(expr2.as_union_all());
+                let m1 = build_union_matcher(expr1.as_union_all(), predicate_matcher)?;
+                let m2 = build_union_matcher(expr2.as_union_all(), predicate_matcher)?;
                 Box::new(DifferenceMatcher::new(m1, m2))
             }
         };
@@ -358,7 +617,12 @@ fn build_union_matcher(expressions: &[FilesetExpression]) -> Box<dyn Matcher> {
     if !file_globs.is_empty() {
         matchers.push(Some(Box::new(FileGlobsMatcher::new(file_globs))));
     }
-    union_all_matchers(&mut matchers)
+    if !file_globs_i.is_empty() {
+        matchers.push(Some(Box::new(FileGlobsMatcher::new_case_insensitive(
+            file_globs_i,
+        ))));
+    }
+    Ok(union_all_matchers(&mut matchers))
 }
 
 /// Concatenates all `matchers` as union.
@@ -397,9 +661,63 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, FilesetFunction>> = LazyLock
         function.expect_no_arguments()?;
         Ok(FilesetExpression::all())
     });
+    map.insert("conflicted", |_diagnostics, _path_converter, function| {
+        function.expect_no_arguments()?;
+        Ok(FilesetExpression::FilePredicate(FilePredicate::Conflicted))
+    });
+    map.insert("executable", |_diagnostics, _path_converter, function| {
+        function.expect_no_arguments()?;
+        Ok(FilesetExpression::FilePredicate(FilePredicate::Executable))
+    });
+    map.insert("symlink", |_diagnostics, _path_converter, function| {
+        function.expect_no_arguments()?;
+        Ok(FilesetExpression::FilePredicate(FilePredicate::Symlink))
+    });
+    map.insert("larger_than", |_diagnostics, _path_converter, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let size = expect_file_size(arg)?;
+        Ok(FilesetExpression::FilePredicate(FilePredicate::LargerThan(
+            size,
+        )))
+    });
     map
 });
 
+/// Parses file size such as `100`, `10KB`, or `1MiB` in bytes.
+fn expect_file_size(node: &ExpressionNode) -> FilesetParseResult<u64> {
+    let text = match &node.kind {
+        ExpressionKind::Identifier(text) => *text,
+        ExpressionKind::String(text) => text.as_str(),
+        _ => {
+            return Err(FilesetParseError::expression(
+                "Expected file size",
+                node.span,
+            ));
+        }
+    };
+    parse_file_size(text).ok_or_else(|| {
+        FilesetParseError::expression(format!("Invalid file size: {text}"), node.span)
+    })
+}
+
+fn parse_file_size(text: &str) -> Option<u64> {
+    let digits_len = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (digits, unit) = text.split_at(digits_len);
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
 fn resolve_function(
     diagnostics: &mut FilesetDiagnostics,
     path_converter: &RepoPathUiConverter,
@@ -737,6 +1055,19 @@ mod tests {
         "#);
         assert!(parse(r#"root-glob:"../*""#).is_err());
         assert!(parse(r#"root-glob:"/*""#).is_err());
+
+        // case-insensitive, with escaped directory path
+        let glob_i_pattern = |text| match parse(text).unwrap() {
+            FilesetExpression::Pattern(FilePattern::FileGlobI { pattern }) => {
+                pattern.as_str().to_owned()
+            }
+            expr => panic!("unexpected expression: {expr:?}"),
+        };
+        assert_eq!(glob_i_pattern(r#"glob-i:"*.RS""#), "cur[*]/*.RS");
+        assert_eq!(glob_i_pattern(r#"cwd-glob-i:"Foo""#), "cur[*]/Foo");
+        assert_eq!(glob_i_pattern(r#"glob-i:"..""#), "");
+        assert_eq!(glob_i_pattern(r#"root-glob-i:"Foo/*.c""#), "Foo/*.c");
+        assert!(parse(r#"root-glob-i:"../*""#).is_err());
     }
 
     #[test]
@@ -765,6 +1096,37 @@ mod tests {
             ],
         }
         "#);
+
+        insta::assert_debug_snapshot!(parse("conflicted()").unwrap(), @"FilePredicate(Conflicted)");
+        insta::assert_debug_snapshot!(parse("executable()").unwrap(), @"FilePredicate(Executable)");
+        insta::assert_debug_snapshot!(parse("symlink()").unwrap(), @"FilePredicate(Symlink)");
+        insta::assert_debug_snapshot!(parse("larger_than(1MB)").unwrap(), @"FilePredicate(LargerThan(1000000))");
+        insta::assert_debug_snapshot!(parse("larger_than('2kib')").unwrap(), @"FilePredicate(LargerThan(2048))");
+        insta::assert_debug_snapshot!(parse("larger_than(1XB)").unwrap_err().kind(), @r#"Expression("Invalid file size: 1XB")"#);
+        insta::assert_debug_snapshot!(parse("larger_than(all())").unwrap_err().kind(), @r#"Expression("Expected file size")"#);
+        insta::assert_debug_snapshot!(parse("larger_than()").unwrap_err().kind(), @r#"
+        InvalidArguments {
+            name: "larger_than",
+            message: "Expected 1 arguments",
+        }
+        "#);
+    }
+
+    #[test]
+    fn test_parse_file_size() {
+        assert_eq!(parse_file_size("0"), Some(0));
+        assert_eq!(parse_file_size("100"), Some(100));
+        assert_eq!(parse_file_size("100B"), Some(100));
+        assert_eq!(parse_file_size("10kb"), Some(10_000));
+        assert_eq!(parse_file_size("1MB"), Some(1_000_000));
+        assert_eq!(parse_file_size("2GB"), Some(2_000_000_000));
+        assert_eq!(parse_file_size("1KiB"), Some(1024));
+        assert_eq!(parse_file_size("1MiB"), Some(1024 * 1024));
+        assert_eq!(parse_file_size("1GiB"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_file_size(""), None);
+        assert_eq!(parse_file_size("MB"), None);
+        assert_eq!(parse_file_size("1.5MB"), None);
+        assert_eq!(parse_file_size("99999999999GiB"), None);
     }
 
     #[test]
@@ -843,10 +1205,10 @@ mod tests {
         let settings = insta_settings();
         let _guard = settings.bind_to_scope();
 
-        insta::assert_debug_snapshot!(FilesetExpression::none().to_matcher(), @"NothingMatcher");
-        insta::assert_debug_snapshot!(FilesetExpression::all().to_matcher(), @"EverythingMatcher");
+        insta::assert_debug_snapshot!(FilesetExpression::none().to_matcher().unwrap(), @"NothingMatcher");
+        insta::assert_debug_snapshot!(FilesetExpression::all().to_matcher().unwrap(), @"EverythingMatcher");
         insta::assert_debug_snapshot!(
-            FilesetExpression::file_path(repo_path_buf("foo")).to_matcher(),
+            FilesetExpression::file_path(repo_path_buf("foo")).to_matcher().unwrap(),
             @r#"
         FilesMatcher {
             tree: Dir {
@@ -855,7 +1217,7 @@ mod tests {
         }
         "#);
         insta::assert_debug_snapshot!(
-            FilesetExpression::prefix_path(repo_path_buf("foo")).to_matcher(),
+            FilesetExpression::prefix_path(repo_path_buf("foo")).to_matcher().unwrap(),
             @r#"
         PrefixMatcher {
             tree: Dir {
@@ -876,7 +1238,7 @@ mod tests {
             })
         };
 
-        insta::assert_debug_snapshot!(glob_expr("", "*").to_matcher(), @r#"
+        insta::assert_debug_snapshot!(glob_expr("", "*").to_matcher().unwrap(), @r#"
         FileGlobsMatcher {
             tree: [
                 Pattern {
@@ -885,12 +1247,13 @@ mod tests {
                     is_recursive: false,
                 },
             ] {},
+            case_sensitive: true,
         }
         "#);
 
         let expr =
             FilesetExpression::union_all(vec![glob_expr("foo", "*"), glob_expr("foo/bar", "*")]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @r#"
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @r#"
         FileGlobsMatcher {
             tree: [] {
                 "foo": [
@@ -909,10 +1272,37 @@ mod tests {
                     ] {},
                 },
             },
+            case_sensitive: true,
         }
         "#);
     }
 
+    #[test]
+    fn test_build_matcher_glob_i_pattern() {
+        let path_converter = RepoPathUiConverter::Fs {
+            cwd: PathBuf::from("/ws/Cur*"),
+            base: PathBuf::from("/ws"),
+        };
+        let to_matcher = |text| {
+            parse(&mut FilesetDiagnostics::new(), text, &path_converter)
+                .unwrap()
+                .to_matcher()
+                .unwrap()
+        };
+
+        let matcher = to_matcher(r#"glob-i:"*.rs""#);
+        assert!(matcher.matches(&repo_path_buf("Cur*/foo.rs")));
+        assert!(matcher.matches(&repo_path_buf("cur*/FOO.RS")));
+        assert!(!matcher.matches(&repo_path_buf("curx/foo.rs")));
+        assert!(!matcher.matches(&repo_path_buf("Cur*/sub/foo.rs")));
+        assert!(!matcher.matches(&repo_path_buf("foo.rs")));
+
+        let matcher = to_matcher(r#"root-glob-i:"Foo/Bar""#);
+        assert!(matcher.matches(&repo_path_buf("foo/bar")));
+        assert!(matcher.matches(&repo_path_buf("FOO/BAR")));
+        assert!(!matcher.matches(&repo_path_buf("foo/bar/baz")));
+    }
+
     #[test]
     fn test_build_matcher_union_patterns_of_same_kind() {
         let settings = insta_settings();
@@ -922,7 +1312,7 @@ mod tests {
             FilesetExpression::file_path(repo_path_buf("foo")),
             FilesetExpression::file_path(repo_path_buf("foo/bar")),
         ]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @r#"
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @r#"
         FilesMatcher {
             tree: Dir {
                 "foo": File {
@@ -936,7 +1326,7 @@ mod tests {
             FilesetExpression::prefix_path(repo_path_buf("bar")),
             FilesetExpression::prefix_path(repo_path_buf("bar/baz")),
         ]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @r#"
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @r#"
         PrefixMatcher {
             tree: Dir {
                 "bar": Prefix {
@@ -956,7 +1346,7 @@ mod tests {
             FilesetExpression::file_path(repo_path_buf("foo")),
             FilesetExpression::prefix_path(repo_path_buf("bar")),
         ]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @r#"
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @r#"
         UnionMatcher {
             input1: FilesMatcher {
                 tree: Dir {
@@ -978,11 +1368,11 @@ mod tests {
         let _guard = settings.bind_to_scope();
 
         let expr = FilesetExpression::UnionAll(vec![]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @"NothingMatcher");
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @"NothingMatcher");
 
         let expr =
             FilesetExpression::UnionAll(vec![FilesetExpression::None, FilesetExpression::All]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @r"
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @r"
         UnionMatcher {
             input1: NothingMatcher,
             input2: EverythingMatcher,
@@ -1001,7 +1391,7 @@ mod tests {
             FilesetExpression::file_path(repo_path_buf("foo")),
             FilesetExpression::prefix_path(repo_path_buf("bar")),
         ]);
-        insta::assert_debug_snapshot!(expr.to_matcher(), @r#"
+        insta::assert_debug_snapshot!(expr.to_matcher().unwrap(), @r#"
         UnionMatcher {
             input1: UnionMatcher {
                 input1: IntersectionMatcher {
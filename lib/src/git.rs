@@ -16,10 +16,12 @@
 
 use std::borrow::Borrow;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::default::Default;
 use std::fs::File;
+use std::iter;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
 use std::str;
@@ -28,18 +30,28 @@ use std::sync::Arc;
 use bstr::BStr;
 use bstr::BString;
 use futures::StreamExt as _;
+use gix::objs::WriteTo as _;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
 
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::CommitId;
+use crate::backend::FileId;
+use crate::backend::Signature;
+use crate::backend::SigningFn;
 use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
+use crate::git_backend::signature_to_git;
 use crate::git_backend::GitBackend;
+#[cfg(feature = "git-builtin-transport")]
+use crate::git_builtin::GitBuiltinContext;
+#[cfg(feature = "git-builtin-transport")]
+use crate::git_builtin::GitBuiltinError;
 use crate::git_subprocess::GitSubprocessContext;
 use crate::git_subprocess::GitSubprocessError;
 use crate::matchers::EverythingMatcher;
@@ -62,8 +74,11 @@ use crate::refs::BookmarkPushUpdate;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
 use crate::revset::RevsetExpression;
 use crate::settings::GitSettings;
+use crate::settings::GitTransport;
+use crate::signing::SignError;
 use crate::store::Store;
 use crate::str_util::StringPattern;
 use crate::view::View;
@@ -104,6 +119,9 @@ fn validate_remote_name(name: &RemoteName) -> Result<(), GitRemoteNameError> {
 pub enum GitRefKind {
     Bookmark,
     Tag,
+    /// Git notes ref. Notes refs are imported to the notes of the view, but
+    /// never as bookmarks or tags.
+    Note,
 }
 
 /// Stats from a git push
@@ -150,7 +168,7 @@ pub(crate) struct RefSpec {
 }
 
 impl RefSpec {
-    fn forced(source: impl Into<String>, destination: impl Into<String>) -> Self {
+    pub(crate) fn forced(source: impl Into<String>, destination: impl Into<String>) -> Self {
         RefSpec {
             forced: true,
             source: Some(source.into()),
@@ -158,6 +176,14 @@ impl RefSpec {
         }
     }
 
+    pub(crate) fn fast_forward(source: impl Into<String>, destination: impl Into<String>) -> Self {
+        RefSpec {
+            forced: false,
+            source: Some(source.into()),
+            destination: destination.into(),
+        }
+    }
+
     fn delete(destination: impl Into<String>) -> Self {
         // We don't force push on branch deletion
         RefSpec {
@@ -167,6 +193,21 @@ impl RefSpec {
         }
     }
 
+    #[cfg(feature = "git-builtin-transport")]
+    pub(crate) fn is_forced(&self) -> bool {
+        self.forced
+    }
+
+    #[cfg(feature = "git-builtin-transport")]
+    pub(crate) fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    #[cfg(feature = "git-builtin-transport")]
+    pub(crate) fn destination(&self) -> &str {
+        &self.destination
+    }
+
     pub(crate) fn to_git_format(&self) -> String {
         format!(
             "{}{}",
@@ -193,7 +234,8 @@ impl RefSpec {
 /// remote it's being pushed to
 pub(crate) struct RefToPush<'a> {
     pub(crate) refspec: &'a RefSpec,
-    pub(crate) expected_location: Option<&'a CommitId>,
+    /// Hex object id of the expected location
+    pub(crate) expected_location: Option<String>,
 }
 
 impl<'a> RefToPush<'a> {
@@ -210,19 +252,26 @@ impl<'a> RefToPush<'a> {
 
         RefToPush {
             refspec,
-            expected_location,
+            expected_location: expected_location.map(|id| id.hex()),
         }
     }
 
-    pub(crate) fn to_git_lease(&self) -> String {
-        format!(
-            "{}:{}",
-            self.refspec.destination,
-            self.expected_location
-                .map(|x| x.to_string())
-                .as_deref()
-                .unwrap_or("")
-        )
+    /// Returns the `--force-with-lease` argument, or `None` if the refspec
+    /// isn't forced. Non-forced updates are checked by the remote instead.
+    pub(crate) fn to_git_lease(&self) -> Option<String> {
+        (!self.is_fast_forward()).then(|| {
+            format!(
+                "{}:{}",
+                self.refspec.destination,
+                self.expected_location.as_deref().unwrap_or("")
+            )
+        })
+    }
+
+    /// Returns true if the update must be a fast-forward, in which case the
+    /// expected location isn't checked.
+    pub(crate) fn is_fast_forward(&self) -> bool {
+        !self.refspec.forced && self.refspec.source.is_some()
     }
 }
 
@@ -250,6 +299,10 @@ pub fn parse_git_ref(full_name: &GitRefName) -> Option<(GitRefKind, RemoteRefSym
         let name = RefName::new(name);
         let remote = REMOTE_NAME_FOR_LOCAL_GIT_REPO;
         Some((GitRefKind::Tag, RemoteRefSymbol { name, remote }))
+    } else if let Some(name) = full_name.as_str().strip_prefix("refs/notes/") {
+        let name = RefName::new(name);
+        let remote = REMOTE_NAME_FOR_LOCAL_GIT_REPO;
+        Some((GitRefKind::Note, RemoteRefSymbol { name, remote }))
     } else {
         None
     }
@@ -276,6 +329,9 @@ fn to_git_ref_name(kind: GitRefKind, symbol: RemoteRefSymbol<'_>) -> Option<GitR
         GitRefKind::Tag => {
             (remote == REMOTE_NAME_FOR_LOCAL_GIT_REPO).then(|| format!("refs/tags/{name}").into())
         }
+        GitRefKind::Note => {
+            (remote == REMOTE_NAME_FOR_LOCAL_GIT_REPO).then(|| format!("refs/notes/{name}").into())
+        }
     }
 }
 
@@ -472,14 +528,35 @@ pub fn import_some_refs(
             head_commits.push(commit);
         }
     }
+    // Notes commits aren't visible, but should be indexed as the other commits
+    // referenced by the view.
+    let mut notes_commits = Vec::new();
+    for (full_name, new_target) in &changed_git_refs {
+        if !matches!(parse_git_ref(full_name), Some((GitRefKind::Note, _))) {
+            continue;
+        }
+        for id in new_target.added_ids() {
+            let commit = get_commit(id).map_err(GitImportError::Backend)?;
+            notes_commits.push(commit);
+        }
+    }
     // It's unlikely the imported commits were missing, but I/O-related error
     // can still occur.
     mut_repo
         .add_heads(&head_commits)
         .map_err(GitImportError::Backend)?;
+    mut_repo
+        .index_commits(&notes_commits)
+        .map_err(GitImportError::Backend)?;
 
     // Apply the change that happened in git since last time we imported refs.
     for (full_name, new_target) in changed_git_refs {
+        // Notes refs have no remote view. The last known Git ref is the merge
+        // base of the notes in the view.
+        if let Some((GitRefKind::Note, symbol)) = parse_git_ref(&full_name) {
+            let base_target = mut_repo.get_git_ref(&full_name);
+            mut_repo.merge_notes(symbol.name, &base_target, &new_target);
+        }
         mut_repo.set_git_ref_target(&full_name, new_target);
     }
     for (symbol, (old_remote_ref, new_target)) in &changed_remote_bookmarks {
@@ -593,18 +670,18 @@ fn diff_refs_to_import(
         .filter(|&(symbol, _)| git_ref_filter(GitRefKind::Bookmark, symbol))
         .map(|(symbol, remote_ref)| (RemoteRefKey(symbol), (&remote_ref.target, remote_ref.state)))
         .collect();
-    // TODO: compare to tags stored in the "git" remote view. Since tags should
-    // never be moved locally in jj, we can consider local tags as merge base.
+    // TODO: compare to tags stored in the "git" remote view. Since local tags
+    // are exported to Git, the last known Git tags can be used as merge base.
     let mut known_remote_tags = view
-        .tags()
+        .git_refs()
         .iter()
-        .map(|(name, target)| {
-            let symbol = name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO);
+        .filter_map(|(full_name, target)| {
+            let (kind, symbol) =
+                parse_git_ref(full_name).expect("stored git ref should be parsable");
             let state = RemoteRefState::Tracked;
-            (symbol, (target, state))
+            (kind == GitRefKind::Tag && git_ref_filter(kind, symbol))
+                .then_some((RemoteRefKey(symbol), (target, state)))
         })
-        .filter(|&(symbol, _)| git_ref_filter(GitRefKind::Tag, symbol))
-        .map(|(symbol, remote_ref)| (RemoteRefKey(symbol), remote_ref))
         .collect();
 
     let mut changed_git_refs = Vec::new();
@@ -639,6 +716,18 @@ fn diff_refs_to_import(
         &mut failed_ref_names,
         &git_ref_filter,
     )?;
+    // Notes refs are only copied to the Git refs of the view.
+    collect_changed_refs_to_import(
+        actual
+            .prefixed(BStr::new("refs/notes/"))
+            .map_err(GitImportError::from_git)?,
+        &mut known_git_refs,
+        &mut HashMap::new(),
+        &mut changed_git_refs,
+        &mut Vec::new(),
+        &mut failed_ref_names,
+        &git_ref_filter,
+    )?;
     for full_name in known_git_refs.into_keys() {
         changed_git_refs.push((full_name.to_owned(), RefTarget::absent()));
     }
@@ -738,7 +827,7 @@ fn default_remote_ref_state_for(
                 RemoteRefState::New
             }
         }
-        GitRefKind::Tag => RemoteRefState::Tracked,
+        GitRefKind::Tag | GitRefKind::Note => RemoteRefState::Tracked,
     }
 }
 
@@ -866,6 +955,10 @@ pub enum FailedRefExportReason {
 pub struct GitExportStats {
     /// Remote bookmarks that couldn't be exported, sorted by `symbol`.
     pub failed_bookmarks: Vec<(RemoteRefSymbolBuf, FailedRefExportReason)>,
+    /// Tags that couldn't be exported, sorted by `name`.
+    pub failed_tags: Vec<(RefNameBuf, FailedRefExportReason)>,
+    /// Notes refs that couldn't be exported, sorted by `name`.
+    pub failed_notes: Vec<(RefNameBuf, FailedRefExportReason)>,
 }
 
 #[derive(Debug)]
@@ -880,18 +973,33 @@ struct RefsToExport {
     bookmarks_to_delete: Vec<(RemoteRefSymbolBuf, gix::ObjectId)>,
     /// Remote bookmarks that couldn't be exported, sorted by `symbol`.
     failed_bookmarks: Vec<(RemoteRefSymbolBuf, FailedRefExportReason)>,
-}
-
-/// Export changes to bookmarks made in the Jujutsu repo compared to our last
-/// seen view of the Git repo in `mut_repo.view().git_refs()`.
+    /// Tag `(symbol, (old_oid, new_oid))`s to update, sorted by `symbol`.
+    tags_to_update: Vec<(RemoteRefSymbolBuf, (Option<gix::ObjectId>, gix::ObjectId))>,
+    /// Tag `(symbol, old_oid)`s to delete, sorted by `symbol`.
+    tags_to_delete: Vec<(RemoteRefSymbolBuf, gix::ObjectId)>,
+    /// Tags that couldn't be exported, sorted by `symbol`.
+    failed_tags: Vec<(RemoteRefSymbolBuf, FailedRefExportReason)>,
+    /// Notes ref `(symbol, (old_oid, new_oid))`s to update, sorted by `symbol`.
+    notes_to_update: Vec<(RemoteRefSymbolBuf, (Option<gix::ObjectId>, gix::ObjectId))>,
+    /// Notes ref `(symbol, old_oid)`s to delete, sorted by `symbol`.
+    notes_to_delete: Vec<(RemoteRefSymbolBuf, gix::ObjectId)>,
+    /// Notes refs that couldn't be exported, sorted by `symbol`.
+    failed_notes: Vec<(RemoteRefSymbolBuf, FailedRefExportReason)>,
+}
+
+/// Export changes to bookmarks and tags made in the Jujutsu repo compared to
+/// our last seen view of the Git repo in `mut_repo.view().git_refs()`.
 ///
 /// We ignore changed bookmarks that are conflicted (were also changed in the
 /// Git repo compared to our last remembered view of the Git repo). These will
 /// be marked conflicted by the next `jj git import`.
 ///
-/// We do not export tags and other refs at the moment, since these aren't
-/// supposed to be modified by JJ. For them, the Git state is considered
-/// authoritative.
+/// Tags are exported as lightweight tags. Annotated tags created by
+/// [`create_annotated_tag()`] are already up to date in Git. Notes refs are
+/// exported to `refs/notes/`.
+///
+/// We do not export other refs at the moment, since these aren't supposed to
+/// be modified by JJ. For them, the Git state is considered authoritative.
 pub fn export_refs(mut_repo: &mut MutableRepo) -> Result<GitExportStats, GitExportError> {
     export_some_refs(mut_repo, |_, _| true)
 }
@@ -913,6 +1021,12 @@ pub fn export_some_refs(
         bookmarks_to_update,
         bookmarks_to_delete,
         mut failed_bookmarks,
+        tags_to_update,
+        tags_to_delete,
+        mut failed_tags,
+        notes_to_update,
+        notes_to_delete,
+        mut failed_notes,
     } = diff_refs_to_export(
         mut_repo.view(),
         mut_repo.store().root_commit_id(),
@@ -981,8 +1095,58 @@ pub fn export_some_refs(
         }
     }
 
+    for (symbol, old_oid) in tags_to_delete {
+        let Some(git_ref_name) = to_git_ref_name(GitRefKind::Tag, symbol.as_ref()) else {
+            failed_tags.push((symbol, FailedRefExportReason::InvalidGitName));
+            continue;
+        };
+        if let Err(reason) = delete_git_ref(&git_repo, &git_ref_name, &old_oid) {
+            failed_tags.push((symbol, reason));
+        } else {
+            mut_repo.set_git_ref_target(&git_ref_name, RefTarget::absent());
+        }
+    }
+    for (symbol, (old_oid, new_oid)) in tags_to_update {
+        let Some(git_ref_name) = to_git_ref_name(GitRefKind::Tag, symbol.as_ref()) else {
+            failed_tags.push((symbol, FailedRefExportReason::InvalidGitName));
+            continue;
+        };
+        if let Err(reason) = update_git_ref(&git_repo, &git_ref_name, old_oid, new_oid) {
+            failed_tags.push((symbol, reason));
+        } else {
+            let new_target = RefTarget::normal(CommitId::from_bytes(new_oid.as_bytes()));
+            mut_repo.set_git_ref_target(&git_ref_name, new_target);
+        }
+    }
+
+    for (symbol, old_oid) in notes_to_delete {
+        let Some(git_ref_name) = to_git_ref_name(GitRefKind::Note, symbol.as_ref()) else {
+            failed_notes.push((symbol, FailedRefExportReason::InvalidGitName));
+            continue;
+        };
+        if let Err(reason) = delete_git_ref(&git_repo, &git_ref_name, &old_oid) {
+            failed_notes.push((symbol, reason));
+        } else {
+            mut_repo.set_git_ref_target(&git_ref_name, RefTarget::absent());
+        }
+    }
+    for (symbol, (old_oid, new_oid)) in notes_to_update {
+        let Some(git_ref_name) = to_git_ref_name(GitRefKind::Note, symbol.as_ref()) else {
+            failed_notes.push((symbol, FailedRefExportReason::InvalidGitName));
+            continue;
+        };
+        if let Err(reason) = update_git_ref(&git_repo, &git_ref_name, old_oid, new_oid) {
+            failed_notes.push((symbol, reason));
+        } else {
+            let new_target = RefTarget::normal(CommitId::from_bytes(new_oid.as_bytes()));
+            mut_repo.set_git_ref_target(&git_ref_name, new_target);
+        }
+    }
+
     // Stabilize output, allow binary search.
     failed_bookmarks.sort_unstable_by(|(name1, _), (name2, _)| name1.cmp(name2));
+    failed_tags.sort_unstable_by(|(name1, _), (name2, _)| name1.cmp(name2));
+    failed_notes.sort_unstable_by(|(name1, _), (name2, _)| name1.cmp(name2));
 
     copy_exportable_local_bookmarks_to_remote_view(
         mut_repo,
@@ -993,7 +1157,19 @@ pub fn export_some_refs(
         },
     );
 
-    Ok(GitExportStats { failed_bookmarks })
+    let failed_tags = failed_tags
+        .into_iter()
+        .map(|(symbol, reason)| (symbol.name, reason))
+        .collect();
+    let failed_notes = failed_notes
+        .into_iter()
+        .map(|(symbol, reason)| (symbol.name, reason))
+        .collect();
+    Ok(GitExportStats {
+        failed_bookmarks,
+        failed_tags,
+        failed_notes,
+    })
 }
 
 fn copy_exportable_local_bookmarks_to_remote_view(
@@ -1023,7 +1199,7 @@ fn copy_exportable_local_bookmarks_to_remote_view(
     }
 }
 
-/// Calculates diff of bookmarks to be exported.
+/// Calculates diff of bookmarks, tags, and notes refs to be exported.
 fn diff_refs_to_export(
     view: &View,
     root_commit_id: &CommitId,
@@ -1044,6 +1220,28 @@ fn diff_refs_to_export(
         .filter(|&(symbol, _)| git_ref_filter(GitRefKind::Bookmark, symbol))
         .map(|(symbol, new_target)| (symbol, (RefTarget::absent_ref(), new_target)))
         .collect();
+    // Local tags are exported to the "git" remote as is.
+    let mut all_tag_targets: HashMap<RemoteRefSymbol, (&RefTarget, &RefTarget)> = view
+        .tags()
+        .iter()
+        .map(|(name, target)| {
+            let symbol = name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO);
+            (symbol, target)
+        })
+        .filter(|&(symbol, _)| git_ref_filter(GitRefKind::Tag, symbol))
+        .map(|(symbol, new_target)| (symbol, (RefTarget::absent_ref(), new_target)))
+        .collect();
+    // Notes refs are exported to the "git" remote as is.
+    let mut all_notes_targets: HashMap<RemoteRefSymbol, (&RefTarget, &RefTarget)> = view
+        .notes()
+        .iter()
+        .map(|(name, target)| {
+            let symbol = name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO);
+            (symbol, target)
+        })
+        .filter(|&(symbol, _)| git_ref_filter(GitRefKind::Note, symbol))
+        .map(|(symbol, new_target)| (symbol, (RefTarget::absent_ref(), new_target)))
+        .collect();
     let known_git_refs = view
         .git_refs()
         .iter()
@@ -1052,30 +1250,63 @@ fn diff_refs_to_export(
                 parse_git_ref(full_name).expect("stored git ref should be parsable");
             ((kind, symbol), target)
         })
-        .filter(|&((kind, symbol), _)| {
-            // There are two situations where remote bookmarks get out of sync:
-            // 1. `jj bookmark forget --include-remotes`
-            // 2. `jj op undo`/`restore` in colocated repo
-            kind == GitRefKind::Bookmark && git_ref_filter(kind, symbol)
-        });
-    for ((_kind, symbol), target) in known_git_refs {
-        all_bookmark_targets
+        .filter(|&((kind, symbol), _)| git_ref_filter(kind, symbol));
+    for ((kind, symbol), target) in known_git_refs {
+        // There are two situations where remote bookmarks get out of sync:
+        // 1. `jj bookmark forget --include-remotes`
+        // 2. `jj op undo`/`restore` in colocated repo
+        let all_targets = match kind {
+            GitRefKind::Bookmark => &mut all_bookmark_targets,
+            GitRefKind::Tag => &mut all_tag_targets,
+            GitRefKind::Note => &mut all_notes_targets,
+        };
+        all_targets
             .entry(symbol)
             .and_modify(|(old_target, _)| *old_target = target)
             .or_insert((target, RefTarget::absent_ref()));
     }
 
-    let mut bookmarks_to_update = Vec::new();
-    let mut bookmarks_to_delete = Vec::new();
-    let mut failed_bookmarks = Vec::new();
+    let (bookmarks_to_update, bookmarks_to_delete, failed_bookmarks) =
+        diff_ref_targets_to_export(all_bookmark_targets, root_commit_id);
+    let (tags_to_update, tags_to_delete, failed_tags) =
+        diff_ref_targets_to_export(all_tag_targets, root_commit_id);
+    let (notes_to_update, notes_to_delete, failed_notes) =
+        diff_ref_targets_to_export(all_notes_targets, root_commit_id);
+    RefsToExport {
+        bookmarks_to_update,
+        bookmarks_to_delete,
+        failed_bookmarks,
+        tags_to_update,
+        tags_to_delete,
+        failed_tags,
+        notes_to_update,
+        notes_to_delete,
+        failed_notes,
+    }
+}
+
+/// Classifies `(old_target, new_target)`s of refs into updates, deletions,
+/// and failures.
+#[expect(clippy::type_complexity)]
+fn diff_ref_targets_to_export(
+    all_targets: HashMap<RemoteRefSymbol, (&RefTarget, &RefTarget)>,
+    root_commit_id: &CommitId,
+) -> (
+    Vec<(RemoteRefSymbolBuf, (Option<gix::ObjectId>, gix::ObjectId))>,
+    Vec<(RemoteRefSymbolBuf, gix::ObjectId)>,
+    Vec<(RemoteRefSymbolBuf, FailedRefExportReason)>,
+) {
+    let mut refs_to_update = Vec::new();
+    let mut refs_to_delete = Vec::new();
+    let mut failed_refs = Vec::new();
     let root_commit_target = RefTarget::normal(root_commit_id.clone());
-    for (symbol, (old_target, new_target)) in all_bookmark_targets {
+    for (symbol, (old_target, new_target)) in all_targets {
         if new_target == old_target {
             continue;
         }
         if *new_target == root_commit_target {
             // Git doesn't have a root commit
-            failed_bookmarks.push((symbol.to_owned(), FailedRefExportReason::OnRootCommit));
+            failed_refs.push((symbol.to_owned(), FailedRefExportReason::OnRootCommit));
             continue;
         }
         let old_oid = if let Some(id) = old_target.as_normal() {
@@ -1083,7 +1314,7 @@ fn diff_refs_to_export(
         } else if old_target.has_conflict() {
             // The old git ref should only be a conflict if there were concurrent import
             // operations while the value changed. Don't overwrite these values.
-            failed_bookmarks.push((symbol.to_owned(), FailedRefExportReason::ConflictedOldState));
+            failed_refs.push((symbol.to_owned(), FailedRefExportReason::ConflictedOldState));
             continue;
         } else {
             assert!(old_target.is_absent());
@@ -1091,25 +1322,21 @@ fn diff_refs_to_export(
         };
         if let Some(id) = new_target.as_normal() {
             let new_oid = gix::ObjectId::from_bytes_or_panic(id.as_bytes());
-            bookmarks_to_update.push((symbol.to_owned(), (old_oid, new_oid)));
+            refs_to_update.push((symbol.to_owned(), (old_oid, new_oid)));
         } else if new_target.has_conflict() {
             // Skip conflicts and leave the old value in git_refs
             continue;
         } else {
             assert!(new_target.is_absent());
-            bookmarks_to_delete.push((symbol.to_owned(), old_oid.unwrap()));
+            refs_to_delete.push((symbol.to_owned(), old_oid.unwrap()));
         }
     }
 
     // Stabilize export order and output, allow binary search.
-    bookmarks_to_update.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
-    bookmarks_to_delete.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
-    failed_bookmarks.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
-    RefsToExport {
-        bookmarks_to_update,
-        bookmarks_to_delete,
-        failed_bookmarks,
-    }
+    refs_to_update.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
+    refs_to_delete.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
+    failed_refs.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
+    (refs_to_update, refs_to_delete, failed_refs)
 }
 
 fn delete_git_ref(
@@ -1118,7 +1345,7 @@ fn delete_git_ref(
     old_oid: &gix::oid,
 ) -> Result<(), FailedRefExportReason> {
     if let Ok(git_ref) = git_repo.find_reference(git_ref_name.as_str()) {
-        if git_ref.inner.target.try_id() == Some(old_oid) {
+        if peel_git_ref_id(&git_ref).as_deref() == Some(old_oid) {
             // The ref has not been updated by git, so go ahead and delete it
             git_ref
                 .delete()
@@ -1144,7 +1371,7 @@ fn update_git_ref(
             if let Ok(git_repo_ref) = git_repo.find_reference(git_ref_name.as_str()) {
                 // The ref was added in jj and in git. We're good if and only if git
                 // pointed it to our desired target.
-                if git_repo_ref.inner.target.try_id() != Some(&new_oid) {
+                if peel_git_ref_id(&git_repo_ref) != Some(new_oid) {
                     return Err(FailedRefExportReason::AddedInJjAddedInGit);
                 }
             } else {
@@ -1160,17 +1387,23 @@ fn update_git_ref(
             }
         }
         Some(old_oid) => {
+            // The ref may point to an annotated tag object of the old target.
+            let old_git_target = git_repo
+                .find_reference(git_ref_name.as_str())
+                .ok()
+                .filter(|git_ref| peel_git_ref_id(git_ref) == Some(old_oid))
+                .map_or(old_oid.into(), |git_ref| git_ref.inner.target);
             // The ref was modified in jj. We can use gix API for updating under a lock.
             if let Err(err) = git_repo.reference(
                 git_ref_name.as_str(),
                 new_oid,
-                gix::refs::transaction::PreviousValue::MustExistAndMatch(old_oid.into()),
+                gix::refs::transaction::PreviousValue::MustExistAndMatch(old_git_target),
                 "export from jj",
             ) {
                 // The reference was probably updated in git
                 if let Ok(git_repo_ref) = git_repo.find_reference(git_ref_name.as_str()) {
                     // We still consider this a success if it was updated to our desired target
-                    if git_repo_ref.inner.target.try_id() != Some(&new_oid) {
+                    if peel_git_ref_id(&git_repo_ref) != Some(new_oid) {
                         return Err(FailedRefExportReason::FailedToSet(err.into()));
                     }
                 } else {
@@ -1186,6 +1419,17 @@ fn update_git_ref(
     Ok(())
 }
 
+/// Returns the object id of the Git ref, peeling annotated tags.
+fn peel_git_ref_id(git_ref: &gix::Reference) -> Option<gix::ObjectId> {
+    let id = git_ref.inner.target.try_id()?;
+    let peeled_id = git_ref
+        .repo
+        .find_object(id)
+        .and_then(|object| object.peel_tags_to_end())
+        .map_or_else(|_| id.to_owned(), |object| object.id);
+    Some(peeled_id)
+}
+
 /// Ensures Git HEAD is detached and pointing to the `new_oid`. If `new_oid`
 /// is `None` (meaning absent), dummy placeholder ref will be set.
 fn update_git_head(
@@ -1227,6 +1471,344 @@ fn update_git_head(
     Ok(())
 }
 
+#[derive(Debug, Error)]
+pub enum GitTagError {
+    #[error("Tag name is not allowed in Git: {}", .0.as_symbol())]
+    InvalidName(RefNameBuf),
+    #[error("Git ref {} has been modified in Git", .0.as_str())]
+    ModifiedInGit(GitRefNameBuf),
+    #[error("Failed to sign tag")]
+    Sign(#[from] SignError),
+    #[error(transparent)]
+    Git(Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+impl GitTagError {
+    fn from_git(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        GitTagError::Git(source.into())
+    }
+}
+
+/// Creates an annotated tag object pointing to `target`, and points the Git
+/// tag ref and the local tag to it.
+///
+/// The tag object is signed if `sign_with` is specified. Since the Git ref is
+/// updated immediately, the tag won't be exported again as a lightweight tag.
+///
+/// The Git ref is updated before the transaction is committed, so it must
+/// still point to the target recorded in the view. If the transaction is
+/// discarded, the next import picks up the tag as if it were created in Git.
+pub fn create_annotated_tag(
+    mut_repo: &mut MutableRepo,
+    name: &RefName,
+    target: &CommitId,
+    message: &str,
+    tagger: &Signature,
+    sign_with: Option<&mut SigningFn>,
+) -> Result<(), GitTagError> {
+    let git_ref_name = to_git_ref_name(
+        GitRefKind::Tag,
+        name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO),
+    )
+    .ok_or_else(|| GitTagError::InvalidName(name.to_owned()))?;
+    let git_repo = get_git_repo(mut_repo.store())?;
+    let mut tag = gix::objs::Tag {
+        target: gix::ObjectId::from_bytes_or_panic(target.as_bytes()),
+        target_kind: gix::object::Kind::Commit,
+        name: name.as_str().into(),
+        tagger: Some(signature_to_git(tagger)),
+        message: format!("{}\n", message.trim_end_matches('\n')).into(),
+        pgp_signature: None,
+    };
+    if let Some(sign) = sign_with {
+        let mut data = Vec::new();
+        tag.write_to(&mut data).map_err(GitTagError::from_git)?;
+        let sig = sign(&data)?;
+        // The signature is written after the message separated by newline.
+        tag.message.pop();
+        tag.pgp_signature = Some(sig.into());
+    }
+    let old_target = mut_repo.get_git_ref(&git_ref_name);
+    let old_git_ref = git_repo.find_reference(git_ref_name.as_str()).ok();
+    let expected = if old_target.is_absent() {
+        if old_git_ref.is_some() {
+            return Err(GitTagError::ModifiedInGit(git_ref_name));
+        }
+        gix::refs::transaction::PreviousValue::MustNotExist
+    } else {
+        // The ref may point to an annotated tag object of the old target.
+        let old_oid = old_target
+            .as_normal()
+            .map(|id| gix::ObjectId::from_bytes_or_panic(id.as_bytes()));
+        let old_git_target = old_git_ref
+            .filter(|git_ref| old_oid.is_some() && peel_git_ref_id(git_ref) == old_oid)
+            .map(|git_ref| git_ref.inner.target)
+            .ok_or_else(|| GitTagError::ModifiedInGit(git_ref_name.clone()))?;
+        gix::refs::transaction::PreviousValue::MustExistAndMatch(old_git_target)
+    };
+    let tag_id = git_repo.write_object(&tag).map_err(GitTagError::from_git)?;
+    git_repo
+        .reference(git_ref_name.as_str(), tag_id, expected, "tag from jj")
+        .map_err(GitTagError::from_git)?;
+    mut_repo.set_git_ref_target(&git_ref_name, RefTarget::normal(target.clone()));
+    mut_repo.set_tag_target(name, RefTarget::normal(target.clone()));
+    Ok(())
+}
+
+/// Name of the default Git notes ref, `refs/notes/commits`.
+pub const DEFAULT_NOTES_REF_NAME: &RefName = RefName::new("commits");
+
+#[derive(Debug, Error)]
+pub enum GitNoteError {
+    #[error("Notes ref name is not allowed in Git: {}", .0.as_symbol())]
+    InvalidName(RefNameBuf),
+    #[error("Notes ref {} is conflicted", .0.as_symbol())]
+    ConflictedRef(RefNameBuf),
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    #[error(transparent)]
+    Git(Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+impl GitNoteError {
+    fn from_git(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        GitNoteError::Git(source.into())
+    }
+}
+
+/// Returns the current notes commit of the notes ref `name` in the view.
+fn get_notes_commit_id(view: &View, name: &RefName) -> Result<Option<CommitId>, GitNoteError> {
+    let symbol = name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO);
+    if to_git_ref_name(GitRefKind::Note, symbol).is_none() {
+        return Err(GitNoteError::InvalidName(name.to_owned()));
+    }
+    let target = view.get_notes(name);
+    if target.is_absent() {
+        return Ok(None);
+    }
+    let id = target
+        .as_normal()
+        .ok_or_else(|| GitNoteError::ConflictedRef(name.to_owned()))?
+        .clone();
+    Ok(Some(id))
+}
+
+/// Looks up the note blob for the commit `hex` in the notes tree. Returns the
+/// path and id of the blob.
+///
+/// Like Git, notes stored in fan-out directories (e.g. `ab/cdef...`) are
+/// supported.
+fn find_note_entry(
+    git_repo: &gix::Repository,
+    tree_id: gix::ObjectId,
+    hex: &str,
+) -> Result<Option<(String, gix::ObjectId)>, GitNoteError> {
+    let mut tree_id = tree_id;
+    let mut dir = String::new();
+    let mut rest = hex;
+    loop {
+        let tree = git_repo
+            .find_tree(tree_id)
+            .map_err(GitNoteError::from_git)?;
+        let mut subtree_id = None;
+        for entry in tree.iter() {
+            let entry = entry.map_err(GitNoteError::from_git)?;
+            let filename = entry.filename();
+            if entry.mode().is_blob() && filename == rest {
+                let path = format!("{dir}{rest}");
+                return Ok(Some((path, entry.oid().to_owned())));
+            } else if entry.mode().is_tree()
+                && filename.len() == 2
+                && rest.len() > 2
+                && filename == &rest[..2]
+            {
+                subtree_id = Some(entry.oid().to_owned());
+            }
+        }
+        let Some(id) = subtree_id else {
+            return Ok(None);
+        };
+        tree_id = id;
+        dir.push_str(&rest[..2]);
+        dir.push('/');
+        rest = &rest[2..];
+    }
+}
+
+fn notes_tree_id(
+    git_repo: &gix::Repository,
+    notes_id: gix::ObjectId,
+) -> Result<gix::ObjectId, GitNoteError> {
+    let commit = git_repo
+        .find_commit(notes_id)
+        .map_err(GitNoteError::from_git)?;
+    let tree_id = commit.tree_id().map_err(GitNoteError::from_git)?;
+    Ok(tree_id.detach())
+}
+
+/// Writes the notes tree with the blob at `path` replaced by `blob_id`, or
+/// removed if `blob_id` is `None`. Returns `None` if the tree becomes empty.
+fn edit_notes_tree(
+    git_repo: &gix::Repository,
+    tree_id: Option<gix::ObjectId>,
+    path: &str,
+    blob_id: Option<gix::ObjectId>,
+) -> Result<Option<gix::ObjectId>, GitNoteError> {
+    let mut entries = if let Some(id) = tree_id {
+        let tree = git_repo.find_tree(id).map_err(GitNoteError::from_git)?;
+        let tree: gix::objs::Tree = tree.decode().map_err(GitNoteError::from_git)?.into();
+        tree.entries
+    } else {
+        vec![]
+    };
+    let (name, rest) = match path.split_once('/') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    };
+    let pos = entries.iter().position(|entry| entry.filename == name);
+    let new_entry = if let Some(rest) = rest {
+        let subtree_id = pos.map(|pos| entries[pos].oid);
+        edit_notes_tree(git_repo, subtree_id, rest, blob_id)?.map(|oid| gix::objs::tree::Entry {
+            mode: gix::object::tree::EntryKind::Tree.into(),
+            filename: name.into(),
+            oid,
+        })
+    } else {
+        blob_id.map(|oid| gix::objs::tree::Entry {
+            mode: gix::object::tree::EntryKind::Blob.into(),
+            filename: name.into(),
+            oid,
+        })
+    };
+    match (pos, new_entry) {
+        (Some(pos), Some(entry)) => entries[pos] = entry,
+        (Some(pos), None) => {
+            entries.remove(pos);
+        }
+        (None, Some(entry)) => {
+            entries.push(entry);
+            entries.sort_unstable();
+        }
+        (None, None) => {}
+    }
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    write_notes_tree(git_repo, entries).map(Some)
+}
+
+fn write_notes_tree(
+    git_repo: &gix::Repository,
+    entries: Vec<gix::objs::tree::Entry>,
+) -> Result<gix::ObjectId, GitNoteError> {
+    let tree = gix::objs::Tree { entries };
+    let id = git_repo
+        .write_object(&tree)
+        .map_err(GitNoteError::from_git)?;
+    Ok(id.detach())
+}
+
+/// Reads the note attached to the commit in the notes ref `name` of the view.
+pub fn read_note(
+    repo: &dyn Repo,
+    name: &RefName,
+    commit_id: &CommitId,
+) -> Result<Option<String>, GitNoteError> {
+    let Some(notes_id) = get_notes_commit_id(repo.view(), name)? else {
+        return Ok(None);
+    };
+    let git_repo = get_git_repo(repo.store())?;
+    let notes_id = gix::ObjectId::from_bytes_or_panic(notes_id.as_bytes());
+    let tree_id = notes_tree_id(&git_repo, notes_id)?;
+    let Some((path, blob_id)) = find_note_entry(&git_repo, tree_id, &commit_id.hex())? else {
+        return Ok(None);
+    };
+    // Read through the store so missing blobs of a partial clone are fetched.
+    let path = RepoPathBuf::from_internal_string(path).map_err(GitNoteError::from_git)?;
+    let id = FileId::from_bytes(blob_id.as_bytes());
+    let content = async {
+        let mut reader = repo.store().read_file(&path, &id).await?;
+        let mut content = vec![];
+        reader
+            .read_to_end(&mut content)
+            .await
+            .map_err(|err| BackendError::ReadFile {
+                path: path.clone(),
+                id: id.clone(),
+                source: err.into(),
+            })?;
+        Ok::<_, BackendError>(content)
+    }
+    .block_on()?;
+    Ok(Some(String::from_utf8_lossy(&content).into_owned()))
+}
+
+/// Attaches the `note` to the commit in the notes ref `name`, or removes the
+/// note if `note` is `None`.
+///
+/// A new notes commit is written on top of the current one, and the notes ref
+/// of the view is updated to it. Like bookmarks and tags, the Git ref is
+/// updated by [`export_refs()`].
+pub fn set_note(
+    mut_repo: &mut MutableRepo,
+    name: &RefName,
+    commit_id: &CommitId,
+    note: Option<&str>,
+    signature: &Signature,
+) -> Result<(), GitNoteError> {
+    let old_notes_id = get_notes_commit_id(mut_repo.view(), name)?;
+    let git_backend = get_git_backend(mut_repo.store())?;
+    let git_repo = git_backend.git_repo();
+    let old_notes_id = old_notes_id.map(|id| gix::ObjectId::from_bytes_or_panic(id.as_bytes()));
+    let old_tree_id = if let Some(id) = old_notes_id {
+        notes_tree_id(&git_repo, id)?
+    } else {
+        gix::ObjectId::empty_tree(git_repo.object_hash())
+    };
+
+    let hex = commit_id.hex();
+    let old_path = find_note_entry(&git_repo, old_tree_id, &hex)?.map(|(path, _)| path);
+    let (path, blob_id, message) = match (note, &old_path) {
+        (Some(note), _) => {
+            let blob_id = git_repo
+                .write_blob(note.as_bytes())
+                .map_err(GitNoteError::from_git)?
+                .detach();
+            let path = old_path.as_deref().unwrap_or(&hex);
+            (path, Some(blob_id), "Notes added by jj\n")
+        }
+        (None, Some(path)) => (path.as_str(), None, "Notes removed by jj\n"),
+        (None, None) => return Ok(()),
+    };
+    let new_tree_id = edit_notes_tree(&git_repo, Some(old_tree_id), path, blob_id)?
+        .map_or_else(|| write_notes_tree(&git_repo, vec![]), Ok)?;
+
+    let notes_commit = gix::objs::Commit {
+        tree: new_tree_id,
+        parents: old_notes_id.into_iter().collect(),
+        author: signature_to_git(signature),
+        committer: signature_to_git(signature),
+        encoding: None,
+        message: message.into(),
+        extra_headers: vec![],
+    };
+    let new_notes_id = git_repo
+        .write_object(&notes_commit)
+        .map_err(GitNoteError::from_git)?
+        .detach();
+
+    let new_notes_id = CommitId::from_bytes(new_notes_id.as_bytes());
+    git_backend.import_head_commits([&new_notes_id])?;
+    let new_notes_commit = mut_repo.store().get_commit(&new_notes_id)?;
+    mut_repo.index_commits(&[new_notes_commit])?;
+    mut_repo.set_notes_target(name, RefTarget::normal(new_notes_id));
+    Ok(())
+}
+
 #[derive(Debug, Error)]
 pub enum GitResetHeadError {
     #[error(transparent)]
@@ -1588,6 +2170,8 @@ pub enum GitRemoteManagementError {
     RemoteName(#[from] GitRemoteNameError),
     #[error("Git remote named '{}' has nonstandard configuration", .0.as_symbol())]
     NonstandardConfiguration(RemoteNameBuf),
+    #[error("Invalid bookmark pattern provided: '{0}'")]
+    InvalidFetchPattern(StringPattern),
     #[error("Error saving Git configuration")]
     GitConfigSaveError(#[source] std::io::Error),
     #[error("Unexpected Git error when managing remotes")]
@@ -1610,7 +2194,7 @@ pub fn is_special_git_remote(remote: &RemoteName) -> bool {
     remote == REMOTE_NAME_FOR_LOCAL_GIT_REPO
 }
 
-fn default_fetch_refspec(remote: &RemoteName) -> String {
+pub(crate) fn default_fetch_refspec(remote: &RemoteName) -> String {
     format!(
         "+refs/heads/*:refs/remotes/{remote}/*",
         remote = remote.as_str()
@@ -1655,7 +2239,7 @@ fn remove_ref(reference: gix::Reference) -> gix::refs::transaction::RefEdit {
 /// Note that the resulting configuration changes are *not* persisted to the
 /// originating [`gix::Repository`]! The repository must be reloaded with the
 /// new configuration if necessary.
-fn save_git_config(config: &gix::config::File) -> std::io::Result<()> {
+pub(crate) fn save_git_config(config: &gix::config::File) -> std::io::Result<()> {
     let mut config_file = File::create(
         config
             .meta()
@@ -1666,7 +2250,7 @@ fn save_git_config(config: &gix::config::File) -> std::io::Result<()> {
     config.write_to_filter(&mut config_file, |section| section.meta() == config.meta())
 }
 
-fn save_remote(
+pub(crate) fn save_remote(
     config: &mut gix::config::File<'static>,
     remote_name: &RemoteName,
     remote: &mut gix::Remote,
@@ -1800,10 +2384,14 @@ pub fn get_all_remote_names(
     Ok(names)
 }
 
+/// Adds a Git remote which fetches the bookmarks matching `fetch_bookmarks`.
+///
+/// All bookmarks are fetched if `fetch_bookmarks` is empty.
 pub fn add_remote(
     store: &Store,
     remote_name: &RemoteName,
     url: &str,
+    fetch_bookmarks: &[StringPattern],
 ) -> Result<(), GitRemoteManagementError> {
     let git_repo = get_git_repo(store)?;
 
@@ -1815,14 +2403,31 @@ pub fn add_remote(
         ));
     }
 
+    let fetch_refspecs = if fetch_bookmarks.is_empty() {
+        vec![default_fetch_refspec(remote_name)]
+    } else {
+        fetch_bookmarks
+            .iter()
+            .map(|pattern| {
+                bookmark_pattern_to_fetch_refspec(remote_name, pattern)
+                    .map(|refspec| refspec.to_git_format())
+                    .filter(|refspec| {
+                        // Git allows only one `*` in refspec.
+                        let refspec = BStr::new(refspec);
+                        gix::refspec::parse(refspec, gix::refspec::parse::Operation::Fetch).is_ok()
+                    })
+                    .ok_or_else(|| GitRemoteManagementError::InvalidFetchPattern(pattern.clone()))
+            })
+            .try_collect()?
+    };
     let mut remote = git_repo
         .remote_at(url)
         .map_err(GitRemoteManagementError::from_git)?
         .with_refspecs(
-            [default_fetch_refspec(remote_name).as_bytes()],
+            fetch_refspecs.iter().map(|refspec| refspec.as_bytes()),
             gix::remote::Direction::Fetch,
         )
-        .expect("default refspec to be valid");
+        .map_err(GitRemoteManagementError::from_git)?;
 
     let mut config = git_repo.config_snapshot().clone();
     save_remote(&mut config, remote_name, &mut remote)?;
@@ -1909,26 +2514,37 @@ pub fn rename_remote(
         ));
     }
 
-    match (
-        remote.refspecs(gix::remote::Direction::Fetch),
-        remote.refspecs(gix::remote::Direction::Push),
-    ) {
-        ([refspec], [])
-            if refspec.to_ref().to_bstring()
-                == default_fetch_refspec(old_remote_name).as_bytes() => {}
+    // Only the refspecs fetching bookmarks to the remote-tracking branches
+    // can be rewritten for the new remote.
+    let fetch_refspecs: Option<Vec<String>> = remote
+        .refspecs(gix::remote::Direction::Fetch)
+        .iter()
+        .map(|refspec| {
+            let refspec = refspec.to_ref().to_bstring();
+            let pattern = bookmark_pattern_from_fetch_refspec(old_remote_name, refspec.as_ref())?;
+            bookmark_pattern_to_fetch_refspec(new_remote_name, &pattern)
+                .map(|refspec| refspec.to_git_format())
+        })
+        .collect();
+    let fetch_refspecs = match fetch_refspecs {
+        Some(refspecs)
+            if !refspecs.is_empty() && remote.refspecs(gix::remote::Direction::Push).is_empty() =>
+        {
+            refspecs
+        }
         _ => {
             return Err(GitRemoteManagementError::NonstandardConfiguration(
                 old_remote_name.to_owned(),
             ))
         }
-    }
+    };
 
     remote
         .replace_refspecs(
-            [default_fetch_refspec(new_remote_name).as_bytes()],
+            fetch_refspecs.iter().map(|refspec| refspec.as_bytes()),
             gix::remote::Direction::Fetch,
         )
-        .expect("default refspec to be valid");
+        .map_err(GitRemoteManagementError::from_git)?;
 
     let mut config = git_repo.config_snapshot().clone();
     save_remote(&mut config, new_remote_name, &mut remote)?;
@@ -2019,6 +2635,40 @@ where
     Ok(new_remote)
 }
 
+/// Configures the remote as the promisor remote of a partial clone.
+///
+/// Subsequent fetches from the remote omit the objects excluded by the
+/// `filter` (e.g. `blob:none`), and missing objects are fetched from the remote
+/// on demand.
+pub fn set_remote_partial_clone_filter(
+    store: &Store,
+    remote_name: &RemoteName,
+    filter: &str,
+) -> Result<(), GitRemoteManagementError> {
+    let git_repo = get_git_repo(store)?;
+
+    if git_repo.try_find_remote(remote_name.as_str()).is_none() {
+        return Err(GitRemoteManagementError::NoSuchRemote(
+            remote_name.to_owned(),
+        ));
+    }
+
+    let mut config = git_repo.config_snapshot().clone();
+    let subsection_name = Some(BStr::new(remote_name.as_str()));
+    config
+        .set_raw_value_by("remote", subsection_name, "promisor", "true")
+        .map_err(GitRemoteManagementError::from_git)?;
+    config
+        .set_raw_value_by("remote", subsection_name, "partialclonefilter", filter)
+        .map_err(GitRemoteManagementError::from_git)?;
+    config
+        .set_raw_value_by("extensions", None, "partialclone", remote_name.as_str())
+        .map_err(GitRemoteManagementError::from_git)?;
+    save_git_config(&config).map_err(GitRemoteManagementError::GitConfigSaveError)?;
+
+    Ok(())
+}
+
 pub fn set_remote_url(
     store: &Store,
     remote_name: &RemoteName,
@@ -2091,6 +2741,13 @@ pub enum GitFetchError {
     RemoteName(#[from] GitRemoteNameError),
     #[error(transparent)]
     Subprocess(#[from] GitSubprocessError),
+    #[cfg(feature = "git-builtin-transport")]
+    #[error(transparent)]
+    Builtin(#[from] GitBuiltinError),
+    #[error(transparent)]
+    TransportUnavailable(#[from] GitTransportUnavailableError),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
 }
 
 struct FetchedBranches {
@@ -2105,29 +2762,110 @@ fn expand_fetch_refspecs(
     branch_names
         .iter()
         .map(|pattern| {
-            pattern
-                .to_glob()
-                .filter(
-                    /* This triggered by non-glob `*`s in addition to INVALID_REFSPEC_CHARS
-                     * because `to_glob()` escapes such `*`s as `[*]`. */
-                    |glob| !glob.contains(INVALID_REFSPEC_CHARS),
-                )
-                .map(|glob| {
-                    RefSpec::forced(
-                        format!("refs/heads/{glob}"),
-                        format!("refs/remotes/{remote}/{glob}", remote = remote.as_str()),
-                    )
-                })
+            bookmark_pattern_to_fetch_refspec(remote, pattern)
                 .ok_or_else(|| GitFetchError::InvalidBranchPattern(pattern.clone()))
         })
         .collect()
 }
 
+/// Builds refspec which fetches the bookmarks matching the `pattern` to the
+/// remote-tracking branches. Returns `None` if the pattern can't be expressed
+/// as a refspec.
+fn bookmark_pattern_to_fetch_refspec(
+    remote: &RemoteName,
+    pattern: &StringPattern,
+) -> Option<RefSpec> {
+    pattern
+        .to_glob()
+        .filter(
+            /* This triggered by non-glob `*`s in addition to INVALID_REFSPEC_CHARS
+             * because `to_glob()` escapes such `*`s as `[*]`. */
+            |glob| !glob.contains(INVALID_REFSPEC_CHARS),
+        )
+        .map(|glob| {
+            RefSpec::forced(
+                format!("refs/heads/{glob}"),
+                format!("refs/remotes/{remote}/{glob}", remote = remote.as_str()),
+            )
+        })
+}
+
+/// Parses the bookmark pattern of refspec which fetches bookmarks to the
+/// remote-tracking branches of the same name. Returns `None` for the other
+/// kinds of refspecs.
+fn bookmark_pattern_from_fetch_refspec(
+    remote: &RemoteName,
+    refspec: &BStr,
+) -> Option<StringPattern> {
+    let refspec = str::from_utf8(refspec).ok()?;
+    let (source, destination) = refspec
+        .strip_prefix('+')
+        .unwrap_or(refspec)
+        .split_once(':')?;
+    let name = source.strip_prefix("refs/heads/")?;
+    let tracking_name = destination
+        .strip_prefix("refs/remotes/")?
+        .strip_prefix(remote.as_str())?
+        .strip_prefix('/')?;
+    if name != tracking_name || name.is_empty() {
+        return None;
+    }
+    if name.contains('*') {
+        StringPattern::glob(name).ok()
+    } else {
+        Some(StringPattern::exact(name))
+    }
+}
+
+/// How much history to fetch into a shallow repository.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GitFetchDepth {
+    /// Limit the history to the given number of commits from the tips.
+    Depth(NonZeroU32),
+    /// Extend the history of the shallow repository by the given number of
+    /// commits.
+    Deepen(NonZeroU32),
+}
+
+/// The configured Git transport isn't compiled in.
+#[derive(Debug, Error)]
+#[error(
+    "The builtin Git transport is unavailable because jj was not compiled with the \
+     `git-builtin-transport` feature"
+)]
+pub struct GitTransportUnavailableError;
+
+/// Context for talking to Git remotes with the configured transport.
+enum GitRemoteContext<'a> {
+    Subprocess(GitSubprocessContext<'a>),
+    #[cfg(feature = "git-builtin-transport")]
+    Builtin(GitBuiltinContext),
+}
+
+impl<'a> GitRemoteContext<'a> {
+    fn from_git_backend(
+        git_backend: &GitBackend,
+        git_settings: &'a GitSettings,
+    ) -> Result<Self, GitTransportUnavailableError> {
+        match git_settings.transport {
+            GitTransport::Subprocess => Ok(GitRemoteContext::Subprocess(
+                GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path),
+            )),
+            #[cfg(feature = "git-builtin-transport")]
+            GitTransport::Builtin => Ok(GitRemoteContext::Builtin(
+                GitBuiltinContext::from_git_backend(git_backend),
+            )),
+            #[cfg(not(feature = "git-builtin-transport"))]
+            GitTransport::Builtin => Err(GitTransportUnavailableError),
+        }
+    }
+}
+
 /// Helper struct to execute multiple `git fetch` operations
 pub struct GitFetch<'a> {
     mut_repo: &'a mut MutableRepo,
     git_repo: Box<gix::Repository>,
-    git_ctx: GitSubprocessContext<'a>,
+    git_ctx: GitRemoteContext<'a>,
     git_settings: &'a GitSettings,
     fetched: Vec<FetchedBranches>,
 }
@@ -2136,11 +2874,10 @@ impl<'a> GitFetch<'a> {
     pub fn new(
         mut_repo: &'a mut MutableRepo,
         git_settings: &'a GitSettings,
-    ) -> Result<Self, UnexpectedGitBackendError> {
+    ) -> Result<Self, GitFetchError> {
         let git_backend = get_git_backend(mut_repo.store())?;
         let git_repo = Box::new(git_backend.git_repo());
-        let git_ctx =
-            GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+        let git_ctx = GitRemoteContext::from_git_backend(git_backend, git_settings)?;
         Ok(GitFetch {
             mut_repo,
             git_repo,
@@ -2161,7 +2898,7 @@ impl<'a> GitFetch<'a> {
         remote_name: &RemoteName,
         branch_names: &[StringPattern],
         mut callbacks: RemoteCallbacks<'_>,
-        depth: Option<NonZeroU32>,
+        depth: Option<GitFetchDepth>,
     ) -> Result<(), GitFetchError> {
         validate_remote_name(remote_name)?;
 
@@ -2180,6 +2917,23 @@ impl<'a> GitFetch<'a> {
             // Don't fall back to the base refspecs.
             return Ok(());
         }
+        #[cfg_attr(
+            not(feature = "git-builtin-transport"),
+            expect(clippy::infallible_destructuring_match)
+        )]
+        let git_ctx = match &self.git_ctx {
+            GitRemoteContext::Subprocess(git_ctx) => git_ctx,
+            #[cfg(feature = "git-builtin-transport")]
+            GitRemoteContext::Builtin(git_ctx) => {
+                // Refs missing on the remote are pruned by the fetch itself.
+                git_ctx.fetch(remote_name, &remaining_refspecs, &mut callbacks, depth)?;
+                self.fetched.push(FetchedBranches {
+                    remote: remote_name.to_owned(),
+                    branches: branch_names.to_vec(),
+                });
+                return Ok(());
+            }
+        };
 
         let mut branches_to_prune = Vec::new();
         // git unfortunately errors out if one of the many refspecs is not found
@@ -2190,8 +2944,7 @@ impl<'a> GitFetch<'a> {
         // even more unfortunately, git errors out one refspec at a time,
         // meaning that the below cycle runs in O(#failed refspecs)
         while let Some(failing_refspec) =
-            self.git_ctx
-                .spawn_fetch(remote_name, &remaining_refspecs, &mut callbacks, depth)?
+            git_ctx.spawn_fetch(remote_name, &remaining_refspecs, &mut callbacks, depth)?
         {
             tracing::debug!(failing_refspec, "failed to fetch ref");
             remaining_refspecs.retain(|r| r.source.as_ref() != Some(&failing_refspec));
@@ -2206,7 +2959,7 @@ impl<'a> GitFetch<'a> {
 
         // Even if git fetch has --prune, if a branch is not found it will not be
         // pruned on fetch
-        self.git_ctx.spawn_branch_prune(&branches_to_prune)?;
+        git_ctx.spawn_branch_prune(&branches_to_prune)?;
 
         self.fetched.push(FetchedBranches {
             remote: remote_name.to_owned(),
@@ -2215,6 +2968,71 @@ impl<'a> GitFetch<'a> {
         Ok(())
     }
 
+    /// Returns the bookmark patterns to fetch from the remote if no patterns
+    /// are specified explicitly.
+    ///
+    /// These are the patterns of the `git.fetch-bookmarks` setting of the
+    /// remote if configured, or the patterns of the remote's fetch refspecs.
+    pub fn default_branch_patterns(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<StringPattern>, GitFetchError> {
+        if let Some(patterns) = self.git_settings.fetch_bookmarks.get(remote_name) {
+            return Ok(patterns.clone());
+        }
+        let Some(remote) = self.git_repo.try_find_remote(remote_name.as_str()) else {
+            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+        };
+        // Invalid remote configuration will be reported by `git fetch`.
+        let patterns = remote
+            .map(|remote| {
+                remote
+                    .refspecs(gix::remote::Direction::Fetch)
+                    .iter()
+                    .filter_map(|refspec| {
+                        let refspec = refspec.to_ref().to_bstring();
+                        bookmark_pattern_from_fetch_refspec(remote_name, refspec.as_ref())
+                    })
+                    .collect_vec()
+            })
+            .unwrap_or_default();
+        if patterns.is_empty() {
+            Ok(vec![StringPattern::everything()])
+        } else {
+            Ok(patterns)
+        }
+    }
+
+    /// Fetches the Git notes refs from the remote.
+    ///
+    /// The local notes refs are only updated if that's a fast-forward. Call
+    /// [`Self::import_refs()`] to import the updated notes refs.
+    #[tracing::instrument(skip(self, callbacks))]
+    pub fn fetch_notes(
+        &mut self,
+        remote_name: &RemoteName,
+        mut callbacks: RemoteCallbacks<'_>,
+    ) -> Result<(), GitFetchError> {
+        validate_remote_name(remote_name)?;
+        if self
+            .git_repo
+            .try_find_remote(remote_name.as_str())
+            .is_none()
+        {
+            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+        }
+        match &self.git_ctx {
+            GitRemoteContext::Subprocess(git_ctx) => {
+                git_ctx.spawn_fetch_notes(remote_name, &mut callbacks)?;
+            }
+            #[cfg(feature = "git-builtin-transport")]
+            GitRemoteContext::Builtin(git_ctx) => {
+                git_ctx.fetch_notes(remote_name, &mut callbacks)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Queries remote for the default branch name.
     #[tracing::instrument(skip(self))]
     pub fn get_default_branch(
@@ -2228,7 +3046,11 @@ impl<'a> GitFetch<'a> {
         {
             return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
         }
-        let default_branch = self.git_ctx.spawn_remote_show(remote_name)?;
+        let default_branch = match &self.git_ctx {
+            GitRemoteContext::Subprocess(git_ctx) => git_ctx.spawn_remote_show(remote_name)?,
+            #[cfg(feature = "git-builtin-transport")]
+            GitRemoteContext::Builtin(git_ctx) => git_ctx.remote_show(remote_name)?,
+        };
         tracing::debug!(?default_branch);
         Ok(default_branch)
     }
@@ -2258,7 +3080,7 @@ impl<'a> GitFetch<'a> {
                                 .iter()
                                 .any(|pattern| pattern.matches(symbol.name.as_str()))
                         }),
-                    GitRefKind::Tag => true,
+                    GitRefKind::Tag | GitRefKind::Note => true,
                 },
             )?;
 
@@ -2268,18 +3090,73 @@ impl<'a> GitFetch<'a> {
     }
 }
 
+/// Fetches the objects of the commit's tree that are missing in a partial
+/// clone, in one request to the promisor remote.
+///
+/// Returns the number of fetched objects. Nothing is fetched if the repository
+/// isn't a partial clone.
+pub fn fetch_missing_objects(
+    store: &Store,
+    git_settings: &GitSettings,
+    commit_id: &CommitId,
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<usize, GitFetchError> {
+    let git_backend = get_git_backend(store)?;
+    let Some(remote_name) = git_backend.promisor_remote_name() else {
+        return Ok(0);
+    };
+    let git_ctx =
+        GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+    let commit_id = gix::ObjectId::from_bytes_or_panic(commit_id.as_bytes());
+    let missing_ids = git_ctx.spawn_list_missing_objects(commit_id)?;
+    git_ctx.spawn_fetch_objects(&remote_name, &missing_ids, &mut callbacks)?;
+    Ok(missing_ids.len())
+}
+
 #[derive(Error, Debug)]
 pub enum GitPushError {
     #[error("No git remote named '{}'", .0.as_symbol())]
     NoSuchRemote(RemoteNameBuf),
+    #[error(
+        "Bookmark {} is not allowed to be pushed to remote {}",
+        name.as_symbol(),
+        remote.as_symbol()
+    )]
+    BookmarkNotAllowed {
+        name: RefNameBuf,
+        remote: RemoteNameBuf,
+    },
     #[error(transparent)]
     RemoteName(#[from] GitRemoteNameError),
     #[error(transparent)]
     Subprocess(#[from] GitSubprocessError),
+    #[cfg(feature = "git-builtin-transport")]
+    #[error(transparent)]
+    Builtin(#[from] GitBuiltinError),
+    #[error(transparent)]
+    TransportUnavailable(#[from] GitTransportUnavailableError),
     #[error(transparent)]
     UnexpectedBackend(#[from] UnexpectedGitBackendError),
 }
 
+/// Returns true if the `git.push-bookmarks` patterns configured for the remote
+/// allow pushing the bookmark. All bookmarks are allowed if no patterns are
+/// configured.
+pub fn is_push_allowed_bookmark(
+    git_settings: &GitSettings,
+    remote: &RemoteName,
+    name: &RefName,
+) -> bool {
+    git_settings
+        .push_bookmarks
+        .get(remote)
+        .is_none_or(|patterns| {
+            patterns
+                .iter()
+                .any(|pattern| pattern.matches(name.as_str()))
+        })
+}
+
 #[derive(Clone, Debug)]
 pub struct GitBranchPushTargets {
     pub branch_updates: Vec<(RefNameBuf, BookmarkPushUpdate)>,
@@ -2342,6 +3219,9 @@ pub fn push_branches(
 }
 
 /// Pushes the specified Git refs without updating the repo view.
+///
+/// Fails if a bookmark isn't allowed to be pushed to the remote by
+/// `git_settings.push_bookmarks`.
 pub fn push_updates(
     repo: &dyn Repo,
     git_settings: &GitSettings,
@@ -2352,6 +3232,15 @@ pub fn push_updates(
     let mut qualified_remote_refs_expected_locations = HashMap::new();
     let mut refspecs = vec![];
     for update in updates {
+        if let Some(name) = update.qualified_name.as_str().strip_prefix("refs/heads/") {
+            let name = RefName::new(name);
+            if !is_push_allowed_bookmark(git_settings, remote_name, name) {
+                return Err(GitPushError::BookmarkNotAllowed {
+                    name: name.to_owned(),
+                    remote: remote_name.to_owned(),
+                });
+            }
+        }
         qualified_remote_refs_expected_locations.insert(
             update.qualified_name.as_ref(),
             update.expected_current_target.as_ref(),
@@ -2369,28 +3258,251 @@ pub fn push_updates(
         }
     }
 
+    let refs_to_push: Vec<RefToPush> = refspecs
+        .iter()
+        .map(|full_refspec| RefToPush::new(full_refspec, &qualified_remote_refs_expected_locations))
+        .collect();
+    push_refs(
+        repo,
+        git_settings,
+        remote_name,
+        &refs_to_push,
+        &mut callbacks,
+    )
+}
+
+/// Pushes the commit to the specified ref on the remote without updating the
+/// repo view.
+///
+/// Unlike [`push_updates()`], the ref is updated only if that's a fast-forward.
+/// This is useful for pushing to refs that aren't tracked locally, such as
+/// Gerrit's `refs/for/<branch>`.
+pub fn push_to_remote_ref(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    qualified_name: &GitRefName,
+    new_target: &CommitId,
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<GitPushStats, GitPushError> {
+    validate_remote_name(remote_name)?;
+    let refspec = RefSpec::fast_forward(new_target.hex(), qualified_name.as_str());
+    let ref_to_push = RefToPush {
+        refspec: &refspec,
+        expected_location: None,
+    };
+    push_refs(
+        repo,
+        git_settings,
+        remote_name,
+        &[ref_to_push],
+        &mut callbacks,
+    )
+}
+
+fn push_refs(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    refs_to_push: &[RefToPush],
+    callbacks: &mut RemoteCallbacks<'_>,
+) -> Result<GitPushStats, GitPushError> {
     let git_backend = get_git_backend(repo.store())?;
     let git_repo = git_backend.git_repo();
-    let git_ctx =
-        GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+    let git_ctx = GitRemoteContext::from_git_backend(git_backend, git_settings)?;
 
     // check the remote exists
     if git_repo.try_find_remote(remote_name.as_str()).is_none() {
         return Err(GitPushError::NoSuchRemote(remote_name.to_owned()));
     }
 
-    let refs_to_push: Vec<RefToPush> = refspecs
-        .iter()
-        .map(|full_refspec| RefToPush::new(full_refspec, &qualified_remote_refs_expected_locations))
-        .collect();
-
-    let mut push_stats = git_ctx.spawn_push(remote_name, &refs_to_push, &mut callbacks)?;
+    let mut push_stats = match &git_ctx {
+        GitRemoteContext::Subprocess(git_ctx) => git_ctx.spawn_push(
+            remote_name,
+            refs_to_push,
+            &git_settings.push_options,
+            callbacks,
+        )?,
+        #[cfg(feature = "git-builtin-transport")]
+        GitRemoteContext::Builtin(git_ctx) => git_ctx.push(
+            remote_name,
+            refs_to_push,
+            &git_settings.push_options,
+            callbacks,
+        )?,
+    };
     push_stats.pushed.sort();
     push_stats.rejected.sort();
     push_stats.remote_rejected.sort();
     Ok(push_stats)
 }
 
+/// Tag on a Git remote.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitRemoteTag {
+    /// Object the tag ref points to. This is an annotated tag object if the tag
+    /// is annotated.
+    pub object_id: gix::ObjectId,
+    /// Commit the tag refers to.
+    pub target: CommitId,
+}
+
+/// Lists tags on the remote by querying it.
+pub fn list_remote_tags(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+) -> Result<BTreeMap<RefNameBuf, GitRemoteTag>, GitPushError> {
+    let git_backend = get_git_backend(repo.store())?;
+    let git_repo = git_backend.git_repo();
+    let git_ctx = GitRemoteContext::from_git_backend(git_backend, git_settings)?;
+
+    if git_repo.try_find_remote(remote_name.as_str()).is_none() {
+        return Err(GitPushError::NoSuchRemote(remote_name.to_owned()));
+    }
+
+    let remote_tags = match &git_ctx {
+        GitRemoteContext::Subprocess(git_ctx) => git_ctx.spawn_ls_remote_tags(remote_name)?,
+        #[cfg(feature = "git-builtin-transport")]
+        GitRemoteContext::Builtin(git_ctx) => git_ctx.ls_remote_tags(remote_name)?,
+    };
+    let mut object_ids = BTreeMap::new();
+    let mut peeled_ids = HashMap::new();
+    for (id, full_name) in remote_tags {
+        let (full_name, peeled) = match full_name.strip_suffix("^{}") {
+            Some(full_name) => (full_name, true),
+            None => (full_name.as_str(), false),
+        };
+        let Some((GitRefKind::Tag, symbol)) = parse_git_ref(GitRefName::new(full_name)) else {
+            continue;
+        };
+        let name = symbol.name.to_owned();
+        if peeled {
+            peeled_ids.insert(name, id);
+        } else {
+            object_ids.insert(name, id);
+        }
+    }
+    let tags = object_ids
+        .into_iter()
+        .map(|(name, object_id)| {
+            let target_id = peeled_ids.remove(&name).unwrap_or(object_id);
+            let tag = GitRemoteTag {
+                object_id,
+                target: CommitId::from_bytes(target_id.as_bytes()),
+            };
+            (name, tag)
+        })
+        .collect();
+    Ok(tags)
+}
+
+/// Returns the Git object the local tag should be pushed as. This is the
+/// annotated tag object if the tag was created as an annotated tag.
+pub fn local_tag_object_id(
+    repo: &dyn Repo,
+    name: &RefName,
+    target: &CommitId,
+) -> Result<gix::ObjectId, UnexpectedGitBackendError> {
+    let git_repo = get_git_repo(repo.store())?;
+    let target_id = gix::ObjectId::from_bytes_or_panic(target.as_bytes());
+    let object_id = to_git_ref_name(
+        GitRefKind::Tag,
+        name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO),
+    )
+    .and_then(|git_ref_name| git_repo.find_reference(git_ref_name.as_str()).ok())
+    .filter(|git_ref| peel_git_ref_id(git_ref) == Some(target_id))
+    .and_then(|git_ref| git_ref.inner.target.try_id().map(ToOwned::to_owned))
+    .unwrap_or(target_id);
+    Ok(object_id)
+}
+
+/// Pushes all notes refs in the view to the remote. The remote refs are only
+/// updated if that's a fast-forward.
+pub fn push_notes(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<GitPushStats, GitPushError> {
+    validate_remote_name(remote_name)?;
+    let refspecs = repo
+        .view()
+        .notes()
+        .iter()
+        .filter_map(|(name, target)| {
+            let id = target.as_normal()?;
+            let symbol = name.to_remote_symbol(REMOTE_NAME_FOR_LOCAL_GIT_REPO);
+            let git_ref_name = to_git_ref_name(GitRefKind::Note, symbol)?;
+            Some(RefSpec::fast_forward(id.hex(), git_ref_name.as_str()))
+        })
+        .collect_vec();
+    if refspecs.is_empty() {
+        return Ok(GitPushStats::default());
+    }
+    let refs_to_push = refspecs
+        .iter()
+        .map(|refspec| RefToPush {
+            refspec,
+            expected_location: None,
+        })
+        .collect_vec();
+    push_refs(
+        repo,
+        git_settings,
+        remote_name,
+        &refs_to_push,
+        &mut callbacks,
+    )
+}
+
+/// Tag update to be pushed to a Git remote.
+#[derive(Clone, Debug)]
+pub struct GitTagPushUpdate {
+    pub name: RefNameBuf,
+    /// Expected object of the tag on the remote, or `None` if the tag is
+    /// expected not to exist on the remote.
+    pub old_object_id: Option<gix::ObjectId>,
+    /// New object of the tag, or `None` to delete the tag.
+    pub new_object_id: Option<gix::ObjectId>,
+}
+
+/// Pushes the specified tags. The push fails for tags that unexpectedly moved
+/// on the remote.
+pub fn push_tags(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    updates: &[GitTagPushUpdate],
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<GitPushStats, GitPushError> {
+    validate_remote_name(remote_name)?;
+    let refspecs = updates
+        .iter()
+        .map(|update| {
+            let qualified_name = format!("refs/tags/{name}", name = update.name.as_str());
+            if let Some(new_id) = &update.new_object_id {
+                RefSpec::forced(new_id.to_string(), qualified_name)
+            } else {
+                RefSpec::delete(qualified_name)
+            }
+        })
+        .collect_vec();
+    let refs_to_push = iter::zip(&refspecs, updates)
+        .map(|(refspec, update)| RefToPush {
+            refspec,
+            expected_location: update.old_object_id.map(|id| id.to_string()),
+        })
+        .collect_vec();
+    push_refs(
+        repo,
+        git_settings,
+        remote_name,
+        &refs_to_push,
+        &mut callbacks,
+    )
+}
+
 #[non_exhaustive]
 #[derive(Default)]
 #[expect(clippy::type_complexity)]
@@ -36,6 +36,7 @@ use std::time::SystemTime;
 
 use async_trait::async_trait;
 use bstr::BStr;
+use bstr::ByteSlice as _;
 use futures::stream::BoxStream;
 use gix::bstr::BString;
 use gix::objs::CommitRefIter;
@@ -80,6 +81,9 @@ use crate::file_util;
 use crate::file_util::BadPathEncoding;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
+use crate::git::RemoteCallbacks;
+use crate::git_subprocess::GitSubprocessContext;
+use crate::git_subprocess::GitSubprocessError;
 use crate::hex_util;
 use crate::index::Index;
 use crate::lock::FileLock;
@@ -159,6 +163,16 @@ impl From<GitBackendError> for BackendError {
     }
 }
 
+/// Error that may occur when a missing object of a partial clone is fetched
+/// on demand.
+#[derive(Debug, Error)]
+#[error("Failed to fetch missing object from the promisor remote '{remote}' of the partial clone")]
+pub struct GitLazyFetchError {
+    pub remote: String,
+    #[source]
+    pub source: GitSubprocessError,
+}
+
 #[derive(Debug, Error)]
 pub enum GitGcError {
     #[error("Failed to run git gc command")]
@@ -358,6 +372,16 @@ impl GitBackend {
         self.base_repo.work_dir()
     }
 
+    /// Name of the remote from which missing objects are fetched if the
+    /// repository is a partial clone.
+    pub fn promisor_remote_name(&self) -> Option<String> {
+        let locked_repo = self.lock_git_repo();
+        let name = locked_repo
+            .config_snapshot()
+            .string("extensions.partialClone")?;
+        Some(name.to_str_lossy().into_owned())
+    }
+
     fn shallow_root_ids(&self, git_repo: &gix::Repository) -> BackendResult<&[CommitId]> {
         // The list of shallow roots is cached by gix, but it's still expensive
         // to stat file on every read_object() call. Refreshing shallow roots is
@@ -458,14 +482,33 @@ impl GitBackend {
     }
 
     fn read_file_sync(&self, id: &FileId) -> BackendResult<Vec<u8>> {
+        self.read_blob(id)
+    }
+
+    fn read_blob(&self, id: &impl ObjectId) -> BackendResult<Vec<u8>> {
         let git_blob_id = validate_git_object_id(id)?;
         let locked_repo = self.lock_git_repo();
-        let mut blob = locked_repo
-            .find_object(git_blob_id)
-            .map_err(|err| map_not_found_err(err, id))?
-            .try_into_blob()
-            .map_err(|err| to_read_object_err(err, id))?;
-        Ok(blob.take_data())
+        let err = match locked_repo.find_object(git_blob_id) {
+            Ok(object) => {
+                let mut blob = object
+                    .try_into_blob()
+                    .map_err(|err| to_read_object_err(err, id))?;
+                return Ok(blob.take_data());
+            }
+            Err(err) => err,
+        };
+        drop(locked_repo);
+        let is_not_found = matches!(err, gix::object::find::existing::Error::NotFound { .. });
+        match self.promisor_remote_name() {
+            Some(remote) if is_not_found => {
+                // Let git fetch the blob from the promisor remote.
+                let git_ctx = GitSubprocessContext::new(self.git_repo_path(), &self.git_executable);
+                git_ctx
+                    .spawn_cat_file_blob(git_blob_id)
+                    .map_err(|source| to_read_object_err(GitLazyFetchError { remote, source }, id))
+            }
+            _ => Err(map_not_found_err(err, id)),
+        }
     }
 
     fn new_diff_platform(&self) -> BackendResult<gix::diff::blob::Platform> {
@@ -692,7 +735,7 @@ fn signature_from_git(signature: gix::actor::SignatureRef) -> Signature {
     }
 }
 
-fn signature_to_git(signature: &Signature) -> gix::actor::Signature {
+pub(crate) fn signature_to_git(signature: &Signature) -> gix::actor::Signature {
     // git does not support empty names or emails
     let name = if !signature.name.is_empty() {
         &signature.name
@@ -1011,6 +1054,42 @@ impl Backend for GitBackend {
         Ok(Box::pin(Cursor::new(data)))
     }
 
+    async fn file_size(&self, _path: &RepoPath, id: &FileId) -> BackendResult<u64> {
+        let git_blob_id = validate_git_object_id(id)?;
+        let header = self.lock_git_repo().find_header(git_blob_id);
+        match header {
+            Ok(header) => Ok(header.size()),
+            // The blob may have to be fetched from the promisor remote.
+            Err(gix::object::find::existing::Error::NotFound { .. }) => {
+                Ok(self.read_blob(id)?.len() as u64)
+            }
+            Err(err) => Err(to_read_object_err(err, id)),
+        }
+    }
+
+    async fn prefetch_files(&self, ids: &[FileId]) -> BackendResult<()> {
+        let Some(remote) = self.promisor_remote_name() else {
+            return Ok(());
+        };
+        let mut missing_ids: Vec<_> = {
+            let locked_repo = self.lock_git_repo();
+            ids.iter()
+                .map(validate_git_object_id)
+                .filter_ok(|id| !locked_repo.has_object(id))
+                .try_collect()?
+        };
+        missing_ids.sort_unstable();
+        missing_ids.dedup();
+        if missing_ids.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(count = missing_ids.len(), "fetching missing blobs");
+        let git_ctx = GitSubprocessContext::new(self.git_repo_path(), &self.git_executable);
+        git_ctx
+            .spawn_fetch_objects(&remote, &missing_ids, &mut RemoteCallbacks::default())
+            .map_err(|source| BackendError::Other(GitLazyFetchError { remote, source }.into()))
+    }
+
     async fn write_file(
         &self,
         _path: &RepoPath,
@@ -1029,15 +1108,9 @@ impl Backend for GitBackend {
     }
 
     async fn read_symlink(&self, _path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
-        let git_blob_id = validate_git_object_id(id)?;
-        let locked_repo = self.lock_git_repo();
-        let mut blob = locked_repo
-            .find_object(git_blob_id)
-            .map_err(|err| map_not_found_err(err, id))?
-            .try_into_blob()
-            .map_err(|err| to_read_object_err(err, id))?;
-        let target = String::from_utf8(blob.take_data())
-            .map_err(|err| to_invalid_utf8_err(err.utf8_error(), id))?;
+        let data = self.read_blob(id)?;
+        let target =
+            String::from_utf8(data).map_err(|err| to_invalid_utf8_err(err.utf8_error(), id))?;
         Ok(target)
     }
 
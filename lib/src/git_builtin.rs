@@ -0,0 +1,1820 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builtin Git transport
+//!
+//! Fetches from and pushes to Git remotes without running the `git`
+//! executable:
+//!
+//! - local repositories are read and written in-process,
+//! - `ssh://`, `git://`, `http://` and `https://` remotes are fetched from with
+//!   gix's network client, and pushed to through gix's transport. HTTP requests
+//!   are made in-process, and credentials are looked up with the credential
+//!   helpers configured in Git.
+//!
+//! Shallow and partial clones aren't supported.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek as _;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bstr::BStr;
+use bstr::BString;
+use bstr::ByteSlice as _;
+use gix::features::progress;
+use gix::features::progress::prodash;
+use gix::protocol::handshake;
+use gix::protocol::transport::client::Capabilities;
+use gix::protocol::transport::client::Transport;
+use gix::protocol::transport::client::TransportWithoutIO as _;
+use gix::protocol::transport::packetline::BandRef;
+use gix::protocol::transport::packetline::PacketLineRef;
+use gix::protocol::transport::packetline::StreamingPeekableIter;
+use gix::protocol::transport::Service;
+use gix::remote::fetch::Tags;
+use gix::ObjectId;
+use gix_pack::data::output::count::objects::ObjectExpansion;
+use itertools::Itertools as _;
+use thiserror::Error;
+
+use crate::git::GitPushStats;
+use crate::git::Progress;
+use crate::git::RefSpec;
+use crate::git::RefToPush;
+use crate::git::RemoteCallbacks;
+use crate::git_backend::GitBackend;
+use crate::ref_name::GitRefNameBuf;
+use crate::ref_name::RefNameBuf;
+use crate::ref_name::RemoteName;
+
+/// Error originating from the builtin Git transport
+#[derive(Error, Debug)]
+pub enum GitBuiltinError {
+    #[error("{0} is not supported by the builtin Git transport")]
+    Unsupported(String),
+    #[error("Failed to connect to {url}")]
+    Connect {
+        url: String,
+        #[source]
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Unexpected response from the remote: {0}")]
+    Protocol(String),
+    #[error("Remote error: {0}")]
+    Remote(String),
+    #[error("Authentication failed for {0}")]
+    Authentication(String),
+    #[error("Failed to fetch from the remote")]
+    Fetch(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Failed to receive pack")]
+    ReceivePack(#[source] gix_pack::bundle::write::Error),
+    #[error("Failed to create pack")]
+    CreatePack(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Failed to update Git refs")]
+    UpdateRefs(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Unexpected Git error")]
+    Git(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("I/O error while talking to the remote")]
+    Io(#[from] io::Error),
+}
+
+fn git_error(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> GitBuiltinError {
+    GitBuiltinError::Git(err.into())
+}
+
+fn protocol_error(message: impl Into<String>) -> GitBuiltinError {
+    GitBuiltinError::Protocol(message.into())
+}
+
+/// Context for talking to Git remotes without the `git` executable
+pub(crate) struct GitBuiltinContext {
+    git_repo: Box<gix::Repository>,
+}
+
+impl GitBuiltinContext {
+    pub(crate) fn from_git_backend(git_backend: &GitBackend) -> Self {
+        GitBuiltinContext {
+            git_repo: Box::new(git_backend.git_repo()),
+        }
+    }
+
+    /// Fetches the refs matching `refspecs` and prunes the local refs that no
+    /// longer exist on the remote. Tags pointing to fetched commits are
+    /// fetched too.
+    ///
+    /// Refspecs which don't exist on the remote are treated as deleted.
+    pub(crate) fn fetch(
+        &self,
+        remote_name: &RemoteName,
+        refspecs: &[RefSpec],
+        callbacks: &mut RemoteCallbacks<'_>,
+        depth: Option<crate::git::GitFetchDepth>,
+    ) -> Result<(), GitBuiltinError> {
+        if depth.is_some() || self.git_repo.is_shallow() {
+            return Err(GitBuiltinError::Unsupported("Shallow fetch".into()));
+        }
+        if self
+            .git_repo
+            .config_snapshot()
+            .string("extensions.partialClone")
+            .is_some()
+        {
+            return Err(GitBuiltinError::Unsupported("Partial clone".into()));
+        }
+        self.fetch_refs(remote_name, refspecs, true, callbacks)
+    }
+
+    /// Fetches notes refs, only updating the local refs on fast-forward.
+    pub(crate) fn fetch_notes(
+        &self,
+        remote_name: &RemoteName,
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(), GitBuiltinError> {
+        let refspec = RefSpec::fast_forward("refs/notes/*", "refs/notes/*");
+        self.fetch_refs(remote_name, &[refspec], false, callbacks)
+    }
+
+    /// Queries the remote for its default branch.
+    pub(crate) fn remote_show(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Option<RefNameBuf>, GitBuiltinError> {
+        let remote_refs = self.list_remote_refs(remote_name, "HEAD")?;
+        let default_branch = remote_refs
+            .head_target
+            .as_ref()
+            .and_then(|target| target.strip_prefix(b"refs/heads/"))
+            .map(|name| name.to_str_lossy().as_ref().into());
+        Ok(default_branch)
+    }
+
+    /// Lists the tags on the remote as `(id, ref)` pairs. Annotated tags are
+    /// followed by `(peeled_id, ref^{})` pairs.
+    pub(crate) fn ls_remote_tags(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<(ObjectId, String)>, GitBuiltinError> {
+        let remote_refs = self.list_remote_refs(remote_name, "refs/tags/*:refs/tags/*")?;
+        let mut tags = vec![];
+        for remote_ref in &remote_refs.refs {
+            if !remote_ref.name.starts_with(b"refs/tags/") {
+                continue;
+            }
+            let name = remote_ref.name.to_str_lossy().into_owned();
+            if let Some(peeled_id) = remote_ref.peeled_id {
+                tags.push((remote_ref.id, name.clone()));
+                tags.push((peeled_id, format!("{name}^{{}}")));
+            } else {
+                tags.push((remote_ref.id, name));
+            }
+        }
+        Ok(tags)
+    }
+
+    /// Pushes the references to the remote.
+    ///
+    /// Forced updates and deletions are checked against the expected location
+    /// like `git push --force-with-lease`. The other updates must be
+    /// fast-forwards.
+    pub(crate) fn push(
+        &self,
+        remote_name: &RemoteName,
+        references: &[RefToPush],
+        push_options: &[String],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<GitPushStats, GitBuiltinError> {
+        let mut stats = GitPushStats::default();
+        // Refs which are up to date on the remote after the push
+        let mut pushed = vec![];
+        let (commands, results) =
+            match self.open_remote(remote_name, gix::remote::Direction::Push)? {
+                RemoteRepo::Local(remote_repo) => {
+                    let remote_refs = local_remote_refs(&remote_repo)?;
+                    let commands =
+                        self.push_commands(&remote_refs, references, &mut stats, &mut pushed)?;
+                    let results =
+                        self.push_to_local(&remote_repo, &remote_refs, &commands, callbacks)?;
+                    (commands, results)
+                }
+                RemoteRepo::Network(url) => {
+                    let mut transport = connect_transport(
+                        &self.git_repo,
+                        remote_name,
+                        &url,
+                        gix::protocol::transport::Protocol::V1,
+                    )?;
+                    let outcome =
+                        receive_pack_handshake(&self.git_repo, &mut transport, &url, callbacks)?;
+                    let remote_refs =
+                        RemoteRefs::from_handshake(outcome.refs.as_deref().unwrap_or_default());
+                    let commands =
+                        self.push_commands(&remote_refs, references, &mut stats, &mut pushed)?;
+                    let results = self.push_to_network(
+                        &mut transport,
+                        &outcome.capabilities,
+                        &remote_refs,
+                        &commands,
+                        push_options,
+                        callbacks,
+                    )?;
+                    (commands, results)
+                }
+            };
+        for (name, result) in results {
+            let git_ref_name = GitRefNameBuf::from(name.to_str_lossy().into_owned());
+            match result {
+                Ok(()) => {
+                    let new_id = commands
+                        .iter()
+                        .find(|command| command.name == name)
+                        .and_then(|command| command.new_id);
+                    stats.pushed.push(git_ref_name);
+                    pushed.push((name, new_id));
+                }
+                Err(reason) => stats.remote_rejected.push((git_ref_name, Some(reason))),
+            }
+        }
+        self.update_tracking_refs(remote_name, &pushed)?;
+        Ok(stats)
+    }
+
+    /// Determines the ref updates to send to the remote. The references which
+    /// are already up to date or are rejected locally are recorded in `stats`.
+    fn push_commands(
+        &self,
+        remote_refs: &RemoteRefs,
+        references: &[RefToPush],
+        stats: &mut GitPushStats,
+        pushed: &mut Vec<(BString, Option<ObjectId>)>,
+    ) -> Result<Vec<PushCommand>, GitBuiltinError> {
+        let mut commands = vec![];
+        for reference in references {
+            let name = reference.refspec.destination();
+            let new_id = reference
+                .refspec
+                .source()
+                .map(parse_object_id)
+                .transpose()?;
+            let old_id = remote_refs
+                .find_ref(name.as_bytes().as_bstr())
+                .map(|remote_ref| remote_ref.id);
+            if old_id == new_id {
+                // Already up to date, which Git accepts regardless of the lease
+                stats.pushed.push(name.into());
+                pushed.push((BString::from(name), new_id));
+                continue;
+            }
+            if reference.is_fast_forward() {
+                if let (Some(old_id), Some(new_id)) = (old_id, new_id) {
+                    if !self.git_repo.has_object(old_id) {
+                        stats
+                            .rejected
+                            .push((name.into(), Some("fetch first".into())));
+                        continue;
+                    } else if !is_ancestor(&self.git_repo, old_id, new_id) {
+                        stats
+                            .rejected
+                            .push((name.into(), Some("non-fast-forward".into())));
+                        continue;
+                    }
+                }
+            } else {
+                let expected_id = reference
+                    .expected_location
+                    .as_deref()
+                    .map(parse_object_id)
+                    .transpose()?;
+                if old_id != expected_id {
+                    stats
+                        .rejected
+                        .push((name.into(), Some("stale info".into())));
+                    continue;
+                }
+            }
+            commands.push(PushCommand {
+                name: name.into(),
+                old_id,
+                new_id,
+            });
+        }
+        Ok(commands)
+    }
+
+    /// Copies the objects to the local remote and updates its refs.
+    fn push_to_local(
+        &self,
+        remote_repo: &gix::Repository,
+        remote_refs: &RemoteRefs,
+        commands: &[PushCommand],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<Vec<PushResult>, GitBuiltinError> {
+        if commands.is_empty() {
+            return Ok(vec![]);
+        }
+        let tips = commands
+            .iter()
+            .filter_map(|command| command.new_id)
+            .unique()
+            .collect_vec();
+        let keep_path = if tips.is_empty() {
+            None
+        } else {
+            let ends = remote_refs.common_ids(&self.git_repo);
+            let mut file = tempfile::tempfile()?;
+            write_pack(&self.git_repo, &tips, &ends, &mut file, &mut |fraction| {
+                report_push_progress(callbacks, fraction);
+            })?;
+            file.rewind()?;
+            receive_pack(remote_repo, &mut BufReader::new(file), &mut |_| {})?
+        };
+        let results = update_local_remote_refs(remote_repo, commands);
+        if let Some(path) = keep_path {
+            fs::remove_file(path).ok();
+        }
+        Ok(results)
+    }
+
+    /// Sends the ref updates to `git-receive-pack` along with the objects the
+    /// remote doesn't have.
+    fn push_to_network(
+        &self,
+        transport: &mut Box<dyn Transport + Send>,
+        capabilities: &Capabilities,
+        remote_refs: &RemoteRefs,
+        commands: &[PushCommand],
+        push_options: &[String],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<Vec<PushResult>, GitBuiltinError> {
+        if commands.is_empty() {
+            gix::protocol::indicate_end_of_interaction(transport, false)
+                .map_err(|err| protocol_error(err.to_string()))?;
+            return Ok(vec![]);
+        }
+        let tips = commands
+            .iter()
+            .filter_map(|command| command.new_id)
+            .unique()
+            .collect_vec();
+        let ends = remote_refs.common_ids(&self.git_repo);
+        let head = receive_pack_request(capabilities, commands, push_options)?;
+        let use_sideband = capabilities.contains("side-band-64k");
+        let (mut writer, mut reader) = transport
+            .request(
+                gix::protocol::transport::client::WriteMode::Binary,
+                gix::protocol::transport::client::MessageKind::Flush,
+                false,
+            )
+            .map_err(|err| protocol_error(err.to_string()))?
+            .into_parts();
+        writer.write_all(&head)?;
+        if !tips.is_empty() {
+            write_pack(&self.git_repo, &tips, &ends, &mut writer, &mut |fraction| {
+                report_push_progress(callbacks, fraction);
+            })?;
+        }
+        writer.flush()?;
+        drop(writer);
+        read_push_report(&mut reader, use_sideband, callbacks)
+    }
+
+    /// Updates the remote-tracking refs of the pushed refs as `git push` does.
+    fn update_tracking_refs(
+        &self,
+        remote_name: &RemoteName,
+        pushed: &[(BString, Option<ObjectId>)],
+    ) -> Result<(), GitBuiltinError> {
+        let remote = self
+            .git_repo
+            .find_remote(remote_name.as_str())
+            .map_err(git_error)?;
+        let mut edits = vec![];
+        for (name, new_id) in pushed {
+            let tracking_name = remote
+                .refspecs(gix::remote::Direction::Fetch)
+                .iter()
+                .find_map(|refspec| {
+                    let refspec = refspec.to_ref();
+                    let source = refspec.source()?.to_str().ok()?;
+                    let destination = refspec.destination()?.to_str().ok()?;
+                    map_ref_name(source, destination, name.as_ref())
+                });
+            let Some(tracking_name) = tracking_name else {
+                continue;
+            };
+            let old_id = self.local_ref_id(tracking_name.as_ref())?;
+            if old_id != *new_id {
+                edits.push(update_ref_edit(
+                    tracking_name.as_ref(),
+                    old_id,
+                    *new_id,
+                    "update by push",
+                )?);
+            }
+        }
+        if !edits.is_empty() {
+            self.git_repo
+                .edit_references(edits)
+                .map_err(|err| GitBuiltinError::UpdateRefs(err.into()))?;
+        }
+        Ok(())
+    }
+
+    fn fetch_refs(
+        &self,
+        remote_name: &RemoteName,
+        refspecs: &[RefSpec],
+        prune_and_follow_tags: bool,
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(), GitBuiltinError> {
+        let mut keep_paths = vec![];
+        // Destination ref name to (new id, forced)
+        let mut updates;
+        match self.open_remote(remote_name, gix::remote::Direction::Fetch)? {
+            RemoteRepo::Local(remote_repo) => {
+                let remote_refs = local_remote_refs(&remote_repo)?;
+                updates = refspec_updates(refspecs, &remote_refs);
+                let wants = updates.values().map(|(id, _)| *id).collect_vec();
+                keep_paths.extend(self.fetch_from_local(&remote_repo, &wants, callbacks)?);
+                if prune_and_follow_tags {
+                    // Like Git, fetch the tags pointing to commits we have.
+                    let mut tags = vec![];
+                    for remote_ref in &remote_refs.refs {
+                        if remote_ref.name.starts_with(b"refs/tags/")
+                            && !updates.contains_key(&remote_ref.name)
+                            && self.git_repo.has_object(remote_ref.target_id())
+                            && self.local_ref_id(remote_ref.name.as_ref())?.is_none()
+                        {
+                            tags.push(remote_ref);
+                        }
+                    }
+                    let tag_ids = tags.iter().map(|remote_ref| remote_ref.id).collect_vec();
+                    keep_paths.extend(self.fetch_from_local(&remote_repo, &tag_ids, callbacks)?);
+                    for remote_ref in tags {
+                        updates.insert(remote_ref.name.clone(), (remote_ref.id, false));
+                    }
+                }
+            }
+            RemoteRepo::Network(url) => {
+                // gix updates the refs matching the refspecs, and creates the
+                // tags pointing to the fetched commits as Git does. The refs
+                // which no longer exist on the remote are pruned below.
+                let tags = if prune_and_follow_tags {
+                    Tags::Included
+                } else {
+                    Tags::None
+                };
+                let specs = refspecs
+                    .iter()
+                    .filter(|refspec| refspec.source().is_some())
+                    .map(|refspec| BString::from(refspec.to_git_format()))
+                    .collect_vec();
+                let (remote_refs, keep_path) =
+                    self.fetch_from_network(remote_name, &url, specs, tags, true, callbacks)?;
+                updates = refspec_updates(refspecs, &remote_refs);
+                keep_paths.extend(keep_path);
+            }
+        }
+
+        let mut edits = vec![];
+        for (name, &(new_id, forced)) in &updates {
+            let old_id = self.local_ref_id(name.as_ref())?;
+            if old_id == Some(new_id) {
+                continue;
+            }
+            if let Some(old_id) = old_id {
+                if !forced && !is_ancestor(&self.git_repo, old_id, new_id) {
+                    tracing::debug!(%name, "skipping non-fast-forward update");
+                    continue;
+                }
+            }
+            edits.push(update_ref_edit(
+                name.as_ref(),
+                old_id,
+                Some(new_id),
+                "fetch",
+            )?);
+        }
+        if prune_and_follow_tags {
+            let mut deletes = BTreeMap::new();
+            for refspec in refspecs {
+                for (name, id) in self.local_refs_matching(refspec.destination())? {
+                    if !updates.contains_key(&name) {
+                        deletes.insert(name, id);
+                    }
+                }
+            }
+            for (name, old_id) in deletes {
+                edits.push(update_ref_edit(name.as_ref(), Some(old_id), None, "fetch")?);
+            }
+        }
+        if !edits.is_empty() {
+            self.git_repo
+                .edit_references(edits)
+                .map_err(|err| GitBuiltinError::UpdateRefs(err.into()))?;
+        }
+        // The fetched objects are now protected by the refs.
+        for path in keep_paths {
+            fs::remove_file(path).ok();
+        }
+        Ok(())
+    }
+
+    /// Copies the `wants` objects which we don't have from the local remote.
+    /// Returns the path to the `.keep` file of the received pack.
+    fn fetch_from_local(
+        &self,
+        remote_repo: &gix::Repository,
+        wants: &[ObjectId],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<Option<PathBuf>, GitBuiltinError> {
+        let wants = wants
+            .iter()
+            .filter(|id| !self.git_repo.has_object(id))
+            .copied()
+            .unique()
+            .collect_vec();
+        if wants.is_empty() {
+            return Ok(None);
+        }
+        let ends = self
+            .local_ref_tips()?
+            .into_iter()
+            .filter(|id| remote_repo.has_object(id))
+            .collect_vec();
+        let mut file = tempfile::tempfile()?;
+        write_pack(remote_repo, &wants, &ends, &mut file, &mut |_| {})?;
+        file.rewind()?;
+        receive_pack(&self.git_repo, &mut BufReader::new(file), &mut |progress| {
+            if let Some(callback) = callbacks.progress.as_deref_mut() {
+                callback(progress);
+            }
+        })
+    }
+
+    /// Lists the refs of the network remote matching the `refspecs`. If
+    /// `receive_objects` is true, receives the objects we don't have and
+    /// updates the local refs as the `refspecs` say.
+    /// Returns the path to the `.keep` file of the received pack.
+    ///
+    /// gix requires the progress to be `Send + Sync`, which the `callbacks`
+    /// aren't, so the remote is talked to from another thread.
+    // The error type is defined by gix.
+    #[expect(clippy::result_large_err)]
+    fn fetch_from_network(
+        &self,
+        remote_name: &RemoteName,
+        url: &gix::Url,
+        refspecs: Vec<BString>,
+        tags: Tags,
+        receive_objects: bool,
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(RemoteRefs, Option<PathBuf>), GitBuiltinError> {
+        let git_repo = (*self.git_repo).clone();
+        run_with_callbacks(callbacks, move |progress, requests| {
+            let mut remote = git_repo
+                .find_remote(remote_name.as_str())
+                .map_err(git_error)?;
+            remote
+                .replace_refspecs(&refspecs, gix::remote::Direction::Fetch)
+                .map_err(git_error)?;
+            let remote = remote.with_fetch_tags(tags);
+            let (_, version) = remote
+                .sanitized_url_and_version(gix::remote::Direction::Fetch)
+                .map_err(git_error)?;
+            let transport = connect_transport(&git_repo, remote_name, url, version)?;
+            let mut credentials = CredentialLookup::new(&git_repo, url)?;
+            let connection = remote
+                .to_connection_with_transport(transport)
+                .with_credentials(move |action| {
+                    credentials.authenticate(action, |url| {
+                        let (reply, response) = mpsc::channel();
+                        let url = url.to_owned();
+                        requests
+                            .send(CallbackRequest::UsernamePassword { url, reply })
+                            .ok()?;
+                        response.recv().ok().flatten()
+                    })
+                });
+            let prepare = connection
+                .prepare_fetch(progress::Discard, Default::default())
+                .map_err(|err| match err {
+                    gix::remote::fetch::prepare::Error::RefMap(
+                        gix::remote::ref_map::Error::Handshake(err),
+                    ) => handshake_error(url, err),
+                    err => GitBuiltinError::Fetch(err.into()),
+                })?;
+            let remote_refs = RemoteRefs::from_handshake(&prepare.ref_map().remote_refs);
+            // gix refuses to fetch if no remote ref matches the refspecs.
+            if !receive_objects || prepare.ref_map().mappings.is_empty() {
+                return Ok((remote_refs, None));
+            }
+            let outcome = prepare
+                .receive(progress, &AtomicBool::new(false))
+                .map_err(|err| GitBuiltinError::Fetch(err.into()))?;
+            let keep_path = match outcome.status {
+                gix::remote::fetch::Status::Change {
+                    write_pack_bundle, ..
+                } => write_pack_bundle.keep_path,
+                gix::remote::fetch::Status::NoPackReceived { .. } => None,
+            };
+            Ok((remote_refs, keep_path))
+        })
+    }
+
+    /// Lists the refs of the remote matching the `refspec`.
+    fn list_remote_refs(
+        &self,
+        remote_name: &RemoteName,
+        refspec: &str,
+    ) -> Result<RemoteRefs, GitBuiltinError> {
+        match self.open_remote(remote_name, gix::remote::Direction::Fetch)? {
+            RemoteRepo::Local(remote_repo) => local_remote_refs(&remote_repo),
+            RemoteRepo::Network(url) => {
+                let (remote_refs, _) = self.fetch_from_network(
+                    remote_name,
+                    &url,
+                    vec![refspec.into()],
+                    Tags::None,
+                    false,
+                    &mut RemoteCallbacks::default(),
+                )?;
+                Ok(remote_refs)
+            }
+        }
+    }
+
+    fn open_remote(
+        &self,
+        remote_name: &RemoteName,
+        direction: gix::remote::Direction,
+    ) -> Result<RemoteRepo, GitBuiltinError> {
+        let remote = self
+            .git_repo
+            .find_remote(remote_name.as_str())
+            .map_err(git_error)?;
+        let url = remote
+            .url(direction)
+            .ok_or_else(|| protocol_error("No URL configured for the remote"))?
+            .clone();
+        match &url.scheme {
+            gix::url::Scheme::File => {
+                // gix would run `git-upload-pack` and `git-receive-pack` to
+                // talk to local repositories.
+                let path = gix::path::from_bstr(url.path.as_bstr());
+                let repo = gix::open(path.as_ref()).map_err(|err| GitBuiltinError::Connect {
+                    url: display_url(&url),
+                    error: err.into(),
+                })?;
+                Ok(RemoteRepo::Local(Box::new(repo)))
+            }
+            gix::url::Scheme::Ssh
+            | gix::url::Scheme::Git
+            | gix::url::Scheme::Http
+            | gix::url::Scheme::Https => Ok(RemoteRepo::Network(url)),
+            gix::url::Scheme::Ext(scheme) => Err(GitBuiltinError::Unsupported(format!(
+                "The URL scheme '{scheme}'"
+            ))),
+        }
+    }
+
+    /// Returns the target of the direct ref `name`.
+    fn local_ref_id(&self, name: &BStr) -> Result<Option<ObjectId>, GitBuiltinError> {
+        let reference = self.git_repo.try_find_reference(name).map_err(git_error)?;
+        Ok(reference.and_then(|reference| reference.target().try_id().map(ToOwned::to_owned)))
+    }
+
+    /// Returns the direct refs matching the refspec `pattern`.
+    fn local_refs_matching(
+        &self,
+        pattern: &str,
+    ) -> Result<Vec<(BString, ObjectId)>, GitBuiltinError> {
+        let prefix = match pattern.split_once('*') {
+            Some((prefix, _)) => prefix,
+            None => pattern,
+        };
+        let Some((directory, _)) = prefix.rsplit_once('/') else {
+            return Ok(vec![]);
+        };
+        let platform = self.git_repo.references().map_err(git_error)?;
+        let mut refs = vec![];
+        for reference in platform
+            .prefixed(format!("{directory}/").as_str())
+            .map_err(git_error)?
+        {
+            let reference = reference.map_err(git_error)?;
+            let name = reference.name().as_bstr();
+            let target = reference.target();
+            let Some(id) = target.try_id() else {
+                continue;
+            };
+            if map_ref_name(pattern, pattern, name).is_some() {
+                refs.push((name.to_owned(), id.to_owned()));
+            }
+        }
+        Ok(refs)
+    }
+
+    /// Returns the commits pointed to by the local refs, which the local
+    /// remote doesn't have to send.
+    fn local_ref_tips(&self) -> Result<Vec<ObjectId>, GitBuiltinError> {
+        let platform = self.git_repo.references().map_err(git_error)?;
+        let mut ids = vec![];
+        for prefix in ["refs/heads/", "refs/remotes/", "refs/tags/", "refs/notes/"] {
+            for reference in platform.prefixed(prefix).map_err(git_error)? {
+                let mut reference = reference.map_err(git_error)?;
+                if let Ok(id) = reference.peel_to_id_in_place() {
+                    ids.push(id.detach());
+                }
+            }
+        }
+        Ok(ids.into_iter().unique().collect())
+    }
+}
+
+/// Remote repository to fetch from or push to
+enum RemoteRepo {
+    /// Repository on the local file system, which is accessed in-process.
+    Local(Box<gix::Repository>),
+    /// Repository accessed through gix's network client.
+    Network(gix::Url),
+}
+
+/// Returns the `url` without the credentials.
+fn display_url(url: &gix::Url) -> String {
+    let mut url = url.clone();
+    url.set_user(None);
+    url.set_password(None);
+    url.to_bstring().to_string()
+}
+
+/// Determines the ssh command with the same precedence as Git.
+fn ssh_options(repo: &gix::Repository) -> gix::protocol::transport::client::ssh::connect::Options {
+    let mut options = gix::protocol::transport::client::ssh::connect::Options::default();
+    if let Some(command) = std::env::var_os("GIT_SSH_COMMAND") {
+        options.command = Some(command);
+    } else if let Some(command) = repo.config_snapshot().string("core.sshCommand") {
+        options.command = Some(gix::path::from_bstr(command.as_ref()).into_owned().into());
+    } else if let Some(command) = std::env::var_os("GIT_SSH") {
+        options.command = Some(command);
+        options.disallow_shell = true;
+    }
+    options
+}
+
+/// Connects to the network remote at `url`, and configures the transport
+/// from the Git config like the `http.*` variables.
+fn connect_transport(
+    repo: &gix::Repository,
+    remote_name: &RemoteName,
+    url: &gix::Url,
+    version: gix::protocol::transport::Protocol,
+) -> Result<Box<dyn Transport + Send>, GitBuiltinError> {
+    let connect_error =
+        |error: Box<dyn std::error::Error + Send + Sync>| GitBuiltinError::Connect {
+            url: display_url(url),
+            error,
+        };
+    let options = gix::protocol::transport::client::connect::Options {
+        version,
+        ssh: ssh_options(repo),
+        trace: false,
+    };
+    let mut transport = gix::protocol::transport::connect(url.clone(), options)
+        .map_err(|err| connect_error(err.into()))?;
+    let config = repo
+        .transport_options(
+            url.to_bstring().as_bstr(),
+            Some(remote_name.as_str().into()),
+        )
+        .map_err(git_error)?;
+    if let Some(config) = config {
+        transport.configure(&*config).map_err(connect_error)?;
+    }
+    Ok(transport)
+}
+
+/// Performs the `git-receive-pack` handshake, asking for credentials if the
+/// remote requires them.
+// The error type is defined by gix.
+#[expect(clippy::result_large_err)]
+fn receive_pack_handshake(
+    repo: &gix::Repository,
+    transport: &mut Box<dyn Transport + Send>,
+    url: &gix::Url,
+    callbacks: &mut RemoteCallbacks<'_>,
+) -> Result<handshake::Outcome, GitBuiltinError> {
+    let mut credentials = CredentialLookup::new(repo, url)?;
+    let get_username_password = &mut callbacks.get_username_password;
+    gix::protocol::handshake(
+        transport,
+        Service::ReceivePack,
+        |action| {
+            credentials.authenticate(action, |url| {
+                let callback = get_username_password.as_deref_mut()?;
+                callback(url)
+            })
+        },
+        vec![],
+        &mut progress::Discard,
+    )
+    .map_err(|err| handshake_error(url, err))
+}
+
+fn handshake_error(url: &gix::Url, err: handshake::Error) -> GitBuiltinError {
+    match err {
+        handshake::Error::Credentials(_)
+        | handshake::Error::EmptyCredentials
+        | handshake::Error::InvalidCredentials { .. } => {
+            GitBuiltinError::Authentication(display_url(url))
+        }
+        handshake::Error::Transport(err) => GitBuiltinError::Connect {
+            url: display_url(url),
+            error: err.into(),
+        },
+        err => protocol_error(err.to_string()),
+    }
+}
+
+/// Looks up credentials with the credential helpers configured in Git, and
+/// asks the user if none of them has any.
+struct CredentialLookup {
+    cascade: gix::credentials::helper::Cascade,
+    prompt_options: gix::prompt::Options<'static>,
+    /// URL to ask the user for credentials for
+    url: String,
+}
+
+impl CredentialLookup {
+    fn new(repo: &gix::Repository, url: &gix::Url) -> Result<Self, GitBuiltinError> {
+        let (cascade, _, mut prompt_options) = repo
+            .config_snapshot()
+            .credential_helpers(url.clone())
+            .map_err(git_error)?;
+        // The user is asked through the callbacks instead of the terminal.
+        prompt_options.mode = gix::prompt::Mode::Disable;
+        Ok(CredentialLookup {
+            cascade,
+            prompt_options,
+            url: display_url(url),
+        })
+    }
+
+    // The error type is defined by gix.
+    #[expect(clippy::result_large_err)]
+    fn authenticate(
+        &mut self,
+        action: gix::credentials::helper::Action,
+        ask_username_password: impl FnOnce(&str) -> Option<(String, String)>,
+    ) -> gix::credentials::protocol::Result {
+        let context = match &action {
+            gix::credentials::helper::Action::Get(context) => Some(context.clone()),
+            _ => None,
+        };
+        let result = self.cascade.invoke(action, self.prompt_options.clone());
+        match (result, context) {
+            (Err(gix::credentials::protocol::Error::IdentityMissing { .. }), Some(context)) => {
+                let Some((username, password)) = ask_username_password(&self.url) else {
+                    return Err(gix::credentials::protocol::Error::IdentityMissing { context });
+                };
+                let next = gix::credentials::protocol::Context {
+                    username: Some(username.clone()),
+                    password: Some(password.clone()),
+                    ..context
+                };
+                Ok(Some(gix::credentials::protocol::Outcome {
+                    identity: gix::sec::identity::Account { username, password },
+                    next: next.into(),
+                }))
+            }
+            (result, _) => result,
+        }
+    }
+}
+
+/// Request from the thread talking to the remote to the thread owning the
+/// callbacks
+enum CallbackRequest {
+    Sideband(Vec<u8>),
+    UsernamePassword {
+        url: String,
+        reply: mpsc::Sender<Option<(String, String)>>,
+    },
+}
+
+/// Runs `task` in another thread, and passes its progress and requests to
+/// the `callbacks` until it completes.
+fn run_with_callbacks<T: Send>(
+    callbacks: &mut RemoteCallbacks<'_>,
+    task: impl FnOnce(TransferProgress, mpsc::Sender<CallbackRequest>) -> T + Send,
+) -> T {
+    let counts = Arc::new(TransferCounts::default());
+    let (sender, receiver) = mpsc::channel();
+    let progress = TransferProgress::new(counts.clone(), ProgressKind::Other, Some(sender.clone()));
+    std::thread::scope(|scope| {
+        let task = scope.spawn(move || task(progress, sender));
+        // The channel is disconnected once the task has dropped the senders.
+        loop {
+            match receiver.recv_timeout(Duration::from_millis(100)) {
+                Ok(CallbackRequest::Sideband(message)) => {
+                    if let Some(callback) = callbacks.sideband_progress.as_deref_mut() {
+                        callback(&message);
+                    }
+                }
+                Ok(CallbackRequest::UsernamePassword { url, reply }) => {
+                    let credential = callbacks
+                        .get_username_password
+                        .as_deref_mut()
+                        .and_then(|callback| callback(&url));
+                    reply.send(credential).ok();
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            if let (Some(callback), Some(progress)) =
+                (callbacks.progress.as_deref_mut(), counts.to_progress())
+            {
+                callback(&progress);
+            }
+        }
+        task.join()
+            .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+    })
+}
+
+/// Maps the ref `name` through the refspec `source` and `destination`, which
+/// may contain one `*` matching any part of the name.
+fn map_ref_name(source: &str, destination: &str, name: &BStr) -> Option<BString> {
+    match (source.split_once('*'), destination.split_once('*')) {
+        (Some((prefix, suffix)), Some((dest_prefix, dest_suffix))) => {
+            let matched = name.strip_prefix(prefix.as_bytes())?;
+            let matched = matched.strip_suffix(suffix.as_bytes())?;
+            let mut mapped = BString::from(dest_prefix);
+            mapped.extend_from_slice(matched);
+            mapped.extend_from_slice(dest_suffix.as_bytes());
+            Some(mapped)
+        }
+        (None, None) => (name == source).then(|| destination.into()),
+        _ => None,
+    }
+}
+
+/// Maps the remote refs through the `refspecs`. Returns the destination ref
+/// names with the new ids and whether the updates are forced.
+fn refspec_updates(
+    refspecs: &[RefSpec],
+    remote_refs: &RemoteRefs,
+) -> BTreeMap<BString, (ObjectId, bool)> {
+    let mut updates = BTreeMap::new();
+    for refspec in refspecs {
+        let source = refspec.source().unwrap_or_default();
+        for remote_ref in &remote_refs.refs {
+            if let Some(destination) =
+                map_ref_name(source, refspec.destination(), remote_ref.name.as_ref())
+            {
+                updates.insert(destination, (remote_ref.id, refspec.is_forced()));
+            }
+        }
+    }
+    updates
+}
+
+fn parse_object_id(hex: &str) -> Result<ObjectId, GitBuiltinError> {
+    ObjectId::from_hex(hex.as_bytes())
+        .map_err(|_| protocol_error(format!("Invalid object id: {hex}")))
+}
+
+fn is_ancestor(repo: &gix::Repository, ancestor: ObjectId, descendant: ObjectId) -> bool {
+    repo.merge_base(ancestor, descendant)
+        .is_ok_and(|base| base.detach() == ancestor)
+}
+
+fn update_ref_edit(
+    name: &BStr,
+    old_id: Option<ObjectId>,
+    new_id: Option<ObjectId>,
+    message: &str,
+) -> Result<gix::refs::transaction::RefEdit, GitBuiltinError> {
+    let expected = match old_id {
+        Some(id) => {
+            gix::refs::transaction::PreviousValue::MustExistAndMatch(gix::refs::Target::Object(id))
+        }
+        None => gix::refs::transaction::PreviousValue::MustNotExist,
+    };
+    let change = match new_id {
+        Some(id) => gix::refs::transaction::Change::Update {
+            log: gix::refs::transaction::LogChange {
+                mode: gix::refs::transaction::RefLog::AndReference,
+                force_create_reflog: false,
+                message: message.into(),
+            },
+            expected,
+            new: gix::refs::Target::Object(id),
+        },
+        None => gix::refs::transaction::Change::Delete {
+            expected,
+            log: gix::refs::transaction::RefLog::AndReference,
+        },
+    };
+    Ok(gix::refs::transaction::RefEdit {
+        change,
+        name: name
+            .try_into()
+            .map_err(|_| protocol_error(format!("Invalid ref name: {name}")))?,
+        deref: false,
+    })
+}
+
+/// Object database which provides the empty tree even if it isn't stored, as
+/// Git does.
+#[derive(Clone)]
+struct WithEmptyTree<T>(T);
+
+impl<T: gix_pack::Find> gix_pack::Find for WithEmptyTree<T> {
+    fn contains(&self, id: &gix::oid) -> bool {
+        id == ObjectId::empty_tree(id.kind()) || self.0.contains(id)
+    }
+
+    fn try_find_cached<'a>(
+        &self,
+        id: &gix::oid,
+        buffer: &'a mut Vec<u8>,
+        pack_cache: &mut dyn gix_pack::cache::DecodeEntry,
+    ) -> Result<
+        Option<(gix::objs::Data<'a>, Option<gix_pack::data::entry::Location>)>,
+        gix::objs::find::Error,
+    > {
+        if id == ObjectId::empty_tree(id.kind()) && !self.0.contains(id) {
+            buffer.clear();
+            let data = gix::objs::Data {
+                kind: gix::object::Kind::Tree,
+                data: buffer,
+            };
+            return Ok(Some((data, None)));
+        }
+        self.0.try_find_cached(id, buffer, pack_cache)
+    }
+
+    fn location_by_oid(
+        &self,
+        id: &gix::oid,
+        buf: &mut Vec<u8>,
+    ) -> Option<gix_pack::data::entry::Location> {
+        self.0.location_by_oid(id, buf)
+    }
+
+    fn pack_offsets_and_oid(&self, pack_id: u32) -> Option<Vec<(u64, ObjectId)>> {
+        self.0.pack_offsets_and_oid(pack_id)
+    }
+
+    fn entry_by_location(
+        &self,
+        location: &gix_pack::data::entry::Location,
+    ) -> Option<gix_pack::find::Entry> {
+        self.0.entry_by_location(location)
+    }
+}
+
+/// Ref of a remote repository
+#[derive(Debug)]
+struct AdvertisedRef {
+    name: BString,
+    id: ObjectId,
+    /// Object the annotated tag points to.
+    peeled_id: Option<ObjectId>,
+}
+
+impl AdvertisedRef {
+    fn target_id(&self) -> ObjectId {
+        self.peeled_id.unwrap_or(self.id)
+    }
+}
+
+/// Refs of a remote repository
+#[derive(Debug, Default)]
+struct RemoteRefs {
+    refs: Vec<AdvertisedRef>,
+    /// Ref which the remote `HEAD` points to
+    head_target: Option<BString>,
+}
+
+impl RemoteRefs {
+    /// Collects the refs listed by gix. Unborn refs are skipped.
+    fn from_handshake(refs: &[handshake::Ref]) -> Self {
+        let mut remote_refs = RemoteRefs::default();
+        for remote_ref in refs {
+            match remote_ref {
+                handshake::Ref::Symbolic {
+                    full_ref_name,
+                    target,
+                    ..
+                }
+                | handshake::Ref::Unborn {
+                    full_ref_name,
+                    target,
+                } if full_ref_name == "HEAD" => {
+                    remote_refs.head_target = Some(target.clone());
+                }
+                _ => {}
+            }
+            let (name, id, peeled_id) = remote_ref.unpack();
+            if let Some(id) = id {
+                remote_refs.refs.push(AdvertisedRef {
+                    name: name.to_owned(),
+                    id: id.to_owned(),
+                    peeled_id: peeled_id.map(ToOwned::to_owned),
+                });
+            }
+        }
+        remote_refs
+    }
+
+    fn find_ref(&self, name: &BStr) -> Option<&AdvertisedRef> {
+        self.refs.iter().find(|remote_ref| remote_ref.name == name)
+    }
+
+    /// Returns the remote objects which exist in `repo`.
+    fn common_ids(&self, repo: &gix::Repository) -> Vec<ObjectId> {
+        self.refs
+            .iter()
+            .flat_map(|remote_ref| [Some(remote_ref.id), remote_ref.peeled_id])
+            .flatten()
+            .filter(|id| repo.has_object(id))
+            .unique()
+            .collect()
+    }
+}
+
+fn agent_capability() -> String {
+    format!("agent=jj/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Ref update sent to `git-receive-pack`
+struct PushCommand {
+    name: BString,
+    old_id: Option<ObjectId>,
+    new_id: Option<ObjectId>,
+}
+
+/// Result of pushing a ref, with the reason if it was rejected
+type PushResult = (BString, Result<(), String>);
+
+/// Lists the refs of a local repository.
+fn local_remote_refs(repo: &gix::Repository) -> Result<RemoteRefs, GitBuiltinError> {
+    let mut remote_refs = RemoteRefs {
+        refs: vec![],
+        head_target: repo
+            .head_name()
+            .map_err(git_error)?
+            .map(|name| name.as_bstr().to_owned()),
+    };
+    let platform = repo.references().map_err(git_error)?;
+    for reference in platform.all().map_err(git_error)? {
+        let mut reference = reference.map_err(git_error)?;
+        let Some(id) = reference.target().try_id().map(ToOwned::to_owned) else {
+            continue;
+        };
+        let name = reference.name().as_bstr().to_owned();
+        let peeled_id = reference
+            .peel_to_id_in_place()
+            .ok()
+            .map(|peeled_id| peeled_id.detach())
+            .filter(|peeled_id| *peeled_id != id);
+        remote_refs.refs.push(AdvertisedRef {
+            name,
+            id,
+            peeled_id,
+        });
+    }
+    Ok(remote_refs)
+}
+
+/// Updates the refs of a local remote as `git-receive-pack` would, except
+/// that no hooks are run.
+fn update_local_remote_refs(
+    remote_repo: &gix::Repository,
+    commands: &[PushCommand],
+) -> Vec<PushResult> {
+    let checked_out_branch = if remote_repo.is_bare() {
+        None
+    } else {
+        remote_repo.head_name().ok().flatten()
+    };
+    commands
+        .iter()
+        .map(|command| {
+            if checked_out_branch
+                .as_ref()
+                .is_some_and(|branch| branch.as_bstr() == command.name)
+            {
+                return (
+                    command.name.clone(),
+                    Err("branch is currently checked out".to_owned()),
+                );
+            }
+            let result = update_ref_edit(
+                command.name.as_ref(),
+                command.old_id,
+                command.new_id,
+                "push",
+            )
+            .map_err(|err| err.to_string())
+            .and_then(|edit| {
+                remote_repo
+                    .edit_reference(edit)
+                    .map_err(|_| "failed to update ref".to_owned())
+            })
+            .map(|_| ());
+            (command.name.clone(), result)
+        })
+        .collect()
+}
+
+/// Encodes the `git-receive-pack` commands. The pack is sent after them.
+fn receive_pack_request(
+    capabilities: &Capabilities,
+    commands: &[PushCommand],
+    push_options: &[String],
+) -> Result<Vec<u8>, GitBuiltinError> {
+    let mut requested = vec!["report-status".to_owned()];
+    if capabilities.contains("side-band-64k") {
+        requested.push("side-band-64k".to_owned());
+    }
+    if commands.iter().any(|command| command.new_id.is_none()) {
+        if !capabilities.contains("delete-refs") {
+            return Err(protocol_error("The remote doesn't support deleting refs"));
+        }
+        requested.push("delete-refs".to_owned());
+    }
+    if !push_options.is_empty() {
+        if !capabilities.contains("push-options") {
+            return Err(protocol_error("The remote doesn't support push options"));
+        }
+        requested.push("push-options".to_owned());
+    }
+    requested.push(agent_capability());
+    let null_id = ObjectId::null(gix::hash::Kind::Sha1);
+    let mut body = vec![];
+    for (i, command) in commands.iter().enumerate() {
+        let mut line = format!(
+            "{} {} ",
+            command.old_id.unwrap_or(null_id),
+            command.new_id.unwrap_or(null_id)
+        )
+        .into_bytes();
+        line.extend_from_slice(&command.name);
+        if i == 0 {
+            line.push(b'\0');
+            line.extend_from_slice(requested.join(" ").as_bytes());
+        }
+        gix::protocol::transport::packetline::encode::data_to_write(&line, &mut body)?;
+    }
+    gix::protocol::transport::packetline::encode::flush_to_write(&mut body)?;
+    if !push_options.is_empty() {
+        for option in push_options {
+            gix::protocol::transport::packetline::encode::data_to_write(
+                option.as_bytes(),
+                &mut body,
+            )?;
+        }
+        gix::protocol::transport::packetline::encode::flush_to_write(&mut body)?;
+    }
+    Ok(body)
+}
+
+/// Reads the `report-status` response of `git-receive-pack`, which is sent on
+/// side-band 1 if `use_sideband`.
+fn read_push_report(
+    reader: &mut dyn gix::protocol::transport::client::ReadlineBufRead,
+    use_sideband: bool,
+    callbacks: &mut RemoteCallbacks<'_>,
+) -> Result<Vec<PushResult>, GitBuiltinError> {
+    let mut data = vec![];
+    while let Some(line) = reader.readline() {
+        let line = line?.map_err(|err| protocol_error(err.to_string()))?;
+        if !use_sideband {
+            if let Some(line_data) = line.as_slice() {
+                gix::protocol::transport::packetline::encode::data_to_write(line_data, &mut data)?;
+            }
+            continue;
+        }
+        match line
+            .decode_band()
+            .map_err(|err| protocol_error(err.to_string()))?
+        {
+            BandRef::Data(line_data) => data.extend_from_slice(line_data),
+            BandRef::Progress(message) => {
+                if let Some(callback) = callbacks.sideband_progress.as_deref_mut() {
+                    callback(message);
+                }
+            }
+            BandRef::Error(message) => {
+                let message = message.trim_end().to_str_lossy().into_owned();
+                return Err(GitBuiltinError::Remote(message));
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let mut report = StreamingPeekableIter::new(&data[..], &[PacketLineRef::Flush], false);
+    while let Some(line) = report.read_line() {
+        let line = line?.map_err(|err| protocol_error(err.to_string()))?;
+        lines.extend(line.as_slice().map(|data| data.to_owned()));
+    }
+    let mut lines = lines.iter().map(|line| line.trim_end_with(|c| c == '\n'));
+    let unpack_status = lines
+        .next()
+        .and_then(|line| line.strip_prefix(b"unpack "))
+        .ok_or_else(|| protocol_error("Missing push status"))?;
+    if unpack_status != b"ok" {
+        return Err(GitBuiltinError::Remote(format!(
+            "unpack failed: {}",
+            unpack_status.as_bstr()
+        )));
+    }
+    let mut results = vec![];
+    for line in lines {
+        if let Some(name) = line.strip_prefix(b"ok ") {
+            results.push((name.into(), Ok(())));
+        } else if let Some(rest) = line.strip_prefix(b"ng ") {
+            let (name, reason) = rest.split_once_str(" ").unwrap_or((rest, b"failed"));
+            results.push((name.into(), Err(reason.to_str_lossy().into_owned())));
+        } else {
+            return Err(protocol_error(format!(
+                "Invalid push status: {}",
+                line.as_bstr()
+            )));
+        }
+    }
+    Ok(results)
+}
+
+fn report_push_progress(callbacks: &mut RemoteCallbacks<'_>, fraction: f32) {
+    if let Some(progress) = callbacks.progress.as_deref_mut() {
+        progress(&Progress {
+            bytes_downloaded: None,
+            overall: fraction,
+        });
+    }
+}
+
+/// Writes a pack of the objects reachable from `tips` but not from `ends`.
+///
+/// `progress` is called with the fraction of the objects written.
+fn write_pack(
+    repo: &gix::Repository,
+    tips: &[ObjectId],
+    ends: &[ObjectId],
+    out: &mut dyn Write,
+    progress: &mut dyn FnMut(f32),
+) -> Result<(), GitBuiltinError> {
+    let create_error =
+        |err: Box<dyn std::error::Error + Send + Sync>| GitBuiltinError::CreatePack(err);
+    let mut input = list_missing_commits(repo, tips, ends)?;
+    // Tags are expanded to the objects they point to.
+    input.extend(tips.iter().filter(|id| {
+        repo.find_header(**id)
+            .is_ok_and(|header| header.kind() != gix::object::Kind::Commit)
+    }));
+
+    let mut db = repo.objects.clone().into_inner();
+    db.prevent_pack_unload();
+    let db = WithEmptyTree(db);
+    let should_interrupt = AtomicBool::new(false);
+    let (mut counts, _) = gix_pack::data::output::count::objects(
+        db.clone(),
+        Box::new(input.into_iter().map(Ok)),
+        &progress::Discard,
+        &should_interrupt,
+        gix_pack::data::output::count::objects::Options {
+            input_object_expansion: ObjectExpansion::TreeAdditionsComparedToAncestor,
+            ..Default::default()
+        },
+    )
+    .map_err(|err| create_error(err.into()))?;
+    counts.sort_by_key(|count| count.id);
+    counts.dedup_by_key(|count| count.id);
+    let num_entries = counts.len();
+    let entries = gix::features::parallel::InOrderIter::from(
+        gix_pack::data::output::entry::iter_from_counts(
+            counts,
+            db,
+            Box::new(progress::Discard),
+            gix_pack::data::output::entry::iter_from_counts::Options {
+                version: gix_pack::data::Version::V2,
+                mode: gix_pack::data::output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+                allow_thin_pack: false,
+                thread_limit: None,
+                chunk_size: 10,
+            },
+        ),
+    );
+    let written = Cell::new(0);
+    let entries = entries.inspect(|chunk| {
+        if let Ok(chunk) = chunk {
+            written.set(written.get() + chunk.len());
+        }
+    });
+    let pack = gix_pack::data::output::bytes::FromEntriesIter::new(
+        entries,
+        out,
+        u32::try_from(num_entries).map_err(|err| create_error(err.into()))?,
+        gix_pack::data::Version::V2,
+        repo.object_hash(),
+    );
+    for result in pack {
+        result.map_err(|err| create_error(err.into()))?;
+        if num_entries > 0 {
+            progress(written.get() as f32 / num_entries as f32);
+        }
+    }
+    progress(1.0);
+    Ok(())
+}
+
+/// Lists the commits reachable from `tips` but not from `ends`, like `git
+/// rev-list tips --not ends`.
+///
+/// Both histories are walked newest first, and the walk from `tips` stops at
+/// the commits already reached by the walk from `ends`. Because of clock skew,
+/// a few commits reachable from `ends` may be listed too, which only makes the
+/// pack larger.
+fn list_missing_commits(
+    repo: &gix::Repository,
+    tips: &[ObjectId],
+    ends: &[ObjectId],
+) -> Result<Vec<ObjectId>, GitBuiltinError> {
+    let is_commit = |id: &ObjectId| {
+        repo.find_header(*id)
+            .is_ok_and(|header| header.kind() == gix::object::Kind::Commit)
+    };
+    let tips = tips.iter().copied().filter(is_commit).collect_vec();
+    let ends = ends.iter().copied().filter(is_commit).collect_vec();
+    let newest_first = gix::revision::walk::Sorting::ByCommitTime(Default::default());
+    let mut hidden: HashSet<ObjectId> = ends.iter().copied().collect();
+    let mut hidden_walk = repo
+        .rev_walk(ends)
+        .sorting(newest_first)
+        .all()
+        .map_err(git_error)?
+        .peekable();
+    let walk = repo
+        .rev_walk(tips)
+        .sorting(newest_first)
+        .selected(move |id| {
+            let time = repo
+                .find_commit(id)
+                .ok()
+                .and_then(|commit| commit.time().ok())
+                .map_or(0, |time| time.seconds);
+            while let Some(info) = hidden_walk.next_if(|info| {
+                info.as_ref()
+                    .map_or(true, |info| info.commit_time.unwrap_or(0) >= time)
+            }) {
+                if let Ok(info) = info {
+                    hidden.insert(info.id);
+                }
+            }
+            !hidden.contains(id)
+        })
+        .map_err(git_error)?;
+    walk.map(|info| info.map(|info| info.id).map_err(git_error))
+        .collect()
+}
+
+/// Receives the `pack` into the objects directory of `repo`. Returns the path
+/// to the `.keep` file which protects the pack until the refs are updated.
+fn receive_pack(
+    repo: &gix::Repository,
+    pack: &mut dyn BufRead,
+    progress: &mut dyn FnMut(&Progress),
+) -> Result<Option<PathBuf>, GitBuiltinError> {
+    let pack_dir = repo.common_dir().join("objects").join("pack");
+    let counts = Arc::new(TransferCounts::default());
+    let mut reader = ProgressReader {
+        inner: pack,
+        bytes: 0,
+        counts: counts.clone(),
+        callback: progress,
+    };
+    let outcome = gix_pack::Bundle::write_to_directory(
+        &mut reader,
+        Some(&pack_dir),
+        &mut TransferProgress::new(counts, ProgressKind::Other, None),
+        &AtomicBool::new(false),
+        Some(repo.objects.clone()),
+        gix_pack::bundle::write::Options {
+            object_hash: repo.object_hash(),
+            ..Default::default()
+        },
+    )
+    .map_err(GitBuiltinError::ReceivePack)?;
+    (reader.callback)(&Progress {
+        bytes_downloaded: None,
+        overall: 1.0,
+    });
+    Ok(outcome.keep_path)
+}
+
+/// Progress of receiving a pack, which is shared by the nodes of the gix
+/// progress tree
+#[derive(Debug, Default)]
+struct TransferCounts {
+    bytes: progress::StepShared,
+    indexed: progress::StepShared,
+    total: AtomicUsize,
+}
+
+impl TransferCounts {
+    fn fraction(&self) -> f32 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            0.0
+        } else {
+            self.indexed.load(Ordering::Relaxed) as f32 / total as f32
+        }
+    }
+
+    /// Returns the progress once the pack is being received.
+    fn to_progress(&self) -> Option<Progress> {
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        (bytes > 0).then(|| Progress {
+            bytes_downloaded: Some(bytes as u64),
+            overall: self.fraction(),
+        })
+    }
+}
+
+/// Reports the progress of receiving a pack.
+struct ProgressReader<'a> {
+    inner: &'a mut dyn BufRead,
+    bytes: u64,
+    counts: Arc<TransferCounts>,
+    callback: &'a mut dyn FnMut(&Progress),
+}
+
+impl ProgressReader<'_> {
+    fn report(&mut self, amt: usize) {
+        self.bytes += amt as u64;
+        (self.callback)(&Progress {
+            bytes_downloaded: Some(self.bytes),
+            overall: self.counts.fraction(),
+        });
+    }
+}
+
+impl Read for ProgressReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let amt = self.inner.read(buf)?;
+        self.report(amt);
+        Ok(amt)
+    }
+}
+
+impl BufRead for ProgressReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.report(amt);
+    }
+}
+
+/// What a node of the gix progress tree keeps track of
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ProgressKind {
+    /// Bytes of the pack read from the remote
+    Bytes,
+    /// Objects indexed while receiving the pack
+    Objects,
+    /// Progress messages sent by the remote
+    Remote,
+    Other,
+}
+
+/// Progress tree which keeps track of the received bytes and indexed objects,
+/// and forwards the messages from the remote.
+struct TransferProgress {
+    counts: Arc<TransferCounts>,
+    kind: ProgressKind,
+    step: progress::StepShared,
+    name: Option<String>,
+    requests: Option<mpsc::Sender<CallbackRequest>>,
+}
+
+impl TransferProgress {
+    fn new(
+        counts: Arc<TransferCounts>,
+        kind: ProgressKind,
+        requests: Option<mpsc::Sender<CallbackRequest>>,
+    ) -> Self {
+        let step = match kind {
+            ProgressKind::Bytes => counts.bytes.clone(),
+            ProgressKind::Objects => counts.indexed.clone(),
+            ProgressKind::Remote | ProgressKind::Other => Default::default(),
+        };
+        TransferProgress {
+            counts,
+            kind,
+            step,
+            name: None,
+            requests,
+        }
+    }
+
+    fn forward_remote_message(&self, message: &str) {
+        if let Some(requests) = &self.requests {
+            let message = format!("{message}\n").into_bytes();
+            requests.send(CallbackRequest::Sideband(message)).ok();
+        }
+    }
+}
+
+impl prodash::Count for TransferProgress {
+    fn set(&self, step: progress::Step) {
+        self.step.store(step, Ordering::Relaxed);
+    }
+
+    fn step(&self) -> progress::Step {
+        self.step.load(Ordering::Relaxed)
+    }
+
+    fn inc_by(&self, step: progress::Step) {
+        self.step.fetch_add(step, Ordering::Relaxed);
+    }
+
+    fn counter(&self) -> progress::StepShared {
+        self.step.clone()
+    }
+}
+
+impl prodash::Progress for TransferProgress {
+    fn init(&mut self, max: Option<progress::Step>, _unit: Option<progress::Unit>) {
+        if self.kind == ProgressKind::Objects {
+            self.counts.total.store(max.unwrap_or(0), Ordering::Relaxed);
+        }
+    }
+
+    fn set_name(&mut self, name: String) {
+        // gix names the progress of the remote after its messages, which are
+        // prefixed with the name of the progress.
+        if self.kind == ProgressKind::Remote && self.name.as_ref() != Some(&name) {
+            if let Some(message) = name.strip_prefix("remote: ") {
+                self.forward_remote_message(message);
+            }
+        }
+        self.name = Some(name);
+    }
+
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> progress::Id {
+        progress::UNKNOWN
+    }
+
+    fn message(&self, level: prodash::messages::MessageLevel, message: String) {
+        if self.kind == ProgressKind::Remote && level == prodash::messages::MessageLevel::Failure {
+            self.forward_remote_message(&message);
+        }
+    }
+}
+
+impl prodash::NestedProgress for TransferProgress {
+    type SubProgress = Self;
+
+    fn add_child(&mut self, name: impl Into<String>) -> Self::SubProgress {
+        self.add_child_with_id(name, progress::UNKNOWN)
+    }
+
+    fn add_child_with_id(
+        &mut self,
+        name: impl Into<String>,
+        id: progress::Id,
+    ) -> Self::SubProgress {
+        let kind = if id == progress::Id::from(gix_pack::bundle::write::ProgressId::ReadPackBytes) {
+            ProgressKind::Bytes
+        } else if id == progress::Id::from(gix_pack::index::write::ProgressId::IndexObjects) {
+            ProgressKind::Objects
+        } else if id == progress::Id::from(gix::protocol::fetch::ProgressId::RemoteProgress) {
+            ProgressKind::Remote
+        } else {
+            ProgressKind::Other
+        };
+        let mut child = TransferProgress::new(self.counts.clone(), kind, self.requests.clone());
+        child.name = Some(name.into());
+        child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_ref_name() {
+        let map = |source, destination, name: &str| {
+            map_ref_name(source, destination, name.into()).map(|name| name.to_string())
+        };
+        assert_eq!(
+            map("refs/heads/*", "refs/remotes/origin/*", "refs/heads/a/b").as_deref(),
+            Some("refs/remotes/origin/a/b")
+        );
+        assert_eq!(
+            map(
+                "refs/heads/f*o",
+                "refs/remotes/origin/f*o",
+                "refs/heads/foo"
+            )
+            .as_deref(),
+            Some("refs/remotes/origin/foo")
+        );
+        assert_eq!(
+            map("refs/heads/f*o", "refs/remotes/origin/f*o", "refs/heads/fo").as_deref(),
+            Some("refs/remotes/origin/fo")
+        );
+        assert_eq!(
+            map("refs/heads/f*o", "refs/remotes/origin/f*o", "refs/heads/f").as_deref(),
+            None
+        );
+        assert_eq!(
+            map(
+                "refs/heads/main",
+                "refs/remotes/origin/main",
+                "refs/heads/main"
+            )
+            .as_deref(),
+            Some("refs/remotes/origin/main")
+        );
+        assert_eq!(
+            map(
+                "refs/heads/main",
+                "refs/remotes/origin/main",
+                "refs/heads/mainx"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remote_refs_from_handshake() {
+        let id1 = ObjectId::from_hex(b"1111111111111111111111111111111111111111").unwrap();
+        let id2 = ObjectId::from_hex(b"2222222222222222222222222222222222222222").unwrap();
+        let remote_refs = RemoteRefs::from_handshake(&[
+            handshake::Ref::Symbolic {
+                full_ref_name: "HEAD".into(),
+                target: "refs/heads/main".into(),
+                tag: None,
+                object: id1,
+            },
+            handshake::Ref::Direct {
+                full_ref_name: "refs/heads/main".into(),
+                object: id1,
+            },
+            handshake::Ref::Peeled {
+                full_ref_name: "refs/tags/v1".into(),
+                tag: id2,
+                object: id1,
+            },
+            handshake::Ref::Unborn {
+                full_ref_name: "refs/heads/unborn".into(),
+                target: "refs/heads/unborn".into(),
+            },
+        ]);
+        assert_eq!(remote_refs.head_target, Some("refs/heads/main".into()));
+        let tag = remote_refs.find_ref(b"refs/tags/v1".as_bstr()).unwrap();
+        assert_eq!(tag.id, id2);
+        assert_eq!(tag.target_id(), id1);
+        assert!(remote_refs
+            .find_ref(b"refs/heads/unborn".as_bstr())
+            .is_none());
+        assert_eq!(remote_refs.refs.len(), 3);
+    }
+}
@@ -0,0 +1,771 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Git LFS support.
+//!
+//! Files with the `filter=lfs` attribute are stored as small pointer files in
+//! the backend, and their content is kept in a local object store next to the
+//! Git repository, like `git lfs` does. Objects are transferred from and to
+//! the LFS server by the batch API.
+
+#![allow(missing_docs)]
+
+#[cfg(feature = "git-builtin-transport")]
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[cfg(feature = "git-builtin-transport")]
+use serde::Deserialize;
+use sha2::Digest as _;
+use sha2::Sha256;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::TreeValue;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::gitattributes::GitAttributes;
+use crate::gitattributes::GitAttributesError;
+use crate::hex_util::encode_hex;
+use crate::matchers::Matcher;
+use crate::merged_tree::MergedTree;
+#[cfg(feature = "git")]
+use crate::ref_name::RemoteName;
+use crate::ref_name::RemoteNameBuf;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::store::Store;
+
+/// Pointer files larger than this aren't recognized.
+pub const LFS_POINTER_MAX_SIZE: usize = 1024;
+
+const LFS_SPEC_V1: &str = "https://git-lfs.github.com/spec/v1";
+#[cfg(feature = "git-builtin-transport")]
+const LFS_MEDIA_TYPE: &str = "application/vnd.git-lfs+json";
+/// Number of objects requested by a single batch API call.
+#[cfg(feature = "git-builtin-transport")]
+const LFS_BATCH_SIZE: usize = 100;
+
+/// Error that may occur while transferring LFS objects.
+#[derive(Debug, Error)]
+pub enum GitLfsError {
+    #[error("No git remote named '{}'", .0.as_symbol())]
+    NoSuchRemote(RemoteNameBuf),
+    #[error(
+        "Cannot determine the LFS server of remote '{}'. Set `lfs.url` in the Git config",
+        .0.as_symbol()
+    )]
+    NoEndpoint(RemoteNameBuf),
+    #[cfg(feature = "git-builtin-transport")]
+    #[error("LFS request failed")]
+    Http {
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("LFS request to {url} failed: {message}")]
+    Request { url: String, message: String },
+    #[error("Authentication failed for {url}")]
+    Unauthorized { url: String },
+    #[error("Invalid LFS batch response from {url}")]
+    InvalidResponse {
+        url: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("LFS server rejected object {oid}: {message}")]
+    Object { oid: String, message: String },
+    #[error("Downloaded LFS object {oid} doesn't match its pointer")]
+    Corrupted { oid: String },
+    #[error(transparent)]
+    Io(#[from] PathError),
+    #[error(transparent)]
+    Attributes(#[from] GitAttributesError),
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Content of an LFS pointer file.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LfsPointer {
+    oid: String,
+    size: u64,
+}
+
+impl LfsPointer {
+    /// Creates pointer to the object with the given hex SHA-256 `oid`. Returns
+    /// `None` if the `oid` isn't valid.
+    pub fn new(oid: &str, size: u64) -> Option<Self> {
+        let is_valid = oid.len() == 64
+            && oid
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        is_valid.then(|| LfsPointer {
+            oid: oid.to_owned(),
+            size,
+        })
+    }
+
+    /// Parses the content of a pointer file.
+    pub fn parse(content: &[u8]) -> Option<Self> {
+        if content.len() > LFS_POINTER_MAX_SIZE {
+            return None;
+        }
+        let text = std::str::from_utf8(content).ok()?;
+        let mut lines = text.lines();
+        if lines.next()?.strip_prefix("version ")? != LFS_SPEC_V1 {
+            return None;
+        }
+        let mut oid = None;
+        let mut size = None;
+        for line in lines {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "oid" => oid = Some(value.strip_prefix("sha256:")?),
+                "size" if value.bytes().all(|b| b.is_ascii_digit()) => {
+                    size = Some(value.parse().ok()?);
+                }
+                "size" => return None,
+                _ => {}
+            }
+        }
+        LfsPointer::new(oid?, size?)
+    }
+
+    /// Hex SHA-256 hash of the object content.
+    pub fn oid(&self) -> &str {
+        &self.oid
+    }
+
+    /// Size of the object content in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Serializes the pointer in the canonical format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        format!(
+            "version {LFS_SPEC_V1}\noid sha256:{}\nsize {}\n",
+            self.oid, self.size
+        )
+        .into_bytes()
+    }
+}
+
+/// Local store of LFS objects, laid out like the one of `git lfs`.
+#[derive(Clone, Debug)]
+pub struct LfsObjectStore {
+    path: PathBuf,
+}
+
+impl LfsObjectStore {
+    /// Creates store at the `path`, which is usually the `lfs` directory
+    /// inside the Git repository.
+    pub fn new(path: PathBuf) -> Self {
+        LfsObjectStore { path }
+    }
+
+    /// Returns the store of the Git repository backing the `store`, or `None`
+    /// if the backend isn't Git.
+    pub fn for_store(store: &Store) -> Option<Self> {
+        #[cfg(feature = "git")]
+        {
+            let backend = crate::git::get_git_backend(store).ok()?;
+            Some(LfsObjectStore::new(backend.git_repo_path().join("lfs")))
+        }
+        #[cfg(not(feature = "git"))]
+        {
+            let _ = store;
+            None
+        }
+    }
+
+    pub fn object_path(&self, pointer: &LfsPointer) -> PathBuf {
+        let oid = &pointer.oid;
+        self.path
+            .join("objects")
+            .join(&oid[0..2])
+            .join(&oid[2..4])
+            .join(oid)
+    }
+
+    /// Opens the object referenced by the `pointer`, or returns `None` if the
+    /// object isn't in the store.
+    pub fn open(&self, pointer: &LfsPointer) -> Result<Option<File>, PathError> {
+        let path = self.object_path(pointer);
+        match File::open(&path) {
+            Ok(file) => {
+                let metadata = file.metadata().context(&path)?;
+                Ok((metadata.len() == pointer.size).then_some(file))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).context(&path),
+        }
+    }
+
+    /// Adds the `contents` to the store, and returns the pointer to it.
+    pub fn insert(&self, contents: impl Read) -> Result<LfsPointer, PathError> {
+        let mut temp_file = self.new_temp_file()?;
+        let pointer = hash_object(contents, Some(temp_file.as_file_mut())).context(&self.path)?;
+        self.persist(temp_file, &pointer)?;
+        Ok(pointer)
+    }
+
+    fn new_temp_file(&self) -> Result<NamedTempFile, PathError> {
+        let temp_dir = self.path.join("tmp");
+        fs::create_dir_all(&temp_dir).context(&temp_dir)?;
+        NamedTempFile::new_in(&temp_dir).context(&temp_dir)
+    }
+
+    fn persist(&self, temp_file: NamedTempFile, pointer: &LfsPointer) -> Result<(), PathError> {
+        let path = self.object_path(pointer);
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir).context(dir)?;
+        persist_content_addressed_temp_file(temp_file, &path).context(&path)?;
+        Ok(())
+    }
+
+    /// Replaces the content of the pointer file at `disk_path` with the object
+    /// referenced by the `pointer`.
+    ///
+    /// Returns `false` if the file doesn't contain the `pointer`, or if the
+    /// object isn't in the store.
+    pub fn smudge_file(&self, pointer: &LfsPointer, disk_path: &Path) -> Result<bool, PathError> {
+        let content = match disk_path.symlink_metadata() {
+            Ok(metadata) if metadata.is_file() && metadata.len() <= LFS_POINTER_MAX_SIZE as u64 => {
+                fs::read(disk_path).context(disk_path)?
+            }
+            Ok(_) => return Ok(false),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err).context(disk_path),
+        };
+        if content != pointer.to_bytes() {
+            return Ok(false);
+        }
+        let Some(mut object) = self.open(pointer)? else {
+            return Ok(false);
+        };
+        // Truncate the existing file to preserve its permissions.
+        let mut file = File::options()
+            .write(true)
+            .truncate(true)
+            .open(disk_path)
+            .context(disk_path)?;
+        io::copy(&mut object, &mut file).context(disk_path)?;
+        Ok(true)
+    }
+}
+
+/// Computes the pointer to the `contents` while copying them to the `output`.
+fn hash_object(mut contents: impl Read, mut output: Option<&mut File>) -> io::Result<LfsPointer> {
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+    let mut buf = vec![0; 64 << 10];
+    loop {
+        let n = match contents.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        hasher.update(&buf[..n]);
+        if let Some(output) = &mut output {
+            output.write_all(&buf[..n])?;
+        }
+        size += n as u64;
+    }
+    let oid = encode_hex(&hasher.finalize());
+    Ok(LfsPointer { oid, size })
+}
+
+/// Returns whether the file at `path` should be stored in LFS.
+pub async fn is_lfs_path(
+    attributes: &GitAttributes,
+    path: &RepoPath,
+) -> Result<bool, GitAttributesError> {
+    Ok(attributes.get(path, "filter").await?.as_value() == Some("lfs"))
+}
+
+/// Returns the LFS pointers stored in the `tree`.
+pub async fn tree_lfs_pointers(
+    tree: &MergedTree,
+    matcher: &dyn Matcher,
+) -> Result<Vec<(RepoPathBuf, LfsPointer)>, GitLfsError> {
+    let attributes = GitAttributes::from_tree(tree.clone());
+    let mut pointers = vec![];
+    for (path, value) in tree.entries_matching(matcher) {
+        let Ok(Some(TreeValue::File { id, .. })) = value?.into_resolved() else {
+            continue;
+        };
+        if !is_lfs_path(&attributes, &path).await? {
+            continue;
+        }
+        let reader = tree.store().read_file(&path, &id).await?;
+        let mut content = vec![];
+        reader
+            .take(LFS_POINTER_MAX_SIZE as u64 + 1)
+            .read_to_end(&mut content)
+            .await
+            .map_err(|err| BackendError::ReadFile {
+                path: path.clone(),
+                id: id.clone(),
+                source: err.into(),
+            })?;
+        if let Some(pointer) = LfsPointer::parse(&content) {
+            pointers.push((path, pointer));
+        }
+    }
+    Ok(pointers)
+}
+
+/// Returns the URL of the LFS server of the remote.
+///
+/// Like `git lfs`, the `remote.<name>.lfsurl` and `lfs.url` settings take
+/// precedence over the URL derived from the remote URL.
+#[cfg(feature = "git")]
+pub fn lfs_endpoint(
+    git_repo: &gix::Repository,
+    remote_name: &RemoteName,
+) -> Result<String, GitLfsError> {
+    let config = git_repo.config_snapshot();
+    let configured_url = config
+        .string(format!("remote.{}.lfsurl", remote_name.as_str()).as_str())
+        .or_else(|| config.string("lfs.url"));
+    if let Some(url) = configured_url {
+        return Ok(url.to_string().trim_end_matches('/').to_owned());
+    }
+    let remote = git_repo
+        .try_find_remote(remote_name.as_str())
+        .and_then(|result| result.ok())
+        .ok_or_else(|| GitLfsError::NoSuchRemote(remote_name.to_owned()))?;
+    let url = remote
+        .url(gix::remote::Direction::Fetch)
+        .filter(|url| matches!(url.scheme, gix::url::Scheme::Http | gix::url::Scheme::Https))
+        .ok_or_else(|| GitLfsError::NoEndpoint(remote_name.to_owned()))?;
+    Ok(endpoint_from_remote_url(&url.to_bstring().to_string()))
+}
+
+#[cfg_attr(not(feature = "git"), allow(dead_code))]
+fn endpoint_from_remote_url(url: &str) -> String {
+    let url = url.trim_end_matches('/');
+    if url.ends_with(".git") {
+        format!("{url}/info/lfs")
+    } else {
+        format!("{url}.git/info/lfs")
+    }
+}
+
+#[cfg(feature = "git-builtin-transport")]
+#[derive(Debug, Deserialize)]
+struct BatchResponse {
+    objects: Vec<BatchObject>,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+#[derive(Debug, Deserialize)]
+struct BatchObject {
+    oid: String,
+    #[serde(default)]
+    actions: BatchActions,
+    error: Option<BatchObjectError>,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+type BatchActions = HashMap<String, BatchAction>;
+
+#[cfg(feature = "git-builtin-transport")]
+#[derive(Debug, Deserialize)]
+struct BatchAction {
+    href: String,
+    #[serde(default)]
+    header: HashMap<String, String>,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+#[derive(Debug, Deserialize)]
+struct BatchObjectError {
+    message: String,
+}
+
+/// Client of the LFS batch API. Requests are sent by the same HTTP client as
+/// the builtin Git transport.
+#[cfg(feature = "git-builtin-transport")]
+#[derive(Clone, Debug)]
+pub struct LfsClient {
+    endpoint: String,
+    url: Option<gix::Url>,
+    http: reqwest::blocking::Client,
+    /// Repository whose credential helpers are asked for credentials
+    git_repo: Option<gix::Repository>,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+impl LfsClient {
+    /// Creates client for the LFS server at `endpoint`, e.g.
+    /// `https://example.com/repo.git/info/lfs`. Credentials embedded in the
+    /// URL are used if the server asks for them.
+    pub fn new(endpoint: String) -> Self {
+        let endpoint = endpoint.trim_end_matches('/');
+        let url = gix::Url::from_bytes(endpoint.into()).ok();
+        let endpoint = match &url {
+            Some(url) => url_without_credentials(url).to_bstring().to_string(),
+            None => endpoint.to_owned(),
+        };
+        LfsClient {
+            endpoint,
+            url,
+            http: reqwest::blocking::Client::new(),
+            git_repo: None,
+        }
+    }
+
+    /// Creates client for the LFS server of the remote. If the server asks for
+    /// credentials, they're looked up with the credential helpers configured
+    /// in the Git config, like `git lfs` does.
+    pub fn for_remote(
+        git_repo: &gix::Repository,
+        remote_name: &RemoteName,
+    ) -> Result<Self, GitLfsError> {
+        let mut client = LfsClient::new(lfs_endpoint(git_repo, remote_name)?);
+        client.git_repo = Some(git_repo.clone());
+        Ok(client)
+    }
+
+    /// Downloads the objects referenced by the `pointers` into the `store`.
+    pub fn download(
+        &self,
+        store: &LfsObjectStore,
+        pointers: &[LfsPointer],
+    ) -> Result<(), GitLfsError> {
+        let mut credential = None;
+        for chunk in pointers.chunks(LFS_BATCH_SIZE) {
+            for (pointer, actions) in self.batch("download", chunk, &mut credential)? {
+                let Some(action) = actions.get("download") else {
+                    return Err(GitLfsError::Object {
+                        oid: pointer.oid.clone(),
+                        message: "No download action".to_owned(),
+                    });
+                };
+                let temp_file = store.new_temp_file()?;
+                let mut response = send(self.action_request(reqwest::Method::GET, action))?;
+                response
+                    .copy_to(&mut temp_file.as_file())
+                    .map_err(|source| GitLfsError::Http { source })?;
+                let file = File::open(temp_file.path()).context(temp_file.path())?;
+                let actual = hash_object(file, None).context(temp_file.path())?;
+                if actual != pointer {
+                    return Err(GitLfsError::Corrupted { oid: pointer.oid });
+                }
+                store.persist(temp_file, &pointer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Uploads the objects referenced by the `pointers` from the `store`.
+    /// Returns the number of objects the server didn't have.
+    pub fn upload(
+        &self,
+        store: &LfsObjectStore,
+        pointers: &[LfsPointer],
+    ) -> Result<usize, GitLfsError> {
+        let mut credential = None;
+        let mut num_uploaded = 0;
+        for chunk in pointers.chunks(LFS_BATCH_SIZE) {
+            for (pointer, actions) in self.batch("upload", chunk, &mut credential)? {
+                // No action means that the server already has the object.
+                let Some(action) = actions.get("upload") else {
+                    continue;
+                };
+                let path = store.object_path(&pointer);
+                let file = File::open(&path).context(&path)?;
+                let request = self
+                    .action_request(reqwest::Method::PUT, action)
+                    .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+                    .body(file);
+                send(request)?;
+                if let Some(action) = actions.get("verify") {
+                    let body = serde_json::json!({ "oid": pointer.oid, "size": pointer.size });
+                    let request = self.action_request(reqwest::Method::POST, action);
+                    send(add_json_body(request, &body))?;
+                }
+                num_uploaded += 1;
+            }
+        }
+        Ok(num_uploaded)
+    }
+
+    /// Sends a batch API request, and returns the actions for the requested
+    /// objects. The `credential` accepted by the server is kept for the next
+    /// requests.
+    fn batch(
+        &self,
+        operation: &str,
+        pointers: &[LfsPointer],
+        credential: &mut Option<gix::credentials::protocol::Outcome>,
+    ) -> Result<Vec<(LfsPointer, BatchActions)>, GitLfsError> {
+        let url = format!("{}/objects/batch", self.endpoint);
+        let objects: Vec<_> = pointers
+            .iter()
+            .map(|pointer| serde_json::json!({ "oid": pointer.oid, "size": pointer.size }))
+            .collect();
+        let body = serde_json::json!({
+            "operation": operation,
+            "transfers": ["basic"],
+            "objects": objects,
+            "hash_algo": "sha256",
+        });
+        let mut sources = [CredentialSource::Url, CredentialSource::Helper].into_iter();
+        let response = loop {
+            let mut request = add_json_body(self.http.post(&url), &body);
+            if let Some(credential) = credential {
+                let identity = &credential.identity;
+                request = request.basic_auth(&identity.username, Some(&identity.password));
+            }
+            match send(request) {
+                Err(GitLfsError::Unauthorized { .. }) => {}
+                result => break result?,
+            }
+            if let Some(credential) = credential.take() {
+                self.run_credential_helpers(credential.next.erase());
+            }
+            let new_credential = sources
+                .by_ref()
+                .find_map(|source| self.get_credential(source))
+                .ok_or_else(|| GitLfsError::Unauthorized {
+                    url: self.endpoint.clone(),
+                })?;
+            *credential = Some(new_credential);
+        };
+        if let Some(credential) = credential {
+            self.run_credential_helpers(credential.next.clone().store());
+        }
+        let response: BatchResponse = response
+            .bytes()
+            .map_err(|source| GitLfsError::Http { source })
+            .and_then(|bytes| {
+                serde_json::from_slice(&bytes)
+                    .map_err(|source| GitLfsError::InvalidResponse { url, source })
+            })?;
+        let mut requested: HashMap<&str, &LfsPointer> = pointers
+            .iter()
+            .map(|pointer| (pointer.oid.as_str(), pointer))
+            .collect();
+        let mut result = vec![];
+        for object in response.objects {
+            if let Some(error) = object.error {
+                return Err(GitLfsError::Object {
+                    oid: object.oid,
+                    message: error.message,
+                });
+            }
+            // Ignore objects we didn't ask for.
+            if let Some(pointer) = requested.remove(object.oid.as_str()) {
+                result.push((pointer.clone(), object.actions));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns a request for the `action`. The headers of the action usually
+    /// contain the credentials needed by the request.
+    fn action_request(
+        &self,
+        method: reqwest::Method,
+        action: &BatchAction,
+    ) -> reqwest::blocking::RequestBuilder {
+        let mut request = self.http.request(method, &action.href);
+        for (name, value) in &action.header {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    fn get_credential(
+        &self,
+        source: CredentialSource,
+    ) -> Option<gix::credentials::protocol::Outcome> {
+        let url = self.url.as_ref()?;
+        match source {
+            CredentialSource::Url => {
+                let context = gix::credentials::protocol::Context {
+                    url: Some(url.to_bstring()),
+                    ..Default::default()
+                };
+                Some(gix::credentials::protocol::Outcome {
+                    identity: gix::sec::identity::Account {
+                        username: url.user()?.to_owned(),
+                        password: url.password()?.to_owned(),
+                    },
+                    next: context.into(),
+                })
+            }
+            CredentialSource::Helper => {
+                let action = gix::credentials::helper::Action::get_for_url(
+                    url_without_credentials(url).to_bstring(),
+                );
+                self.run_credential_helpers(action)
+            }
+        }
+    }
+
+    /// Runs the credential helpers configured in Git without prompting the
+    /// user.
+    fn run_credential_helpers(
+        &self,
+        action: gix::credentials::helper::Action,
+    ) -> Option<gix::credentials::protocol::Outcome> {
+        let git_repo = self.git_repo.as_ref()?;
+        let (mut cascade, _, mut prompt_options) = git_repo
+            .config_snapshot()
+            .credential_helpers(self.url.clone()?)
+            .ok()?;
+        prompt_options.mode = gix::prompt::Mode::Disable;
+        cascade.invoke(action, prompt_options).ok().flatten()
+    }
+}
+
+/// Where to get the credentials for the batch API from, in order of
+/// preference
+#[cfg(feature = "git-builtin-transport")]
+#[derive(Clone, Copy, Debug)]
+enum CredentialSource {
+    Url,
+    Helper,
+}
+
+#[cfg(feature = "git-builtin-transport")]
+fn url_without_credentials(url: &gix::Url) -> gix::Url {
+    let mut url = url.clone();
+    url.set_user(None);
+    url.set_password(None);
+    url
+}
+
+#[cfg(feature = "git-builtin-transport")]
+fn add_json_body(
+    request: reqwest::blocking::RequestBuilder,
+    body: &serde_json::Value,
+) -> reqwest::blocking::RequestBuilder {
+    request
+        .header(reqwest::header::ACCEPT, LFS_MEDIA_TYPE)
+        .header(reqwest::header::CONTENT_TYPE, LFS_MEDIA_TYPE)
+        .body(body.to_string())
+}
+
+/// Sends the request, and returns the response if it succeeded.
+#[cfg(feature = "git-builtin-transport")]
+fn send(
+    request: reqwest::blocking::RequestBuilder,
+) -> Result<reqwest::blocking::Response, GitLfsError> {
+    let response = request
+        .send()
+        .map_err(|source| GitLfsError::Http { source })?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else if status == reqwest::StatusCode::UNAUTHORIZED {
+        Err(GitLfsError::Unauthorized {
+            url: response.url().to_string(),
+        })
+    } else {
+        Err(GitLfsError::Request {
+            url: response.url().to_string(),
+            message: format!("HTTP status {status}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OID: &str = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+
+    #[test]
+    fn test_pointer_round_trip() {
+        let pointer = LfsPointer::new(OID, 12345).unwrap();
+        let bytes = pointer.to_bytes();
+        insta::assert_snapshot!(String::from_utf8_lossy(&bytes), @r"
+        version https://git-lfs.github.com/spec/v1
+        oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393
+        size 12345
+        ");
+        assert_eq!(LfsPointer::parse(&bytes), Some(pointer));
+    }
+
+    #[test]
+    fn test_pointer_parse_invalid() {
+        let parse = |text: String| LfsPointer::parse(text.as_bytes());
+        // Extension keys are allowed
+        assert!(parse(format!(
+            "version {LFS_SPEC_V1}\next-0-foo sha256:{OID}\noid sha256:{OID}\nsize 1\n"
+        ))
+        .is_some());
+        assert!(parse(format!("version {LFS_SPEC_V1}\noid sha256:{OID}\n")).is_none());
+        assert!(parse(format!("version {LFS_SPEC_V1}\noid md5:{OID}\nsize 1\n")).is_none());
+        assert!(parse(format!(
+            "version {LFS_SPEC_V1}\noid sha256:../{OID}\nsize 1\n"
+        ))
+        .is_none());
+        assert!(parse(format!(
+            "version {LFS_SPEC_V1}\noid sha256:{OID}\nsize -1\n"
+        ))
+        .is_none());
+        assert!(parse(format!("version 2\noid sha256:{OID}\nsize 1\n")).is_none());
+        assert!(parse("binary\0content".to_owned()).is_none());
+    }
+
+    #[test]
+    fn test_object_store_insert() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = LfsObjectStore::new(temp_dir.path().join("lfs"));
+        let pointer = store.insert(&b"content"[..]).unwrap();
+        assert_eq!(
+            pointer.oid(),
+            "ed7002b439e9ac845f22357d822bac1444730fbdb6016d3ec9432297b9ec9f73"
+        );
+        assert_eq!(pointer.size(), 7);
+        let mut content = String::new();
+        store
+            .open(&pointer)
+            .unwrap()
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "content");
+        let missing = LfsPointer::new(OID, 1).unwrap();
+        assert!(store.open(&missing).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_endpoint_from_remote_url() {
+        assert_eq!(
+            endpoint_from_remote_url("https://example.com/repo.git"),
+            "https://example.com/repo.git/info/lfs"
+        );
+        assert_eq!(
+            endpoint_from_remote_url("https://example.com/repo/"),
+            "https://example.com/repo.git/info/lfs"
+        );
+    }
+}
@@ -0,0 +1,686 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks out Git submodules into the working copy.
+//!
+//! Submodule repositories are stored in the
+//! [`SubmoduleStore`](crate::submodule_store::SubmoduleStore), and the
+//! submodule directory in the working copy links to it by a `.git` file. The
+//! working copy is materialized from the Git objects directly, so Git's index
+//! of the submodule is rewritten to match the checked-out tree.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use bstr::ByteSlice as _;
+use thiserror::Error;
+
+use crate::backend::CommitId;
+use crate::backend::MillisSinceEpoch;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::git::get_git_repo;
+use crate::git::RefSpec;
+use crate::git::RemoteCallbacks;
+use crate::git::UnexpectedGitBackendError;
+use crate::git_subprocess::GitSubprocessContext;
+use crate::git_subprocess::GitSubprocessError;
+use crate::local_working_copy::mtime_from_metadata;
+use crate::object_id::ObjectId as _;
+use crate::ref_name::RemoteName;
+use crate::repo::Repo;
+use crate::repo_path::RepoPathBuf;
+use crate::settings::GitSettings;
+
+const SUBMODULE_REMOTE_NAME: &str = "origin";
+
+/// Submodule declared in the `.gitmodules` file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitSubmodule {
+    /// Name of the submodule.
+    pub name: String,
+    /// Path to the submodule relative to the workspace root.
+    pub path: RepoPathBuf,
+    /// URL of the submodule repository as written in `.gitmodules`.
+    pub url: String,
+}
+
+/// Error that may occur while managing Git submodules.
+#[derive(Debug, Error)]
+pub enum GitSubmoduleError {
+    #[error("Failed to parse .gitmodules")]
+    InvalidGitModules(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Invalid submodule name: {0}")]
+    InvalidName(String),
+    #[error("Submodule {name} has relative URL {url}, but the repo has no remote to resolve it")]
+    UnresolvableUrl { name: String, url: String },
+    #[error("Submodule at {} hasn't been initialized", path.display())]
+    NotInitialized { path: PathBuf },
+    #[error(transparent)]
+    Subprocess(#[from] GitSubprocessError),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+    #[error(transparent)]
+    Io(#[from] PathError),
+    #[error("Unexpected Git error when managing submodule")]
+    Git(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl GitSubmoduleError {
+    fn from_git(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        GitSubmoduleError::Git(source.into())
+    }
+}
+
+/// Result of checking out a commit in a submodule.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubmoduleCheckout {
+    /// The submodule repository hasn't been initialized.
+    Uninitialized,
+    /// The commit was checked out. Holds the modification time of the
+    /// submodule `HEAD`.
+    CheckedOut(MillisSinceEpoch),
+    /// The commit doesn't exist in the submodule repository. Holds the
+    /// modification time of the submodule `HEAD`, which was left unchanged.
+    MissingCommit(MillisSinceEpoch),
+    /// The submodule has uncommitted changes, so the commit wasn't checked
+    /// out. Holds the modification time of the submodule `HEAD`, which was
+    /// left unchanged.
+    Modified(MillisSinceEpoch),
+}
+
+/// Parses the content of a `.gitmodules` file.
+pub fn parse_gitmodules(content: &[u8]) -> Result<Vec<GitSubmodule>, GitSubmoduleError> {
+    let to_err =
+        |err: Box<dyn std::error::Error + Send + Sync>| GitSubmoduleError::InvalidGitModules(err);
+    let file = gix::submodule::File::from_bytes(content, None, &Default::default())
+        .map_err(|err| to_err(err.into()))?;
+    file.names()
+        .map(|name| {
+            let path = file.path(name).map_err(|err| to_err(err.into()))?;
+            let path = RepoPathBuf::from_relative_path(gix::path::from_bstr(path))
+                .map_err(|err| to_err(err.into()))?;
+            let url = file
+                .config()
+                .string(format!("submodule.{name}.url"))
+                .ok_or_else(|| to_err(format!("Submodule {name} has no URL").into()))?;
+            Ok(GitSubmodule {
+                name: name.to_str_lossy().into_owned(),
+                path,
+                url: url.to_str_lossy().into_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the Git directory of the submodule checked out at `worktree`, or
+/// `None` if the submodule hasn't been initialized.
+pub fn submodule_git_dir(worktree: &Path) -> Result<Option<PathBuf>, GitSubmoduleError> {
+    if !worktree.is_dir() {
+        return Ok(None);
+    }
+    let dot_git = worktree.join(".git");
+    match dot_git.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => Ok(Some(dot_git)),
+        Ok(_) => {
+            let git_dir = gix::discover::path::from_gitdir_file(&dot_git)
+                .map_err(GitSubmoduleError::from_git)?;
+            Ok(Some(git_dir))
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(PathError {
+            path: dot_git,
+            error: err,
+        }
+        .into()),
+    }
+}
+
+/// Returns the modification time of the submodule `HEAD`, or `None` if the
+/// submodule hasn't been initialized.
+///
+/// If `HEAD` is a symbolic ref, the newer of the `HEAD` file and the loose
+/// ref it points to is returned.
+pub fn submodule_head_mtime(
+    worktree: &Path,
+) -> Result<Option<MillisSinceEpoch>, GitSubmoduleError> {
+    let Some(git_dir) = submodule_git_dir(worktree)? else {
+        return Ok(None);
+    };
+    head_mtime(&git_dir).map(Some)
+}
+
+fn head_mtime(git_dir: &Path) -> Result<MillisSinceEpoch, GitSubmoduleError> {
+    let head_path = git_dir.join("HEAD");
+    let mut mtime = mtime_from_metadata(&head_path.symlink_metadata().context(&head_path)?);
+    let head = fs::read(&head_path).context(&head_path)?;
+    if let Some(ref_name) = head.strip_prefix(b"ref: ") {
+        let ref_path = git_dir.join(gix::path::from_bstr(ref_name.trim().as_bstr()));
+        match ref_path.symlink_metadata() {
+            Ok(metadata) => mtime = mtime.max(mtime_from_metadata(&metadata)),
+            // Packed or unborn ref
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(PathError {
+                    path: ref_path,
+                    error: err,
+                }
+                .into())
+            }
+        }
+    }
+    Ok(mtime)
+}
+
+/// Returns the commit checked out in the submodule, or `None` if the
+/// submodule hasn't been initialized or its `HEAD` is unborn.
+pub fn submodule_head_id(worktree: &Path) -> Result<Option<CommitId>, GitSubmoduleError> {
+    let Some(git_repo) = open_submodule(worktree)? else {
+        return Ok(None);
+    };
+    let head_id = git_repo.head_id().ok();
+    Ok(head_id.map(|id| CommitId::from_bytes(id.as_bytes())))
+}
+
+fn open_submodule(worktree: &Path) -> Result<Option<gix::Repository>, GitSubmoduleError> {
+    if submodule_git_dir(worktree)?.is_none() {
+        return Ok(None);
+    }
+    let mut git_repo = gix::open(worktree).map_err(GitSubmoduleError::from_git)?;
+    // Like Git, don't fail to write reflog if the user identity isn't
+    // configured.
+    if git_repo.committer().is_none() {
+        let mut config = git_repo.config_snapshot_mut();
+        config
+            .set_raw_value(&"committer.name", "jj")
+            .map_err(GitSubmoduleError::from_git)?;
+        config
+            .set_raw_value(&"committer.email", "jj@localhost")
+            .map_err(GitSubmoduleError::from_git)?;
+    }
+    Ok(Some(git_repo))
+}
+
+/// Checks out the `commit_id` in the submodule at `worktree`, detaching the
+/// submodule `HEAD`.
+///
+/// Files that differ between the old and new `HEAD` trees are rewritten, and
+/// the submodule index is reset to the new tree. Nested submodules are left
+/// as empty directories.
+///
+/// Like `git submodule update`, this doesn't discard uncommitted changes. If
+/// the checkout would overwrite modified or untracked files, the submodule is
+/// left unchanged.
+pub fn check_out_submodule(
+    worktree: &Path,
+    commit_id: &CommitId,
+) -> Result<SubmoduleCheckout, GitSubmoduleError> {
+    let Some(git_repo) = open_submodule(worktree)? else {
+        return Ok(SubmoduleCheckout::Uninitialized);
+    };
+    let git_dir = git_repo.path().to_owned();
+    let new_oid = gix::ObjectId::from_bytes_or_panic(commit_id.as_bytes());
+    let Some(new_commit) = git_repo
+        .try_find_object(new_oid)
+        .map_err(GitSubmoduleError::from_git)?
+    else {
+        return Ok(SubmoduleCheckout::MissingCommit(head_mtime(&git_dir)?));
+    };
+    let new_tree_id = new_commit
+        .try_into_commit()
+        .map_err(GitSubmoduleError::from_git)?
+        .tree_id()
+        .map_err(GitSubmoduleError::from_git)?;
+    let old_tree_id = match git_repo.head_commit() {
+        Ok(commit) => Some(commit.tree_id().map_err(GitSubmoduleError::from_git)?),
+        Err(_) => None,
+    };
+    if old_tree_id != Some(new_tree_id) {
+        let mut new_index = git_repo
+            .index_from_tree(&new_tree_id)
+            .map_err(GitSubmoduleError::from_git)?;
+        let old_entries = match old_tree_id {
+            Some(tree_id) => index_entries(
+                &git_repo
+                    .index_from_tree(&tree_id)
+                    .map_err(GitSubmoduleError::from_git)?,
+            ),
+            None => HashMap::new(),
+        };
+        let new_entries = index_entries(&new_index);
+        if has_local_changes(&git_repo, worktree, &old_entries, &new_entries)? {
+            return Ok(SubmoduleCheckout::Modified(head_mtime(&git_dir)?));
+        }
+        for path in old_entries.keys() {
+            if !new_entries.contains_key(path) {
+                remove_file(worktree, path)?;
+            }
+        }
+        for (path, entry) in &new_entries {
+            if old_entries.get(path) != Some(entry) {
+                write_entry(&git_repo, worktree, path, entry)?;
+            }
+        }
+        update_index_stats(worktree, &mut new_index)?;
+        new_index
+            .write(gix::index::write::Options::default())
+            .map_err(GitSubmoduleError::from_git)?;
+    }
+    git_repo
+        .edit_reference(gix::refs::transaction::RefEdit {
+            change: gix::refs::transaction::Change::Update {
+                log: gix::refs::transaction::LogChange {
+                    message: "checkout from jj".into(),
+                    ..Default::default()
+                },
+                expected: gix::refs::transaction::PreviousValue::Any,
+                new: gix::refs::Target::Object(new_oid),
+            },
+            name: "HEAD".try_into().unwrap(),
+            deref: false,
+        })
+        .map_err(GitSubmoduleError::from_git)?;
+    Ok(SubmoduleCheckout::CheckedOut(head_mtime(&git_dir)?))
+}
+
+type IndexEntries = HashMap<PathBuf, (gix::index::entry::Mode, gix::ObjectId)>;
+
+fn index_entries(index: &gix::index::File) -> IndexEntries {
+    index
+        .entries()
+        .iter()
+        .map(|entry| {
+            let path = gix::path::from_bstr(entry.path(index)).into_owned();
+            (path, (entry.mode, entry.id))
+        })
+        .collect()
+}
+
+/// Returns true if checking out the `new_entries` over the `old_entries` of
+/// the `HEAD` tree would discard uncommitted changes in the submodule.
+///
+/// Like `git checkout`, files that differ between the trees must be unchanged
+/// in the index and on disk, or already match the new tree. Untracked files
+/// mustn't be overwritten. The other files are left as is, but since the index
+/// is reset to the new tree, they mustn't have staged changes.
+fn has_local_changes(
+    git_repo: &gix::Repository,
+    worktree: &Path,
+    old_entries: &IndexEntries,
+    new_entries: &IndexEntries,
+) -> Result<bool, GitSubmoduleError> {
+    let index = git_repo
+        .index_or_empty()
+        .map_err(GitSubmoduleError::from_git)?;
+    if index
+        .entries()
+        .iter()
+        .any(|entry| entry.stage() != gix::index::entry::Stage::Unconflicted)
+    {
+        return Ok(true);
+    }
+    let current_entries: HashMap<PathBuf, &gix::index::Entry> = index
+        .entries()
+        .iter()
+        .map(|entry| (gix::path::from_bstr(entry.path(&index)).into_owned(), entry))
+        .collect();
+    let paths: HashSet<&PathBuf> = old_entries
+        .keys()
+        .chain(new_entries.keys())
+        .chain(current_entries.keys())
+        .collect();
+    for path in paths {
+        let old = old_entries.get(path);
+        let new = new_entries.get(path);
+        let current_entry = current_entries.get(path).copied();
+        let current = current_entry.map(|entry| (entry.mode, entry.id));
+        if old == new {
+            if current.as_ref() != old {
+                return Ok(true);
+            }
+            continue;
+        }
+        if current.as_ref() != old && current.as_ref() != new {
+            return Ok(true);
+        }
+        let disk_path = worktree.join(path);
+        let unchanged = match current_entry {
+            Some(entry) => is_entry_unchanged(git_repo, &disk_path, &index, entry)?,
+            None => disk_path.symlink_metadata().is_err(),
+        };
+        if !unchanged {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns true if the file at `disk_path` matches the `entry` of the `index`.
+/// The file content is read only if the stat information doesn't match.
+fn is_entry_unchanged(
+    git_repo: &gix::Repository,
+    disk_path: &Path,
+    index: &gix::index::State,
+    entry: &gix::index::Entry,
+) -> Result<bool, GitSubmoduleError> {
+    use gix::index::entry::Mode;
+
+    // Nested submodules aren't checked out.
+    if entry.mode == Mode::COMMIT {
+        return Ok(true);
+    }
+    let metadata = match gix::index::fs::Metadata::from_path_no_follow(disk_path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => {
+            return Err(PathError {
+                path: disk_path.to_owned(),
+                error: err,
+            }
+            .into())
+        }
+    };
+    let data = if entry.mode == Mode::SYMLINK {
+        if !metadata.is_symlink() {
+            return Ok(false);
+        }
+        let target = fs::read_link(disk_path).context(disk_path)?;
+        gix::path::into_bstr(target).into_owned().into()
+    } else {
+        if !metadata.is_file() {
+            return Ok(false);
+        }
+        #[cfg(unix)]
+        if metadata.is_executable() != (entry.mode == Mode::FILE_EXECUTABLE) {
+            return Ok(false);
+        }
+        let stat =
+            gix::index::entry::Stat::from_fs(&metadata).map_err(GitSubmoduleError::from_git)?;
+        let options = gix::index::entry::stat::Options::default();
+        if entry.stat.matches(&stat, options) && !entry.stat.is_racy(index.timestamp(), options) {
+            return Ok(true);
+        }
+        fs::read(disk_path).context(disk_path)?
+    };
+    let id = gix::objs::compute_hash(git_repo.object_hash(), gix::objs::Kind::Blob, &data)
+        .map_err(GitSubmoduleError::from_git)?;
+    Ok(id == entry.id)
+}
+
+/// Records the stat information of the checked-out files in the `index` so
+/// that unchanged files don't have to be read to detect local changes.
+fn update_index_stats(
+    worktree: &Path,
+    index: &mut gix::index::File,
+) -> Result<(), GitSubmoduleError> {
+    for (entry, path) in index.entries_mut_with_paths() {
+        if entry.mode == gix::index::entry::Mode::COMMIT {
+            continue;
+        }
+        let disk_path = worktree.join(gix::path::from_bstr(path));
+        let metadata =
+            gix::index::fs::Metadata::from_path_no_follow(&disk_path).context(&disk_path)?;
+        entry.stat =
+            gix::index::entry::Stat::from_fs(&metadata).map_err(GitSubmoduleError::from_git)?;
+    }
+    Ok(())
+}
+
+fn remove_file(worktree: &Path, path: &Path) -> Result<(), GitSubmoduleError> {
+    let disk_path = worktree.join(path);
+    let result = match disk_path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(&disk_path),
+        Ok(_) => fs::remove_file(&disk_path),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    };
+    result.context(&disk_path)?;
+    // Remove parent directories that became empty
+    let mut parent = disk_path.parent();
+    while let Some(dir) = parent.filter(|dir| *dir != worktree) {
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        parent = dir.parent();
+    }
+    Ok(())
+}
+
+fn write_entry(
+    git_repo: &gix::Repository,
+    worktree: &Path,
+    path: &Path,
+    (mode, id): &(gix::index::entry::Mode, gix::ObjectId),
+) -> Result<(), GitSubmoduleError> {
+    let disk_path = worktree.join(path);
+    remove_file(worktree, path)?;
+    let parent = disk_path.parent().unwrap();
+    fs::create_dir_all(parent).context(parent)?;
+    if *mode == gix::index::entry::Mode::COMMIT {
+        fs::create_dir(&disk_path).context(&disk_path)?;
+        return Ok(());
+    }
+    let blob = git_repo
+        .find_blob(*id)
+        .map_err(GitSubmoduleError::from_git)?;
+    if *mode == gix::index::entry::Mode::SYMLINK {
+        let target = gix::path::from_byte_slice(&blob.data);
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(target, &disk_path).context(&disk_path)?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(target, &disk_path).context(&disk_path)?;
+        return Ok(());
+    }
+    let mut file = fs::File::create(&disk_path).context(&disk_path)?;
+    file.write_all(&blob.data).context(&disk_path)?;
+    #[cfg(unix)]
+    if *mode == gix::index::entry::Mode::FILE_EXECUTABLE {
+        use std::os::unix::fs::PermissionsExt as _;
+        let permissions = fs::Permissions::from_mode(0o755);
+        fs::set_permissions(&disk_path, permissions).context(&disk_path)?;
+    }
+    Ok(())
+}
+
+/// Sets up the repository of the `submodule` in the submodule store, links
+/// the submodule directory at `worktree` to it, and fetches from the
+/// submodule remote.
+///
+/// A relative submodule URL is resolved against the URL of the `origin`
+/// remote of the `repo`.
+pub fn init_submodule(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    submodule: &GitSubmodule,
+    worktree: &Path,
+    callbacks: RemoteCallbacks<'_>,
+) -> Result<(), GitSubmoduleError> {
+    let name = &submodule.name;
+    if name.is_empty()
+        || Path::new(name).is_absolute()
+        || name.split(['/', '\\']).any(|component| component == "..")
+    {
+        return Err(GitSubmoduleError::InvalidName(name.clone()));
+    }
+    let remote_url = get_git_repo(repo.store())?
+        .try_find_remote(SUBMODULE_REMOTE_NAME)
+        .and_then(Result::ok)
+        .and_then(|remote| {
+            let url = remote.url(gix::remote::Direction::Fetch)?;
+            Some(url.to_bstring().to_str_lossy().into_owned())
+        });
+    let url = resolve_submodule_url(submodule, remote_url.as_deref())?;
+    let git_dir = repo.submodule_store().git_dir(name);
+    if !git_dir.exists() {
+        fs::create_dir_all(&git_dir).context(&git_dir)?;
+        let git_repo = gix::init_bare(&git_dir).map_err(GitSubmoduleError::from_git)?;
+        let mut config = git_repo.config_snapshot().clone();
+        config
+            .set_raw_value(&"core.bare", "false")
+            .map_err(GitSubmoduleError::from_git)?;
+        let worktree_str = gix::path::into_bstr(worktree).into_owned();
+        config
+            .set_raw_value_by("core", None, "worktree", worktree_str.as_bstr())
+            .map_err(GitSubmoduleError::from_git)?;
+        let remote_name = RemoteName::new(SUBMODULE_REMOTE_NAME);
+        let mut remote = git_repo
+            .remote_at(url.as_str())
+            .map_err(GitSubmoduleError::from_git)?
+            .with_refspecs(
+                [crate::git::default_fetch_refspec(remote_name).as_bytes()],
+                gix::remote::Direction::Fetch,
+            )
+            .expect("default refspec to be valid");
+        crate::git::save_remote(&mut config, remote_name, &mut remote)
+            .map_err(GitSubmoduleError::from_git)?;
+        crate::git::save_git_config(&config).context(&git_dir)?;
+    }
+    fs::create_dir_all(worktree).context(worktree)?;
+    let dot_git = worktree.join(".git");
+    let git_dir = dunce::canonicalize(&git_dir).context(&git_dir)?;
+    fs::write(&dot_git, format!("gitdir: {}\n", git_dir.display())).context(&dot_git)?;
+    fetch_submodule(git_settings, worktree, callbacks)
+}
+
+/// Fetches all branches from the remote of the submodule at `worktree`.
+pub fn fetch_submodule(
+    git_settings: &GitSettings,
+    worktree: &Path,
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<(), GitSubmoduleError> {
+    let Some(git_dir) = submodule_git_dir(worktree)? else {
+        return Err(GitSubmoduleError::NotInitialized {
+            path: worktree.to_owned(),
+        });
+    };
+    let remote_name = RemoteName::new(SUBMODULE_REMOTE_NAME);
+    let refspecs = [RefSpec::forced(
+        "refs/heads/*",
+        format!("refs/remotes/{SUBMODULE_REMOTE_NAME}/*"),
+    )];
+    let git_ctx = GitSubprocessContext::new(git_dir, &git_settings.executable_path);
+    git_ctx.spawn_fetch(remote_name, &refspecs, &mut callbacks, None)?;
+    Ok(())
+}
+
+/// Resolves the submodule URL relative to the `remote_url` of the
+/// superproject if it starts with `./` or `../`.
+fn resolve_submodule_url(
+    submodule: &GitSubmodule,
+    remote_url: Option<&str>,
+) -> Result<String, GitSubmoduleError> {
+    let url = &submodule.url;
+    if !url.starts_with("./") && !url.starts_with("../") {
+        return Ok(url.clone());
+    }
+    let Some(remote_url) = remote_url else {
+        return Err(GitSubmoduleError::UnresolvableUrl {
+            name: submodule.name.clone(),
+            url: url.clone(),
+        });
+    };
+    let mut base = remote_url.trim_end_matches('/').to_owned();
+    let mut rest = url.as_str();
+    loop {
+        if let Some(tail) = rest.strip_prefix("./") {
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("../") {
+            rest = tail;
+            match base.rfind(['/', ':']) {
+                Some(pos) => base.truncate(pos),
+                None => {
+                    return Err(GitSubmoduleError::UnresolvableUrl {
+                        name: submodule.name.clone(),
+                        url: url.clone(),
+                    });
+                }
+            }
+        } else {
+            break;
+        }
+    }
+    Ok(format!("{base}/{rest}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submodule_with_url(url: &str) -> GitSubmodule {
+        GitSubmodule {
+            name: "sub".to_owned(),
+            path: RepoPathBuf::from_internal_string("sub").unwrap(),
+            url: url.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_parse_gitmodules() {
+        let content = indoc::indoc! {r#"
+            [submodule "foo"]
+                path = libs/foo
+                url = https://example.com/foo.git
+            [submodule "bar"]
+                path = bar
+                url = ../bar.git
+        "#};
+        assert_eq!(
+            parse_gitmodules(content.as_bytes()).unwrap(),
+            vec![
+                GitSubmodule {
+                    name: "foo".to_owned(),
+                    path: RepoPathBuf::from_internal_string("libs/foo").unwrap(),
+                    url: "https://example.com/foo.git".to_owned(),
+                },
+                GitSubmodule {
+                    name: "bar".to_owned(),
+                    path: RepoPathBuf::from_internal_string("bar").unwrap(),
+                    url: "../bar.git".to_owned(),
+                },
+            ]
+        );
+        assert!(parse_gitmodules(b"[submodule \"foo\"]\npath = foo\n").is_err());
+    }
+
+    #[test]
+    fn test_resolve_submodule_url() {
+        let resolve = |url: &str, remote_url: Option<&str>| {
+            resolve_submodule_url(&submodule_with_url(url), remote_url).ok()
+        };
+        assert_eq!(
+            resolve("https://example.com/a.git", None).as_deref(),
+            Some("https://example.com/a.git")
+        );
+        assert_eq!(
+            resolve("../b.git", Some("https://example.com/org/a.git")).as_deref(),
+            Some("https://example.com/org/b.git")
+        );
+        assert_eq!(
+            resolve("./b.git", Some("https://example.com/org/a/")).as_deref(),
+            Some("https://example.com/org/a/b.git")
+        );
+        assert_eq!(
+            resolve("../b.git", Some("git@example.com:org/a.git")).as_deref(),
+            Some("git@example.com:org/b.git")
+        );
+        assert_eq!(resolve("../b.git", None), None);
+    }
+}
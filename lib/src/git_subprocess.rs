@@ -15,7 +15,7 @@
 use std::io;
 use std::io::BufReader;
 use std::io::Read;
-use std::num::NonZeroU32;
+use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Child;
@@ -28,6 +28,7 @@ use bstr::ByteSlice as _;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::git::GitFetchDepth;
 use crate::git::GitPushStats;
 use crate::git::Progress;
 use crate::git::RefSpec;
@@ -160,7 +161,7 @@ impl<'a> GitSubprocessContext<'a> {
         remote_name: &RemoteName,
         refspecs: &[RefSpec],
         callbacks: &mut RemoteCallbacks<'_>,
-        depth: Option<NonZeroU32>,
+        depth: Option<GitFetchDepth>,
     ) -> Result<Option<String>, GitSubprocessError> {
         if refspecs.is_empty() {
             return Ok(None);
@@ -173,8 +174,14 @@ impl<'a> GitSubprocessContext<'a> {
         if callbacks.progress.is_some() {
             command.arg("--progress");
         }
-        if let Some(d) = depth {
-            command.arg(format!("--depth={d}"));
+        match depth {
+            Some(GitFetchDepth::Depth(d)) => {
+                command.arg(format!("--depth={d}"));
+            }
+            Some(GitFetchDepth::Deepen(d)) => {
+                command.arg(format!("--deepen={d}"));
+            }
+            None => {}
         }
         command.arg("--").arg(remote_name.as_str());
         command.args(refspecs.iter().map(|x| x.to_git_format()));
@@ -184,6 +191,110 @@ impl<'a> GitSubprocessContext<'a> {
         parse_git_fetch_output(output)
     }
 
+    /// Fetch Git notes refs
+    ///
+    /// Unlike bookmarks, the local notes refs aren't pruned, and are only
+    /// updated if that's a fast-forward.
+    pub(crate) fn spawn_fetch_notes(
+        &self,
+        remote_name: &RemoteName,
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(), GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["fetch", "--no-write-fetch-head", "--no-tags"]);
+        if callbacks.progress.is_some() {
+            command.arg("--progress");
+        }
+        command
+            .arg("--")
+            .arg(remote_name.as_str())
+            .arg("refs/notes/*:refs/notes/*");
+        let output = wait_with_progress(self.spawn_cmd(command)?, callbacks)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(())
+    }
+
+    /// Fetch objects by id from a promisor remote of a partial clone
+    ///
+    /// This is how `git` itself fetches missing objects in bulk. The object ids
+    /// are passed through stdin.
+    pub(crate) fn spawn_fetch_objects(
+        &self,
+        remote_name: &str,
+        ids: &[gix::ObjectId],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(), GitSubprocessError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let mut command = self.create_command();
+        command.stdin(Stdio::piped());
+        command.stdout(Stdio::piped());
+        command.args(["-c", "fetch.negotiationAlgorithm=noop", "fetch"]);
+        command.args([
+            "--no-tags",
+            "--no-write-fetch-head",
+            "--recurse-submodules=no",
+            "--filter=blob:none",
+            "--stdin",
+        ]);
+        if callbacks.progress.is_some() {
+            command.arg("--progress");
+        }
+        command.arg("--").arg(remote_name);
+        let mut child = self.spawn_cmd(command)?;
+        let mut stdin = child.stdin.take().unwrap();
+        // If git exits early, the error message is more useful than the write
+        // error.
+        let write_result = ids.iter().try_for_each(|id| writeln!(stdin, "{id}"));
+        drop(stdin);
+
+        let output = wait_with_progress(child, callbacks)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        write_result.map_err(GitSubprocessError::Wait)
+    }
+
+    /// List the objects reachable from the tree of the commit that are missing
+    /// in a partial clone
+    ///
+    /// `git rev-list --objects --missing=print` prints missing objects as
+    /// `?<oid>` lines.
+    pub(crate) fn spawn_list_missing_objects(
+        &self,
+        commit_id: gix::ObjectId,
+    ) -> Result<Vec<gix::ObjectId>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["rev-list", "--objects", "--missing=print", "--no-walk"]);
+        command.arg(commit_id.to_string());
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        parse_git_missing_objects(&output.stdout)
+    }
+
+    /// Read a blob, which is fetched from the promisor remote of a partial
+    /// clone if missing
+    pub(crate) fn spawn_cat_file_blob(
+        &self,
+        id: gix::ObjectId,
+    ) -> Result<Vec<u8>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["cat-file", "blob"]).arg(id.to_string());
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(output.stdout)
+    }
+
     /// Prune particular branches
     pub(crate) fn spawn_branch_prune(
         &self,
@@ -228,6 +339,24 @@ impl<'a> GitSubprocessContext<'a> {
         Ok(maybe_branch.map(Into::into))
     }
 
+    /// List tags on the remote
+    ///
+    /// `git ls-remote --tags <remote_name>` prints `<oid>\t<ref>` lines.
+    /// Annotated tags are followed by `<oid>\t<ref>^{}` lines pointing to the
+    /// peeled objects.
+    pub(crate) fn spawn_ls_remote_tags(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<(gix::ObjectId, String)>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["ls-remote", "--tags", "--", remote_name.as_str()]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+
+        let output = parse_git_remote_show_output(output)?;
+        parse_git_ls_remote_refs(&output.stdout)
+    }
+
     /// Push references to git
     ///
     /// All pushes are forced, using --force-with-lease to perform a test&set
@@ -240,6 +369,7 @@ impl<'a> GitSubprocessContext<'a> {
         &self,
         remote_name: &RemoteName,
         references: &[RefToPush],
+        push_options: &[String],
         callbacks: &mut RemoteCallbacks<'_>,
     ) -> Result<GitPushStats, GitSubprocessError> {
         let mut command = self.create_command();
@@ -253,10 +383,16 @@ impl<'a> GitSubprocessContext<'a> {
         if callbacks.progress.is_some() {
             command.arg("--progress");
         }
+        command.args(
+            push_options
+                .iter()
+                .map(|option| format!("--push-option={option}")),
+        );
         command.args(
             references
                 .iter()
-                .map(|reference| format!("--force-with-lease={}", reference.to_git_lease())),
+                .filter_map(|reference| reference.to_git_lease())
+                .map(|lease| format!("--force-with-lease={lease}")),
         );
         command.args(["--", remote_name.as_str()]);
         // with --force-with-lease we cannot have the forced refspec,
@@ -388,6 +524,21 @@ fn parse_git_fetch_output(output: Output) -> Result<Option<String>, GitSubproces
     Err(external_git_error(&output.stderr))
 }
 
+fn parse_git_missing_objects(stdout: &[u8]) -> Result<Vec<gix::ObjectId>, GitSubprocessError> {
+    stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix(b"?"))
+        .map(|hex| {
+            gix::ObjectId::from_hex(hex).map_err(|_| {
+                GitSubprocessError::External(format!(
+                    "Unexpected object id in git rev-list output: {}",
+                    hex.to_str_lossy()
+                ))
+            })
+        })
+        .collect()
+}
+
 fn parse_git_branch_prune_output(output: Output) -> Result<(), GitSubprocessError> {
     if output.status.success() {
         return Ok(());
@@ -438,6 +589,28 @@ fn parse_git_remote_show_default_branch(
         .map(|b| b.map(|x| x.to_string()))
 }
 
+fn parse_git_ls_remote_refs(
+    stdout: &[u8],
+) -> Result<Vec<(gix::ObjectId, String)>, GitSubprocessError> {
+    stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let parsed = line.split_once_str("\t").and_then(|(id, name)| {
+                let id = gix::ObjectId::from_hex(id).ok()?;
+                let name = name.to_str().ok()?;
+                Some((id, name.to_owned()))
+            });
+            parsed.ok_or_else(|| {
+                GitSubprocessError::External(format!(
+                    "git-ls-remote output has unknown format: {}",
+                    line.to_str_lossy()
+                ))
+            })
+        })
+        .try_collect()
+}
+
 // git-push porcelain has the following format (per line)
 // `<flag>\t<from>:<to>\t<summary> (<reason>)`
 //
@@ -752,6 +925,7 @@ fn trim_sideband_line(line: &[u8]) -> (&[u8], Option<u8>) {
 #[cfg(test)]
 mod test {
     use indoc::formatdoc;
+    use indoc::indoc;
 
     use super::*;
 
@@ -970,4 +1144,44 @@ Done";
         );
         assert!(parse_unknown_option(b"error: unknown option: 'abc'").is_none());
     }
+
+    #[test]
+    fn test_parse_git_ls_remote_refs() {
+        let output = b"\
+d9a107e637f60cf0984e5a2c1141306330ef9ead\trefs/tags/v1
+137d548c2081eceafe907973d0f59a5e10c7aba5\trefs/tags/v2
+d9a107e637f60cf0984e5a2c1141306330ef9ead\trefs/tags/v2^{}
+";
+        let refs = parse_git_ls_remote_refs(output).unwrap();
+        assert_eq!(
+            refs.iter()
+                .map(|(id, name)| format!("{id} {name}"))
+                .collect_vec(),
+            [
+                "d9a107e637f60cf0984e5a2c1141306330ef9ead refs/tags/v1",
+                "137d548c2081eceafe907973d0f59a5e10c7aba5 refs/tags/v2",
+                "d9a107e637f60cf0984e5a2c1141306330ef9ead refs/tags/v2^{}",
+            ]
+        );
+        assert!(parse_git_ls_remote_refs(b"not a ref line\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_git_missing_objects() {
+        let stdout = indoc! {b"
+            f40e46ff9e09223b3617109f14d21213d4305741
+            3e48917a9eab5022a8568debb3dad1d9a1cb5030 dir
+            ?f2ad6c76f0115a6ba5b00456a849810e7ec0af20
+            ?61780798228d17af2d34fce4cfbdf35556832472
+        "};
+        let ids = parse_git_missing_objects(stdout).unwrap();
+        assert_eq!(
+            ids.iter().map(|id| id.to_string()).collect_vec(),
+            [
+                "f2ad6c76f0115a6ba5b00456a849810e7ec0af20",
+                "61780798228d17af2d34fce4cfbdf35556832472",
+            ]
+        );
+        assert!(parse_git_missing_objects(b"?not an id\n").is_err());
+    }
 }
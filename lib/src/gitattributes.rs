@@ -0,0 +1,360 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use ignore::gitignore;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::TreeValue;
+use crate::merged_tree::MergedTree;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::repo_path::RepoPathComponent;
+
+/// State of a Git attribute for a path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GitAttributeState {
+    /// The attribute is set, e.g. `text`.
+    Set,
+    /// The attribute is unset, e.g. `-text`.
+    Unset,
+    /// The attribute is set to a value, e.g. `eol=crlf`.
+    Value(String),
+    /// No pattern specifies the attribute, or it was reset by `!text`.
+    Unspecified,
+}
+
+impl GitAttributeState {
+    /// Returns the value if the attribute is set to a value.
+    pub fn as_value(&self) -> Option<&str> {
+        match self {
+            GitAttributeState::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct GitAttributesRule {
+    matcher: gitignore::Gitignore,
+    attributes: Vec<(String, GitAttributeState)>,
+}
+
+/// Models the effective contents of multiple .gitattributes files.
+#[derive(Debug)]
+pub struct GitAttributesFile {
+    parent: Option<Arc<GitAttributesFile>>,
+    prefix: String,
+    rules: Vec<GitAttributesRule>,
+}
+
+impl GitAttributesFile {
+    pub fn empty() -> Arc<GitAttributesFile> {
+        Arc::new(GitAttributesFile {
+            parent: None,
+            prefix: String::new(),
+            rules: vec![],
+        })
+    }
+
+    /// Concatenates new `.gitattributes` content at the `prefix` directory.
+    ///
+    /// The `prefix` should be a slash-separated path relative to the workspace
+    /// root. Lines that Git would reject, such as negative patterns, are
+    /// skipped.
+    pub fn chain(self: &Arc<GitAttributesFile>, prefix: &str, input: &[u8]) -> Arc<Self> {
+        let rules: Vec<_> = input
+            .split(|b| *b == b'\n')
+            .filter_map(|line| parse_rule(prefix, &String::from_utf8_lossy(line)))
+            .collect();
+        if rules.is_empty() {
+            return self.clone();
+        }
+        let parent = if self.rules.is_empty() {
+            self.parent.clone() // omit the empty root
+        } else {
+            Some(self.clone())
+        };
+        Arc::new(GitAttributesFile {
+            parent,
+            prefix: prefix.to_owned(),
+            rules,
+        })
+    }
+
+    /// Returns the state of the attribute `name` for the file at `path`.
+    ///
+    /// The last matching line wins, and lines in nested directories take
+    /// precedence over the ones in their parents.
+    pub fn get(&self, path: &str, name: &str) -> GitAttributeState {
+        iter::successors(Some(self), |file| file.parent.as_deref())
+            .filter(|file| path.starts_with(&file.prefix))
+            .flat_map(|file| file.rules.iter().rev())
+            .find_map(|rule| {
+                let (_, state) = rule.attributes.iter().rev().find(|(n, _)| n == name)?;
+                rule.matcher
+                    .matched(path, false)
+                    .is_ignore()
+                    .then(|| state.clone())
+            })
+            .unwrap_or(GitAttributeState::Unspecified)
+    }
+}
+
+fn parse_rule(prefix: &str, line: &str) -> Option<GitAttributesRule> {
+    let mut tokens = line.split_ascii_whitespace();
+    let pattern = tokens.next()?;
+    // Macro definitions aren't supported, and negative patterns are forbidden.
+    if pattern.starts_with('#') || pattern.starts_with("[attr]") || pattern.starts_with('!') {
+        return None;
+    }
+    let mut builder = gitignore::GitignoreBuilder::new(prefix);
+    builder.add_line(None, pattern).ok()?;
+    let matcher = builder.build().ok()?;
+    let mut attributes = vec![];
+    for token in tokens {
+        if let Some(name) = token.strip_prefix('-') {
+            attributes.push((name.to_owned(), GitAttributeState::Unset));
+        } else if let Some(name) = token.strip_prefix('!') {
+            attributes.push((name.to_owned(), GitAttributeState::Unspecified));
+        } else if let Some((name, value)) = token.split_once('=') {
+            attributes.push((name.to_owned(), GitAttributeState::Value(value.to_owned())));
+        } else if token == "binary" {
+            // The only built-in macro
+            attributes.push(("binary".to_owned(), GitAttributeState::Set));
+            for name in ["diff", "merge", "text"] {
+                attributes.push((name.to_owned(), GitAttributeState::Unset));
+            }
+        } else {
+            attributes.push((token.to_owned(), GitAttributeState::Set));
+        }
+    }
+    Some(GitAttributesRule {
+        matcher,
+        attributes,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum GitAttributesError {
+    #[error("Failed to read attributes from file {path}")]
+    ReadFile { path: PathBuf, source: io::Error },
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+enum GitAttributesSource {
+    Tree(MergedTree),
+    Disk(PathBuf),
+}
+
+/// Resolves Git attributes from the `.gitattributes` files in a tree or in
+/// the working copy.
+///
+/// The files are read on demand, and cached per directory.
+pub struct GitAttributes {
+    source: GitAttributesSource,
+    files: Mutex<HashMap<RepoPathBuf, Arc<GitAttributesFile>>>,
+}
+
+impl GitAttributes {
+    /// Reads the `.gitattributes` files from the `tree`.
+    pub fn from_tree(tree: MergedTree) -> Self {
+        Self::new(GitAttributesSource::Tree(tree))
+    }
+
+    /// Reads the `.gitattributes` files from the directory at `root`.
+    pub fn from_disk(root: PathBuf) -> Self {
+        Self::new(GitAttributesSource::Disk(root))
+    }
+
+    fn new(source: GitAttributesSource) -> Self {
+        GitAttributes {
+            source,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the state of the attribute `name` for the file at `path`.
+    pub async fn get(
+        &self,
+        path: &RepoPath,
+        name: &str,
+    ) -> Result<GitAttributeState, GitAttributesError> {
+        let dir = path.parent().unwrap_or(RepoPath::root());
+        let file = self.file_for_dir(dir).await?;
+        Ok(file.get(path.as_internal_file_string(), name))
+    }
+
+    async fn file_for_dir(
+        &self,
+        dir: &RepoPath,
+    ) -> Result<Arc<GitAttributesFile>, GitAttributesError> {
+        let mut file = GitAttributesFile::empty();
+        let mut dirs_to_load = vec![];
+        for ancestor in dir.ancestors() {
+            if let Some(cached) = self.files.lock().unwrap().get(ancestor) {
+                file = cached.clone();
+                break;
+            }
+            dirs_to_load.push(ancestor);
+        }
+        let basename = RepoPathComponent::new(".gitattributes").unwrap();
+        for dir in dirs_to_load.into_iter().rev() {
+            if let Some(content) = self.read_file(&dir.join(basename)).await? {
+                file = file.chain(&dir.to_internal_dir_string(), &content);
+            }
+            self.files
+                .lock()
+                .unwrap()
+                .insert(dir.to_owned(), file.clone());
+        }
+        Ok(file)
+    }
+
+    async fn read_file(&self, path: &RepoPath) -> Result<Option<Vec<u8>>, GitAttributesError> {
+        match &self.source {
+            GitAttributesSource::Tree(tree) => Ok(read_file_from_tree(tree, path).await?),
+            GitAttributesSource::Disk(root) => {
+                let disk_path = path.to_fs_path_unchecked(root);
+                match fs::read(&disk_path) {
+                    Ok(content) => Ok(Some(content)),
+                    Err(err)
+                        if err.kind() == io::ErrorKind::NotFound
+                            || err.kind() == io::ErrorKind::NotADirectory
+                            || disk_path.is_dir() =>
+                    {
+                        Ok(None)
+                    }
+                    Err(err) => Err(GitAttributesError::ReadFile {
+                        path: disk_path,
+                        source: err,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+async fn read_file_from_tree(tree: &MergedTree, path: &RepoPath) -> BackendResult<Option<Vec<u8>>> {
+    // Conflicted .gitattributes files are ignored.
+    let Ok(Some(TreeValue::File { id, .. })) = tree.path_value_async(path).await?.into_resolved()
+    else {
+        return Ok(None);
+    };
+    let mut reader = tree.store().read_file(path, &id).await?;
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .await
+        .map_err(|err| BackendError::ReadFile {
+            path: path.to_owned(),
+            id: id.clone(),
+            source: err.into(),
+        })?;
+    Ok(Some(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(input: &[u8], path: &str, name: &str) -> GitAttributeState {
+        GitAttributesFile::empty().chain("", input).get(path, name)
+    }
+
+    #[test]
+    fn test_gitattributes_empty_file() {
+        let file = GitAttributesFile::empty();
+        assert_eq!(file.get("foo", "text"), GitAttributeState::Unspecified);
+    }
+
+    #[test]
+    fn test_gitattributes_states() {
+        let input = b"*.txt text eol=crlf\n*.bin -text\n*.dat !text\n";
+        assert_eq!(get(input, "a.txt", "text"), GitAttributeState::Set);
+        assert_eq!(
+            get(input, "dir/a.txt", "eol"),
+            GitAttributeState::Value("crlf".to_owned())
+        );
+        assert_eq!(get(input, "a.bin", "text"), GitAttributeState::Unset);
+        assert_eq!(get(input, "a.dat", "text"), GitAttributeState::Unspecified);
+        assert_eq!(get(input, "a.txt", "diff"), GitAttributeState::Unspecified);
+    }
+
+    #[test]
+    fn test_gitattributes_last_line_wins() {
+        let input = b"*.png filter=lfs\nicon.png -filter\n";
+        assert_eq!(
+            get(input, "a.png", "filter"),
+            GitAttributeState::Value("lfs".to_owned())
+        );
+        assert_eq!(get(input, "icon.png", "filter"), GitAttributeState::Unset);
+        // Lines not mentioning the attribute don't override earlier lines
+        let input = b"*.png filter=lfs\n*.png diff\n";
+        assert_eq!(
+            get(input, "a.png", "filter"),
+            GitAttributeState::Value("lfs".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_gitattributes_binary_macro() {
+        let input = b"*.bin binary\n";
+        assert_eq!(get(input, "a.bin", "binary"), GitAttributeState::Set);
+        assert_eq!(get(input, "a.bin", "text"), GitAttributeState::Unset);
+        assert_eq!(get(input, "a.bin", "diff"), GitAttributeState::Unset);
+        assert_eq!(get(input, "a.bin", "merge"), GitAttributeState::Unset);
+    }
+
+    #[test]
+    fn test_gitattributes_ignored_lines() {
+        let input = b"# comment\n!*.txt text\n[attr]foo text\n\n";
+        assert_eq!(get(input, "a.txt", "text"), GitAttributeState::Unspecified);
+    }
+
+    #[test]
+    fn test_gitattributes_directory_pattern() {
+        // Patterns matching a directory don't apply to files in it
+        let input = b"dir text\ndir/ diff\nother/** -text\n";
+        assert_eq!(get(input, "dir/a", "text"), GitAttributeState::Unspecified);
+        assert_eq!(get(input, "dir/a", "diff"), GitAttributeState::Unspecified);
+        assert_eq!(get(input, "other/a", "text"), GitAttributeState::Unset);
+    }
+
+    #[test]
+    fn test_gitattributes_nested_files() {
+        let file = GitAttributesFile::empty()
+            .chain("", b"*.txt text\n/a.txt -diff\n")
+            .chain("dir/", b"*.txt -text\n/a.txt diff\n");
+        assert_eq!(file.get("a.txt", "text"), GitAttributeState::Set);
+        assert_eq!(file.get("a.txt", "diff"), GitAttributeState::Unset);
+        assert_eq!(file.get("dir/a.txt", "text"), GitAttributeState::Unset);
+        assert_eq!(file.get("dir/a.txt", "diff"), GitAttributeState::Set);
+        assert_eq!(file.get("dir/sub/b.txt", "text"), GitAttributeState::Unset);
+        assert_eq!(file.get("other/b.txt", "text"), GitAttributeState::Set);
+    }
+}
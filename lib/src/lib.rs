@@ -31,6 +31,7 @@ pub mod content_hash;
 pub mod absorb;
 pub mod annotate;
 pub mod backend;
+pub mod bisect;
 pub mod commit;
 pub mod commit_builder;
 pub mod config;
@@ -69,8 +70,14 @@ pub mod git {
 }
 #[cfg(feature = "git")]
 pub mod git_backend;
+#[cfg(feature = "git-builtin-transport")]
+mod git_builtin;
+pub mod git_lfs;
+#[cfg(feature = "git")]
+pub mod git_submodule;
 #[cfg(feature = "git")]
 mod git_subprocess;
+pub mod gitattributes;
 pub mod gitignore;
 pub mod gpg_signing;
 pub mod graph;
@@ -93,6 +100,7 @@ pub mod ref_name;
 pub mod refs;
 pub mod repo;
 pub mod repo_path;
+pub mod resolution_cache;
 pub mod revset;
 mod revset_parser;
 pub mod rewrite;
@@ -110,6 +118,7 @@ pub mod ssh_signing;
 pub mod stacked_table;
 pub mod store;
 pub mod str_util;
+pub mod structural_merge;
 pub mod submodule_store;
 #[cfg(feature = "testing")]
 pub mod test_signing_backend;
@@ -25,7 +25,7 @@ use std::fs::File;
 use std::fs::Metadata;
 use std::fs::OpenOptions;
 use std::io;
-use std::io::Read as _;
+use std::io::Read;
 use std::io::Write as _;
 use std::iter;
 use std::mem;
@@ -54,12 +54,13 @@ use rayon::prelude::ParallelIterator as _;
 use tempfile::NamedTempFile;
 use thiserror::Error;
 use tokio::io::AsyncRead;
-use tokio::io::AsyncReadExt as _;
+use tokio::io::AsyncReadExt;
 use tracing::instrument;
 use tracing::trace_span;
 
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
+use crate::backend::CommitId;
 use crate::backend::CopyId;
 use crate::backend::FileId;
 use crate::backend::MergedTreeId;
@@ -74,9 +75,11 @@ use crate::conflicts::choose_materialized_conflict_marker_len;
 use crate::conflicts::materialize_merge_result_to_bytes_with_marker_len;
 use crate::conflicts::materialize_tree_value;
 use crate::conflicts::ConflictMarkerStyle;
+use crate::conflicts::MaterializedFileValue;
 use crate::conflicts::MaterializedTreeValue;
 use crate::conflicts::MIN_CONFLICT_MARKER_LEN;
 use crate::eol::create_target_eol_strategy;
+use crate::eol::EolAttributes;
 pub use crate::eol::EolConversionMode;
 use crate::eol::TargetEolStrategy;
 use crate::file_util::check_symlink_support;
@@ -88,6 +91,12 @@ use crate::fsmonitor::watchman;
 use crate::fsmonitor::FsmonitorSettings;
 #[cfg(feature = "watchman")]
 use crate::fsmonitor::WatchmanConfig;
+use crate::git_lfs::is_lfs_path;
+use crate::git_lfs::LfsObjectStore;
+use crate::git_lfs::LfsPointer;
+use crate::git_lfs::LFS_POINTER_MAX_SIZE;
+use crate::gitattributes::GitAttributes;
+use crate::gitattributes::GitAttributesError;
 use crate::gitignore::GitIgnoreFile;
 use crate::lock::FileLock;
 use crate::matchers::DifferenceMatcher;
@@ -226,10 +235,10 @@ impl FileState {
         }
     }
 
-    fn for_gitsubmodule() -> Self {
+    fn for_gitsubmodule(head_mtime: MillisSinceEpoch) -> Self {
         FileState {
             file_type: FileType::GitSubmodule,
-            mtime: MillisSinceEpoch(0),
+            mtime: head_mtime,
             size: 0,
             materialized_conflict_data: None,
         }
@@ -476,6 +485,7 @@ pub struct TreeState {
     watchman_clock: Option<crate::protos::working_copy::WatchmanClock>,
 
     target_eol_strategy: TargetEolStrategy,
+    lfs_store: Option<LfsObjectStore>,
 }
 
 fn file_state_from_proto(proto: &crate::protos::working_copy::FileState) -> FileState {
@@ -727,7 +737,38 @@ fn reject_reserved_existing_path(disk_path: &Path) -> Result<(), CheckoutError>
     Ok(())
 }
 
-fn mtime_from_metadata(metadata: &Metadata) -> MillisSinceEpoch {
+/// Checks out the commit in the Git submodule if the submodule has been
+/// initialized. If the commit doesn't exist in the submodule repository, or if
+/// the submodule has uncommitted changes, the submodule is left as is, and the
+/// state is recorded so that the old `HEAD` won't be snapshotted.
+fn check_out_git_submodule(disk_path: &Path, id: &CommitId) -> Result<FileState, CheckoutError> {
+    #[cfg(feature = "git")]
+    {
+        use crate::git_submodule;
+        use crate::git_submodule::SubmoduleCheckout;
+
+        let checkout = git_submodule::check_out_submodule(disk_path, id).map_err(|err| {
+            CheckoutError::Other {
+                message: format!("Failed to check out Git submodule {}", disk_path.display()),
+                err: err.into(),
+            }
+        })?;
+        let head_mtime = match checkout {
+            SubmoduleCheckout::Uninitialized => MillisSinceEpoch(0),
+            SubmoduleCheckout::CheckedOut(mtime)
+            | SubmoduleCheckout::MissingCommit(mtime)
+            | SubmoduleCheckout::Modified(mtime) => mtime,
+        };
+        Ok(FileState::for_gitsubmodule(head_mtime))
+    }
+    #[cfg(not(feature = "git"))]
+    {
+        let _ = (disk_path, id);
+        Ok(FileState::for_gitsubmodule(MillisSinceEpoch(0)))
+    }
+}
+
+pub(crate) fn mtime_from_metadata(metadata: &Metadata) -> MillisSinceEpoch {
     let time = metadata
         .modified()
         .expect("File mtime not supported on this platform?");
@@ -831,6 +872,7 @@ impl TreeState {
         target_eol_strategy: TargetEolStrategy,
     ) -> TreeState {
         let tree_id = store.empty_merged_tree_id();
+        let lfs_store = LfsObjectStore::for_store(&store);
         TreeState {
             store,
             working_copy_path,
@@ -842,6 +884,7 @@ impl TreeState {
             symlink_support: check_symlink_support().unwrap_or(false),
             watchman_clock: None,
             target_eol_strategy,
+            lfs_store,
         }
     }
 
@@ -910,8 +953,10 @@ impl TreeState {
         Ok(())
     }
 
+    /// Writes the tree state to the state directory so that it can be loaded
+    /// again by [`TreeState::load()`].
     #[expect(clippy::assigning_clones)]
-    fn save(&mut self) -> Result<(), TreeStateError> {
+    pub fn save(&mut self) -> Result<(), TreeStateError> {
         let mut proto: crate::protos::working_copy::TreeState = Default::default();
         match &self.tree_id {
             MergedTreeId::Legacy(tree_id) => {
@@ -1049,6 +1094,7 @@ impl TreeState {
             let snapshotter = FileSnapshotter {
                 tree_state: self,
                 current_tree: &self.current_tree()?,
+                git_attributes: GitAttributes::from_disk(self.working_copy_path.clone()),
                 matcher: &matcher,
                 start_tracking_matcher,
                 // Move tx sides so they'll be dropped at the end of the scope.
@@ -1198,6 +1244,7 @@ struct PresentDirEntries {
 struct FileSnapshotter<'a> {
     tree_state: &'a TreeState,
     current_tree: &'a MergedTree,
+    git_attributes: GitAttributes,
     matcher: &'a dyn Matcher,
     start_tracking_matcher: &'a dyn Matcher,
     tree_entries_tx: Sender<(RepoPathBuf, MergedTreeValue)>,
@@ -1300,6 +1347,9 @@ impl FileSnapshotter<'_> {
         let maybe_current_file_state = file_states.get_at(dir, name);
         if let Some(file_state) = &maybe_current_file_state {
             if file_state.file_type == FileType::GitSubmodule {
+                if self.matcher.matches(&path) {
+                    self.process_git_submodule(path, file_state)?;
+                }
                 return Ok(None);
             }
         }
@@ -1379,10 +1429,11 @@ impl FileSnapshotter<'_> {
     /// Visits only paths we're already tracking.
     fn visit_tracked_files(&self, file_states: FileStates<'_>) -> Result<(), SnapshotError> {
         for (tracked_path, current_file_state) in file_states {
-            if current_file_state.file_type == FileType::GitSubmodule {
+            if !self.matcher.matches(tracked_path) {
                 continue;
             }
-            if !self.matcher.matches(tracked_path) {
+            if current_file_state.file_type == FileType::GitSubmodule {
+                self.process_git_submodule(tracked_path.to_owned(), &current_file_state)?;
                 continue;
             }
             let disk_path = tracked_path.to_fs_path(&self.tree_state.working_copy_path)?;
@@ -1410,6 +1461,48 @@ impl FileSnapshotter<'_> {
         Ok(())
     }
 
+    /// Records the commit checked out in the Git submodule if the submodule
+    /// has been initialized and its `HEAD` has changed.
+    fn process_git_submodule(
+        &self,
+        path: RepoPathBuf,
+        current_file_state: &FileState,
+    ) -> Result<(), SnapshotError> {
+        #[cfg(feature = "git")]
+        {
+            use crate::git_submodule;
+
+            let disk_path = path.to_fs_path(&self.tree_state.working_copy_path)?;
+            let to_snapshot_err = |err: git_submodule::GitSubmoduleError| SnapshotError::Other {
+                message: format!("Failed to read Git submodule {}", disk_path.display()),
+                err: err.into(),
+            };
+            let Some(head_mtime) =
+                git_submodule::submodule_head_mtime(&disk_path).map_err(to_snapshot_err)?
+            else {
+                return Ok(());
+            };
+            let new_file_state = FileState::for_gitsubmodule(head_mtime);
+            if new_file_state.is_clean(current_file_state) {
+                return Ok(());
+            }
+            if let Some(head_id) =
+                git_submodule::submodule_head_id(&disk_path).map_err(to_snapshot_err)?
+            {
+                let new_tree_value = Merge::normal(TreeValue::GitSubmodule(head_id));
+                if self.current_tree.path_value(&path)? != new_tree_value {
+                    self.tree_entries_tx
+                        .send((path.clone(), new_tree_value))
+                        .ok();
+                }
+            }
+            self.file_states_tx.send((path, new_file_state)).ok();
+        }
+        #[cfg(not(feature = "git"))]
+        let _ = (path, current_file_state);
+        Ok(())
+    }
+
     fn process_present_file(
         &self,
         path: RepoPathBuf,
@@ -1590,8 +1683,9 @@ impl FileSnapshotter<'_> {
                 message: format!("Failed to open file {}", disk_path.display()),
                 err: err.into(),
             })?;
+            let eol_attributes = self.eol_attributes(repo_path, disk_path).await?;
             self.target_eol_strategy
-                .convert_eol_for_snapshot(BlockingAsyncReader::new(file))
+                .convert_eol_for_snapshot(&eol_attributes, BlockingAsyncReader::new(file))
                 .await
                 .map_err(|err| SnapshotError::Other {
                     message: "Failed to convert the EOL".to_string(),
@@ -1655,9 +1749,23 @@ impl FileSnapshotter<'_> {
             message: format!("Failed to open file {}", disk_path.display()),
             err: err.into(),
         })?;
+        if let Some(lfs_store) = &self.tree_state.lfs_store {
+            let is_lfs_path = is_lfs_path(&self.git_attributes, path)
+                .await
+                .map_err(|err| SnapshotError::Other {
+                    message: format!("Failed to read attributes of {}", disk_path.display()),
+                    err: err.into(),
+                })?;
+            if is_lfs_path {
+                return self
+                    .write_lfs_file_to_store(lfs_store, path, disk_path, file)
+                    .await;
+            }
+        }
+        let eol_attributes = self.eol_attributes(path, disk_path).await?;
         let mut contents = self
             .target_eol_strategy
-            .convert_eol_for_snapshot(BlockingAsyncReader::new(file))
+            .convert_eol_for_snapshot(&eol_attributes, BlockingAsyncReader::new(file))
             .await
             .map_err(|err| SnapshotError::Other {
                 message: "Failed to convert the EOL".to_string(),
@@ -1666,6 +1774,53 @@ impl FileSnapshotter<'_> {
         Ok(self.store().write_file(path, &mut contents).await?)
     }
 
+    async fn eol_attributes(
+        &self,
+        path: &RepoPath,
+        disk_path: &Path,
+    ) -> Result<EolAttributes, SnapshotError> {
+        EolAttributes::resolve(&self.git_attributes, path)
+            .await
+            .map_err(|err| SnapshotError::Other {
+                message: format!("Failed to read attributes of {}", disk_path.display()),
+                err: err.into(),
+            })
+    }
+
+    /// Moves the file content to the LFS store, and writes a pointer to it
+    /// instead. Pointer files and empty files are written as is.
+    async fn write_lfs_file_to_store(
+        &self,
+        lfs_store: &LfsObjectStore,
+        path: &RepoPath,
+        disk_path: &Path,
+        mut file: File,
+    ) -> Result<FileId, SnapshotError> {
+        let mut peek = vec![];
+        (&mut file)
+            .take(LFS_POINTER_MAX_SIZE as u64 + 1)
+            .read_to_end(&mut peek)
+            .map_err(|err| SnapshotError::Other {
+                message: format!("Failed to read file {}", disk_path.display()),
+                err: err.into(),
+            })?;
+        let content = if peek.is_empty() || LfsPointer::parse(&peek).is_some() {
+            peek
+        } else {
+            let pointer = lfs_store
+                .insert(Read::chain(io::Cursor::new(peek), file))
+                .map_err(|err| SnapshotError::Other {
+                    message: format!("Failed to store LFS object for {}", disk_path.display()),
+                    err: err.into(),
+                })?;
+            pointer.to_bytes()
+        };
+        Ok(self
+            .store()
+            .write_file(path, &mut content.as_slice())
+            .await?)
+    }
+
     async fn write_symlink_to_store(
         &self,
         path: &RepoPath,
@@ -1704,7 +1859,7 @@ impl TreeState {
         disk_path: &Path,
         contents: impl AsyncRead + Send + Unpin,
         executable: bool,
-        apply_eol_conversion: bool,
+        eol_attributes: Option<&EolAttributes>,
     ) -> Result<FileState, CheckoutError> {
         let mut file = File::options()
             .write(true)
@@ -1714,9 +1869,9 @@ impl TreeState {
                 message: format!("Failed to open file {} for writing", disk_path.display()),
                 err: err.into(),
             })?;
-        let contents = if apply_eol_conversion {
+        let contents = if let Some(eol_attributes) = eol_attributes {
             self.target_eol_strategy
-                .convert_eol_for_update(contents)
+                .convert_eol_for_update(eol_attributes, contents)
                 .await
                 .map_err(|err| CheckoutError::Other {
                     message: "Failed to convert the EOL for the content".to_string(),
@@ -1750,6 +1905,44 @@ impl TreeState {
         ))
     }
 
+    /// Writes the LFS object referenced by the pointer file, or the pointer
+    /// file itself if the object isn't in the LFS store.
+    async fn write_lfs_file(
+        &self,
+        lfs_store: &LfsObjectStore,
+        disk_path: &Path,
+        mut file: MaterializedFileValue,
+        eol_attributes: &EolAttributes,
+    ) -> Result<FileState, CheckoutError> {
+        let mut peek = vec![];
+        (&mut file.reader)
+            .take(LFS_POINTER_MAX_SIZE as u64 + 1)
+            .read_to_end(&mut peek)
+            .await
+            .map_err(|err| CheckoutError::Other {
+                message: format!("Failed to read the content for {}", disk_path.display()),
+                err: err.into(),
+            })?;
+        let object = match LfsPointer::parse(&peek) {
+            Some(pointer) => lfs_store
+                .open(&pointer)
+                .map_err(|err| CheckoutError::Other {
+                    message: format!("Failed to open LFS object for {}", disk_path.display()),
+                    err: err.into(),
+                })?,
+            None => None,
+        };
+        if let Some(object) = object {
+            let contents = BlockingAsyncReader::new(object);
+            self.write_file(disk_path, contents, file.executable, None)
+                .await
+        } else {
+            let contents = AsyncReadExt::chain(io::Cursor::new(peek), file.reader);
+            self.write_file(disk_path, contents, file.executable, Some(eol_attributes))
+                .await
+        }
+    }
+
     fn write_symlink(&self, disk_path: &Path, target: String) -> Result<FileState, CheckoutError> {
         let target = PathBuf::from(&target);
         try_symlink(&target, disk_path).map_err(|err| CheckoutError::Other {
@@ -1896,6 +2089,9 @@ impl TreeState {
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
+        let git_attributes = GitAttributes::from_tree(new_tree.clone());
+        // Fetch the remotely stored files at once rather than one by one.
+        old_tree.prefetch_diff_files(new_tree, matcher).await?;
         let mut diff_stream = old_tree
             .diff_stream_for_file_system(new_tree, matcher)
             .map(|TreeDiffEntry { path, values }| async {
@@ -1918,22 +2114,6 @@ impl TreeState {
                 stats.updated_files += 1;
             }
 
-            // Existing Git submodule can be a non-empty directory on disk. We
-            // shouldn't attempt to manage it as a tracked path.
-            //
-            // TODO: It might be better to add general support for paths not
-            // tracked by jj than processing submodules specially. For example,
-            // paths excluded by .gitignore can be marked as such so that
-            // newly-"unignored" paths won't be snapshotted automatically.
-            if matches!(before.as_normal(), Some(TreeValue::GitSubmodule(_)))
-                && matches!(after, MaterializedTreeValue::GitSubmodule(_))
-            {
-                eprintln!("ignoring git submodule at {path:?}");
-                // Not updating the file state as if there were no diffs. Leave
-                // the state type as FileType::GitSubmodule if it was before.
-                continue;
-            }
-
             // Create parent directories no matter if after.is_present(). This
             // ensures that the path never traverses symlinks.
             let Some(disk_path) = create_parent_dirs(&self.working_copy_path, &path)? else {
@@ -1941,6 +2121,30 @@ impl TreeState {
                 stats.skipped_files += 1;
                 continue;
             };
+
+            // Git submodule can be a non-empty directory on disk. We shouldn't
+            // attempt to manage it as a tracked path, but the new commit can
+            // be checked out in place.
+            //
+            // TODO: It might be better to add general support for paths not
+            // tracked by jj than processing submodules specially. For example,
+            // paths excluded by .gitignore can be marked as such so that
+            // newly-"unignored" paths won't be snapshotted automatically.
+            if let MaterializedTreeValue::GitSubmodule(id) = &after {
+                if !matches!(before.as_normal(), Some(TreeValue::GitSubmodule(_))) {
+                    let present_file_deleted = before.is_present() && remove_old_file(&disk_path)?;
+                    if disk_path.is_dir() {
+                        reject_reserved_existing_path(&disk_path)?;
+                    } else if !present_file_deleted && !can_create_new_file(&disk_path)? {
+                        changed_file_states.push((path, FileState::placeholder()));
+                        stats.skipped_files += 1;
+                        continue;
+                    }
+                }
+                let file_state = check_out_git_submodule(&disk_path, id)?;
+                changed_file_states.push((path, file_state));
+                continue;
+            }
             // If the path was present, check reserved path first and delete it.
             let present_file_deleted = before.is_present() && remove_old_file(&disk_path)?;
             // If not, create temporary file to test the path validity.
@@ -1964,20 +2168,43 @@ impl TreeState {
                     continue;
                 }
                 MaterializedTreeValue::File(file) => {
-                    self.write_file(&disk_path, file.reader, file.executable, true)
-                        .await?
+                    let attributes_error = |err: GitAttributesError| CheckoutError::Other {
+                        message: format!("Failed to read attributes of {}", disk_path.display()),
+                        err: err.into(),
+                    };
+                    let is_lfs_path = self.lfs_store.is_some()
+                        && is_lfs_path(&git_attributes, &path)
+                            .await
+                            .map_err(attributes_error)?;
+                    let eol_attributes = EolAttributes::resolve(&git_attributes, &path)
+                        .await
+                        .map_err(attributes_error)?;
+                    match &self.lfs_store {
+                        Some(lfs_store) if is_lfs_path => {
+                            self.write_lfs_file(lfs_store, &disk_path, file, &eol_attributes)
+                                .await?
+                        }
+                        _ => {
+                            self.write_file(
+                                &disk_path,
+                                file.reader,
+                                file.executable,
+                                Some(&eol_attributes),
+                            )
+                            .await?
+                        }
+                    }
                 }
                 MaterializedTreeValue::Symlink { id: _, target } => {
                     if self.symlink_support {
                         self.write_symlink(&disk_path, target)?
                     } else {
-                        self.write_file(&disk_path, target.as_bytes(), false, false)
+                        self.write_file(&disk_path, target.as_bytes(), false, None)
                             .await?
                     }
                 }
                 MaterializedTreeValue::GitSubmodule(_) => {
-                    eprintln!("ignoring git submodule at {path:?}");
-                    FileState::for_gitsubmodule()
+                    panic!("git submodule should have been checked out at {path:?}");
                 }
                 MaterializedTreeValue::Tree(_) => {
                     panic!("unexpected tree entry in diff at {path:?}");
@@ -2046,10 +2273,7 @@ impl TreeState {
                         TreeValue::Conflict(_id) => {
                             panic!("unexpected conflict entry in diff at {path:?}");
                         }
-                        TreeValue::GitSubmodule(_id) => {
-                            eprintln!("ignoring git submodule at {path:?}");
-                            FileType::GitSubmodule
-                        }
+                        TreeValue::GitSubmodule(_id) => FileType::GitSubmodule,
                         TreeValue::Tree(_id) => {
                             panic!("unexpected tree entry in diff at {path:?}");
                         }
@@ -244,6 +244,7 @@ fn prefix_tree_to_visit_sets(tree: &RepoPathTree<PrefixNodeKind>) -> Visit {
 #[derive(Clone, Debug)]
 pub struct FileGlobsMatcher {
     tree: RepoPathTree<Vec<glob::Pattern>>,
+    case_sensitive: bool,
 }
 
 impl FileGlobsMatcher {
@@ -254,7 +255,21 @@ impl FileGlobsMatcher {
         for (dir, pattern) in dir_patterns {
             tree.add(dir.as_ref()).value.push(pattern);
         }
-        FileGlobsMatcher { tree }
+        FileGlobsMatcher {
+            tree,
+            case_sensitive: true,
+        }
+    }
+
+    /// Creates matcher of case-insensitive `patterns` relative to the root
+    /// directory.
+    pub fn new_case_insensitive(patterns: impl IntoIterator<Item = glob::Pattern>) -> Self {
+        let mut tree: RepoPathTree<Vec<glob::Pattern>> = Default::default();
+        tree.value.extend(patterns);
+        FileGlobsMatcher {
+            tree,
+            case_sensitive: false,
+        }
     }
 }
 
@@ -263,8 +278,8 @@ impl Matcher for FileGlobsMatcher {
         // TODO: glob::Pattern relies on path::is_separator() internally, but
         // RepoPath separator should be '/'. One way to address this problem is
         // to switch to globset::Glob, and use the underlying regex pattern.
-        const OPTIONS: glob::MatchOptions = glob::MatchOptions {
-            case_sensitive: true,
+        let options = glob::MatchOptions {
+            case_sensitive: self.case_sensitive,
             require_literal_separator: true,
             require_literal_leading_dot: false,
         };
@@ -274,7 +289,7 @@ impl Matcher for FileGlobsMatcher {
             .take_while(|(_, tail_path)| !tail_path.is_root()) // only dirs
             .any(|(sub, tail_path)| {
                 let name = tail_path.as_internal_file_string();
-                sub.value.iter().any(|pat| pat.matches_with(name, OPTIONS))
+                sub.value.iter().any(|pat| pat.matches_with(name, options))
             })
     }
 
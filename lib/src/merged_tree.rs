@@ -326,6 +326,30 @@ impl MergedTree {
         stream_without_trees(self.diff_stream_internal(other, matcher))
     }
 
+    /// Fetches the contents of the files that differ between this tree and
+    /// another tree at once, if the backend stores them remotely.
+    pub async fn prefetch_diff_files(
+        &self,
+        other: &MergedTree,
+        matcher: &dyn Matcher,
+    ) -> BackendResult<()> {
+        let mut ids = Vec::new();
+        let mut diff_stream = self.diff_stream(other, matcher);
+        while let Some(entry) = diff_stream.next().await {
+            let (before, after) = entry.values?;
+            ids.extend(before.iter().chain(after.iter()).flatten().filter_map(
+                |value| match value {
+                    TreeValue::File { id, .. } => Some(id.clone()),
+                    _ => None,
+                },
+            ));
+        }
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.store().prefetch_files(&ids).await
+    }
+
     /// Like `diff_stream()` but files in a removed tree will be returned before
     /// a file that replaces it.
     pub fn diff_stream_for_file_system<'matcher>(
@@ -251,6 +251,8 @@ pub struct View {
     pub head_ids: HashSet<CommitId>,
     pub local_bookmarks: BTreeMap<RefNameBuf, RefTarget>,
     pub tags: BTreeMap<RefNameBuf, RefTarget>,
+    /// Git notes refs, `refs/notes/<name>`, pointing to notes commits
+    pub notes: BTreeMap<RefNameBuf, RefTarget>,
     pub remote_views: BTreeMap<RemoteNameBuf, RemoteView>,
     pub git_refs: BTreeMap<GitRefNameBuf, RefTarget>,
     /// The commit the Git HEAD points to.
@@ -273,6 +275,7 @@ impl View {
             head_ids: HashSet::new(),
             local_bookmarks: BTreeMap::new(),
             tags: BTreeMap::new(),
+            notes: BTreeMap::new(),
             remote_views: BTreeMap::new(),
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
@@ -286,6 +289,7 @@ impl View {
             head_ids: HashSet::from([root_commit_id]),
             local_bookmarks: BTreeMap::new(),
             tags: BTreeMap::new(),
+            notes: BTreeMap::new(),
             remote_views: BTreeMap::new(),
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
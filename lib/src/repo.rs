@@ -43,12 +43,14 @@ use crate::commit::Commit;
 use crate::commit::CommitByCommitterTimestamp;
 use crate::commit_builder::CommitBuilder;
 use crate::commit_builder::DetachedCommitBuilder;
+use crate::config::ConfigGetError;
 use crate::dag_walk;
 use crate::default_index::DefaultIndexStore;
 use crate::default_index::DefaultMutableIndex;
 use crate::default_submodule_store::DefaultSubmoduleStore;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
+use crate::files::MergeOptions;
 use crate::index::ChangeIdIndex;
 use crate::index::Index;
 use crate::index::IndexReadError;
@@ -84,6 +86,7 @@ use crate::refs::diff_named_ref_targets;
 use crate::refs::diff_named_remote_refs;
 use crate::refs::merge_ref_targets;
 use crate::refs::merge_remote_refs;
+use crate::resolution_cache::ResolutionCache;
 use crate::revset;
 use crate::revset::RevsetEvaluationError;
 use crate::revset::RevsetExpression;
@@ -176,7 +179,14 @@ impl ReadonlyRepo {
     }
 
     pub fn default_index_store_initializer() -> &'static IndexStoreInitializer<'static> {
-        &|_settings, store_path| Ok(Box::new(DefaultIndexStore::init(store_path)?))
+        &|settings, store_path| {
+            let index_changed_paths = settings
+                .get_bool("index.changed-paths")
+                .map_err(|err| BackendInitError(err.into()))?;
+            let store =
+                DefaultIndexStore::init(store_path)?.with_changed_paths(index_changed_paths);
+            Ok(Box::new(store))
+        }
     }
 
     pub fn default_submodule_store_initializer() -> &'static SubmoduleStoreInitializer<'static> {
@@ -201,7 +211,9 @@ impl ReadonlyRepo {
         let backend = backend_initializer(settings, &store_path)?;
         let backend_path = store_path.join("type");
         fs::write(&backend_path, backend.name()).context(&backend_path)?;
-        let store = Store::new(backend, signer);
+        let merge_options = merge_options_from_settings(settings, &repo_path)
+            .map_err(|err| BackendInitError(err.into()))?;
+        let store = Store::new(backend, signer, merge_options);
 
         let op_store_path = repo_path.join("op_store");
         fs::create_dir(&op_store_path).context(&op_store_path)?;
@@ -444,7 +456,14 @@ impl Default for StoreFactories {
         // Index
         factories.add_index_store(
             DefaultIndexStore::name(),
-            Box::new(|_settings, store_path| Ok(Box::new(DefaultIndexStore::load(store_path)))),
+            Box::new(|settings, store_path| {
+                let index_changed_paths = settings
+                    .get_bool("index.changed-paths")
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                let store =
+                    DefaultIndexStore::load(store_path).with_changed_paths(index_changed_paths);
+                Ok(Box::new(store))
+            }),
         );
 
         // SubmoduleStores
@@ -473,6 +492,8 @@ pub enum StoreLoadError {
     Backend(#[from] BackendLoadError),
     #[error(transparent)]
     Signing(#[from] SignInitError),
+    #[error(transparent)]
+    Config(#[from] ConfigGetError),
 }
 
 impl StoreFactories {
@@ -618,6 +639,19 @@ pub fn read_store_type(
         .map_err(|source| StoreLoadError::ReadError { store, source })
 }
 
+/// Loads the merge options, including the resolution cache of the repo at
+/// `repo_path` if `merge.rerere` is enabled.
+fn merge_options_from_settings(
+    settings: &UserSettings,
+    repo_path: &Path,
+) -> Result<MergeOptions, ConfigGetError> {
+    let mut merge_options = MergeOptions::from_settings(settings)?;
+    if settings.get_bool("merge.rerere")? {
+        merge_options.resolution_cache = Some(Arc::new(ResolutionCache::load(repo_path)));
+    }
+    Ok(merge_options)
+}
+
 #[derive(Debug, Error)]
 pub enum RepoLoaderError {
     #[error(transparent)]
@@ -676,6 +710,7 @@ impl RepoLoader {
         let store = Store::new(
             store_factories.load_backend(settings, &repo_path.join("store"))?,
             Signer::from_settings(settings)?,
+            merge_options_from_settings(settings, repo_path)?,
         );
         let root_op_data = RootOperationData {
             root_commit_id: store.root_commit_id().clone(),
@@ -1594,27 +1629,7 @@ impl MutableRepo {
                 }
             }
             _ => {
-                let missing_commits = dag_walk::topo_order_reverse_ord_ok(
-                    heads
-                        .iter()
-                        .cloned()
-                        .map(CommitByCommitterTimestamp)
-                        .map(Ok),
-                    |CommitByCommitterTimestamp(commit)| commit.id().clone(),
-                    |CommitByCommitterTimestamp(commit)| {
-                        commit
-                            .parent_ids()
-                            .iter()
-                            .filter(|id| !self.index().has_id(id))
-                            .map(|id| self.store().get_commit(id))
-                            .map_ok(CommitByCommitterTimestamp)
-                            .collect_vec()
-                    },
-                    |_| panic!("graph has cycle"),
-                )?;
-                for CommitByCommitterTimestamp(missing_commit) in missing_commits.iter().rev() {
-                    self.index.add_commit(missing_commit);
-                }
+                self.index_commits(heads)?;
                 for head in heads {
                     self.view.get_mut().add_head(head.id());
                 }
@@ -1624,6 +1639,36 @@ impl MutableRepo {
         Ok(())
     }
 
+    /// Adds the given `commits` and ancestor commits to the index without
+    /// making them visible.
+    ///
+    /// This is useful for commits referenced only by Git refs, such as Git
+    /// notes commits.
+    pub fn index_commits(&mut self, commits: &[Commit]) -> BackendResult<()> {
+        let missing_commits = dag_walk::topo_order_reverse_ord_ok(
+            commits
+                .iter()
+                .cloned()
+                .map(CommitByCommitterTimestamp)
+                .map(Ok),
+            |CommitByCommitterTimestamp(commit)| commit.id().clone(),
+            |CommitByCommitterTimestamp(commit)| {
+                commit
+                    .parent_ids()
+                    .iter()
+                    .filter(|id| !self.index().has_id(id))
+                    .map(|id| self.store().get_commit(id))
+                    .map_ok(CommitByCommitterTimestamp)
+                    .collect_vec()
+            },
+            |_| panic!("graph has cycle"),
+        )?;
+        for CommitByCommitterTimestamp(missing_commit) in missing_commits.iter().rev() {
+            self.index.add_commit(missing_commit);
+        }
+        Ok(())
+    }
+
     pub fn remove_head(&mut self, head: &CommitId) {
         self.view_mut().remove_head(head);
         self.view.mark_dirty();
@@ -1718,6 +1763,27 @@ impl MutableRepo {
         view.set_tag_target(name, new_target);
     }
 
+    pub fn get_notes(&self, name: &RefName) -> RefTarget {
+        self.view.with_ref(|v| v.get_notes(name).clone())
+    }
+
+    pub fn set_notes_target(&mut self, name: &RefName, target: RefTarget) {
+        self.view_mut().set_notes_target(name, target);
+    }
+
+    pub fn merge_notes(
+        &mut self,
+        name: &RefName,
+        base_target: &RefTarget,
+        other_target: &RefTarget,
+    ) {
+        let view = self.view.get_mut();
+        let index = self.index.as_index();
+        let self_target = view.get_notes(name);
+        let new_target = merge_ref_targets(index, self_target, base_target, other_target);
+        view.set_notes_target(name, new_target);
+    }
+
     pub fn get_git_ref(&self, name: &GitRefName) -> RefTarget {
         self.view.with_ref(|v| v.get_git_ref(name).clone())
     }
@@ -1815,6 +1881,11 @@ impl MutableRepo {
             self.merge_tag(name, base_target, other_target);
         }
 
+        let changed_notes = diff_named_ref_targets(base.notes(), other.notes());
+        for (name, (base_target, other_target)) in changed_notes {
+            self.merge_notes(name, base_target, other_target);
+        }
+
         let changed_git_refs = diff_named_ref_targets(base.git_refs(), other.git_refs());
         for (name, (base_target, other_target)) in changed_git_refs {
             self.merge_git_ref(name, base_target, other_target);
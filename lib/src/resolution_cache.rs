@@ -0,0 +1,340 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records resolutions of file conflicts so they can be reused when the same
+//! conflict appears again ("rerere").
+//!
+//! A resolution is keyed by the hash of the conflicting file contents, so the
+//! recorded resolution applies to the same conflict at any path and in any
+//! commit.
+
+#![allow(missing_docs)]
+
+use std::borrow::Borrow;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::NamedTempFile;
+use tempfile::PersistError;
+use thiserror::Error;
+
+use crate::backend::BackendResult;
+use crate::backend::CommitId;
+use crate::backend::FileId;
+use crate::backend::TreeValue;
+use crate::content_hash::blake2b_hash;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::merge::Merge;
+use crate::merge::MergedTreeValue;
+use crate::merged_tree::MergedTree;
+use crate::object_id::id_type;
+use crate::object_id::ObjectId as _;
+use crate::repo_path::RepoPathBuf;
+
+id_type!(
+    /// Identifier of a recorded resolution, which is the hash of the
+    /// conflicting file contents.
+    pub ResolutionId { hex() }
+);
+
+/// Error that may occur when reading or writing recorded resolutions.
+#[derive(Debug, Error)]
+pub enum ResolutionCacheError {
+    /// Failed to access the cache directory or a resolution file.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// The resolution file couldn't be parsed.
+    #[error("Invalid recorded resolution {}", path.display())]
+    Invalid {
+        /// Path to the resolution file.
+        path: PathBuf,
+        /// Underlying error.
+        source: serde_json::Error,
+    },
+}
+
+/// Resolution of a file conflict.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordedResolution {
+    /// Path at which the conflict was resolved.
+    pub path: RepoPathBuf,
+    /// Resolved file content.
+    pub file_id: FileId,
+    /// Commit which resolved the conflict.
+    pub commit_id: CommitId,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct ResolutionData {
+    path: String,
+    file_id: String,
+    commit_id: String,
+}
+
+/// Directory of recorded conflict resolutions.
+#[derive(Debug)]
+pub struct ResolutionCache {
+    dir: PathBuf,
+}
+
+impl ResolutionCache {
+    /// Loads the resolution cache of the repo at `repo_path`. The directory is
+    /// created when the first resolution is recorded.
+    pub fn load(repo_path: &Path) -> Self {
+        ResolutionCache {
+            dir: repo_path.join("resolutions"),
+        }
+    }
+
+    /// Looks up the resolution recorded for `id`.
+    pub fn get(
+        &self,
+        id: &ResolutionId,
+    ) -> Result<Option<RecordedResolution>, ResolutionCacheError> {
+        let path = self.dir.join(id.hex());
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(PathError { path, error: err }.into()),
+        };
+        let parse = || -> Result<_, serde_json::Error> {
+            use serde::de::Error as _;
+            let data: ResolutionData = serde_json::from_slice(&data)?;
+            let path =
+                RepoPathBuf::from_internal_string(data.path).map_err(serde_json::Error::custom)?;
+            let file_id = FileId::try_from_hex(&data.file_id)
+                .ok_or_else(|| serde_json::Error::custom("invalid file id"))?;
+            let commit_id = CommitId::try_from_hex(&data.commit_id)
+                .ok_or_else(|| serde_json::Error::custom("invalid commit id"))?;
+            Ok(RecordedResolution {
+                path,
+                file_id,
+                commit_id,
+            })
+        };
+        parse()
+            .map(Some)
+            .map_err(|source| ResolutionCacheError::Invalid { path, source })
+    }
+
+    /// Records `resolution` for `id`, replacing the existing one if any.
+    pub fn insert(
+        &self,
+        id: &ResolutionId,
+        resolution: &RecordedResolution,
+    ) -> Result<(), ResolutionCacheError> {
+        let data = ResolutionData {
+            path: resolution.path.as_internal_file_string().to_owned(),
+            file_id: resolution.file_id.hex(),
+            commit_id: resolution.commit_id.hex(),
+        };
+        fs::create_dir_all(&self.dir).context(&self.dir)?;
+        let mut temp_file = NamedTempFile::new_in(&self.dir).context(&self.dir)?;
+        serde_json::to_writer(temp_file.as_file_mut(), &data)
+            .map_err(io::Error::from)
+            .and_then(|()| temp_file.as_file_mut().flush())
+            .context(temp_file.path())?;
+        let new_path = self.dir.join(id.hex());
+        persist_replacing_temp_file(temp_file, &new_path).context(&new_path)?;
+        Ok(())
+    }
+
+    /// Forgets the resolution recorded for `id`.
+    pub fn remove(&self, id: &ResolutionId) -> Result<(), ResolutionCacheError> {
+        let path = self.dir.join(id.hex());
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(PathError { path, error: err }.into()),
+        }
+    }
+
+    /// Returns all recorded resolutions sorted by id.
+    pub fn entries(&self) -> Result<Vec<(ResolutionId, RecordedResolution)>, ResolutionCacheError> {
+        let dir_entries = match fs::read_dir(&self.dir) {
+            Ok(dir_entries) => dir_entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => {
+                return Err(PathError {
+                    path: self.dir.clone(),
+                    error: err,
+                }
+                .into())
+            }
+        };
+        let mut entries = vec![];
+        for entry in dir_entries {
+            let entry = entry.context(&self.dir)?;
+            // Skip temporary files
+            let Some(id) = entry
+                .file_name()
+                .to_str()
+                .and_then(ResolutionId::try_from_hex)
+            else {
+                continue;
+            };
+            if let Some(resolution) = self.get(&id)? {
+                entries.push((id, resolution));
+            }
+        }
+        entries.sort_unstable_by(|(id1, _), (id2, _)| id1.cmp(id2));
+        Ok(entries)
+    }
+
+    /// Records the resolutions of the file conflicts in `old_tree` which are
+    /// resolved to a file in `new_tree`. An existing resolution of the same
+    /// conflict is replaced if it has different content.
+    ///
+    /// Returns the number of recorded resolutions.
+    pub fn record_resolved_conflicts(
+        &self,
+        old_tree: &MergedTree,
+        new_tree: &MergedTree,
+        commit_id: &CommitId,
+    ) -> BackendResult<usize> {
+        let mut count = 0;
+        for (path, value) in old_tree.conflicts() {
+            let Some(file_ids) = file_id_conflict(&value?) else {
+                continue;
+            };
+            let Some(Some(TreeValue::File { id, .. })) =
+                new_tree.path_value(&path)?.into_resolved().ok()
+            else {
+                continue;
+            };
+            let resolution_id = resolution_id(&file_ids);
+            let result = self.get(&resolution_id).and_then(|existing| {
+                if existing.is_some_and(|resolution| resolution.file_id == id) {
+                    return Ok(false);
+                }
+                let resolution = RecordedResolution {
+                    path,
+                    file_id: id,
+                    commit_id: commit_id.clone(),
+                };
+                self.insert(&resolution_id, &resolution)?;
+                Ok(true)
+            });
+            match result {
+                Ok(true) => count += 1,
+                Ok(false) => {}
+                Err(err) => tracing::warn!(?err, "Failed to record conflict resolution"),
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// Moves `temp_file` to `new_path`, replacing the existing file. Unlike
+/// content-addressed files, the existing file may have different content.
+fn persist_replacing_temp_file(temp_file: NamedTempFile, new_path: &Path) -> io::Result<()> {
+    match temp_file.persist(new_path) {
+        Ok(_) => Ok(()),
+        // On Windows, rename() fails if the destination exists and is opened
+        // without FILE_SHARE_DELETE for example.
+        Err(PersistError { error: _, file }) if cfg!(windows) && new_path.exists() => {
+            fs::remove_file(new_path)?;
+            file.persist(new_path)
+                .map(|_| ())
+                .map_err(|PersistError { error, file: _ }| error)
+        }
+        Err(PersistError { error, file: _ }) => Err(error),
+    }
+}
+
+/// Computes the id of the resolution for the given file conflict. The
+/// `conflict` is supposed to be simplified.
+pub fn resolution_id(conflict: &Merge<impl Borrow<FileId>>) -> ResolutionId {
+    let conflict = conflict.map(|id| id.borrow().clone());
+    ResolutionId::new(blake2b_hash(&conflict).to_vec())
+}
+
+/// Returns the file contents of the conflict if all its terms are files and
+/// the contents don't resolve trivially.
+fn file_id_conflict(value: &MergedTreeValue) -> Option<Merge<FileId>> {
+    let file_ids = value
+        .simplify()
+        .try_map(|term| match term {
+            Some(TreeValue::File { id, .. }) => Ok(id.clone()),
+            _ => Err(()),
+        })
+        .ok()?
+        .simplify();
+    (!file_ids.is_resolved()).then_some(file_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools as _;
+
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = ResolutionCache::load(temp_dir.path());
+        assert_eq!(cache.entries().unwrap(), vec![]);
+
+        let id1 = ResolutionId::from_hex("0123");
+        let id2 = ResolutionId::from_hex("4567");
+        let resolution = RecordedResolution {
+            path: RepoPathBuf::from_internal_string("dir/file").unwrap(),
+            file_id: FileId::from_hex("abcd"),
+            commit_id: CommitId::from_hex("ef01"),
+        };
+        cache.insert(&id2, &resolution).unwrap();
+        cache.insert(&id1, &resolution).unwrap();
+        assert_eq!(cache.get(&id1).unwrap(), Some(resolution.clone()));
+        assert_eq!(
+            cache
+                .entries()
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect_vec(),
+            vec![id1.clone(), id2.clone()]
+        );
+
+        cache.remove(&id1).unwrap();
+        cache.remove(&id1).unwrap();
+        assert_eq!(cache.get(&id1).unwrap(), None);
+        assert_eq!(cache.entries().unwrap(), vec![(id2, resolution)]);
+    }
+
+    #[test]
+    fn test_insert_replaces_existing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = ResolutionCache::load(temp_dir.path());
+
+        let id = ResolutionId::from_hex("0123");
+        let resolution1 = RecordedResolution {
+            path: RepoPathBuf::from_internal_string("file").unwrap(),
+            file_id: FileId::from_hex("abcd"),
+            commit_id: CommitId::from_hex("ef01"),
+        };
+        let resolution2 = RecordedResolution {
+            path: RepoPathBuf::from_internal_string("file").unwrap(),
+            file_id: FileId::from_hex("2345"),
+            commit_id: CommitId::from_hex("6789"),
+        };
+        cache.insert(&id, &resolution1).unwrap();
+        cache.insert(&id, &resolution2).unwrap();
+        assert_eq!(cache.get(&id).unwrap(), Some(resolution2.clone()));
+        assert_eq!(cache.entries().unwrap(), vec![(id, resolution2)]);
+    }
+}
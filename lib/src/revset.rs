@@ -2652,7 +2652,11 @@ fn resolve_commit_ref(
         }
         RevsetCommitRef::GitRefs => {
             let mut commit_ids = vec![];
-            for ref_target in repo.view().git_refs().values() {
+            for (name, ref_target) in repo.view().git_refs() {
+                // Notes commits aren't part of the history.
+                if name.as_str().starts_with("refs/notes/") {
+                    continue;
+                }
                 commit_ids.extend(ref_target.added_ids().cloned());
             }
             Ok(commit_ids)
@@ -14,6 +14,7 @@
 
 #![allow(missing_docs)]
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -22,6 +23,7 @@ use std::sync::Mutex;
 use chrono::DateTime;
 use rand::prelude::*;
 use rand_chacha::ChaCha20Rng;
+use serde::de::IntoDeserializer as _;
 use serde::Deserialize;
 
 use crate::backend::ChangeId;
@@ -36,7 +38,9 @@ use crate::config::StackedConfig;
 use crate::config::ToConfigNamePath;
 use crate::fmt_util::binary_prefix;
 use crate::fsmonitor::FsmonitorSettings;
+use crate::ref_name::RemoteNameBuf;
 use crate::signing::SignBehavior;
+use crate::str_util::StringPattern;
 
 #[derive(Debug, Clone)]
 pub struct UserSettings {
@@ -63,6 +67,26 @@ pub struct GitSettings {
     pub abandon_unreachable_commits: bool,
     pub executable_path: PathBuf,
     pub write_change_id_header: bool,
+    /// Push options to transmit to the server on push.
+    pub push_options: Vec<String>,
+    /// Bookmarks to fetch from each remote by default.
+    pub fetch_bookmarks: HashMap<RemoteNameBuf, Vec<StringPattern>>,
+    /// Bookmarks allowed to be pushed to each remote.
+    pub push_bookmarks: HashMap<RemoteNameBuf, Vec<StringPattern>>,
+    /// How to communicate with Git remotes.
+    pub transport: GitTransport,
+}
+
+/// How to communicate with Git remotes when fetching and pushing.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitTransport {
+    /// Run the `git` executable.
+    #[default]
+    Subprocess,
+    /// Talk to the remote in-process without the `git` executable. Requires
+    /// the `git-builtin-transport` feature.
+    Builtin,
 }
 
 impl GitSettings {
@@ -72,10 +96,37 @@ impl GitSettings {
             abandon_unreachable_commits: settings.get_bool("git.abandon-unreachable-commits")?,
             executable_path: settings.get("git.executable-path")?,
             write_change_id_header: settings.get("git.write-change-id-header")?,
+            push_options: settings.get("git.push-options")?,
+            fetch_bookmarks: get_bookmark_patterns_by_remote(settings, "fetch-bookmarks")?,
+            push_bookmarks: get_bookmark_patterns_by_remote(settings, "push-bookmarks")?,
+            transport: settings.get("git.transport")?,
         })
     }
 }
 
+/// Parses `git.<key>.<remote>` tables of bookmark patterns.
+fn get_bookmark_patterns_by_remote(
+    settings: &UserSettings,
+    key: &str,
+) -> Result<HashMap<RemoteNameBuf, Vec<StringPattern>>, ConfigGetError> {
+    settings
+        .table_keys(["git", key])
+        .map(|remote| {
+            let patterns = settings.get_value_with(
+                ["git", key, remote],
+                |value| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+                    let patterns = Vec::<String>::deserialize(value.into_deserializer())?;
+                    Ok(patterns
+                        .iter()
+                        .map(|pattern| StringPattern::parse(pattern))
+                        .collect::<Result<Vec<_>, _>>()?)
+                },
+            )?;
+            Ok((remote.into(), patterns))
+        })
+        .collect()
+}
+
 impl Default for GitSettings {
     fn default() -> Self {
         GitSettings {
@@ -83,6 +134,10 @@ impl Default for GitSettings {
             abandon_unreachable_commits: true,
             executable_path: PathBuf::from("git"),
             write_change_id_header: true,
+            push_options: vec![],
+            fetch_bookmarks: HashMap::new(),
+            push_bookmarks: HashMap::new(),
+            transport: GitTransport::default(),
         }
     }
 }
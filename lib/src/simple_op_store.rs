@@ -552,6 +552,13 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
         });
     }
 
+    for (name, target) in &view.notes {
+        proto.notes.push(crate::protos::op_store::Notes {
+            name: name.into(),
+            target: ref_target_to_proto(target),
+        });
+    }
+
     for (git_ref_name, target) in &view.git_refs {
         proto.git_refs.push(crate::protos::op_store::GitRef {
             name: git_ref_name.into(),
@@ -595,6 +602,12 @@ fn view_from_proto(proto: crate::protos::op_store::View) -> View {
             .insert(name, ref_target_from_proto(tag_proto.target));
     }
 
+    for notes_proto in proto.notes {
+        let name: RefNameBuf = notes_proto.name.into();
+        view.notes
+            .insert(name, ref_target_from_proto(notes_proto.target));
+    }
+
     for git_ref in proto.git_refs {
         let name: GitRefNameBuf = git_ref.name.into();
         let target = if git_ref.target.is_some() {
@@ -810,6 +823,7 @@ mod tests {
         let bookmark_main_origin_target = RefTarget::normal(CommitId::from_hex("ccc222"));
         let bookmark_deleted_origin_target = RefTarget::normal(CommitId::from_hex("ccc333"));
         let tag_v1_target = RefTarget::normal(CommitId::from_hex("ddd111"));
+        let notes_commits_target = RefTarget::normal(CommitId::from_hex("eee111"));
         let git_refs_main_target = RefTarget::normal(CommitId::from_hex("fff111"));
         let git_refs_feature_target = RefTarget::from_legacy_form(
             [CommitId::from_hex("fff111")],
@@ -825,6 +839,9 @@ mod tests {
             tags: btreemap! {
                 "v1.0".into() => tag_v1_target,
             },
+            notes: btreemap! {
+                "commits".into() => notes_commits_target,
+            },
             remote_views: btreemap! {
                 "origin".into() => RemoteView {
                     bookmarks: btreemap! {
@@ -892,7 +909,7 @@ mod tests {
         // Test exact output so we detect regressions in compatibility
         assert_snapshot!(
             ViewId::new(blake2b_hash(&create_view()).to_vec()).hex(),
-            @"f426676b3a2f7c6b9ec8677cb05ed249d0d244ab7e86a7c51117e2d8a4829db65e55970c761231e2107d303bf3d33a1f2afdd4ed2181f223e99753674b20a35e"
+            @"309f394eef12e5fc5851e9ce2bc5f190cdc2b27238c0fbec236134c13b4f5f26b8abe5587816355386acff15a434e7dfbdf80720069d777092e0c6135966706d"
         );
     }
 
@@ -40,6 +40,7 @@ use crate::backend::SigningFn;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::commit::Commit;
+use crate::files::MergeOptions;
 use crate::index::Index;
 use crate::merge::Merge;
 use crate::merge::MergedTreeValue;
@@ -60,6 +61,7 @@ const TREE_CACHE_CAPACITY: usize = 1000;
 pub struct Store {
     backend: Box<dyn Backend>,
     signer: Signer,
+    merge_options: MergeOptions,
     commit_cache: Mutex<CLruCache<CommitId, Arc<backend::Commit>>>,
     tree_cache: Mutex<CLruCache<(RepoPathBuf, TreeId), Arc<backend::Tree>>>,
 }
@@ -73,10 +75,15 @@ impl Debug for Store {
 }
 
 impl Store {
-    pub fn new(backend: Box<dyn Backend>, signer: Signer) -> Arc<Self> {
+    pub fn new(
+        backend: Box<dyn Backend>,
+        signer: Signer,
+        merge_options: MergeOptions,
+    ) -> Arc<Self> {
         Arc::new(Store {
             backend,
             signer,
+            merge_options,
             commit_cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
             tree_cache: Mutex::new(CLruCache::new(TREE_CACHE_CAPACITY.try_into().unwrap())),
         })
@@ -94,6 +101,10 @@ impl Store {
         &self.signer
     }
 
+    pub fn merge_options(&self) -> &MergeOptions {
+        &self.merge_options
+    }
+
     pub fn get_copy_records(
         &self,
         paths: Option<&[RepoPathBuf]>,
@@ -252,6 +263,14 @@ impl Store {
         self.backend.read_file(path, id).await
     }
 
+    pub async fn file_size(&self, path: &RepoPath, id: &FileId) -> BackendResult<u64> {
+        self.backend.file_size(path, id).await
+    }
+
+    pub async fn prefetch_files(&self, ids: &[FileId]) -> BackendResult<()> {
+        self.backend.prefetch_files(ids).await
+    }
+
     pub async fn write_file(
         &self,
         path: &RepoPath,
@@ -0,0 +1,728 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Syntax-aware merging of file contents.
+//!
+//! The line-level merge in [`crate::files`] reports a conflict as soon as the
+//! sides change the same or adjacent lines. A [`StructuralMerger`] retries such
+//! merges on the syntax tokens of the file, so that changes to neighboring
+//! lines, or to different parts of a line, can be merged independently. Imports
+//! inserted at the same position by different sides, such as names added to
+//! the same import list, are merged by keeping the imports from all sides
+//! unless they import the same name. Any other insertions at the same position
+//! conflict.
+//!
+//! Languages are selected by file extension. Parsers based on tree-sitter
+//! grammars are available if the corresponding `tree-sitter-<language>` crate
+//! features are enabled, and other parsers can be plugged in by implementing
+//! [`SyntaxLanguage`].
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::iter;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bstr::BString;
+use itertools::Itertools as _;
+
+use crate::diff::find_line_ranges;
+use crate::diff::CompareBytesExactly;
+use crate::diff::Diff;
+use crate::diff::DiffHunkKind;
+use crate::merge::Merge;
+use crate::repo_path::RepoPath;
+
+/// Parser for the files of a programming language.
+pub trait SyntaxLanguage: Debug + Send + Sync {
+    /// Name of the language.
+    fn name(&self) -> &str;
+
+    /// Parses `source` into a syntax tree. Returns `None` if the source
+    /// contains syntax errors.
+    fn parse(&self, source: &[u8]) -> Option<SyntaxNode>;
+
+    /// Returns true if nodes of the given `kind` are lists of imported names,
+    /// whose children can be put in any order.
+    fn is_import_list(&self, kind: &str) -> bool;
+
+    /// Returns true if nodes of the given `kind` are import statements, which
+    /// can be put in any order relative to each other.
+    fn is_import(&self, kind: &str) -> bool;
+}
+
+/// Node of a syntax tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyntaxNode {
+    /// Kind of the node as named by the grammar.
+    pub kind: &'static str,
+    /// Whether the node is a named node as opposed to punctuation or keyword.
+    pub is_named: bool,
+    /// Byte range of the node in the source.
+    pub range: Range<usize>,
+    /// Child nodes in source order.
+    pub children: Vec<SyntaxNode>,
+}
+
+/// Merges file contents at the syntax level, using the language selected by
+/// the file extension.
+#[derive(Debug, Default)]
+pub struct StructuralMerger {
+    languages: HashMap<String, Arc<dyn SyntaxLanguage>>,
+}
+
+impl StructuralMerger {
+    /// Creates a merger with no languages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a merger with the languages enabled by crate features.
+    pub fn with_builtin_languages() -> Self {
+        let mut merger = Self::new();
+        for (extensions, language) in builtin_languages() {
+            merger.add_language(extensions, language);
+        }
+        merger
+    }
+
+    /// Uses `language` for files with the given `extensions`, replacing any
+    /// language previously registered for them.
+    pub fn add_language(&mut self, extensions: &[&str], language: Arc<dyn SyntaxLanguage>) {
+        for extension in extensions {
+            self.languages
+                .insert(extension.to_ascii_lowercase(), language.clone());
+        }
+    }
+
+    /// Returns the language to use for the file at `path`, if any.
+    pub fn language_for_path(&self, path: &RepoPath) -> Option<&dyn SyntaxLanguage> {
+        let (_, name) = path.split()?;
+        let (stem, extension) = name.as_internal_str().rsplit_once('.')?;
+        if stem.is_empty() {
+            return None;
+        }
+        let language = self.languages.get(&extension.to_ascii_lowercase())?;
+        Some(language.as_ref())
+    }
+
+    /// Attempts to merge the contents of the file at `path`.
+    ///
+    /// Returns `None` if there's no language for the file, or if the inputs
+    /// can't be merged without conflicts.
+    pub fn try_merge<T: AsRef<[u8]>>(&self, path: &RepoPath, inputs: &Merge<T>) -> Option<BString> {
+        let language = self.language_for_path(path)?;
+        try_merge_with_language(language, inputs)
+    }
+}
+
+/// Attempts to merge `inputs` written in the given `language`.
+///
+/// Returns `None` if any of the inputs can't be parsed, if the inputs can't be
+/// merged without conflicts, or if the merged content can't be parsed.
+pub fn try_merge_with_language<T: AsRef<[u8]>>(
+    language: &dyn SyntaxLanguage,
+    inputs: &Merge<T>,
+) -> Option<BString> {
+    // Use the first remove as base, the same as the line-level merge.
+    let num_diffs = inputs.removes().len();
+    let texts = inputs
+        .removes()
+        .chain(inputs.adds())
+        .map(AsRef::as_ref)
+        .collect_vec();
+    let trees: Vec<SyntaxNode> = texts
+        .iter()
+        .map(|text| language.parse(text))
+        .collect::<Option<_>>()?;
+    let token_ranges = iter::zip(&texts, &trees)
+        .map(|(text, tree)| token_ranges(text, tree))
+        .collect_vec();
+    let diff = Diff::for_token_ranges(texts.iter().copied(), &token_ranges, CompareBytesExactly);
+
+    let mut merged = BString::default();
+    for hunk in diff.hunk_ranges() {
+        let contents = iter::zip(&texts, &hunk.ranges)
+            .map(|(text, range)| &text[range.clone()])
+            .collect_vec();
+        match hunk.kind {
+            DiffHunkKind::Matching => merged.extend_from_slice(contents[0]),
+            DiffHunkKind::Different => {
+                let merge = Merge::from_removes_adds(
+                    contents[..num_diffs].iter().copied(),
+                    contents[num_diffs..].iter().copied(),
+                );
+                if let Some(content) = merge.resolve_trivial() {
+                    merged.extend_from_slice(content);
+                    continue;
+                }
+                if merge.removes().any(|content| !content.is_empty())
+                    || merge.adds().any(|content| content.is_empty())
+                {
+                    return None;
+                }
+                // All sides inserted something at the same position. Keep
+                // all of them if they're imports, unless different sides
+                // import the same name.
+                let mut parent_kind = None;
+                let mut seen_contents = HashSet::new();
+                let mut imported_names = HashSet::new();
+                for (text, tree, range) in itertools::izip!(
+                    &texts[num_diffs..],
+                    &trees[num_diffs..],
+                    &hunk.ranges[num_diffs..]
+                ) {
+                    if !seen_contents.insert(&text[range.clone()]) {
+                        continue;
+                    }
+                    let (kind, items) = inserted_items(text, tree, range.clone())?;
+                    if *parent_kind.get_or_insert(kind) != kind {
+                        return None;
+                    }
+                    let mut named_items = items.iter().filter(|item| item.is_named).peekable();
+                    named_items.peek()?;
+                    for item in named_items {
+                        if !language.is_import_list(kind) && !language.is_import(item.kind) {
+                            return None;
+                        }
+                        if !imported_names.insert(imported_name(text, item)) {
+                            return None;
+                        }
+                    }
+                }
+                for content in merge.adds().unique() {
+                    merged.extend_from_slice(content);
+                }
+            }
+        }
+    }
+    // Token-level changes from different sides may not fit together.
+    language.parse(&merged)?;
+    Some(merged)
+}
+
+/// Splits `text` into the leaves of `tree`, and the whitespace lines between
+/// them.
+fn token_ranges(text: &[u8], tree: &SyntaxNode) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let add_gap = |ranges: &mut Vec<Range<usize>>, gap: Range<usize>| {
+        ranges.extend(
+            find_line_ranges(&text[gap.clone()])
+                .into_iter()
+                .map(|range| range.start + gap.start..range.end + gap.start),
+        );
+    };
+    let mut pos = 0;
+    let mut stack = vec![tree];
+    while let Some(node) = stack.pop() {
+        if !node.children.is_empty() {
+            stack.extend(node.children.iter().rev());
+        } else if !node.range.is_empty() && node.range.start >= pos {
+            add_gap(&mut ranges, pos..node.range.start);
+            ranges.push(node.range.clone());
+            pos = node.range.end;
+        }
+    }
+    add_gap(&mut ranges, pos..text.len());
+    ranges
+}
+
+/// If the `range` of `text` consists of whole children of a node of `tree`,
+/// ignoring surrounding whitespace, returns the kind of that node and the
+/// children.
+fn inserted_items<'a>(
+    text: &[u8],
+    tree: &'a SyntaxNode,
+    range: Range<usize>,
+) -> Option<(&'static str, &'a [SyntaxNode])> {
+    let leading = text[range.clone()]
+        .iter()
+        .take_while(|b| b.is_ascii_whitespace())
+        .count();
+    let trailing = text[range.clone()]
+        .iter()
+        .rev()
+        .take_while(|b| b.is_ascii_whitespace())
+        .count();
+    if leading == range.len() {
+        return None;
+    }
+    let range = range.start + leading..range.end - trailing;
+    let mut node = tree;
+    while let Some(child) = node.children.iter().find(|child| {
+        child.range.start <= range.start && range.end <= child.range.end && child.range != range
+    }) {
+        node = child;
+    }
+    let first = node
+        .children
+        .iter()
+        .position(|child| child.range.start == range.start)?;
+    let last = node
+        .children
+        .iter()
+        .position(|child| child.range.end == range.end)?;
+    let items = node.children.get(first..=last)?;
+    Some((node.kind, items))
+}
+
+/// Returns the name imported by `item`, which is the last named token in it,
+/// e.g. `Path` in `use std::path::Path;`, or `p` in `import path as p`.
+fn imported_name<'a>(text: &'a [u8], item: &SyntaxNode) -> &'a [u8] {
+    let mut node = item;
+    while let Some(child) = node.children.iter().rev().find(|child| child.is_named) {
+        node = child;
+    }
+    &text[node.range.clone()]
+}
+
+#[cfg(feature = "tree-sitter")]
+use self::tree_sitter_impl::builtin_languages;
+#[cfg(feature = "tree-sitter")]
+pub use self::tree_sitter_impl::TreeSitterLanguage;
+
+#[cfg(not(feature = "tree-sitter"))]
+fn builtin_languages() -> Vec<(&'static [&'static str], Arc<dyn SyntaxLanguage>)> {
+    vec![]
+}
+
+#[cfg(feature = "tree-sitter")]
+mod tree_sitter_impl {
+    use std::sync::Arc;
+
+    use super::SyntaxLanguage;
+    use super::SyntaxNode;
+
+    /// Language parsed by a tree-sitter grammar.
+    #[derive(Debug)]
+    pub struct TreeSitterLanguage {
+        name: &'static str,
+        language: tree_sitter::Language,
+        import_list_kinds: &'static [&'static str],
+        import_kinds: &'static [&'static str],
+    }
+
+    impl TreeSitterLanguage {
+        /// Creates a language from tree-sitter grammar. Nodes of the
+        /// `import_list_kinds` are lists of imported names, and nodes of the
+        /// `import_kinds` are import statements.
+        pub fn new(
+            name: &'static str,
+            language: tree_sitter::Language,
+            import_list_kinds: &'static [&'static str],
+            import_kinds: &'static [&'static str],
+        ) -> Self {
+            TreeSitterLanguage {
+                name,
+                language,
+                import_list_kinds,
+                import_kinds,
+            }
+        }
+    }
+
+    impl SyntaxLanguage for TreeSitterLanguage {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn parse(&self, source: &[u8]) -> Option<SyntaxNode> {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&self.language).ok()?;
+            let tree = parser.parse(source, None)?;
+            let root = tree.root_node();
+            if root.has_error() {
+                return None;
+            }
+            Some(to_syntax_tree(root))
+        }
+
+        fn is_import_list(&self, kind: &str) -> bool {
+            self.import_list_kinds.contains(&kind)
+        }
+
+        fn is_import(&self, kind: &str) -> bool {
+            self.import_kinds.contains(&kind)
+        }
+    }
+
+    fn to_syntax_node(node: tree_sitter::Node) -> SyntaxNode {
+        SyntaxNode {
+            kind: node.kind(),
+            is_named: node.is_named(),
+            range: node.byte_range(),
+            children: vec![],
+        }
+    }
+
+    fn to_syntax_tree(root: tree_sitter::Node) -> SyntaxNode {
+        // Walk the tree without recursion so that deeply nested sources can't
+        // overflow the stack.
+        let mut cursor = root.walk();
+        let mut stack = vec![to_syntax_node(root)];
+        loop {
+            if cursor.goto_first_child() {
+                stack.push(to_syntax_node(cursor.node()));
+                continue;
+            }
+            loop {
+                let node = stack.pop().unwrap();
+                let Some(parent) = stack.last_mut() else {
+                    return node;
+                };
+                parent.children.push(node);
+                if cursor.goto_next_sibling() {
+                    stack.push(to_syntax_node(cursor.node()));
+                    break;
+                }
+                cursor.goto_parent();
+            }
+        }
+    }
+
+    pub(super) fn builtin_languages() -> Vec<(&'static [&'static str], Arc<dyn SyntaxLanguage>)> {
+        let languages: Vec<(&[&str], TreeSitterLanguage)> = vec![
+            #[cfg(feature = "tree-sitter-c")]
+            (
+                &["c", "h"],
+                TreeSitterLanguage::new(
+                    "c",
+                    tree_sitter_c::LANGUAGE.into(),
+                    &[],
+                    &["preproc_include"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-go")]
+            (
+                &["go"],
+                TreeSitterLanguage::new(
+                    "go",
+                    tree_sitter_go::LANGUAGE.into(),
+                    &["import_spec_list"],
+                    &["import_declaration"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-java")]
+            (
+                &["java"],
+                TreeSitterLanguage::new(
+                    "java",
+                    tree_sitter_java::LANGUAGE.into(),
+                    &[],
+                    &["import_declaration"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-javascript")]
+            (
+                &["js", "mjs", "cjs", "jsx"],
+                TreeSitterLanguage::new(
+                    "javascript",
+                    tree_sitter_javascript::LANGUAGE.into(),
+                    &["named_imports"],
+                    &["import_statement"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-python")]
+            (
+                &["py", "pyi"],
+                TreeSitterLanguage::new(
+                    "python",
+                    tree_sitter_python::LANGUAGE.into(),
+                    &["import_statement", "import_from_statement"],
+                    &["import_statement", "import_from_statement"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-rust")]
+            (
+                &["rs"],
+                TreeSitterLanguage::new(
+                    "rust",
+                    tree_sitter_rust::LANGUAGE.into(),
+                    &["use_list"],
+                    &["use_declaration"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-typescript")]
+            (
+                &["ts", "mts", "cts"],
+                TreeSitterLanguage::new(
+                    "typescript",
+                    tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+                    &["named_imports"],
+                    &["import_statement"],
+                ),
+            ),
+            #[cfg(feature = "tree-sitter-typescript")]
+            (
+                &["tsx"],
+                TreeSitterLanguage::new(
+                    "tsx",
+                    tree_sitter_typescript::LANGUAGE_TSX.into(),
+                    &["named_imports"],
+                    &["import_statement"],
+                ),
+            ),
+        ];
+        languages
+            .into_iter()
+            .map(|(extensions, language)| {
+                (extensions, Arc::new(language) as Arc<dyn SyntaxLanguage>)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Language where each line is an import of the line text, and lines
+    /// starting with `!` are syntax errors.
+    #[derive(Debug)]
+    struct LinesLanguage;
+
+    impl SyntaxLanguage for LinesLanguage {
+        fn name(&self) -> &str {
+            "lines"
+        }
+
+        fn parse(&self, source: &[u8]) -> Option<SyntaxNode> {
+            let children = find_line_ranges(source)
+                .into_iter()
+                .map(|range| {
+                    let range =
+                        range.start..range.end - usize::from(source[range.end - 1] == b'\n');
+                    (!source[range.clone()].starts_with(b"!")).then_some(SyntaxNode {
+                        kind: "line",
+                        is_named: true,
+                        range,
+                        children: vec![],
+                    })
+                })
+                .collect::<Option<_>>()?;
+            Some(SyntaxNode {
+                kind: "lines",
+                is_named: true,
+                range: 0..source.len(),
+                children,
+            })
+        }
+
+        fn is_import_list(&self, _kind: &str) -> bool {
+            false
+        }
+
+        fn is_import(&self, kind: &str) -> bool {
+            kind == "line"
+        }
+    }
+
+    fn repo_path(value: &str) -> &RepoPath {
+        RepoPath::from_internal_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_language_for_path() {
+        let mut merger = StructuralMerger::new();
+        merger.add_language(&["txt", "Lines"], Arc::new(LinesLanguage));
+        let name_for_path = |path| {
+            merger
+                .language_for_path(repo_path(path))
+                .map(|language| language.name())
+        };
+        assert_eq!(name_for_path("a.txt"), Some("lines"));
+        assert_eq!(name_for_path("dir/a.b.LINES"), Some("lines"));
+        assert_eq!(name_for_path("dir.txt/a"), None);
+        assert_eq!(name_for_path(".txt"), None);
+        assert_eq!(name_for_path("a.rs"), None);
+    }
+
+    #[test]
+    fn test_merge_with_plugged_language() {
+        let merge = |values: [&str; 3]| {
+            try_merge_with_language(&LinesLanguage, &Merge::from_vec(values.to_vec()))
+        };
+        // Insertions at the same position are both kept
+        assert_eq!(
+            merge(["a\nb\nx\n", "a\nb\n", "a\nb\ny\n"]),
+            Some("a\nb\nx\ny\n".into())
+        );
+        // Identical insertions are kept once
+        assert_eq!(merge(["a\nx\n", "a\n", "a\nx\n"]), Some("a\nx\n".into()));
+        // Conflicting modifications can't be merged
+        assert_eq!(merge(["x\n", "a\n", "y\n"]), None);
+        // Inputs and result must be valid
+        assert_eq!(merge(["a\n!x\n", "a\n", "a\ny\n"]), None);
+        assert_eq!(merge(["x\n", "", "!\n"]), None);
+    }
+
+    #[cfg(feature = "tree-sitter-rust")]
+    #[test]
+    fn test_merge_rust() {
+        use indoc::indoc;
+
+        let merger = StructuralMerger::with_builtin_languages();
+        let path = repo_path("src/lib.rs");
+        let merge = |values: [&str; 3]| {
+            merger
+                .try_merge(path, &Merge::from_vec(values.to_vec()))
+                .map(|merged| merged.to_string())
+        };
+
+        // Edits to adjacent lines of different functions
+        let base = indoc! {"
+            fn a() -> i32 {
+                1
+            }
+            fn b() -> i32 {
+                2
+            }
+        "};
+        let left = indoc! {"
+            fn a() -> i32 {
+                10
+            }
+            fn b() -> i32 {
+                2
+            }
+        "};
+        let right = indoc! {"
+            fn a() -> i32 {
+                1
+            }
+            fn b() -> u32 {
+                2
+            }
+        "};
+        insta::assert_snapshot!(merge([left, base, right]).unwrap(), @r"
+        fn a() -> i32 {
+            10
+        }
+        fn b() -> u32 {
+            2
+        }
+        ");
+
+        // Imports added to the same list
+        let base = indoc! {"
+            use std::collections::{BTreeMap, HashMap};
+            use std::fmt;
+        "};
+        let left = indoc! {"
+            use std::collections::{BTreeMap, HashMap, HashSet};
+            use std::fmt;
+            use std::io;
+        "};
+        let right = indoc! {"
+            use std::collections::{BTreeMap, HashMap, BTreeSet};
+            use std::fmt;
+            use std::path::Path;
+        "};
+        insta::assert_snapshot!(merge([left, base, right]).unwrap(), @r"
+        use std::collections::{BTreeMap, HashMap, HashSet, BTreeSet};
+        use std::fmt;
+        use std::io;
+        use std::path::Path;
+        ");
+
+        // Imports of the same name from different paths conflict
+        let base = "use std::fmt;\n";
+        let left = "use std::fmt;\nuse std::io::Result;\n";
+        let right = "use std::fmt;\nuse std::fmt::Result;\n";
+        assert_eq!(merge([left, base, right]), None);
+        let base = "use std::io::{Read, Write};\n";
+        let left = "use std::io::{Read, Write, Error};\n";
+        let right = "use std::io::{Read, Write, std::fmt::Error};\n";
+        assert_eq!(merge([left, base, right]), None);
+
+        // Items other than imports added at the same position conflict, even
+        // if they have the same name
+        let base = "fn a() {}\n";
+        let left = "fn a() {}\nfn b() -> u32 { 1 }\n";
+        let right = "fn a() {}\nfn b() -> u32 { 2 }\n";
+        assert_eq!(merge([left, base, right]), None);
+        let base = "fn a() {}\n";
+        let left = "fn a() {}\nfn b() {}\n";
+        let right = "fn a() {}\nfn c() {}\n";
+        assert_eq!(merge([left, base, right]), None);
+        let base = "use std::fmt;\n";
+        let left = "use std::fmt;\nuse std::io;\n";
+        let right = "use std::fmt;\nfn io() {}\n";
+        assert_eq!(merge([left, base, right]), None);
+
+        // Insertions into ordered nodes still conflict
+        let base = "fn f() -> i32 { 1 }\n";
+        let left = "fn f() -> i32 { 1 + 2 }\n";
+        let right = "fn f() -> i32 { 1 * 3 }\n";
+        assert_eq!(merge([left, base, right]), None);
+
+        // Same token changed on both sides
+        let base = "const X: i32 = 1;\n";
+        let left = "const X: i32 = 2;\n";
+        let right = "const X: i32 = 3;\n";
+        assert_eq!(merge([left, base, right]), None);
+
+        // Syntax errors aren't merged
+        let base = "fn f() {}\n";
+        let left = "fn f() {\n";
+        let right = "fn g() {}\n";
+        assert_eq!(merge([left, base, right]), None);
+    }
+
+    #[cfg(feature = "tree-sitter-python")]
+    #[test]
+    fn test_merge_python() {
+        use indoc::indoc;
+
+        let merger = StructuralMerger::with_builtin_languages();
+        let path = repo_path("main.py");
+        let merge = |values: [&str; 3]| {
+            merger
+                .try_merge(path, &Merge::from_vec(values.to_vec()))
+                .map(|merged| merged.to_string())
+        };
+
+        let base = indoc! {"
+            from os import path, sep
+            import sys
+        "};
+        let left = indoc! {"
+            from os import path, sep, getcwd
+            import sys
+            import re
+        "};
+        let right = indoc! {"
+            from os import path, sep, environ
+            import sys
+            import json
+        "};
+        insta::assert_snapshot!(merge([left, base, right]).unwrap(), @r"
+        from os import path, sep, getcwd, environ
+        import sys
+        import re
+        import json
+        ");
+
+        // Imports of the same name conflict
+        let base = "import sys\n";
+        let left = "import sys\nimport json\n";
+        let right = "import sys\nimport simplejson as json\n";
+        assert_eq!(merge([left, base, right]), None);
+        let base = "x = 1\n";
+        let left = "x = 1\ny = 2\n";
+        let right = "x = 1\ny = 3\n";
+        assert_eq!(merge([left, base, right]), None);
+    }
+}
@@ -15,7 +15,12 @@
 #![allow(missing_docs)]
 
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 pub trait SubmoduleStore: Send + Sync + Debug {
     fn name(&self) -> &str;
+
+    /// Returns the path where the Git repository of the named submodule is
+    /// stored.
+    fn git_dir(&self, name: &str) -> PathBuf;
 }
@@ -14,6 +14,8 @@
 
 //! Parsing trailers from commit messages.
 
+use std::ops::Range;
+
 use itertools::Itertools as _;
 use thiserror::Error;
 
@@ -58,51 +60,103 @@ pub enum TrailerParseError {
 /// In this case, there are four trailers: two `Co-authored-by` lines, one
 /// `Reviewed-by` line, and one `Change-Id` line.
 pub fn parse_description_trailers(body: &str) -> Vec<Trailer> {
-    let (trailers, blank, found_git_trailer, non_trailer) = parse_trailers_impl(body);
-    if !blank {
-        // no blank found, this means there was a single paragraph, so whatever
-        // was found can't come from the trailer
-        vec![]
-    } else if non_trailer.is_some() && !found_git_trailer {
-        // at least one non trailer line was found in the trailers paragraph
-        // the trailers are considered as trailers only if there is a predefined
-        // trailers from git
-        vec![]
-    } else {
-        trailers
+    locate_description_trailers(body)
+        .map(|paragraph| {
+            paragraph
+                .trailers
+                .into_iter()
+                .map(|(trailer, _)| trailer)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Location of the trailer paragraph in a commit message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrailerParagraph {
+    /// Byte offset of the first line of the trailer paragraph.
+    pub start: usize,
+    /// Trailers and byte ranges of their lines, including continuation lines.
+    pub trailers: Vec<(Trailer, Range<usize>)>,
+}
+
+/// Locates the trailers in a commit message in the same way as
+/// `parse_description_trailers()`. Returns `None` if the message has no
+/// trailers.
+pub fn locate_description_trailers(body: &str) -> Option<TrailerParagraph> {
+    let parsed = parse_trailers_impl(body);
+    let start = parsed.paragraph_start?;
+    if parsed.trailers.is_empty()
+        || (parsed.non_trailer_line.is_some() && !parsed.found_git_trailer)
+    {
+        // At least one non trailer line was found in the trailers paragraph.
+        // The trailers are considered as trailers only if there is a
+        // predefined trailer from git.
+        return None;
     }
+    Some(TrailerParagraph {
+        start,
+        trailers: parsed.trailers,
+    })
 }
 
 /// Parse the trailers from a trailer paragraph. This function behaves like
 /// `parse_description_trailer`, but will return an error if a blank or
 /// non trailer line is found.
 pub fn parse_trailers(body: &str) -> Result<Vec<Trailer>, TrailerParseError> {
-    let (trailers, blank, _, non_trailer) = parse_trailers_impl(body);
-    if blank {
+    let parsed = parse_trailers_impl(body);
+    if parsed.paragraph_start.is_some() {
         return Err(TrailerParseError::BlankLine);
     }
-    if let Some(line) = non_trailer {
+    if let Some(line) = parsed.non_trailer_line {
         return Err(TrailerParseError::NonTrailerLine { line });
     }
-    Ok(trailers)
+    Ok(parsed
+        .trailers
+        .into_iter()
+        .map(|(trailer, _)| trailer)
+        .collect())
+}
+
+struct ParsedTrailers {
+    trailers: Vec<(Trailer, Range<usize>)>,
+    /// Start of the line following the blank line, if any.
+    paragraph_start: Option<usize>,
+    found_git_trailer: bool,
+    non_trailer_line: Option<String>,
 }
 
-fn parse_trailers_impl(body: &str) -> (Vec<Trailer>, bool, bool, Option<String>) {
+fn parse_trailers_impl(body: &str) -> ParsedTrailers {
     // a trailer always comes at the end of a message; we can split the message
     // by newline, but we need to immediately reverse the order of the lines
     // to ensure we parse the trailer in an unambiguous manner; this avoids cases
     // where a colon in the body of the message is mistaken for a trailer
-    let lines = body.trim_ascii_end().lines().rev();
+    let lines = body
+        .trim_ascii_end()
+        .split_inclusive('\n')
+        .scan(0, |start, line| {
+            let range = *start..*start + line.len();
+            *start = range.end;
+            // strip the line terminator in the same way as str::lines()
+            let line = line
+                .strip_suffix('\n')
+                .map_or(line, |line| line.strip_suffix('\r').unwrap_or(line));
+            Some((line, range))
+        })
+        .collect_vec();
     let trailer_re =
         regex::Regex::new(r"^([a-zA-Z0-9-]+) *: *(.*)$").expect("Trailer regex should be valid");
-    let mut trailers: Vec<Trailer> = Vec::new();
+    let mut trailers = Vec::new();
     let mut multiline_value = vec![];
-    let mut found_blank = false;
+    // end of the last continuation line of the multiline value
+    let mut multiline_end = None;
+    let mut paragraph_start = None;
     let mut found_git_trailer = false;
     let mut non_trailer_line = None;
-    for line in lines {
+    for (line, range) in lines.into_iter().rev() {
         if line.starts_with(' ') {
             multiline_value.push(line);
+            multiline_end.get_or_insert(range.end);
         } else if let Some(groups) = trailer_re.captures(line) {
             let key = groups[1].to_string();
             multiline_value.push(groups.get(2).unwrap().as_str());
@@ -111,29 +165,37 @@ fn parse_trailers_impl(body: &str) -> (Vec<Trailer>, bool, bool, Option<String>)
             multiline_value[0] = multiline_value[0].trim_ascii_end();
             let value = multiline_value.iter().rev().join("\n");
             multiline_value.clear();
+            let end = multiline_end.take().unwrap_or(range.end);
             if key == "Signed-off-by" {
                 found_git_trailer = true;
             }
-            trailers.push(Trailer { key, value });
+            trailers.push((Trailer { key, value }, range.start..end));
         } else if line.starts_with("(cherry picked from commit ") {
             found_git_trailer = true;
             non_trailer_line = Some(line.to_owned());
             multiline_value.clear();
+            multiline_end = None;
         } else if line.trim_ascii().is_empty() {
             // end of the trailer
-            found_blank = true;
+            paragraph_start = Some(range.end);
             break;
         } else {
             // a non trailer in the trailer paragraph
             // the line is ignored, as well as the multiline value that may
             // have previously been accumulated
             multiline_value.clear();
+            multiline_end = None;
             non_trailer_line = Some(line.to_owned());
         }
     }
     // reverse the insert order, since we parsed the trailer in reverse
     trailers.reverse();
-    (trailers, found_blank, found_git_trailer, non_trailer_line)
+    ParsedTrailers {
+        trailers,
+        paragraph_start,
+        found_git_trailer,
+        non_trailer_line,
+    }
 }
 
 #[cfg(test)]
@@ -301,6 +363,28 @@ mod tests {
         assert_eq!(trailers.len(), 1);
     }
 
+    #[test]
+    fn test_locate_description_trailers() {
+        let description = "subject\n\t\r\nfoo: 1\r\nbar: 2\n  continued\n";
+        let paragraph = locate_description_trailers(description).unwrap();
+        assert_eq!(
+            &description[paragraph.start..],
+            "foo: 1\r\nbar: 2\n  continued\n"
+        );
+        let ranges = paragraph
+            .trailers
+            .iter()
+            .map(|(trailer, range)| (trailer.key.as_str(), &description[range.clone()]))
+            .collect_vec();
+        assert_eq!(
+            ranges,
+            [("foo", "foo: 1\r\n"), ("bar", "bar: 2\n  continued")]
+        );
+
+        assert_eq!(locate_description_trailers("foo: 1\n"), None);
+        assert_eq!(locate_description_trailers("subject\n\nbody\n"), None);
+    }
+
     #[test]
     fn test_no_blank_line() {
         let description = indoc! {r#"
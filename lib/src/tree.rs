@@ -29,17 +29,20 @@ use crate::backend;
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::ConflictId;
+use crate::backend::FileId;
 use crate::backend::TreeEntriesNonRecursiveIterator;
 use crate::backend::TreeId;
 use crate::backend::TreeValue;
 use crate::files;
 use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
+use crate::merge::Merge;
 use crate::merge::MergedTreeVal;
 use crate::object_id::ObjectId as _;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponent;
+use crate::resolution_cache::resolution_id;
 use crate::store::Store;
 
 #[derive(Clone)]
@@ -256,6 +259,10 @@ impl Iterator for TreeEntriesIterator<'_> {
 
 /// Resolves file-level conflict by merging content hunks.
 ///
+/// The contents are merged according to the merge options of the `store`. See
+/// [`files::try_merge_file()`]. If a resolution of the same conflict has been
+/// recorded in the resolution cache of the `store`, it is used instead.
+///
 /// The input `conflict` is supposed to be simplified. It shouldn't contain
 /// non-file values that cancel each other.
 pub async fn try_resolve_file_conflict(
@@ -323,6 +330,14 @@ pub async fn try_resolve_file_conflict(
     //    cannot
     let file_id_conflict = file_id_conflict.simplify();
 
+    if let Some(id) = find_recorded_resolution(store, filename, &file_id_conflict).await {
+        return Ok(Some(TreeValue::File {
+            id,
+            executable,
+            copy_id: copy_id.clone(),
+        }));
+    }
+
     let contents = file_id_conflict
         .try_map_async(|file_id| async {
             let mut content = vec![];
@@ -338,7 +353,8 @@ pub async fn try_resolve_file_conflict(
             BackendResult::Ok(content)
         })
         .await?;
-    if let Some(merged_content) = files::try_merge(&contents) {
+    if let Some(merged_content) = files::try_merge_file(filename, &contents, store.merge_options())
+    {
         let id = store
             .write_file(filename, &mut merged_content.as_slice())
             .await?;
@@ -351,3 +367,24 @@ pub async fn try_resolve_file_conflict(
         Ok(None)
     }
 }
+
+/// Looks up the resolution recorded for the file conflict. Resolutions that
+/// can't be read, or whose content no longer exists, are ignored.
+async fn find_recorded_resolution(
+    store: &Store,
+    filename: &RepoPath,
+    file_id_conflict: &Merge<&FileId>,
+) -> Option<FileId> {
+    let cache = store.merge_options().resolution_cache.as_ref()?;
+    let resolution = cache
+        .get(&resolution_id(file_id_conflict))
+        .inspect_err(|err| tracing::warn!(?err, "Failed to read recorded resolution"))
+        .ok()??;
+    // The resolved content may have been garbage collected.
+    store
+        .read_file(filename, &resolution.file_id)
+        .await
+        .inspect_err(|err| tracing::warn!(?err, "Failed to read recorded resolution"))
+        .ok()?;
+    Some(resolution.file_id)
+}
@@ -86,6 +86,10 @@ impl View {
         &self.data.tags
     }
 
+    pub fn notes(&self) -> &BTreeMap<RefNameBuf, RefTarget> {
+        &self.data.notes
+    }
+
     pub fn git_refs(&self) -> &BTreeMap<GitRefNameBuf, RefTarget> {
         &self.data.git_refs
     }
@@ -336,6 +340,20 @@ impl View {
         }
     }
 
+    pub fn get_notes(&self, name: &RefName) -> &RefTarget {
+        self.data.notes.get(name).flatten()
+    }
+
+    /// Sets notes ref to point to the given notes commit. If the target is
+    /// absent, the notes ref will be removed.
+    pub fn set_notes_target(&mut self, name: &RefName, target: RefTarget) {
+        if target.is_present() {
+            self.data.notes.insert(name.to_owned(), target);
+        } else {
+            self.data.notes.remove(name);
+        }
+    }
+
     pub fn get_git_ref(&self, name: &GitRefName) -> &RefTarget {
         self.data.git_refs.get(name).flatten()
     }
@@ -377,6 +395,7 @@ impl View {
             head_ids,
             local_bookmarks,
             tags,
+            notes,
             remote_views,
             git_refs,
             git_head,
@@ -386,6 +405,7 @@ impl View {
             head_ids,
             local_bookmarks.values().flat_map(ref_target_ids),
             tags.values().flat_map(ref_target_ids),
+            notes.values().flat_map(ref_target_ids),
             remote_views.values().flat_map(|remote_view| {
                 let op_store::RemoteView { bookmarks } = remote_view;
                 bookmarks
@@ -15,9 +15,13 @@
 use std::fmt::Write as _;
 use std::rc::Rc;
 
+use bstr::ByteSlice as _;
 use itertools::Itertools as _;
+use jj_lib::annotate::find_line_range_changes;
+use jj_lib::annotate::CopyTracking;
 use jj_lib::annotate::FileAnnotation;
 use jj_lib::annotate::FileAnnotator;
+use jj_lib::annotate::LineRangeChange;
 use jj_lib::backend::CommitId;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::backend::MillisSinceEpoch;
@@ -25,15 +29,20 @@ use jj_lib::backend::Signature;
 use jj_lib::backend::Timestamp;
 use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::default_index::DefaultReadonlyIndex;
 use jj_lib::repo::MutableRepo;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::revset::ResolvedRevsetExpression;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::settings::UserSettings;
 use testutils::create_tree;
 use testutils::read_file;
 use testutils::repo_path;
 use testutils::TestRepo;
+use testutils::TestRepoBackend;
 
 fn create_commit_fn(
     mut_repo: &mut MutableRepo,
@@ -75,6 +84,19 @@ fn annotate_within(
     format_annotation(repo, &annotator.to_annotation())
 }
 
+fn annotate_with_copy_tracking(
+    repo: &dyn Repo,
+    commit: &Commit,
+    domain: &Rc<ResolvedRevsetExpression>,
+    file_path: &RepoPath,
+    copy_tracking: CopyTracking,
+) -> String {
+    let mut annotator = FileAnnotator::from_commit(commit, file_path).unwrap();
+    annotator.set_copy_tracking(copy_tracking);
+    annotator.compute(repo, domain).unwrap();
+    format_annotation(repo, &annotator.to_annotation())
+}
+
 fn annotate_parent_tree(repo: &dyn Repo, commit: &Commit, file_path: &RepoPath) -> String {
     let tree = commit.parent_tree(repo).unwrap();
     let text = match tree.path_value(file_path).unwrap().into_resolved().unwrap() {
@@ -86,6 +108,24 @@ fn annotate_parent_tree(repo: &dyn Repo, commit: &Commit, file_path: &RepoPath)
     format_annotation(repo, &annotator.to_annotation())
 }
 
+fn format_line_range_changes(repo: &dyn Repo, changes: &[LineRangeChange]) -> String {
+    let mut output = String::new();
+    for change in changes {
+        let commit = repo.store().get_commit(&change.new.commit_id).unwrap();
+        let desc = commit.description().trim_end();
+        writeln!(output, "{desc}: {:?}", change.new.line_range).unwrap();
+        if let Some(old) = &change.old {
+            for line in old.text.split_inclusive(|b| *b == b'\n') {
+                write!(output, "-{}", line.to_str_lossy()).unwrap();
+            }
+        }
+        for line in change.new.text.split_inclusive(|b| *b == b'\n') {
+            write!(output, "+{}", line.to_str_lossy()).unwrap();
+        }
+    }
+    output
+}
+
 fn format_annotation(repo: &dyn Repo, annotation: &FileAnnotation) -> String {
     let mut output = String::new();
     for (commit_id, line) in annotation.lines() {
@@ -409,3 +449,216 @@ fn test_annotate_file_directory_transition() {
 
     insta::assert_snapshot!(annotate(tx.repo(), &commit2, file_path2), @"commit2 : 2");
 }
+
+#[test]
+fn test_annotate_rename() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+
+    let root_commit_id = repo.store().root_commit_id();
+    let file_path1 = repo_path("old");
+    let file_path2 = repo_path("new");
+
+    let mut tx = repo.start_transaction();
+    let mut create_commit = create_commit_fn(tx.repo_mut());
+    let tree1 = create_tree(repo, &[(file_path1, "1a\n1b\n1c\n1d\n")]);
+    let tree2 = create_tree(repo, &[(file_path1, "1a\n2\n1c\n1d\n")]);
+    let tree3 = create_tree(repo, &[(file_path2, "1a\n2\n1c\n1d\n3\n")]);
+    let tree4 = create_tree(repo, &[(file_path2, "4\n1a\n2\n1c\n1d\n3\n")]);
+    let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+    let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+    let commit3 = create_commit("commit3", &[commit2.id()], tree3.id());
+    let commit4 = create_commit("commit4", &[commit3.id()], tree4.id());
+    drop(create_commit);
+
+    let domain = RevsetExpression::all();
+    insta::assert_snapshot!(
+        annotate_with_copy_tracking(tx.repo(), &commit4, &domain, file_path2, CopyTracking::Files),
+        @r"
+    commit4 : 4
+    commit1 : 1a
+    commit2 : 2
+    commit1 : 1c
+    commit1 : 1d
+    commit3 : 3
+    ");
+    insta::assert_snapshot!(
+        annotate_with_copy_tracking(tx.repo(), &commit4, &domain, file_path2, CopyTracking::None),
+        @r"
+    commit4 : 4
+    commit3 : 1a
+    commit3 : 2
+    commit3 : 1c
+    commit3 : 1d
+    commit3 : 3
+    ");
+
+    // Exclude the renamed file's parent and its ancestors.
+    let domain = RevsetExpression::commit(commit2.id().clone())
+        .ancestors()
+        .negated();
+    insta::assert_snapshot!(
+        annotate_with_copy_tracking(tx.repo(), &commit4, &domain, file_path2, CopyTracking::Files),
+        @r"
+    commit4 : 4
+    commit3*: 1a
+    commit3*: 2
+    commit3*: 1c
+    commit3*: 1d
+    commit3 : 3
+    ");
+}
+
+#[test]
+fn test_annotate_moved_lines() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+
+    let root_commit_id = repo.store().root_commit_id();
+    let file_path1 = repo_path("file1");
+    let file_path2 = repo_path("file2");
+
+    let mut tx = repo.start_transaction();
+    let mut create_commit = create_commit_fn(tx.repo_mut());
+    let tree1 = create_tree(repo, &[(file_path1, "1a\n1b\n1c\n"), (file_path2, "1d\n")]);
+    let tree2 = create_tree(
+        repo,
+        &[(file_path1, "1a\n2\n1b\n1c\n"), (file_path2, "1d\n")],
+    );
+    // Move lines from file1 to file2
+    let tree3 = create_tree(
+        repo,
+        &[(file_path1, "1a\n1c\n"), (file_path2, "2\n1b\n3\n\n1d\n")],
+    );
+    let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+    let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+    let commit3 = create_commit("commit3", &[commit2.id()], tree3.id());
+    drop(create_commit);
+
+    let domain = RevsetExpression::all();
+    insta::assert_snapshot!(
+        annotate_with_copy_tracking(tx.repo(), &commit3, &domain, file_path2, CopyTracking::Lines),
+        @r"
+    commit2 : 2
+    commit1 : 1b
+    commit3 : 3
+    commit3 : 
+    commit1 : 1d
+    ");
+    insta::assert_snapshot!(
+        annotate_with_copy_tracking(tx.repo(), &commit3, &domain, file_path2, CopyTracking::Files),
+        @r"
+    commit3 : 2
+    commit3 : 1b
+    commit3 : 3
+    commit3 : 
+    commit1 : 1d
+    ");
+}
+
+#[test]
+fn test_annotate_with_changed_paths_index() {
+    let file_path1 = repo_path("file1");
+    let file_path2 = repo_path("file2");
+    let annotate_in_repo = |settings: &UserSettings| {
+        let test_repo = TestRepo::init_with_settings(settings);
+        let repo = &test_repo.repo;
+        let root_commit_id = repo.store().root_commit_id();
+
+        let mut tx = repo.start_transaction();
+        let mut create_commit = create_commit_fn(tx.repo_mut());
+        let tree1 = create_tree(repo, &[(file_path1, "1\n")]);
+        let tree2 = create_tree(repo, &[(file_path1, "1\n"), (file_path2, "2\n")]);
+        let tree3 = create_tree(repo, &[(file_path1, "1\n3\n"), (file_path2, "2\n")]);
+        let tree4 = create_tree(repo, &[(file_path1, "1\n3\n"), (file_path2, "4\n")]);
+        let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+        let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+        let commit3 = create_commit("commit3", &[commit2.id()], tree3.id());
+        let commit4 = create_commit("commit4", &[commit3.id()], tree4.id());
+        drop(create_commit);
+        let repo = tx.commit("test").unwrap();
+
+        let index: &DefaultReadonlyIndex = repo.readonly_index().as_any().downcast_ref().unwrap();
+        let changed_paths = index.changed_paths(commit2.id());
+        let annotation = annotate(repo.as_ref(), &commit4, file_path1);
+        (changed_paths.is_some(), annotation)
+    };
+
+    let (indexed, annotation) = annotate_in_repo(&testutils::user_settings());
+    assert!(!indexed);
+    insta::assert_snapshot!(annotation, @r"
+    commit1 : 1
+    commit3 : 3
+    ");
+
+    // Commits which don't touch file1 are skipped by the changed-paths index,
+    // which shouldn't change the result
+    let mut config = testutils::base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer.set_value("index.changed-paths", true).unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let (indexed, annotation_with_index) = annotate_in_repo(&settings);
+    assert!(indexed);
+    assert_eq!(annotation_with_index, annotation);
+}
+
+#[test]
+fn test_find_line_range_changes() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let root_commit_id = repo.store().root_commit_id();
+    let file_path = repo_path("file");
+
+    let mut tx = repo.start_transaction();
+    let mut create_commit = create_commit_fn(tx.repo_mut());
+    let tree1 = create_tree(repo, &[(file_path, "a\nb\nc\nd\n")]);
+    let tree2 = create_tree(repo, &[(file_path, "a\nB\nc\nd\n")]);
+    let tree3 = create_tree(repo, &[(file_path, "a\nB\nc\nD\n")]);
+    let tree4 = create_tree(repo, &[(file_path, "x\na\nB\nc\nD\n")]);
+    let tree5 = create_tree(repo, &[(file_path, "x\na\nB\nnew\nc\nD\n")]);
+    let commit1 = create_commit("commit1", &[root_commit_id], tree1.id());
+    let commit2 = create_commit("commit2", &[commit1.id()], tree2.id());
+    let commit3 = create_commit("commit3", &[commit2.id()], tree3.id());
+    let commit4 = create_commit("commit4", &[commit3.id()], tree4.id());
+    let commit5 = create_commit("commit5", &[commit4.id()], tree5.id());
+    drop(create_commit);
+
+    let find_changes = |commit: &Commit, line_range| {
+        let changes = find_line_range_changes(
+            tx.repo(),
+            commit,
+            file_path,
+            line_range,
+            &RevsetExpression::all(),
+        )
+        .unwrap();
+        format_line_range_changes(tx.repo(), &changes)
+    };
+
+    // Lines moved by commit4 and changes outside of the range are skipped
+    insta::assert_snapshot!(find_changes(&commit4, 2..4), @r"
+    commit2: 1..3
+    -b
+    -c
+    +B
+    +c
+    commit1: 1..3
+    +b
+    +c
+    ");
+
+    // Lines inserted within the range
+    insta::assert_snapshot!(find_changes(&commit5, 3..5), @r"
+    commit5: 3..5
+    -c
+    +new
+    +c
+    commit1: 2..3
+    +c
+    ");
+
+    // Range outside of the file
+    insta::assert_snapshot!(find_changes(&commit5, 10..12), @"");
+}
@@ -0,0 +1,195 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert_matches::assert_matches;
+use jj_lib::backend::CommitId;
+use jj_lib::bisect::BisectionResult;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::commit::Commit;
+use jj_lib::repo::Repo;
+use jj_lib::revset::RevsetExpression;
+use testutils::CommitGraphBuilder;
+use testutils::TestRepo;
+
+/// Runs bisection, evaluating commits with `evaluate`. Returns the evaluated
+/// commits and the result.
+fn test_bisection(
+    repo: &dyn Repo,
+    range: &[&Commit],
+    mut evaluate: impl FnMut(&CommitId) -> Evaluation,
+) -> (Vec<CommitId>, BisectionResult) {
+    let range_ids = range.iter().map(|commit| commit.id().clone()).collect();
+    let mut bisector = Bisector::new(repo, RevsetExpression::commits(range_ids));
+    let mut evaluated = vec![];
+    loop {
+        match bisector.next_step().unwrap() {
+            NextStep::Evaluate(commit) => {
+                assert!(!evaluated.contains(commit.id()), "evaluated twice");
+                let evaluation = evaluate(commit.id());
+                evaluated.push(commit.id().clone());
+                bisector.mark(commit.id().clone(), evaluation);
+            }
+            NextStep::Done(result) => return (evaluated, result),
+        }
+    }
+}
+
+#[test]
+fn test_bisect_empty_range() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let (evaluated, result) = test_bisection(repo.as_ref(), &[], |_| panic!());
+    assert_eq!(evaluated, vec![]);
+    assert_eq!(result, BisectionResult::Indeterminate);
+}
+
+#[test]
+fn test_bisect_linear() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let mut commits = vec![graph_builder.initial_commit()];
+    for _ in 1..10 {
+        let commit = graph_builder.commit_with_parents(&[commits.last().unwrap()]);
+        commits.push(commit);
+    }
+    let range = commits.iter().collect::<Vec<_>>();
+
+    // The first bad commit is found in O(log n) steps
+    let bad_ids = commits[6..]
+        .iter()
+        .map(|commit| commit.id())
+        .collect::<Vec<_>>();
+    let (evaluated, result) = test_bisection(tx.repo(), &range, |id| {
+        if bad_ids.contains(&id) {
+            Evaluation::Bad
+        } else {
+            Evaluation::Good
+        }
+    });
+    assert!(evaluated.len() <= 4, "{evaluated:?}");
+    assert_eq!(result, BisectionResult::Found(vec![commits[6].clone()]));
+
+    // All commits are bad
+    let (_, result) = test_bisection(tx.repo(), &range, |_| Evaluation::Bad);
+    assert_eq!(result, BisectionResult::Found(vec![commits[0].clone()]));
+
+    // No commits are bad
+    let (evaluated, result) = test_bisection(tx.repo(), &range, |_| Evaluation::Good);
+    assert_eq!(evaluated.last(), Some(commits[9].id()));
+    assert_eq!(result, BisectionResult::Indeterminate);
+
+    // Skipped commits next to the first bad commit are candidates
+    let skipped_ids = commits[4..6]
+        .iter()
+        .map(|commit| commit.id())
+        .collect::<Vec<_>>();
+    let (_, result) = test_bisection(tx.repo(), &range, |id| {
+        if skipped_ids.contains(&id) {
+            Evaluation::Skip
+        } else if bad_ids.contains(&id) {
+            Evaluation::Bad
+        } else {
+            Evaluation::Good
+        }
+    });
+    assert_eq!(
+        result,
+        BisectionResult::Found(vec![
+            commits[6].clone(),
+            commits[5].clone(),
+            commits[4].clone(),
+        ])
+    );
+
+    // All commits are skipped
+    let (evaluated, result) = test_bisection(tx.repo(), &range, |_| Evaluation::Skip);
+    assert_eq!(evaluated.len(), 10);
+    assert_eq!(result, BisectionResult::Indeterminate);
+}
+
+#[test]
+fn test_bisect_merge() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // D
+    // |\
+    // B C
+    // |/
+    // A
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_b, &commit_c]);
+    let range = [&commit_a, &commit_b, &commit_c, &commit_d];
+
+    // The bad side of the merge is found
+    let (_, result) = test_bisection(tx.repo(), &range, |id| {
+        if [commit_c.id(), commit_d.id()].contains(&id) {
+            Evaluation::Bad
+        } else {
+            Evaluation::Good
+        }
+    });
+    assert_eq!(result, BisectionResult::Found(vec![commit_c.clone()]));
+
+    // The merge itself can be the first bad commit
+    let (_, result) = test_bisection(tx.repo(), &range, |id| {
+        if id == commit_d.id() {
+            Evaluation::Bad
+        } else {
+            Evaluation::Good
+        }
+    });
+    assert_eq!(result, BisectionResult::Found(vec![commit_d.clone()]));
+}
+
+#[test]
+fn test_bisect_marked_outside_range() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_b]);
+
+    // Marking a commit good implies its ancestors are good
+    let range_ids = vec![commit_a.id().clone(), commit_b.id().clone()];
+    let mut bisector = Bisector::new(tx.repo(), RevsetExpression::commits(range_ids));
+    bisector.mark_good(commit_c.id().clone());
+    assert_matches!(
+        bisector.next_step().unwrap(),
+        NextStep::Done(BisectionResult::Indeterminate)
+    );
+
+    // Re-marking a commit replaces the previous evaluation
+    bisector.mark_bad(commit_c.id().clone());
+    bisector.mark_bad(commit_a.id().clone());
+    assert_eq!(bisector.good_commits().len(), 0);
+    assert_eq!(bisector.bad_commits().len(), 2);
+    assert_matches!(
+        bisector.next_step().unwrap(),
+        NextStep::Done(BisectionResult::Found(commits)) if commits == [commit_a.clone()]
+    );
+}
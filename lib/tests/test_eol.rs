@@ -16,10 +16,12 @@ use std::fs::File;
 use std::io::Write as _;
 
 use bstr::ByteSlice as _;
+use jj_lib::backend::TreeValue;
 use jj_lib::config::ConfigLayer;
 use jj_lib::config::ConfigSource;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo::StoreFactories;
+use jj_lib::repo_path::RepoPath;
 use jj_lib::rewrite::merge_commit_trees;
 use jj_lib::settings::UserSettings;
 use jj_lib::working_copy::CheckoutOptions;
@@ -503,3 +505,62 @@ fn test_eol_conversion_checkout(
     assert!(std::fs::exists(&file_disk_path).unwrap());
     std::fs::read(&file_disk_path).unwrap()
 }
+
+#[test]
+fn test_eol_conversion_gitattributes() {
+    // The attributes in .gitattributes override the working-copy.eol-conversion
+    // setting.
+    let user_settings =
+        base_user_settings_with_extra_configs("working-copy.eol-conversion = \"input\"\n");
+    let mut test_workspace =
+        TestWorkspace::init_with_backend_and_settings(TestRepoBackend::Git, &user_settings);
+    let store = test_workspace.repo.store().clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let gitattributes_path = repo_path(".gitattributes");
+    let crlf_path = repo_path("crlf.txt");
+    let binary_path = repo_path("data.bin");
+    let default_path = repo_path("default.md");
+    testutils::write_working_copy_file(
+        &workspace_root,
+        gitattributes_path,
+        "*.txt text eol=crlf\n*.bin binary\n",
+    );
+    for path in [crlf_path, binary_path, default_path] {
+        testutils::write_working_copy_file(&workspace_root, path, CRLF_FILE_CONTENT);
+    }
+
+    let tree = test_workspace.snapshot().unwrap();
+    let read_content = |path: &RepoPath| match tree.path_value(path).unwrap().into_resolved() {
+        Ok(Some(TreeValue::File { id, .. })) => testutils::read_file(&store, path, &id),
+        value => panic!("unexpected value: {value:?}"),
+    };
+    assert_eq!(read_content(crlf_path), LF_FILE_CONTENT);
+    assert_eq!(read_content(binary_path), CRLF_FILE_CONTENT);
+    assert_eq!(read_content(default_path), LF_FILE_CONTENT);
+
+    // Check out the commit again to recreate the files
+    let commit = commit_with_tree(&store, tree.id());
+    let empty_commit = commit_with_tree(&store, store.empty_merged_tree_id());
+    for commit in [&empty_commit, &commit] {
+        test_workspace
+            .workspace
+            .check_out(
+                test_workspace.repo.op_id().clone(),
+                None,
+                commit,
+                &CheckoutOptions::empty_for_test(),
+            )
+            .unwrap();
+    }
+    let read_disk_file =
+        |path: &RepoPath| std::fs::read(path.to_fs_path_unchecked(&workspace_root)).unwrap();
+    assert_eq!(read_disk_file(crlf_path), CRLF_FILE_CONTENT);
+    assert_eq!(read_disk_file(binary_path), CRLF_FILE_CONTENT);
+    assert_eq!(read_disk_file(default_path), LF_FILE_CONTENT);
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(
+        new_tree.id(),
+        tree.id(),
+        "The working copy should be clean."
+    );
+}
@@ -42,11 +42,14 @@ use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitFetch;
 use jj_lib::git::GitFetchError;
 use jj_lib::git::GitImportError;
+use jj_lib::git::GitNoteError;
 use jj_lib::git::GitPushError;
 use jj_lib::git::GitPushStats;
 use jj_lib::git::GitRefKind;
 use jj_lib::git::GitRefUpdate;
+use jj_lib::git::GitRemoteManagementError;
 use jj_lib::git::GitResetHeadError;
+use jj_lib::git::GitTagError;
 use jj_lib::git_backend::GitBackend;
 use jj_lib::hex_util;
 use jj_lib::object_id::ObjectId as _;
@@ -64,6 +67,7 @@ use jj_lib::repo::MutableRepo;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::settings::GitSettings;
+use jj_lib::settings::GitTransport;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::Signer;
 use jj_lib::str_util::StringPattern;
@@ -185,8 +189,9 @@ fn test_import_refs() {
     let commit4 = empty_git_commit(&git_repo, "refs/heads/feature2", &[commit2]);
     let commit5 = empty_git_commit(&git_repo, "refs/tags/v1.0", &[commit1]);
     let commit6 = empty_git_commit(&git_repo, "refs/remotes/origin/feature3", &[commit1]);
+    // Should be imported only as Git ref
+    let commit7 = empty_git_commit(&git_repo, "refs/notes/x", &[commit2]);
     // Should not be imported
-    empty_git_commit(&git_repo, "refs/notes/x", &[commit2]);
     empty_git_commit(&git_repo, "refs/remotes/origin/HEAD", &[commit2]);
 
     testutils::git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
@@ -274,7 +279,7 @@ fn test_import_refs() {
         &RefTarget::normal(jj_id(commit5))
     );
 
-    assert_eq!(view.git_refs().len(), 6);
+    assert_eq!(view.git_refs().len(), 7);
     assert_eq!(
         view.get_git_ref("refs/heads/main".as_ref()),
         &RefTarget::normal(jj_id(commit2))
@@ -299,6 +304,10 @@ fn test_import_refs() {
         view.get_git_ref("refs/tags/v1.0".as_ref()),
         &RefTarget::normal(jj_id(commit5))
     );
+    assert_eq!(
+        view.get_git_ref("refs/notes/x".as_ref()),
+        &RefTarget::normal(jj_id(commit7))
+    );
     assert_eq!(view.git_head(), &RefTarget::normal(jj_id(commit2)));
 }
 
@@ -2002,6 +2011,495 @@ fn test_export_bookmark_on_root_commit() {
     );
 }
 
+#[test]
+fn test_export_tags() {
+    // Local tags are exported as lightweight tags
+    let test_data = GitRepoData::create();
+    let git_repo = test_data.git_repo;
+    let mut tx = test_data.repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    mut_repo.set_tag_target("v1".as_ref(), RefTarget::normal(commit1.id().clone()));
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert!(stats.failed_tags.is_empty());
+    assert_eq!(
+        mut_repo.get_git_ref("refs/tags/v1".as_ref()),
+        RefTarget::normal(commit1.id().clone())
+    );
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_id(&commit1)
+    );
+
+    // Move the tag
+    mut_repo.set_tag_target("v1".as_ref(), RefTarget::normal(commit2.id().clone()));
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert!(stats.failed_tags.is_empty());
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_id(&commit2)
+    );
+
+    // Re-importing doesn't change the tag
+    git::import_refs(mut_repo, &GitSettings::default()).unwrap();
+    assert_eq!(
+        *mut_repo.view().get_tag("v1".as_ref()),
+        RefTarget::normal(commit2.id().clone())
+    );
+
+    // Delete the tag
+    mut_repo.set_tag_target("v1".as_ref(), RefTarget::absent());
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert!(stats.failed_tags.is_empty());
+    assert!(git_repo
+        .try_find_reference("refs/tags/v1")
+        .unwrap()
+        .is_none());
+    assert!(mut_repo.get_git_ref("refs/tags/v1".as_ref()).is_absent());
+
+    // Tags pointing to the root commit can't be exported
+    mut_repo.set_tag_target(
+        "on_root".as_ref(),
+        RefTarget::normal(mut_repo.store().root_commit_id().clone()),
+    );
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert_eq!(stats.failed_tags.len(), 1);
+    assert_eq!(stats.failed_tags[0].0.as_str(), "on_root");
+    assert_matches!(stats.failed_tags[0].1, FailedRefExportReason::OnRootCommit);
+}
+
+#[test]
+fn test_create_annotated_tag() {
+    let test_data = GitRepoData::create();
+    let git_repo = test_data.git_repo;
+    let mut tx = test_data.repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let tagger = Signature {
+        name: "Someone".to_owned(),
+        email: "someone@example.com".to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+    let mut sign_fn = |data: &[u8]| Ok(format!("signature of {} bytes\n", data.len()).into());
+    git::create_annotated_tag(
+        mut_repo,
+        "v1".as_ref(),
+        commit1.id(),
+        "Release 1",
+        &tagger,
+        Some(&mut sign_fn),
+    )
+    .unwrap();
+    assert_eq!(
+        *mut_repo.view().get_tag("v1".as_ref()),
+        RefTarget::normal(commit1.id().clone())
+    );
+    assert_eq!(
+        mut_repo.get_git_ref("refs/tags/v1".as_ref()),
+        RefTarget::normal(commit1.id().clone())
+    );
+    let git_tag = git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .id()
+        .object()
+        .unwrap();
+    let git_tag_data = String::from_utf8_lossy(&git_tag.data);
+    let (object_line, git_tag_data) = git_tag_data.split_once('\n').unwrap();
+    assert_eq!(object_line, format!("object {}", commit1.id().hex()));
+    insta::assert_snapshot!(git_tag_data, @r"
+    type commit
+    tag v1
+    tagger Someone <someone@example.com> 0 +0000
+
+    Release 1
+    signature of 123 bytes
+    ");
+
+    // Nothing to export
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert!(stats.failed_tags.is_empty());
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_tag.id
+    );
+
+    // Moving the tag replaces the annotated tag with a lightweight tag
+    mut_repo.set_tag_target("v1".as_ref(), RefTarget::normal(commit2.id().clone()));
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert!(stats.failed_tags.is_empty());
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_id(&commit2)
+    );
+}
+
+#[test]
+fn test_create_annotated_tag_modified_in_git() {
+    let test_data = GitRepoData::create();
+    let git_repo = test_data.git_repo;
+    let mut tx = test_data.repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let tagger = Signature {
+        name: "Someone".to_owned(),
+        email: "someone@example.com".to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+
+    // The tag was created in Git but hasn't been imported
+    git_repo
+        .reference(
+            "refs/tags/v1",
+            git_id(&commit2),
+            gix::refs::transaction::PreviousValue::MustNotExist,
+            "test",
+        )
+        .unwrap();
+    let result =
+        git::create_annotated_tag(mut_repo, "v1".as_ref(), commit1.id(), "", &tagger, None);
+    assert_matches!(result, Err(GitTagError::ModifiedInGit(_)));
+    assert!(mut_repo.view().get_tag("v1".as_ref()).is_absent());
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_id(&commit2)
+    );
+
+    // The tag is moved in Git after it was created by jj
+    git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .delete()
+        .unwrap();
+    git::create_annotated_tag(mut_repo, "v1".as_ref(), commit1.id(), "", &tagger, None).unwrap();
+    git_repo
+        .reference(
+            "refs/tags/v1",
+            git_id(&commit2),
+            gix::refs::transaction::PreviousValue::Any,
+            "test",
+        )
+        .unwrap();
+    let result =
+        git::create_annotated_tag(mut_repo, "v1".as_ref(), commit1.id(), "", &tagger, None);
+    assert_matches!(result, Err(GitTagError::ModifiedInGit(_)));
+    assert_eq!(
+        git_repo.find_reference("refs/tags/v1").unwrap().id(),
+        git_id(&commit2)
+    );
+
+    // The tag can be updated if it still points to the recorded target
+    git_repo
+        .reference(
+            "refs/tags/v1",
+            git_id(&commit1),
+            gix::refs::transaction::PreviousValue::Any,
+            "test",
+        )
+        .unwrap();
+    git::create_annotated_tag(mut_repo, "v1".as_ref(), commit2.id(), "", &tagger, None).unwrap();
+    assert_eq!(
+        *mut_repo.view().get_tag("v1".as_ref()),
+        RefTarget::normal(commit2.id().clone())
+    );
+}
+
+#[test]
+fn test_set_note() {
+    let test_data = GitRepoData::create();
+    let git_repo = test_data.git_repo;
+    let mut tx = test_data.repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let signature = Signature {
+        name: "Someone".to_owned(),
+        email: "someone@example.com".to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+    let notes_ref = git::DEFAULT_NOTES_REF_NAME;
+    assert_eq!(
+        git::read_note(mut_repo, notes_ref, commit1.id()).unwrap(),
+        None
+    );
+
+    git::set_note(
+        mut_repo,
+        notes_ref,
+        commit1.id(),
+        Some("note 1\n"),
+        &signature,
+    )
+    .unwrap();
+    git::set_note(
+        mut_repo,
+        notes_ref,
+        commit2.id(),
+        Some("note 2\n"),
+        &signature,
+    )
+    .unwrap();
+    assert_eq!(
+        git::read_note(mut_repo, notes_ref, commit1.id()).unwrap(),
+        Some("note 1\n".to_owned())
+    );
+    assert_eq!(
+        git::read_note(mut_repo, notes_ref, commit2.id()).unwrap(),
+        Some("note 2\n".to_owned())
+    );
+    // The view is updated, but the notes commit isn't visible
+    let notes_target = mut_repo.get_notes(notes_ref);
+    let notes_id = notes_target.as_normal().unwrap().clone();
+    assert!(mut_repo.index().has_id(&notes_id));
+    assert!(!mut_repo.view().heads().contains(&notes_id));
+    // The Git ref is updated on export
+    assert!(git_repo.find_reference("refs/notes/commits").is_err());
+    assert!(mut_repo
+        .get_git_ref("refs/notes/commits".as_ref())
+        .is_absent());
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert!(stats.failed_bookmarks.is_empty());
+    assert!(stats.failed_tags.is_empty());
+    assert!(stats.failed_notes.is_empty());
+    assert_eq!(
+        git_repo.find_reference("refs/notes/commits").unwrap().id(),
+        gix::ObjectId::from_bytes_or_panic(notes_id.as_bytes())
+    );
+    assert_eq!(
+        mut_repo.get_git_ref("refs/notes/commits".as_ref()),
+        notes_target
+    );
+    // Notes refs aren't exported as bookmarks or tags
+    assert!(git_repo.find_reference("refs/heads/commits").is_err());
+    assert!(git_repo.find_reference("refs/tags/commits").is_err());
+
+    // Overwrite and remove notes
+    git::set_note(
+        mut_repo,
+        notes_ref,
+        commit1.id(),
+        Some("new note 1\n"),
+        &signature,
+    )
+    .unwrap();
+    git::set_note(mut_repo, notes_ref, commit2.id(), None, &signature).unwrap();
+    assert_eq!(
+        git::read_note(mut_repo, notes_ref, commit1.id()).unwrap(),
+        Some("new note 1\n".to_owned())
+    );
+    assert_eq!(
+        git::read_note(mut_repo, notes_ref, commit2.id()).unwrap(),
+        None
+    );
+    git::export_refs(mut_repo).unwrap();
+    let notes_commit = git_repo
+        .find_reference("refs/notes/commits")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(notes_commit.parent_ids().count(), 1);
+    assert_eq!(notes_commit.tree().unwrap().iter().count(), 1);
+}
+
+#[test]
+fn test_set_note_discarded() {
+    let test_data = GitRepoData::create();
+    let git_repo = test_data.git_repo;
+    let mut tx = test_data.repo.start_transaction();
+    let commit = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+    let signature = Signature {
+        name: "Someone".to_owned(),
+        email: "someone@example.com".to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+    let notes_ref = git::DEFAULT_NOTES_REF_NAME;
+
+    // Notes added in a discarded transaction don't exist anywhere
+    let mut tx = repo.start_transaction();
+    git::set_note(
+        tx.repo_mut(),
+        notes_ref,
+        commit.id(),
+        Some("note\n"),
+        &signature,
+    )
+    .unwrap();
+    drop(tx);
+    assert_eq!(
+        git::read_note(repo.as_ref(), notes_ref, commit.id()).unwrap(),
+        None
+    );
+    assert!(git_repo.find_reference("refs/notes/commits").is_err());
+}
+
+#[test]
+fn test_export_notes_modified_in_git() {
+    let test_data = GitRepoData::create();
+    let git_settings = GitSettings::default();
+    let git_repo = test_data.git_repo;
+    let mut tx = test_data.repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit = write_random_commit(mut_repo);
+    let signature = Signature {
+        name: "Someone".to_owned(),
+        email: "someone@example.com".to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+    let notes_ref = git::DEFAULT_NOTES_REF_NAME;
+    git::set_note(mut_repo, notes_ref, commit.id(), Some("note\n"), &signature).unwrap();
+    git::export_refs(mut_repo).unwrap();
+    let notes_target = mut_repo.get_notes(notes_ref);
+
+    // Notes were added in Git, and removed in jj
+    let notes_id = notes_target.as_normal().unwrap();
+    let git_notes_id = empty_git_commit(
+        &git_repo,
+        "refs/notes/commits",
+        &[gix::ObjectId::from_bytes_or_panic(notes_id.as_bytes())],
+    );
+    git::set_note(mut_repo, notes_ref, commit.id(), None, &signature).unwrap();
+    let stats = git::export_refs(mut_repo).unwrap();
+    assert_eq!(stats.failed_notes.len(), 1);
+    assert_eq!(stats.failed_notes[0].0.as_str(), "commits");
+    assert_matches!(
+        stats.failed_notes[0].1,
+        FailedRefExportReason::FailedToSet(_)
+    );
+    assert_eq!(
+        mut_repo.get_git_ref("refs/notes/commits".as_ref()),
+        notes_target
+    );
+    assert_eq!(
+        git_repo.find_reference("refs/notes/commits").unwrap().id(),
+        git_notes_id
+    );
+
+    // The notes ref becomes conflicted on import
+    git::import_refs(mut_repo, &git_settings).unwrap();
+    assert!(mut_repo.get_notes(notes_ref).has_conflict());
+    assert_matches!(
+        git::read_note(mut_repo, notes_ref, commit.id()),
+        Err(GitNoteError::ConflictedRef(_))
+    );
+}
+
+#[test]
+fn test_import_notes() {
+    let test_data = GitRepoData::create();
+    let git_settings = GitSettings::default();
+    let git_repo = test_data.git_repo;
+    let commit_id = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    let commit_hex = commit_id.to_string();
+
+    // Write a note in a fan-out directory, as Git does for many notes
+    let blob_id = git_repo.write_blob(b"test results\n").unwrap().detach();
+    let subtree = gix::objs::Tree {
+        entries: vec![gix::objs::tree::Entry {
+            mode: gix::object::tree::EntryKind::Blob.into(),
+            filename: commit_hex[2..].into(),
+            oid: blob_id,
+        }],
+    };
+    let subtree_id = git_repo.write_object(&subtree).unwrap().detach();
+    let tree = gix::objs::Tree {
+        entries: vec![gix::objs::tree::Entry {
+            mode: gix::object::tree::EntryKind::Tree.into(),
+            filename: commit_hex[..2].into(),
+            oid: subtree_id,
+        }],
+    };
+    let tree_id = git_repo.write_object(&tree).unwrap().detach();
+    let notes_id = testutils::git::write_commit(&git_repo, "refs/notes/ci", tree_id, "notes", &[]);
+
+    let mut tx = test_data.repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    git::import_refs(mut_repo, &git_settings).unwrap();
+    let notes_id = CommitId::from_bytes(notes_id.as_bytes());
+    assert_eq!(
+        mut_repo.get_git_ref("refs/notes/ci".as_ref()),
+        RefTarget::normal(notes_id.clone())
+    );
+    assert_eq!(
+        mut_repo.get_notes("ci".as_ref()),
+        RefTarget::normal(notes_id.clone())
+    );
+    assert!(mut_repo.index().has_id(&notes_id));
+    assert!(!mut_repo.view().heads().contains(&notes_id));
+    assert!(mut_repo
+        .view()
+        .get_local_bookmark("ci".as_ref())
+        .is_absent());
+    assert!(mut_repo.view().get_tag("ci".as_ref()).is_absent());
+    let commit_id = CommitId::from_bytes(commit_id.as_bytes());
+    assert_eq!(
+        git::read_note(mut_repo, "ci".as_ref(), &commit_id).unwrap(),
+        Some("test results\n".to_owned())
+    );
+
+    // Updating a note keeps the fan-out directory
+    let signature = Signature {
+        name: "Someone".to_owned(),
+        email: "someone@example.com".to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+    git::set_note(
+        mut_repo,
+        "ci".as_ref(),
+        &commit_id,
+        Some("fixed\n"),
+        &signature,
+    )
+    .unwrap();
+    assert_eq!(
+        git::read_note(mut_repo, "ci".as_ref(), &commit_id).unwrap(),
+        Some("fixed\n".to_owned())
+    );
+    git::export_refs(mut_repo).unwrap();
+    let notes_tree = git_repo
+        .find_reference("refs/notes/ci")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap()
+        .tree()
+        .unwrap();
+    assert!(notes_tree
+        .lookup_entry_by_path(format!("{}/{}", &commit_hex[..2], &commit_hex[2..]))
+        .unwrap()
+        .is_some());
+
+    // Deleted notes ref is removed from the view
+    git_repo
+        .find_reference("refs/notes/ci")
+        .unwrap()
+        .delete()
+        .unwrap();
+    git::import_refs(mut_repo, &git_settings).unwrap();
+    assert!(mut_repo.get_git_ref("refs/notes/ci".as_ref()).is_absent());
+    assert!(mut_repo.get_notes("ci".as_ref()).is_absent());
+}
+
 #[test]
 fn test_export_partial_failure() {
     // Check that we skip bookmarks that fail to export
@@ -2892,11 +3390,16 @@ fn test_fetch_initial_commit_head_is_set() {
     assert!(stats.import_stats.abandoned_commits.is_empty());
 }
 
-#[test]
-fn test_fetch_success() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_fetch_success(transport: GitTransport) {
     let mut test_data = GitRepoData::create();
     let git_settings = GitSettings {
         auto_local_bookmark: true,
+        transport,
         ..GitSettings::default()
     };
     let initial_git_commit = empty_git_commit(&test_data.origin_repo, "refs/heads/main", &[]);
@@ -2973,11 +3476,16 @@ fn test_fetch_success() {
     );
 }
 
-#[test]
-fn test_fetch_prune_deleted_ref() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_fetch_prune_deleted_ref(transport: GitTransport) {
     let test_data = GitRepoData::create();
     let git_settings = GitSettings {
         auto_local_bookmark: true,
+        transport,
         ..GitSettings::default()
     };
     let commit = empty_git_commit(&test_data.origin_repo, "refs/heads/main", &[]);
@@ -3093,12 +3601,17 @@ fn test_fetch_no_such_remote() {
     assert!(matches!(result, Err(GitFetchError::NoSuchRemote(_))));
 }
 
-#[test]
-fn test_fetch_multiple_branches() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_fetch_multiple_branches(transport: GitTransport) {
     let test_data = GitRepoData::create();
     let _initial_git_commit = empty_git_commit(&test_data.origin_repo, "refs/heads/main", &[]);
     let git_settings = GitSettings {
         auto_local_bookmark: true,
+        transport,
         ..Default::default()
     };
 
@@ -3218,14 +3731,21 @@ fn set_up_push_repos(settings: &UserSettings, temp_dir: &TempDir) -> PushTestSet
     }
 }
 
-#[test]
-fn test_push_bookmarks_success() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_push_bookmarks_success(transport: GitTransport) {
     let settings = testutils::user_settings();
     let temp_dir = testutils::new_temp_dir();
     let mut setup = set_up_push_repos(&settings, &temp_dir);
     let clone_repo = get_git_repo(&setup.jj_repo);
     let mut tx = setup.jj_repo.start_transaction();
-    let git_settings = GitSettings::default();
+    let git_settings = GitSettings {
+        transport,
+        ..GitSettings::default()
+    };
 
     let targets = GitBranchPushTargets {
         branch_updates: vec![(
@@ -3286,14 +3806,21 @@ fn test_push_bookmarks_success() {
     assert!(!tx.repo().has_changes());
 }
 
-#[test]
-fn test_push_bookmarks_deletion() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_push_bookmarks_deletion(transport: GitTransport) {
     let settings = testutils::user_settings();
     let temp_dir = testutils::new_temp_dir();
     let mut setup = set_up_push_repos(&settings, &temp_dir);
     let clone_repo = get_git_repo(&setup.jj_repo);
     let mut tx = setup.jj_repo.start_transaction();
-    let git_settings = GitSettings::default();
+    let git_settings = GitSettings {
+        transport,
+        ..GitSettings::default()
+    };
 
     let source_repo = testutils::git::open(&setup.source_repo_dir);
     // Test the setup
@@ -3428,13 +3955,20 @@ fn test_push_bookmarks_mixed_deletion_and_addition() {
     assert!(!tx.repo().has_changes());
 }
 
-#[test]
-fn test_push_bookmarks_not_fast_forward() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_push_bookmarks_not_fast_forward(transport: GitTransport) {
     let settings = testutils::user_settings();
     let temp_dir = testutils::new_temp_dir();
     let setup = set_up_push_repos(&settings, &temp_dir);
     let mut tx = setup.jj_repo.start_transaction();
-    let git_settings = GitSettings::default();
+    let git_settings = GitSettings {
+        transport,
+        ..GitSettings::default()
+    };
 
     let targets = GitBranchPushTargets {
         branch_updates: vec![(
@@ -3470,12 +4004,19 @@ fn test_push_bookmarks_not_fast_forward() {
 // may want to add tests for when a bookmark unexpectedly moved backwards or
 // unexpectedly does not exist for bookmark deletion.
 
-#[test]
-fn test_push_updates_unexpectedly_moved_sideways_on_remote() {
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_push_updates_unexpectedly_moved_sideways_on_remote(transport: GitTransport) {
     let settings = testutils::user_settings();
     let temp_dir = testutils::new_temp_dir();
     let setup = set_up_push_repos(&settings, &temp_dir);
-    let git_settings = GitSettings::default();
+    let git_settings = GitSettings {
+        transport,
+        ..GitSettings::default()
+    };
 
     // The main bookmark is actually at `main_commit` on the remote. If we expect
     // it to be at `sideways_commit`, it unexpectedly moved sideways from our
@@ -3615,11 +4156,51 @@ fn test_push_updates_unexpectedly_moved_forward_on_remote() {
 }
 
 #[test]
-fn test_push_updates_unexpectedly_exists_on_remote() {
+fn test_push_updates_not_allowed_bookmark() {
     let settings = testutils::user_settings();
     let temp_dir = testutils::new_temp_dir();
     let setup = set_up_push_repos(&settings, &temp_dir);
-    let git_settings = GitSettings::default();
+    let git_settings = GitSettings {
+        push_bookmarks: [(
+            "origin".into(),
+            vec![StringPattern::glob("team/*").unwrap()],
+        )]
+        .into(),
+        ..GitSettings::default()
+    };
+
+    let targets = [GitRefUpdate {
+        qualified_name: "refs/heads/main".into(),
+        expected_current_target: Some(setup.main_commit.id().clone()),
+        new_target: Some(setup.child_of_main_commit.id().clone()),
+    }];
+    let result = git::push_updates(
+        setup.jj_repo.as_ref(),
+        &git_settings,
+        "origin".as_ref(),
+        &targets,
+        git::RemoteCallbacks::default(),
+    );
+    assert_matches!(
+        result,
+        Err(GitPushError::BookmarkNotAllowed { name, remote })
+            if name == "main" && remote == "origin"
+    );
+}
+
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_push_updates_unexpectedly_exists_on_remote(transport: GitTransport) {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+    let git_settings = GitSettings {
+        transport,
+        ..GitSettings::default()
+    };
 
     // The main bookmark is actually at `main_commit` on the remote. In this test,
     // we expect it to not exist on the remote at all.
@@ -3702,6 +4283,201 @@ fn test_push_updates_success() {
     assert_eq!(new_target.target().id(), new_oid);
 }
 
+#[test_case(GitTransport::Subprocess; "subprocess")]
+#[cfg_attr(
+    feature = "git-builtin-transport",
+    test_case(GitTransport::Builtin; "builtin")
+)]
+fn test_push_to_remote_ref(transport: GitTransport) {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+    let git_settings = GitSettings {
+        transport,
+        ..GitSettings::default()
+    };
+    let source_repo = testutils::git::open(&setup.source_repo_dir);
+    let push_to_remote_ref = |qualified_name: &str, target: &Commit| {
+        git::push_to_remote_ref(
+            setup.jj_repo.as_ref(),
+            &git_settings,
+            "origin".as_ref(),
+            qualified_name.as_ref(),
+            target.id(),
+            git::RemoteCallbacks::default(),
+        )
+    };
+
+    // New ref can be created
+    let stats = push_to_remote_ref("refs/for/main", &setup.child_of_main_commit).unwrap();
+    assert_eq!(stats.pushed, ["refs/for/main"].map(GitRefNameBuf::from));
+    let new_target = source_repo.find_reference("refs/for/main").unwrap();
+    assert_eq!(
+        new_target.target().id(),
+        git_id(&setup.child_of_main_commit)
+    );
+
+    // Existing ref can only be fast-forwarded
+    let stats = push_to_remote_ref("refs/heads/main", &setup.sideways_commit).unwrap();
+    assert_eq!(
+        push_status_rejected_references(stats),
+        ["refs/heads/main"].map(GitRefNameBuf::from)
+    );
+    let stats = push_to_remote_ref("refs/heads/main", &setup.child_of_main_commit).unwrap();
+    assert_eq!(stats.pushed, ["refs/heads/main"].map(GitRefNameBuf::from));
+}
+
+#[cfg(all(unix, feature = "git-builtin-transport"))]
+#[test]
+fn test_fetch_and_push_builtin_transport_over_ssh() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let source_repo_dir = temp_dir.path().join("source");
+    let git_repo_dir = temp_dir.path().join("git");
+    let jj_repo_dir = temp_dir.path().join("jj");
+    let source_repo = testutils::git::init_bare(&source_repo_dir);
+    let initial_git_commit = empty_git_commit(&source_repo, "refs/heads/main", &[]);
+    let git_repo = testutils::git::init(&git_repo_dir);
+
+    // Fake ssh command which runs the remote command locally
+    let ssh_path = temp_dir.path().join("fake-ssh");
+    fs::write(&ssh_path, "#!/bin/sh\nshift\nexec sh -c \"$*\"\n").unwrap();
+    fs::set_permissions(&ssh_path, fs::Permissions::from_mode(0o755)).unwrap();
+    testutils::git::add_remote(
+        &git_repo_dir,
+        "origin",
+        &format!("ssh://localhost{}", source_repo_dir.display()),
+    );
+    let mut config_file = fs::OpenOptions::new()
+        .append(true)
+        .open(git_repo.path().join("config"))
+        .unwrap();
+    writeln!(config_file, "[core]\n\tsshCommand = {}", ssh_path.display()).unwrap();
+    drop(config_file);
+
+    std::fs::create_dir(&jj_repo_dir).unwrap();
+    let repo = ReadonlyRepo::init(
+        &settings,
+        &jj_repo_dir,
+        &|settings, store_path| {
+            Ok(Box::new(GitBackend::init_external(
+                settings,
+                store_path,
+                git_repo.path(),
+            )?))
+        },
+        Signer::from_settings(&settings).unwrap(),
+        ReadonlyRepo::default_op_store_initializer(),
+        ReadonlyRepo::default_op_heads_store_initializer(),
+        ReadonlyRepo::default_index_store_initializer(),
+        ReadonlyRepo::default_submodule_store_initializer(),
+    )
+    .unwrap();
+    let git_settings = GitSettings {
+        transport: GitTransport::Builtin,
+        ..GitSettings::default()
+    };
+
+    let mut tx = repo.start_transaction();
+    git_fetch(
+        tx.repo_mut(),
+        "origin".as_ref(),
+        &[StringPattern::everything()],
+        &git_settings,
+    )
+    .unwrap();
+    assert_eq!(
+        *tx.repo().view().git_refs(),
+        btreemap! {
+            "refs/remotes/origin/main".into() => RefTarget::normal(jj_id(initial_git_commit)),
+        }
+    );
+
+    let new_commit = create_random_commit(tx.repo_mut())
+        .set_parents(vec![jj_id(initial_git_commit)])
+        .write()
+        .unwrap();
+    let targets = GitBranchPushTargets {
+        branch_updates: vec![(
+            "main".into(),
+            BookmarkPushUpdate {
+                old_target: Some(jj_id(initial_git_commit)),
+                new_target: Some(new_commit.id().clone()),
+            },
+        )],
+    };
+    let stats = git::push_branches(
+        tx.repo_mut(),
+        &git_settings,
+        "origin".as_ref(),
+        &targets,
+        git::RemoteCallbacks::default(),
+    )
+    .unwrap();
+    assert_eq!(stats.pushed, ["refs/heads/main"].map(GitRefNameBuf::from));
+    let source_repo = testutils::git::open(&source_repo_dir);
+    let new_target = source_repo.find_reference("refs/heads/main").unwrap();
+    assert_eq!(new_target.target().id(), git_id(&new_commit));
+}
+
+#[cfg(all(unix, feature = "git-builtin-transport"))]
+#[test]
+fn test_push_builtin_transport_skips_hooks_of_local_remote() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+    // The hook rejects any push
+    let hook_path = setup.source_repo_dir.join("hooks").join("pre-receive");
+    std::fs::create_dir_all(hook_path.parent().unwrap()).unwrap();
+    std::fs::write(&hook_path, "#!/bin/sh\nexit 1\n").unwrap();
+    std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let push = |transport| {
+        let git_settings = GitSettings {
+            transport,
+            ..GitSettings::default()
+        };
+        let targets = GitBranchPushTargets {
+            branch_updates: vec![(
+                "main".into(),
+                BookmarkPushUpdate {
+                    old_target: Some(setup.main_commit.id().clone()),
+                    new_target: Some(setup.child_of_main_commit.id().clone()),
+                },
+            )],
+        };
+        let mut tx = setup.jj_repo.start_transaction();
+        git::push_branches(
+            tx.repo_mut(),
+            &git_settings,
+            "origin".as_ref(),
+            &targets,
+            git::RemoteCallbacks::default(),
+        )
+        .unwrap()
+    };
+
+    // git-receive-pack runs the hook
+    let stats = push(GitTransport::Subprocess);
+    assert!(stats.pushed.is_empty());
+    assert_eq!(stats.remote_rejected.len(), 1);
+
+    // The builtin transport updates the refs of local remotes in-process
+    // without running the hooks
+    let stats = push(GitTransport::Builtin);
+    assert_eq!(stats.pushed, ["refs/heads/main"].map(GitRefNameBuf::from));
+    let source_repo = testutils::git::open(&setup.source_repo_dir);
+    let new_target = source_repo.find_reference("refs/heads/main").unwrap();
+    assert_eq!(
+        new_target.target().id(),
+        git_id(&setup.child_of_main_commit)
+    );
+}
+
 #[test]
 fn test_push_updates_no_such_remote() {
     let settings = testutils::user_settings();
@@ -4149,7 +4925,7 @@ fn test_remote_remove_refs() {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
     let repo = &test_repo.repo;
 
-    git::add_remote(repo.store(), "foo".as_ref(), "https://example.com/").unwrap();
+    git::add_remote(repo.store(), "foo".as_ref(), "https://example.com/", &[]).unwrap();
     // Reload after Git configuration change.
     let repo = &test_repo
         .env
@@ -4187,7 +4963,7 @@ fn test_remote_rename_refs() {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
     let repo = &test_repo.repo;
 
-    git::add_remote(repo.store(), "foo".as_ref(), "https://example.com/").unwrap();
+    git::add_remote(repo.store(), "foo".as_ref(), "https://example.com/", &[]).unwrap();
     // Reload after Git configuration change.
     let repo = &test_repo
         .env
@@ -4231,6 +5007,95 @@ fn test_remote_rename_refs() {
     );
 }
 
+#[test]
+fn test_remote_fetch_bookmarks() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+
+    let patterns = [
+        StringPattern::exact("main"),
+        StringPattern::glob("team/*").unwrap(),
+    ];
+    git::add_remote(
+        repo.store(),
+        "foo".as_ref(),
+        "https://example.com/",
+        &patterns,
+    )
+    .unwrap();
+    let invalid_patterns = [StringPattern::substring("main")];
+    assert_matches!(
+        git::add_remote(
+            repo.store(),
+            "bar".as_ref(),
+            "https://example.com/",
+            &invalid_patterns
+        ),
+        Err(GitRemoteManagementError::InvalidFetchPattern(_))
+    );
+    // Reload after Git configuration change.
+    let repo = &test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    let fetch_refspecs = |repo: &Arc<ReadonlyRepo>, remote_name: &str| {
+        get_git_repo(repo)
+            .find_remote(remote_name)
+            .unwrap()
+            .refspecs(gix::remote::Direction::Fetch)
+            .iter()
+            .map(|refspec| refspec.to_ref().to_bstring().to_string())
+            .collect_vec()
+    };
+    assert_eq!(
+        fetch_refspecs(repo, "foo"),
+        [
+            "+refs/heads/main:refs/remotes/foo/main",
+            "+refs/heads/team/*:refs/remotes/foo/team/*",
+        ]
+    );
+
+    let git_settings = GitSettings::default();
+    let mut tx = repo.start_transaction();
+    let git_fetch = GitFetch::new(tx.repo_mut(), &git_settings).unwrap();
+    let to_globs = |patterns: Vec<StringPattern>| {
+        patterns
+            .iter()
+            .map(|pattern| pattern.to_glob().unwrap().into_owned())
+            .collect_vec()
+    };
+    assert_eq!(
+        to_globs(git_fetch.default_branch_patterns("foo".as_ref()).unwrap()),
+        ["main", "team/*"]
+    );
+    drop(git_fetch);
+
+    // The setting takes precedence over the refspecs
+    let git_settings = GitSettings {
+        fetch_bookmarks: [("foo".into(), vec![StringPattern::exact("other")])].into(),
+        ..GitSettings::default()
+    };
+    let git_fetch = GitFetch::new(tx.repo_mut(), &git_settings).unwrap();
+    assert_eq!(
+        to_globs(git_fetch.default_branch_patterns("foo".as_ref()).unwrap()),
+        ["other"]
+    );
+    drop(git_fetch);
+
+    // The bookmark patterns are preserved when renaming the remote
+    git::rename_remote(tx.repo_mut(), "foo".as_ref(), "bar".as_ref()).unwrap();
+    tx.commit("rename").unwrap();
+    let repo = &test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    assert_eq!(
+        fetch_refspecs(repo, "bar"),
+        [
+            "+refs/heads/main:refs/remotes/bar/main",
+            "+refs/heads/team/*:refs/remotes/bar/team/*",
+        ]
+    );
+}
+
 fn user_settings_without_change_id() -> UserSettings {
     let mut config = base_user_config();
     let mut layer = ConfigLayer::empty(ConfigSource::Default);
@@ -17,15 +17,20 @@ use std::fs;
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
+use itertools::Itertools as _;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::commit_builder::CommitBuilder;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::default_index::DefaultIndexStoreError;
 use jj_lib::default_index::DefaultMutableIndex;
 use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::index::Index as _;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::HexPrefix;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::object_id::PrefixResolution;
@@ -37,11 +42,18 @@ use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::repo::MutableRepo;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
 use jj_lib::revset::ResolvedExpression;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::GENERATION_RANGE_FULL;
+use jj_lib::settings::UserSettings;
 use maplit::hashset;
 use testutils::commit_transactions;
 use testutils::create_random_commit;
+use testutils::create_tree;
+use testutils::repo_path;
+use testutils::repo_path_buf;
 use testutils::test_backend::TestBackend;
 use testutils::write_random_commit;
 use testutils::CommitGraphBuilder;
@@ -513,6 +525,19 @@ fn create_n_commits(repo: &Arc<ReadonlyRepo>, num_commits: i32) -> Arc<ReadonlyR
     tx.commit("test").unwrap()
 }
 
+fn changed_paths<'a>(repo: &'a Arc<ReadonlyRepo>, commit: &Commit) -> Option<Vec<&'a RepoPath>> {
+    let paths = as_readonly_index(repo).changed_paths(commit.id())?;
+    Some(paths.into_iter().sorted().collect())
+}
+
+fn settings_with_changed_paths() -> UserSettings {
+    let mut config = testutils::base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer.set_value("index.changed-paths", true).unwrap();
+    config.add_layer(layer);
+    UserSettings::from_config(config).unwrap()
+}
+
 fn as_readonly_index(repo: &Arc<ReadonlyRepo>) -> &DefaultReadonlyIndex {
     repo.readonly_index().as_any().downcast_ref().unwrap()
 }
@@ -591,6 +616,109 @@ fn test_index_commits_incremental_squashed() {
     assert_eq!(commits_by_level(&repo), vec![71, 20]);
 }
 
+#[test]
+fn test_index_changed_paths() {
+    let settings = settings_with_changed_paths();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let test_env = &test_repo.env;
+    let repo = &test_repo.repo;
+
+    let file1 = repo_path("file1");
+    let file2 = repo_path("dir/file2");
+    let tree_a = create_tree(repo, &[(file1, "a")]);
+    let tree_b = create_tree(repo, &[(file1, "a"), (file2, "b")]);
+    let tree_c = create_tree(repo, &[(file1, "c")]);
+    let tree_d = create_tree(repo, &[(file1, "c"), (file2, "b")]);
+
+    let mut tx = repo.start_transaction();
+    let root_commit_id = repo.store().root_commit_id();
+    let mut new_commit = |parents: &[&Commit], tree: &MergedTree| {
+        let parent_ids = parents.iter().map(|commit| commit.id().clone()).collect();
+        tx.repo_mut()
+            .new_commit(parent_ids, tree.id())
+            .write()
+            .unwrap()
+    };
+    let root_commit = repo.store().root_commit();
+    let commit_a = new_commit(&[&root_commit], &tree_a);
+    let commit_b = new_commit(&[&commit_a], &tree_b);
+    let commit_c = new_commit(&[&commit_a], &tree_c);
+    let commit_d = new_commit(&[&commit_b, &commit_c], &tree_d);
+    let commit_e = new_commit(&[&commit_d], &tree_d);
+    let repo = tx.commit("test").unwrap();
+
+    let check_changed_paths = |repo: &Arc<ReadonlyRepo>| {
+        let index = as_readonly_index(repo);
+        assert_eq!(index.changed_paths(root_commit_id), Some(vec![]));
+        assert_eq!(changed_paths(repo, &commit_a), Some(vec![file1]));
+        assert_eq!(changed_paths(repo, &commit_b), Some(vec![file2]));
+        assert_eq!(changed_paths(repo, &commit_c), Some(vec![file1]));
+        // Merge of both sides, no new changes
+        assert_eq!(changed_paths(repo, &commit_d), Some(vec![]));
+        assert_eq!(changed_paths(repo, &commit_e), Some(vec![]));
+    };
+    check_changed_paths(&repo);
+
+    // Changed paths are rebuilt along with the index
+    let default_index_store: &DefaultIndexStore =
+        repo.index_store().as_any().downcast_ref().unwrap();
+    default_index_store.reinit().unwrap();
+    let repo = test_env.load_repo_at_head(&settings, test_repo.repo_path());
+    check_changed_paths(&repo);
+
+    // New commits inherit the setting from the parent index
+    let repo = test_env.load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    let mut tx = repo.start_transaction();
+    let commit_f = tx
+        .repo_mut()
+        .new_commit(vec![commit_e.id().clone()], tree_a.id())
+        .write()
+        .unwrap();
+    let repo = tx.commit("test").unwrap();
+    assert_eq!(changed_paths(&repo, &commit_f), Some(vec![file2, file1]));
+
+    // The revset engine should produce the same results with and without
+    // changed paths
+    let evaluate = |repo: &Arc<ReadonlyRepo>, revset_str: &str| {
+        let expression = RevsetExpression::all().filtered(RevsetFilterPredicate::File(
+            FilesetExpression::prefix_path(repo_path_buf(revset_str)),
+        ));
+        expression
+            .evaluate(repo.as_ref())
+            .unwrap()
+            .iter()
+            .map(Result::unwrap)
+            .collect_vec()
+    };
+    assert_eq!(
+        evaluate(&repo, "dir"),
+        vec![commit_f.id().clone(), commit_b.id().clone()]
+    );
+    assert_eq!(
+        evaluate(&repo, "file1"),
+        vec![
+            commit_f.id().clone(),
+            commit_c.id().clone(),
+            commit_a.id().clone()
+        ]
+    );
+    default_index_store.reinit().unwrap();
+    let repo = test_env.load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    assert_eq!(changed_paths(&repo, &commit_a), None);
+    assert_eq!(
+        evaluate(&repo, "dir"),
+        vec![commit_f.id().clone(), commit_b.id().clone()]
+    );
+    assert_eq!(
+        evaluate(&repo, "file1"),
+        vec![
+            commit_f.id().clone(),
+            commit_c.id().clone(),
+            commit_a.id().clone()
+        ]
+    );
+}
+
 #[test]
 fn test_reindex_no_segments_dir() {
     let settings = testutils::user_settings();
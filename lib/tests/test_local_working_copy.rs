@@ -23,6 +23,7 @@ use std::sync::Arc;
 use assert_matches::assert_matches;
 use indoc::indoc;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::backend::CopyId;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::backend::TreeId;
@@ -30,6 +31,8 @@ use jj_lib::backend::TreeValue;
 use jj_lib::file_util::check_symlink_support;
 use jj_lib::file_util::try_symlink;
 use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::git_lfs::LfsObjectStore;
+use jj_lib::git_lfs::LfsPointer;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::merge::Merge;
@@ -1466,6 +1469,185 @@ fn test_git_submodule(gitignore_content: &str) {
     assert_eq!(stats.skipped_files, 1);
 }
 
+#[test]
+fn test_git_submodule_checkout_and_snapshot() {
+    let mut test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Git);
+    let repo = test_workspace.repo.clone();
+    let store = repo.store().clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let submodule_path = repo_path("sub");
+    let submodule_root = submodule_path.to_fs_path_unchecked(&workspace_root);
+    let submodule_repo = testutils::git::init(&submodule_root);
+    let submodule_commit1 = testutils::git::add_commit(
+        &submodule_repo,
+        "refs/heads/main",
+        "file",
+        b"1\n",
+        "commit1",
+        &[],
+    )
+    .commit_id;
+    let submodule_commit2 = testutils::git::add_commit(
+        &submodule_repo,
+        "refs/heads/main",
+        "added",
+        b"added\n",
+        "commit2",
+        &[submodule_commit1],
+    )
+    .commit_id;
+    let tree_with_submodule = |commit_id: gix::ObjectId| {
+        let mut tree_builder = MergedTreeBuilder::new(store.empty_merged_tree_id());
+        tree_builder.set_or_remove(
+            submodule_path.to_owned(),
+            Merge::normal(TreeValue::GitSubmodule(CommitId::from_bytes(
+                commit_id.as_bytes(),
+            ))),
+        );
+        tree_builder.write_tree(&store).unwrap()
+    };
+    let tree_id1 = tree_with_submodule(submodule_commit1);
+    let tree_id2 = tree_with_submodule(submodule_commit2);
+    let commit1 = commit_with_tree(&store, tree_id1.clone());
+    let commit2 = commit_with_tree(&store, tree_id2.clone());
+
+    // Check out the submodule commit, which detaches the submodule HEAD
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(submodule_root.join("file")).unwrap(),
+        "1\n"
+    );
+    assert!(!submodule_root.join("added").exists());
+    let submodule_repo = testutils::git::open(&submodule_root);
+    assert_eq!(
+        submodule_repo.head_id().unwrap().detach(),
+        submodule_commit1
+    );
+
+    // The checked-out submodule commit isn't recorded as a change
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree_id1);
+
+    // Update the submodule
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit2,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(submodule_root.join("added")).unwrap(),
+        "added\n"
+    );
+    let submodule_repo = testutils::git::open(&submodule_root);
+    assert_eq!(
+        submodule_repo.head_id().unwrap().detach(),
+        submodule_commit2
+    );
+
+    // Move the submodule HEAD behind jj's back. Set the mtime explicitly in
+    // case the file system timestamp is coarse.
+    testutils::git::set_head_to_id(&submodule_repo, submodule_commit1);
+    std::fs::File::options()
+        .write(true)
+        .open(submodule_repo.path().join("HEAD"))
+        .unwrap()
+        .set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1))
+        .unwrap();
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree_id1);
+}
+
+#[test]
+fn test_git_lfs_checkout_and_snapshot() {
+    let mut test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Git);
+    let repo = test_workspace.repo.clone();
+    let store = repo.store().clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let lfs_store = LfsObjectStore::for_store(&store).unwrap();
+
+    let gitattributes_path = repo_path(".gitattributes");
+    let asset_path = repo_path("asset.bin");
+    let text_path = repo_path("text.txt");
+    testutils::write_working_copy_file(&workspace_root, gitattributes_path, "*.bin filter=lfs\n");
+    testutils::write_working_copy_file(&workspace_root, asset_path, "asset");
+    testutils::write_working_copy_file(&workspace_root, text_path, "text");
+
+    // The content of LFS-tracked files is moved to the LFS store
+    let tree = test_workspace.snapshot().unwrap();
+    let read_content = |path: &RepoPath| match tree.path_value(path).unwrap().into_resolved() {
+        Ok(Some(TreeValue::File { id, .. })) => testutils::read_file(&store, path, &id),
+        value => panic!("unexpected value: {value:?}"),
+    };
+    let pointer = LfsPointer::parse(&read_content(asset_path)).unwrap();
+    assert_eq!(pointer.size(), 5);
+    assert_eq!(
+        std::fs::read(lfs_store.object_path(&pointer)).unwrap(),
+        b"asset"
+    );
+    assert_eq!(read_content(text_path), b"text");
+
+    // The content is restored on checkout
+    let commit = commit_with_tree(&store, tree.id());
+    let empty_commit = commit_with_tree(&store, store.empty_merged_tree_id());
+    let ws = &mut test_workspace.workspace;
+    for commit in [&empty_commit, &commit] {
+        ws.check_out(
+            repo.op_id().clone(),
+            None,
+            commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        std::fs::read(asset_path.to_fs_path_unchecked(&workspace_root)).unwrap(),
+        b"asset"
+    );
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree.id());
+
+    // Pointer files are checked out as is if the object isn't available
+    let missing_pointer = LfsPointer::new(&"0".repeat(64), 10).unwrap();
+    let missing_pointer_text = String::from_utf8(missing_pointer.to_bytes()).unwrap();
+    let missing_path = repo_path("missing.bin");
+    let tree_with_missing = create_tree(
+        &repo,
+        &[
+            (gitattributes_path, "*.bin filter=lfs\n"),
+            (missing_path, &missing_pointer_text),
+        ],
+    );
+    let commit = commit_with_tree(&store, tree_with_missing.id());
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(missing_path.to_fs_path_unchecked(&workspace_root)).unwrap(),
+        missing_pointer_text
+    );
+    // Modified pointer files aren't moved to the LFS store
+    std::fs::remove_file(missing_path.to_fs_path_unchecked(&workspace_root)).unwrap();
+    testutils::write_working_copy_file(&workspace_root, missing_path, &missing_pointer_text);
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree_with_missing.id());
+}
+
 #[test]
 fn test_check_out_existing_file_cannot_be_removed() {
     let mut test_workspace = TestWorkspace::init();
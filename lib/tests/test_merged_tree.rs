@@ -1644,3 +1644,52 @@ fn test_merge_simplify_file_conflict_with_absent() {
     let merged = child1_merged.merge(&parent_merged, &child2_merged).unwrap();
     assert_eq!(merged, expected_merged);
 }
+
+#[cfg(feature = "tree-sitter-rust")]
+#[test]
+fn test_merge_structural() {
+    use std::sync::Arc;
+
+    use jj_lib::config::ConfigLayer;
+    use jj_lib::config::ConfigSource;
+    use jj_lib::repo::ReadonlyRepo;
+    use jj_lib::settings::UserSettings;
+
+    let rust_path = repo_path("src/lib.rs");
+    let text_path = repo_path("lib.txt");
+    let base_text = "use std::fmt;\nuse std::io;\n";
+    let left_text = "use std::fmt;\nuse std::io;\nuse std::path;\n";
+    let right_text = "use std::fmt;\nuse std::io;\nuse std::sync;\n";
+    let merged_text = "use std::fmt;\nuse std::io;\nuse std::path;\nuse std::sync;\n";
+    let merge_trees = |repo: &Arc<ReadonlyRepo>| {
+        let create = |text| create_single_tree(repo, &[(rust_path, text), (text_path, text)]);
+        let base = MergedTree::resolved(create(base_text));
+        let left = MergedTree::resolved(create(left_text));
+        let right = MergedTree::resolved(create(right_text));
+        let merged = left.merge(&base, &right).unwrap();
+        (
+            merged.path_value(rust_path).unwrap(),
+            merged.path_value(text_path).unwrap(),
+        )
+    };
+
+    // Structural merge is disabled by default
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let (rust_value, text_value) = merge_trees(repo);
+    assert!(!rust_value.is_resolved());
+    assert!(!text_value.is_resolved());
+
+    // Only the file with a known language is merged
+    let mut config = testutils::base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer.set_value("merge.structural", true).unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let repo = &test_repo.repo;
+    let (rust_value, text_value) = merge_trees(repo);
+    let expected_id = write_file(repo.store(), rust_path, merged_text);
+    assert_eq!(rust_value, Merge::normal(file_value(&expected_id)));
+    assert!(!text_value.is_resolved());
+}
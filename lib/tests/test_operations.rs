@@ -663,20 +663,20 @@ fn test_resolve_op_id() {
     let mut operations = Vec::new();
     // The actual value of `i` doesn't matter, we just need to make sure we end
     // up with hashes with ambiguous prefixes.
-    for i in (1..5).chain([9, 27]) {
+    for i in (1..5).chain([9, 18]) {
         let tx = repo.start_transaction();
         let repo = tx.commit(format!("transaction {i}")).unwrap();
         operations.push(repo.operation().clone());
     }
-    // "6" and "0" are ambiguous
+    // "9" and "0" are ambiguous
     insta::assert_debug_snapshot!(operations.iter().map(|op| op.id().hex()).collect_vec(), @r#"
     [
-        "ff7608ec55acf1ccb44bee52a0972f8b36864540ee6547d5e124a30bafd3bfbe00418446d5581cf71debac912a091dd2f93a2dd3bcb34bc53c61604aa9c129d6",
-        "b83ded05e46bef569737b8c1293c59af3fe89f72bc2cecd017b5eb96f5c69e50205069eedf144ca4fa9e55ac0c27842dce874b371a752223c5f85c4b6faadf96",
-        "6412c4e33f791b71f440817d3d16c0ee1b7640845db8f5e4146c58e8c3f4329df4662b0edeab5860c86b4679b150f38318a6e3d4ada5803176f9c5273d97f4dd",
-        "6838be3a934e1b8fc966dcf43796a3cc639a3d26edb1e9af94a285f4ce7edaecfe9e101dd7f0338af22e1632f36d634916015b72b026e1529a7b600566c29067",
-        "0ecccbdd90dd12a592dd0de010feda8bf23e4a5650f1946a82df854fc28791ad046b2d42b38060103db7fb99c00787689df98a7d2166d8180666b745cc32b172",
-        "065be6feb0ab573f0638e952ead8482899307d211f80af5dc90952a6171cc7122d5af9f13fde2ce3e37fc5e7776d5e3bc4236d82ce7d2ecbf1f63373c20772e4",
+        "9e847bcc4997fba1d1508b7110063ce52afeb8846fbcb79f67545e6ef5366f260f1ab02fbb5b14bae67b7eaff88261ab714b69a8bef819fc2acdd9d88e534aff",
+        "9d6b83a00efb864e25a2ed7bc74ee8b37968f3c32190713eb0fa9b5cb9ce9c6fbf02ee1fc2124a3690db53de5a7aa58bf3709c2841dccb9ebec884ba5a0155fb",
+        "8a958e1b4627a296e2b203f916407114be8911f3262af25c5c3b47b2664ce5039902dbd6db68f3cf617eafeee54a6aede317f2b4ab26f88d7f0556cd3a232db5",
+        "97df3532fa37dd427449fd8433a58e97b873878f66d5d6932bafc00cf03ee985684ed1ac15c1033eb2fb5110ae819dc9050dab6c224bb0c75be5799bd730c354",
+        "1ca657eaf3982ad90e70837cf01761febcbc54cef360f410c7ab01033ec093e45dabfd15b0ec0a019c651668d0732ffe4f65dc44595984b40dc02df9e94a2b35",
+        "0d310b2678e43019c7cea86ebc33a5db4f6699148e89dca44634daa7c400fbef57b7fa9ce3af02723f4e5fb91a3f306a630a8b772304cdfba0f4e59f8a3cf16b",
     ]
     "#);
 
@@ -697,7 +697,7 @@ fn test_resolve_op_id() {
     );
     // Ambiguous id
     assert_matches!(
-        resolve("6"),
+        resolve("9"),
         Err(OpsetEvaluationError::OpsetResolution(
             OpsetResolutionError::AmbiguousIdPrefix(_)
         ))
@@ -720,7 +720,7 @@ fn test_resolve_op_id() {
     let root_operation = loader.root_operation();
     assert_eq!(resolve(&root_operation.id().hex()).unwrap(), root_operation);
     assert_eq!(resolve("00").unwrap(), root_operation);
-    assert_eq!(resolve("0e").unwrap(), operations[4]);
+    assert_eq!(resolve("0d").unwrap(), operations[5]);
     assert_matches!(
         resolve("0"),
         Err(OpsetEvaluationError::OpsetResolution(
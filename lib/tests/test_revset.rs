@@ -4024,6 +4024,63 @@ fn test_evaluate_expression_file() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_file_predicate() {
+    let test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+
+    let small = repo_path("small");
+    let large = repo_path("large");
+    let large_content = "x".repeat(2000);
+    let tree1 = create_tree(repo, &[(small, "1"), (large, &large_content)]);
+    let tree2 = create_tree(repo, &[(small, "1"), (large, "x")]);
+    let tree3 = create_tree(repo, &[(small, "2"), (large, "x")]);
+    let commit1 = mut_repo
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree1.id())
+        .write()
+        .unwrap();
+    let commit2 = mut_repo
+        .new_commit(vec![commit1.id().clone()], tree2.id())
+        .write()
+        .unwrap();
+    let commit3 = mut_repo
+        .new_commit(vec![commit2.id().clone()], tree3.id())
+        .write()
+        .unwrap();
+
+    // A file matches if it's large on either side of the diff
+    assert_eq!(
+        resolve_commit_ids_in_workspace(
+            mut_repo,
+            "files(larger_than(1KB))",
+            &test_workspace.workspace,
+            Some(test_workspace.workspace.workspace_root()),
+        ),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids_in_workspace(
+            mut_repo,
+            "files(small & larger_than(1KB))",
+            &test_workspace.workspace,
+            Some(test_workspace.workspace.workspace_root()),
+        ),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids_in_workspace(
+            mut_repo,
+            "files(~larger_than(1KB))",
+            &test_workspace.workspace,
+            Some(test_workspace.workspace.workspace_root()),
+        ),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_diff_contains() {
     let test_workspace = TestWorkspace::init();
@@ -15,6 +15,8 @@
 use itertools::Itertools as _;
 use jj_lib::backend::ChangeId;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::matchers::EverythingMatcher;
 use jj_lib::matchers::FilesMatcher;
 use jj_lib::merge::Merge;
@@ -29,6 +31,7 @@ use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::find_duplicate_divergent_commits;
+use jj_lib::rewrite::rebase_commit;
 use jj_lib::rewrite::rebase_commit_with_options;
 use jj_lib::rewrite::restore_tree;
 use jj_lib::rewrite::CommitRewriter;
@@ -37,6 +40,7 @@ use jj_lib::rewrite::EmptyBehaviour;
 use jj_lib::rewrite::MoveCommitsTarget;
 use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RewriteRefsOptions;
+use jj_lib::settings::UserSettings;
 use maplit::hashmap;
 use maplit::hashset;
 use pollster::FutureExt as _;
@@ -2008,3 +2012,52 @@ fn test_find_duplicate_divergent_commits() {
     // Commit c2 is a duplicate
     assert_eq!(duplicate_commits, std::slice::from_ref(&commit_c2));
 }
+
+#[test]
+fn test_rebase_with_recorded_resolution() {
+    let mut config = testutils::base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer.set_value("merge.rerere", true).unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let repo = &test_repo.repo;
+    let path = repo_path("file");
+
+    let tree_a = create_tree(repo, &[(path, "a\n")]);
+    let tree_b = create_tree(repo, &[(path, "b\n")]);
+    let tree_c = create_tree(repo, &[(path, "c\n")]);
+    let tree_d = create_tree(repo, &[(path, "b\nc\n")]);
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit_a = mut_repo
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree_a.id())
+        .write()
+        .unwrap();
+    let commit_b = mut_repo
+        .new_commit(vec![commit_a.id().clone()], tree_b.id())
+        .write()
+        .unwrap();
+    let commit_c = mut_repo
+        .new_commit(vec![commit_a.id().clone()], tree_c.id())
+        .write()
+        .unwrap();
+
+    // Rebasing C onto B results in a conflict, which is then resolved by D
+    let commit_c2 = rebase_commit(mut_repo, commit_c.clone(), vec![commit_b.id().clone()]).unwrap();
+    assert!(commit_c2.has_conflict().unwrap());
+    mut_repo
+        .new_commit(vec![commit_c2.id().clone()], tree_d.id())
+        .write()
+        .unwrap();
+
+    // The same conflict is resolved when C is rebased onto another commit
+    let commit_e = mut_repo
+        .new_commit(vec![commit_a.id().clone()], tree_b.id())
+        .set_description("E")
+        .write()
+        .unwrap();
+    let commit_c3 = rebase_commit(mut_repo, commit_c, vec![commit_e.id().clone()]).unwrap();
+    assert_eq!(*commit_c3.tree_id(), tree_d.id());
+    assert_eq!(tree_b.merge(&tree_a, &tree_c).unwrap().id(), tree_d.id());
+}